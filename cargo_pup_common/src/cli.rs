@@ -1,6 +1,8 @@
 // This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
 
+use crate::project_context::PUP_DIR;
 use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
 
 // PUP Commands
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
@@ -9,12 +11,118 @@ pub enum PupCommand {
     PrintTraits,
     Check,
     GenerateConfig,
+    ListLints,
+    ValidateConfig,
+    Graph,
+    Stats,
+    /// Write the merged project context as JSON, for other tools to consume
+    ExportContext,
+    Watch,
+    Init,
+    /// Compare the lints of two configuration files
+    Diff {
+        from: String,
+        to: String,
+    },
+}
+
+/// How the check and stats commands report their results
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+pub enum OutputFormat {
+    /// The usual rustc diagnostics, or a table of stats
+    #[default]
+    Text,
+    /// A JUnit XML report, with one test case per configured lint
+    Junit,
+    /// The stats as a JSON object
+    Json,
+}
+
+/// How the check command groups violations when it re-renders them
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum GroupBy {
+    /// One group per source file
+    File,
+    /// One group per configured lint
+    Lint,
+}
+
+/// How much cargo-pup prints around the diagnostics themselves
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+pub enum Verbosity {
+    /// No puppy, banners or progress notes
+    Quiet,
+    #[default]
+    Normal,
+    /// Also report which lints ran, how many files were checked and how long it took
+    Verbose,
+}
+
+/// Whether output is colored with ANSI codes
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+pub enum ColorChoice {
+    /// Color output for a terminal, unless `NO_COLOR` is set
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    /// Whether to color output, given whether `NO_COLOR` is set and whether the output
+    /// goes to a terminal. An explicit `--color` wins over `NO_COLOR`.
+    pub fn enabled(self, no_color: bool, is_terminal: bool) -> bool {
+        match self {
+            ColorChoice::Auto => !no_color && is_terminal,
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+        }
+    }
+
+    /// The `--color` value to give cargo, which renders the compiler's diagnostics.
+    /// `None` leaves cargo to work it out for itself.
+    pub fn cargo_value(self, no_color: bool) -> Option<&'static str> {
+        match self {
+            ColorChoice::Auto if no_color => Some("never"),
+            ColorChoice::Auto => None,
+            ColorChoice::Always => Some("always"),
+            ColorChoice::Never => Some("never"),
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct PupCli {
     pub command: PupCommand,
     pub config_path: Option<String>,
+    /// Fail the compilation if any cargo-pup warning is emitted
+    #[serde(default)]
+    pub deny_warnings: bool,
+    /// Exit successfully even if lints report errors, as long as the crate compiles
+    #[serde(default)]
+    pub exit_zero: bool,
+    /// Stop compiling a crate at its first error-severity violation
+    #[serde(default)]
+    pub fail_fast: bool,
+    /// When set, only report diagnostics in these files, given as absolute paths
+    #[serde(default)]
+    pub changed_files: Option<Vec<String>>,
+    #[serde(default)]
+    pub verbosity: Verbosity,
+    /// Where context files are written and read instead of `.pup`, as an absolute path
+    #[serde(default)]
+    pub output_dir: Option<String>,
+    /// The JSON lines file each crate appends its violations to for `check --report`,
+    /// as an absolute path
+    #[serde(default)]
+    pub report_file: Option<String>,
+    /// The JSON file each crate adds its lint timings to for `check --timings`, as an
+    /// absolute path
+    #[serde(default)]
+    pub timings_file: Option<String>,
+    /// The configuration profile whose lints run, from `--pup-profile` or `PUP_PROFILE`
+    #[serde(default)]
+    pub profile: Option<String>,
 }
 
 impl Default for PupCli {
@@ -22,6 +130,15 @@ impl Default for PupCli {
         Self {
             command: PupCommand::Check,
             config_path: None,
+            deny_warnings: false,
+            exit_zero: false,
+            fail_fast: false,
+            changed_files: None,
+            verbosity: Verbosity::Normal,
+            output_dir: None,
+            report_file: None,
+            timings_file: None,
+            profile: None,
         }
     }
 }
@@ -35,16 +152,53 @@ impl PupCli {
     pub fn from_env_str(env_str: &str) -> PupCli {
         serde_json::from_str(env_str).expect("Failed deserializing CLI args")
     }
+
+    /// The working directory for context files: `--output-dir` if given, else `.pup`
+    pub fn pup_dir(&self) -> PathBuf {
+        PathBuf::from(self.output_dir.as_deref().unwrap_or(PUP_DIR))
+    }
 }
 
 #[allow(dead_code)]
 pub struct PupArgs {
     pub command: PupCommand,
     pub config_path: Option<String>,
+    pub output_path: Option<String>,
+    pub output_format: OutputFormat,
+    pub deny_warnings: bool,
+    /// Whether lint violations are reported without failing the run
+    pub exit_zero: bool,
+    /// Whether the first error-severity violation stops the check
+    pub fail_fast: bool,
+    /// Whether init should also scaffold an integration test
+    pub init_test: bool,
+    /// Git ref to diff against, so check only reports on files changed since
+    pub since: Option<String>,
+    /// Re-render check violations grouped by file or by lint
+    pub group_by: Option<GroupBy>,
+    /// Where check writes the combined report for the whole workspace
+    pub report_path: Option<String>,
+    /// Whether check reports how long each lint spent checking
+    pub timings: bool,
+    /// Regex restricting print-modules and print-traits to the paths it matches
+    pub filter: Option<String>,
+    /// The configuration profile to select, in place of the default lints
+    pub profile: Option<String>,
+    pub verbosity: Verbosity,
+    pub color: ColorChoice,
+    /// Replaces `.pup` as the directory for build output, context files and
+    /// generated configuration
+    pub output_dir: Option<String>,
     pub cargo_args: Vec<String>,
 }
 
 impl PupArgs {
+    /// The working directory for build output and context files: `--output-dir` if
+    /// given, else `.pup`
+    pub fn pup_dir(&self) -> PathBuf {
+        PathBuf::from(self.output_dir.as_deref().unwrap_or(PUP_DIR))
+    }
+
     #[allow(dead_code)]
     pub fn parse<I>(args: I) -> Self
     where
@@ -52,6 +206,21 @@ impl PupArgs {
     {
         let mut command = PupCommand::Check; // Default command
         let mut config_path = None;
+        let mut output_path = None;
+        let mut output_format = OutputFormat::Text;
+        let mut deny_warnings = false;
+        let mut exit_zero = false;
+        let mut fail_fast = false;
+        let mut init_test = false;
+        let mut since = None;
+        let mut group_by = None;
+        let mut report_path = None;
+        let mut timings = false;
+        let mut filter = None;
+        let mut profile = None;
+        let mut verbosity = Verbosity::Normal;
+        let mut color = ColorChoice::Auto;
+        let mut output_dir = None;
 
         // Convert args to a vector for easier processing
         let args: Vec<String> = args.collect();
@@ -84,22 +253,213 @@ impl PupArgs {
                     command = PupCommand::GenerateConfig;
                     start_idx += 1;
                 }
+                "list-lints" => {
+                    command = PupCommand::ListLints;
+                    start_idx += 1;
+                }
+                "validate-config" => {
+                    command = PupCommand::ValidateConfig;
+                    start_idx += 1;
+                }
+                "graph" => {
+                    command = PupCommand::Graph;
+                    start_idx += 1;
+                }
+                "stats" => {
+                    command = PupCommand::Stats;
+                    start_idx += 1;
+                }
+                "export-context" => {
+                    command = PupCommand::ExportContext;
+                    start_idx += 1;
+                }
+                "watch" => {
+                    command = PupCommand::Watch;
+                    start_idx += 1;
+                }
+                "init" => {
+                    command = PupCommand::Init;
+                    start_idx += 1;
+                }
+                "diff" => {
+                    // The two configuration files come straight after the command
+                    let files: Vec<String> = args[start_idx + 1..]
+                        .iter()
+                        .take(2)
+                        .take_while(|arg| !arg.starts_with('-'))
+                        .cloned()
+                        .collect();
+                    start_idx += 1 + files.len();
+                    let mut files = files.into_iter();
+                    command = PupCommand::Diff {
+                        from: files.next().unwrap_or_default(),
+                        to: files.next().unwrap_or_default(),
+                    };
+                }
                 _ => { /* Not a command, use default and keep this arg */ }
             }
         }
 
-        // Look for --pup-config argument
+        // --output belongs to us for graph and export-context, and for check when
+        // writing a report.
+        // The format can come after --output, so look for it up front.
+        let wants_report = command == PupCommand::Check
+            && args[start_idx..]
+                .windows(2)
+                .any(|pair| pair[0] == "--format" && pair[1] == "junit");
+        let owns_output =
+            matches!(command, PupCommand::Graph | PupCommand::ExportContext) || wants_report;
+
+        // Look for --config/--pup-config and --deny-warnings, --format for check, and --output
         let mut filtered_cargo_args = Vec::new();
         let mut i = start_idx;
         while i < args.len() {
-            if args[i] == "--pup-config" {
-                // Check if there's a value after --pup-config
+            if args[i] == "--config" && args.get(i + 1).is_some_and(|v| is_cargo_config(v)) {
+                // cargo's own `--config KEY=VALUE` or `--config extra.toml`
+                filtered_cargo_args.extend_from_slice(&args[i..i + 2]);
+                i += 2;
+            } else if args[i] == "--config" || args[i] == "--pup-config" {
+                // Check if there's a value after the flag
                 if i + 1 < args.len() {
                     config_path = Some(args[i + 1].clone());
                     i += 2; // Skip both the flag and its value
                 } else {
-                    // Missing value for --pup-config
-                    eprintln!("Warning: --pup-config flag requires a path argument");
+                    // Missing value for --config
+                    eprintln!("Warning: {} flag requires a path argument", args[i]);
+                    i += 1;
+                }
+            } else if let Some(path) = args[i]
+                .strip_prefix("--config=")
+                .filter(|value| !is_cargo_config(value))
+                .or_else(|| args[i].strip_prefix("--pup-config="))
+            {
+                config_path = Some(path.to_string());
+                i += 1;
+            } else if args[i] == "--pup-profile" {
+                if i + 1 < args.len() {
+                    profile = Some(args[i + 1].clone());
+                    i += 2;
+                } else {
+                    eprintln!("Warning: {} flag requires a profile name", args[i]);
+                    i += 1;
+                }
+            } else if let Some(name) = args[i].strip_prefix("--pup-profile=") {
+                profile = Some(name.to_string());
+                i += 1;
+            } else if args[i] == "--output-dir" {
+                if i + 1 < args.len() {
+                    output_dir = Some(args[i + 1].clone());
+                    i += 2;
+                } else {
+                    eprintln!("Warning: --output-dir flag requires a path argument");
+                    i += 1;
+                }
+            } else if let Some(path) = args[i].strip_prefix("--output-dir=") {
+                output_dir = Some(path.to_string());
+                i += 1;
+            } else if args[i] == "--deny-warnings" {
+                deny_warnings = true;
+                i += 1;
+            } else if args[i] == "--exit-zero" {
+                exit_zero = true;
+                i += 1;
+            } else if args[i] == "--fail-fast" {
+                fail_fast = true;
+                i += 1;
+            } else if args[i] == "--quiet" || args[i] == "-q" {
+                // Cargo's own progress output is quietened too
+                verbosity = Verbosity::Quiet;
+                filtered_cargo_args.push("--quiet".to_string());
+                i += 1;
+            } else if args[i] == "--verbose" || args[i] == "-v" {
+                verbosity = Verbosity::Verbose;
+                i += 1;
+            } else if args[i] == "--color" || args[i].starts_with("--color=") {
+                // Cargo is given the same choice once NO_COLOR has been taken into account
+                let (value, consumed) = match args[i].strip_prefix("--color=") {
+                    Some(value) => (Some(value), 1),
+                    None => (args.get(i + 1).map(String::as_str), 2),
+                };
+                match value {
+                    Some("auto") => color = ColorChoice::Auto,
+                    Some("always") => color = ColorChoice::Always,
+                    Some("never") => color = ColorChoice::Never,
+                    Some(other) => eprintln!(
+                        "Warning: unknown --color '{other}', expected 'auto', 'always' or 'never'"
+                    ),
+                    None => eprintln!("Warning: --color flag requires a value"),
+                }
+                i += consumed;
+            } else if args[i] == "--test" && command == PupCommand::Init {
+                init_test = true;
+                i += 1;
+            } else if args[i] == "--since" && command == PupCommand::Check {
+                if i + 1 < args.len() {
+                    since = Some(args[i + 1].clone());
+                    i += 2;
+                } else {
+                    eprintln!("Warning: --since flag requires a git ref");
+                    i += 1;
+                }
+            } else if args[i] == "--report" && command == PupCommand::Check {
+                if i + 1 < args.len() {
+                    report_path = Some(args[i + 1].clone());
+                    i += 2;
+                } else {
+                    eprintln!("Warning: --report flag requires a path argument");
+                    i += 1;
+                }
+            } else if args[i] == "--timings" && command == PupCommand::Check {
+                timings = true;
+                i += 1;
+            } else if args[i] == "--filter"
+                && matches!(command, PupCommand::PrintModules | PupCommand::PrintTraits)
+            {
+                if i + 1 < args.len() {
+                    filter = Some(args[i + 1].clone());
+                    i += 2;
+                } else {
+                    eprintln!("Warning: --filter flag requires a pattern");
+                    i += 1;
+                }
+            } else if args[i] == "--group-by" && command == PupCommand::Check {
+                match args.get(i + 1).map(String::as_str) {
+                    Some("file") => group_by = Some(GroupBy::File),
+                    Some("lint") => group_by = Some(GroupBy::Lint),
+                    Some(other) => {
+                        eprintln!(
+                            "Warning: unknown --group-by '{other}', expected 'file' or 'lint'"
+                        )
+                    }
+                    None => eprintln!("Warning: --group-by flag requires a value"),
+                }
+                i += 2;
+            } else if args[i] == "--format" && command == PupCommand::Check {
+                match args.get(i + 1).map(String::as_str) {
+                    Some("text") => output_format = OutputFormat::Text,
+                    Some("junit") => output_format = OutputFormat::Junit,
+                    Some(other) => {
+                        eprintln!("Warning: unknown --format '{other}', expected 'text' or 'junit'")
+                    }
+                    None => eprintln!("Warning: --format flag requires a value"),
+                }
+                i += 2;
+            } else if args[i] == "--format" && command == PupCommand::Stats {
+                match args.get(i + 1).map(String::as_str) {
+                    Some("text") => output_format = OutputFormat::Text,
+                    Some("json") => output_format = OutputFormat::Json,
+                    Some(other) => {
+                        eprintln!("Warning: unknown --format '{other}', expected 'text' or 'json'")
+                    }
+                    None => eprintln!("Warning: --format flag requires a value"),
+                }
+                i += 2;
+            } else if args[i] == "--output" && owns_output {
+                if i + 1 < args.len() {
+                    output_path = Some(args[i + 1].clone());
+                    i += 2;
+                } else {
+                    eprintln!("Warning: --output flag requires a path argument");
                     i += 1;
                 }
             } else {
@@ -112,11 +472,32 @@ impl PupArgs {
         Self {
             command,
             config_path,
+            output_path,
+            output_format,
+            deny_warnings,
+            exit_zero,
+            fail_fast,
+            init_test,
+            since,
+            group_by,
+            report_path,
+            timings,
+            filter,
+            profile,
+            verbosity,
+            color,
+            output_dir,
             cargo_args: filtered_cargo_args,
         }
     }
 }
 
+// cargo takes `--config` too, with a `KEY=VALUE` setting or the path of an extra TOML
+// file. Those stay cargo's; any other value names a pup configuration.
+fn is_cargo_config(value: &str) -> bool {
+    value.contains('=') || value.ends_with(".toml")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -152,6 +533,53 @@ mod tests {
         let args = parse_args(&["cargo-pup", "generate-config"]);
         assert_eq!(args.command, PupCommand::GenerateConfig);
         assert!(args.cargo_args.is_empty());
+
+        // Test watch command
+        let args = parse_args(&["cargo-pup", "watch", "--features=foo"]);
+        assert_eq!(args.command, PupCommand::Watch);
+        assert_eq!(args.cargo_args, vec!["--features=foo"]);
+
+        // Test list-lints command
+        let args = parse_args(&["cargo-pup", "list-lints", "--pup-config", "custom.ron"]);
+        assert_eq!(args.command, PupCommand::ListLints);
+        assert_eq!(args.config_path, Some("custom.ron".to_string()));
+        assert!(args.cargo_args.is_empty());
+
+        // Test validate-config command
+        let args = parse_args(&["cargo", "pup", "validate-config", "--config", "pup.ci.ron"]);
+        assert_eq!(args.command, PupCommand::ValidateConfig);
+        assert_eq!(args.config_path, Some("pup.ci.ron".to_string()));
+        assert!(args.cargo_args.is_empty());
+
+        // Test init command, where --test is ours rather than cargo's
+        let args = parse_args(&["cargo", "pup", "init", "--test"]);
+        assert_eq!(args.command, PupCommand::Init);
+        assert!(args.init_test);
+        assert!(args.cargo_args.is_empty());
+        assert!(!parse_args(&["cargo-pup", "init"]).init_test);
+    }
+
+    #[test]
+    fn test_diff_command() {
+        let args = parse_args(&["cargo", "pup", "diff", "old.ron", "pup.ron", "--quiet"]);
+        assert_eq!(
+            args.command,
+            PupCommand::Diff {
+                from: "old.ron".to_string(),
+                to: "pup.ron".to_string(),
+            }
+        );
+        assert_eq!(args.verbosity, Verbosity::Quiet);
+
+        // Missing files are left empty, for cargo-pup to report
+        let args = parse_args(&["cargo-pup", "diff", "old.ron"]);
+        assert_eq!(
+            args.command,
+            PupCommand::Diff {
+                from: "old.ron".to_string(),
+                to: String::new(),
+            }
+        );
     }
 
     #[test]
@@ -215,6 +643,451 @@ mod tests {
         assert!(args.cargo_args.is_empty());
     }
 
+    #[test]
+    fn test_config_argument() {
+        let args = parse_args(&["cargo", "pup", "check", "--config", "pup.strict.ron"]);
+        assert_eq!(args.config_path, Some("pup.strict.ron".to_string()));
+        assert!(args.cargo_args.is_empty());
+
+        // Both spellings also accept the value after an equals sign
+        let args = parse_args(&["cargo-pup", "--config=pup.strict.ron", "--all-features"]);
+        assert_eq!(args.command, PupCommand::Check);
+        assert_eq!(args.config_path, Some("pup.strict.ron".to_string()));
+        assert_eq!(args.cargo_args, vec!["--all-features"]);
+
+        let args = parse_args(&["cargo-pup", "print-modules", "--pup-config=other.ron"]);
+        assert_eq!(args.config_path, Some("other.ron".to_string()));
+        assert!(args.cargo_args.is_empty());
+    }
+
+    #[test]
+    fn test_cargo_config_argument_is_passed_through() {
+        let args = parse_args(&[
+            "cargo",
+            "pup",
+            "check",
+            "--config",
+            "build.jobs=2",
+            "--config=net.offline=true",
+            "--config",
+            "ci.toml",
+            "--pup-config",
+            "pup.strict.ron",
+        ]);
+        assert_eq!(args.config_path, Some("pup.strict.ron".to_string()));
+        assert_eq!(
+            args.cargo_args,
+            vec![
+                "--config",
+                "build.jobs=2",
+                "--config=net.offline=true",
+                "--config",
+                "ci.toml"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_since_argument() {
+        let args = parse_args(&[
+            "cargo",
+            "pup",
+            "check",
+            "--since",
+            "origin/main",
+            "--all-features",
+        ]);
+        assert_eq!(args.command, PupCommand::Check);
+        assert_eq!(args.since, Some("origin/main".to_string()));
+        assert_eq!(args.cargo_args, vec!["--all-features"]);
+
+        // Check is the default command
+        let args = parse_args(&["cargo-pup", "--since", "HEAD~1"]);
+        assert_eq!(args.since, Some("HEAD~1".to_string()));
+        assert!(args.cargo_args.is_empty());
+
+        assert_eq!(parse_args(&["cargo-pup", "check"]).since, None);
+    }
+
+    #[test]
+    fn test_report_argument() {
+        let args = parse_args(&[
+            "cargo",
+            "pup",
+            "check",
+            "--report",
+            "pup-report.txt",
+            "--workspace",
+        ]);
+        assert_eq!(args.report_path, Some("pup-report.txt".to_string()));
+        assert_eq!(args.cargo_args, vec!["--workspace"]);
+
+        // Only check writes a report, so elsewhere the flag is left for cargo
+        let args = parse_args(&["cargo-pup", "print-modules", "--report", "x"]);
+        assert_eq!(args.report_path, None);
+        assert_eq!(args.cargo_args, vec!["--report", "x"]);
+
+        // The drivers learn where to append through PupCli; older strings have no report
+        let cli = PupCli {
+            report_file: Some("/tmp/.pup/report.jsonl".to_string()),
+            ..PupCli::default()
+        };
+        assert_eq!(
+            PupCli::from_env_str(&cli.to_env_str()).report_file,
+            cli.report_file
+        );
+        let cli = PupCli::from_env_str(r#"{"command":"Check","config_path":null}"#);
+        assert!(cli.report_file.is_none());
+    }
+
+    #[test]
+    fn test_timings_argument() {
+        let args = parse_args(&["cargo", "pup", "check", "--timings", "--workspace"]);
+        assert!(args.timings);
+        assert_eq!(args.cargo_args, vec!["--workspace"]);
+
+        // Only check is timed, so elsewhere the flag is left for cargo
+        let args = parse_args(&["cargo-pup", "print-modules", "--timings"]);
+        assert!(!args.timings);
+        assert_eq!(args.cargo_args, vec!["--timings"]);
+
+        // The drivers learn where to add their timings through PupCli
+        let cli = PupCli {
+            timings_file: Some("/tmp/.pup/timings-1.json".to_string()),
+            ..PupCli::default()
+        };
+        assert_eq!(
+            PupCli::from_env_str(&cli.to_env_str()).timings_file,
+            cli.timings_file
+        );
+        let cli = PupCli::from_env_str(r#"{"command":"Check","config_path":null}"#);
+        assert!(cli.timings_file.is_none());
+    }
+
+    #[test]
+    fn test_filter_argument() {
+        let args = parse_args(&[
+            "cargo",
+            "pup",
+            "print-modules",
+            "--filter",
+            "::api",
+            "--workspace",
+        ]);
+        assert_eq!(args.filter, Some("::api".to_string()));
+        assert_eq!(args.cargo_args, vec!["--workspace"]);
+
+        let args = parse_args(&["cargo-pup", "print-traits", "--filter", "Handler$"]);
+        assert_eq!(args.filter, Some("Handler$".to_string()));
+        assert!(args.cargo_args.is_empty());
+
+        // Only the print commands filter, so elsewhere the flag is left for cargo
+        let args = parse_args(&["cargo-pup", "check", "--filter", "x"]);
+        assert_eq!(args.filter, None);
+        assert_eq!(args.cargo_args, vec!["--filter", "x"]);
+    }
+
+    #[test]
+    fn test_profile_argument() {
+        let args = parse_args(&[
+            "cargo",
+            "pup",
+            "check",
+            "--pup-profile",
+            "strict",
+            "--workspace",
+        ]);
+        assert_eq!(args.profile, Some("strict".to_string()));
+        assert_eq!(args.cargo_args, vec!["--workspace"]);
+
+        // --profile is cargo's, for its build profiles
+        let args = parse_args(&["cargo", "pup", "check", "--profile", "release"]);
+        assert_eq!(args.profile, None);
+        assert_eq!(args.cargo_args, vec!["--profile", "release"]);
+
+        let args = parse_args(&["cargo-pup", "print-modules", "--pup-profile=ci"]);
+        assert_eq!(args.profile, Some("ci".to_string()));
+        assert!(args.cargo_args.is_empty());
+
+        let args = parse_args(&["cargo-pup", "check"]);
+        assert_eq!(args.profile, None);
+    }
+
+    #[test]
+    fn test_group_by_argument() {
+        let args = parse_args(&[
+            "cargo",
+            "pup",
+            "check",
+            "--group-by",
+            "file",
+            "--all-features",
+        ]);
+        assert_eq!(args.group_by, Some(GroupBy::File));
+        assert_eq!(args.cargo_args, vec!["--all-features"]);
+
+        let args = parse_args(&["cargo-pup", "--group-by", "lint"]);
+        assert_eq!(args.group_by, Some(GroupBy::Lint));
+        assert!(args.cargo_args.is_empty());
+
+        // An unknown grouping leaves the output flat
+        let args = parse_args(&["cargo-pup", "check", "--group-by", "severity"]);
+        assert_eq!(args.group_by, None);
+        assert!(args.cargo_args.is_empty());
+    }
+
+    #[test]
+    fn test_verbosity_arguments() {
+        let args = parse_args(&["cargo", "pup", "check", "--all-features"]);
+        assert_eq!(args.verbosity, Verbosity::Normal);
+
+        // --quiet also quietens cargo, --verbose is ours alone
+        let args = parse_args(&["cargo", "pup", "check", "--quiet", "--all-features"]);
+        assert_eq!(args.verbosity, Verbosity::Quiet);
+        assert_eq!(args.cargo_args, vec!["--quiet", "--all-features"]);
+
+        let args = parse_args(&["cargo-pup", "print-modules", "-v"]);
+        assert_eq!(args.command, PupCommand::PrintModules);
+        assert_eq!(args.verbosity, Verbosity::Verbose);
+        assert!(args.cargo_args.is_empty());
+
+        // The driver learns the verbosity through PupCli; older strings default to normal
+        let cli = PupCli {
+            verbosity: Verbosity::Verbose,
+            ..PupCli::default()
+        };
+        assert_eq!(
+            PupCli::from_env_str(&cli.to_env_str()).verbosity,
+            Verbosity::Verbose
+        );
+        let cli = PupCli::from_env_str(r#"{"command":"Check","config_path":null}"#);
+        assert_eq!(cli.verbosity, Verbosity::Normal);
+    }
+
+    #[test]
+    fn test_color_argument() {
+        assert_eq!(parse_args(&["cargo", "pup"]).color, ColorChoice::Auto);
+
+        let args = parse_args(&[
+            "cargo",
+            "pup",
+            "check",
+            "--color",
+            "never",
+            "--all-features",
+        ]);
+        assert_eq!(args.color, ColorChoice::Never);
+        // Cargo's --color is set from ours rather than passed along
+        assert_eq!(args.cargo_args, vec!["--all-features"]);
+
+        let args = parse_args(&["cargo-pup", "print-modules", "--color=always"]);
+        assert_eq!(args.color, ColorChoice::Always);
+        assert!(args.cargo_args.is_empty());
+
+        // An unknown value is ignored
+        assert_eq!(
+            parse_args(&["cargo", "pup", "--color", "rainbow"]).color,
+            ColorChoice::Auto
+        );
+    }
+
+    #[test]
+    fn test_color_choice() {
+        // Auto colors a terminal unless NO_COLOR is set
+        assert!(ColorChoice::Auto.enabled(false, true));
+        assert!(!ColorChoice::Auto.enabled(false, false));
+        assert!(!ColorChoice::Auto.enabled(true, true));
+        // An explicit choice wins over NO_COLOR
+        assert!(ColorChoice::Always.enabled(true, false));
+        assert!(!ColorChoice::Never.enabled(false, true));
+
+        assert_eq!(ColorChoice::Auto.cargo_value(false), None);
+        assert_eq!(ColorChoice::Auto.cargo_value(true), Some("never"));
+        assert_eq!(ColorChoice::Always.cargo_value(true), Some("always"));
+        assert_eq!(ColorChoice::Never.cargo_value(false), Some("never"));
+    }
+
+    #[test]
+    fn test_output_dir_argument() {
+        let args = parse_args(&["cargo", "pup", "check"]);
+        assert_eq!(args.output_dir, None);
+        assert_eq!(args.pup_dir(), PathBuf::from(".pup"));
+
+        // Accepted for every command, and never passed on to cargo
+        let args = parse_args(&["cargo", "pup", "check", "--output-dir", "/tmp/pup", "--lib"]);
+        assert_eq!(args.output_dir, Some("/tmp/pup".to_string()));
+        assert_eq!(args.pup_dir(), PathBuf::from("/tmp/pup"));
+        assert_eq!(args.cargo_args, vec!["--lib"]);
+
+        let args = parse_args(&["cargo-pup", "print-modules", "--output-dir=build/pup"]);
+        assert_eq!(args.command, PupCommand::PrintModules);
+        assert_eq!(args.output_dir, Some("build/pup".to_string()));
+        assert!(args.cargo_args.is_empty());
+
+        // The driver learns the directory through PupCli; older strings use .pup
+        let cli = PupCli {
+            output_dir: Some("/tmp/pup".to_string()),
+            ..PupCli::default()
+        };
+        assert_eq!(
+            PupCli::from_env_str(&cli.to_env_str()).pup_dir(),
+            PathBuf::from("/tmp/pup")
+        );
+        let cli = PupCli::from_env_str(r#"{"command":"Check","config_path":null}"#);
+        assert_eq!(cli.pup_dir(), PathBuf::from(".pup"));
+    }
+
+    #[test]
+    fn test_feature_flags_are_forwarded_to_cargo() {
+        let args = parse_args(&["cargo", "pup", "generate-config", "--all-features"]);
+        assert_eq!(args.command, PupCommand::GenerateConfig);
+        assert_eq!(args.cargo_args, vec!["--all-features"]);
+
+        let args = parse_args(&[
+            "cargo",
+            "pup",
+            "generate-config",
+            "--no-default-features",
+            "--features",
+            "metrics,tracing",
+        ]);
+        assert_eq!(
+            args.cargo_args,
+            vec!["--no-default-features", "--features", "metrics,tracing"]
+        );
+    }
+
+    #[test]
+    fn test_graph_output_argument() {
+        let args = parse_args(&["cargo", "pup", "graph"]);
+        assert_eq!(args.command, PupCommand::Graph);
+        assert_eq!(args.output_path, None);
+
+        let args = parse_args(&[
+            "cargo-pup",
+            "graph",
+            "--output",
+            "/tmp/modules.dot",
+            "--features=foo",
+        ]);
+        assert_eq!(args.command, PupCommand::Graph);
+        assert_eq!(args.output_path, Some("/tmp/modules.dot".to_string()));
+        assert_eq!(args.cargo_args, vec!["--features=foo"]);
+
+        // --output is only ours for the graph command
+        let args = parse_args(&["cargo-pup", "check", "--output", "x"]);
+        assert_eq!(args.output_path, None);
+        assert_eq!(args.cargo_args, vec!["--output", "x"]);
+    }
+
+    #[test]
+    fn test_export_context_output_argument() {
+        let args = parse_args(&["cargo", "pup", "export-context", "--workspace"]);
+        assert_eq!(args.command, PupCommand::ExportContext);
+        assert_eq!(args.output_path, None);
+        assert_eq!(args.cargo_args, vec!["--workspace"]);
+
+        let args = parse_args(&["cargo-pup", "export-context", "--output", "context.json"]);
+        assert_eq!(args.command, PupCommand::ExportContext);
+        assert_eq!(args.output_path, Some("context.json".to_string()));
+        assert!(args.cargo_args.is_empty());
+    }
+
+    #[test]
+    fn test_deny_warnings_argument() {
+        let args = parse_args(&["cargo-pup", "check", "--features=foo"]);
+        assert!(!args.deny_warnings);
+
+        let args = parse_args(&["cargo", "pup", "check", "--deny-warnings", "--features=foo"]);
+        assert!(args.deny_warnings);
+        assert_eq!(args.cargo_args, vec!["--features=foo"]);
+
+        // The flag reaches pup-driver through PupCli, and older PupCli strings still parse
+        let cli = PupCli {
+            deny_warnings: true,
+            ..PupCli::default()
+        };
+        assert!(PupCli::from_env_str(&cli.to_env_str()).deny_warnings);
+        let cli = PupCli::from_env_str(r#"{"command":"Check","config_path":null}"#);
+        assert!(!cli.deny_warnings);
+        assert!(cli.changed_files.is_none());
+    }
+
+    #[test]
+    fn test_exit_zero_argument() {
+        let args = parse_args(&["cargo-pup", "check", "--features=foo"]);
+        assert!(!args.exit_zero);
+
+        let args = parse_args(&["cargo", "pup", "check", "--exit-zero", "--workspace"]);
+        assert!(args.exit_zero);
+        assert_eq!(args.cargo_args, vec!["--workspace"]);
+
+        let cli = PupCli {
+            exit_zero: true,
+            ..PupCli::default()
+        };
+        assert!(PupCli::from_env_str(&cli.to_env_str()).exit_zero);
+        let cli = PupCli::from_env_str(r#"{"command":"Check","config_path":null}"#);
+        assert!(!cli.exit_zero);
+    }
+
+    #[test]
+    fn test_fail_fast_argument() {
+        let args = parse_args(&["cargo-pup", "check"]);
+        assert!(!args.fail_fast);
+
+        let args = parse_args(&["cargo", "pup", "check", "--fail-fast", "--workspace"]);
+        assert!(args.fail_fast);
+        assert_eq!(args.cargo_args, vec!["--workspace"]);
+
+        let cli = PupCli {
+            fail_fast: true,
+            ..PupCli::default()
+        };
+        assert!(PupCli::from_env_str(&cli.to_env_str()).fail_fast);
+        let cli = PupCli::from_env_str(r#"{"command":"Check","config_path":null}"#);
+        assert!(!cli.fail_fast);
+    }
+
+    #[test]
+    fn test_junit_format_argument() {
+        let args = parse_args(&["cargo", "pup", "check"]);
+        assert_eq!(args.output_format, OutputFormat::Text);
+
+        // Check is the default command, and --output may come before --format
+        let args = parse_args(&[
+            "cargo-pup",
+            "--output",
+            "pup.xml",
+            "--format",
+            "junit",
+            "--features=foo",
+        ]);
+        assert_eq!(args.command, PupCommand::Check);
+        assert_eq!(args.output_format, OutputFormat::Junit);
+        assert_eq!(args.output_path, Some("pup.xml".to_string()));
+        assert_eq!(args.cargo_args, vec!["--features=foo"]);
+
+        // An unknown format falls back to text
+        let args = parse_args(&["cargo-pup", "check", "--format", "sarif"]);
+        assert_eq!(args.output_format, OutputFormat::Text);
+        assert!(args.cargo_args.is_empty());
+    }
+
+    #[test]
+    fn test_stats_format_argument() {
+        let args = parse_args(&["cargo", "pup", "stats", "--format", "json", "--workspace"]);
+        assert_eq!(args.command, PupCommand::Stats);
+        assert_eq!(args.output_format, OutputFormat::Json);
+        assert_eq!(args.cargo_args, vec!["--workspace"]);
+
+        // JSON is only a stats format, and junit only a check one
+        let args = parse_args(&["cargo", "pup", "check", "--format", "json"]);
+        assert_eq!(args.output_format, OutputFormat::Text);
+        let args = parse_args(&["cargo", "pup", "stats", "--format", "junit"]);
+        assert_eq!(args.output_format, OutputFormat::Text);
+    }
+
     #[test]
     fn test_unknown_args() {
         // When invoked with unknown args, should use default command
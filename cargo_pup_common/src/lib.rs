@@ -2,4 +2,6 @@
 
 pub mod cli;
 pub mod project_context;
+pub mod stats;
+pub mod timings;
 pub mod workspace;
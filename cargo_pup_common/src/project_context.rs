@@ -2,11 +2,59 @@
 
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
-use std::fs::{self, OpenOptions};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Write;
 use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use tempfile::NamedTempFile;
 
 pub const PUP_DIR: &str = ".pup";
 pub const CONTEXT_FILE_SUFFIX: &str = "_context.json";
+pub const FINGERPRINT_FILE_SUFFIX: &str = "_context.fingerprint";
+
+/// What a crate's project context was built from: the arguments cargo-pup and cargo were
+/// given, and the modification time of each file the context depends on
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct SourceFingerprint {
+    pub cli_args: String,
+    pub cargo_args: Vec<String>,
+    pub inputs: BTreeMap<PathBuf, SystemTime>,
+}
+
+impl SourceFingerprint {
+    /// Fingerprint the given files as they are now. Files that can't be read are left out.
+    pub fn new(
+        cli_args: String,
+        cargo_args: Vec<String>,
+        files: impl IntoIterator<Item = PathBuf>,
+    ) -> Self {
+        let inputs = files
+            .into_iter()
+            .filter_map(|file| Some((file.clone(), modified_time(&file)?)))
+            .collect();
+        SourceFingerprint {
+            cli_args,
+            cargo_args,
+            inputs,
+        }
+    }
+
+    /// Whether the context would be built the same way with these arguments, because
+    /// none of its files have been changed or removed since
+    pub fn is_current(&self, cli_args: &str, cargo_args: &[String]) -> bool {
+        self.cli_args == cli_args
+            && self.cargo_args == cargo_args
+            && self
+                .inputs
+                .iter()
+                .all(|(file, modified)| modified_time(file) == Some(*modified))
+    }
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
 
 /// Information about a module and the lints that apply to it
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
@@ -41,6 +89,15 @@ pub struct ProjectContext {
     pub module_root: String,
     /// List of all traits, fully qualified, and their implementations
     pub traits: Vec<TraitInfo>,
+    /// Module-level import edges discovered from `use` items
+    #[serde(default)]
+    pub module_dependencies: Vec<ModuleDependency>,
+    /// Modules declared behind a `cfg` that depends on a cargo feature
+    #[serde(default)]
+    pub feature_gated_modules: Vec<FeatureGatedModule>,
+    /// How many items each module, including the crate root, declares directly
+    #[serde(default)]
+    pub module_items: Vec<ModuleItems>,
     /// Base directory for storing context files (not serialized)
     #[serde(skip)]
     base_dir: PathBuf,
@@ -58,6 +115,39 @@ pub struct TraitInfo {
     pub applicable_lints: Vec<String>,
 }
 
+/// A `use` edge from one module to the module that owns the imported item
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ModuleDependency {
+    /// Fully qualified name of the importing module
+    pub from: String,
+    /// Fully qualified name of the imported module
+    pub to: String,
+}
+
+/// A module that is only compiled when some cargo features are enabled (or disabled)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct FeatureGatedModule {
+    /// Fully qualified module name
+    pub module: String,
+    /// The module's `cfg` predicate, e.g. `feature = "metrics"`
+    pub cfg: String,
+}
+
+/// The items a module declares directly, not counting those of its submodules
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct ModuleItems {
+    /// Fully qualified module name
+    pub module: String,
+    /// Structs declared in the module
+    pub structs: usize,
+    /// Traits declared in the module
+    pub traits: usize,
+    /// Free functions, along with the methods of impl blocks in the module
+    pub functions: usize,
+    /// Every item in the module other than `use` imports, methods included
+    pub total: usize,
+}
+
 impl Default for ProjectContext {
     fn default() -> Self {
         Self::new()
@@ -72,6 +162,9 @@ impl ProjectContext {
             modules: Vec::new(),
             module_root: String::new(),
             traits: Vec::new(),
+            module_dependencies: Vec::new(),
+            feature_gated_modules: Vec::new(),
+            module_items: Vec::new(),
             base_dir: PathBuf::from(PUP_DIR),
         }
     }
@@ -82,6 +175,9 @@ impl ProjectContext {
             modules: Vec::new(),
             module_root: String::new(),
             traits: Vec::new(),
+            module_dependencies: Vec::new(),
+            feature_gated_modules: Vec::new(),
+            module_items: Vec::new(),
             base_dir: dir_path.as_ref().to_path_buf(),
         }
     }
@@ -101,6 +197,9 @@ impl ProjectContext {
             modules: module_infos,
             module_root,
             traits,
+            module_dependencies: Vec::new(),
+            feature_gated_modules: Vec::new(),
+            module_items: Vec::new(),
             base_dir: PathBuf::from(PUP_DIR),
         }
     }
@@ -125,6 +224,9 @@ impl ProjectContext {
             modules: module_infos,
             module_root,
             traits,
+            module_dependencies: Vec::new(),
+            feature_gated_modules: Vec::new(),
+            module_items: Vec::new(),
             base_dir: dir_path.as_ref().to_path_buf(),
         }
     }
@@ -145,27 +247,66 @@ impl ProjectContext {
         ))?;
 
         // Create a predictable filename using just the crate name
-        let filename = format!("{}{CONTEXT_FILE_SUFFIX}", self.module_root);
-        let file_path = self.base_dir.join(&filename);
+        let file_path = Self::context_file_path(&self.base_dir, &self.module_root);
 
-        let file = OpenOptions::new()
-            .create(true)
-            .write(true)
-            .truncate(true)
-            .open(&file_path)
-            .context(format!(
-                "Failed to open file for writing: {}",
-                file_path.display()
-            ))?;
-
-        serde_json::to_writer_pretty(file, &self).context(format!(
+        let content = serde_json::to_string_pretty(&self).context(format!(
             "Failed to serialize ProjectContext to: {}",
             file_path.display()
         ))?;
+        Self::write_atomically(&self.base_dir, &file_path, content.as_bytes())?;
 
         Ok(file_path)
     }
 
+    /// Serialize this project context to a file, and record the fingerprint of what it was
+    /// built from alongside it so later runs can tell whether it is stale
+    pub fn serialize_to_file_with_fingerprint(
+        &self,
+        fingerprint: &SourceFingerprint,
+    ) -> Result<PathBuf> {
+        let file_path = self.serialize_to_file()?;
+
+        let fingerprint_path = Self::fingerprint_file_path(&self.base_dir, &self.module_root);
+        let content = serde_json::to_string(fingerprint).context(format!(
+            "Failed to serialize fingerprint to: {}",
+            fingerprint_path.display()
+        ))?;
+        Self::write_atomically(&self.base_dir, &fingerprint_path, content.as_bytes())?;
+
+        Ok(file_path)
+    }
+
+    /// Check whether every context file in the given directory was built from files that
+    /// haven't changed since, with the same cargo-pup and cargo arguments. A context without
+    /// a fingerprint is never fresh, and neither is a directory without any contexts.
+    pub fn contexts_are_fresh(dir_path: &Path, cli_args: &str, cargo_args: &[String]) -> bool {
+        let Ok(entries) = fs::read_dir(dir_path) else {
+            return false;
+        };
+
+        let mut contexts_found = false;
+        for path in entries.filter_map(Result::ok).map(|e| e.path()) {
+            let Some(module_root) = path
+                .file_name()
+                .and_then(|f| f.to_str())
+                .and_then(|f| f.strip_suffix(CONTEXT_FILE_SUFFIX))
+            else {
+                continue;
+            };
+            contexts_found = true;
+
+            let fresh = fs::read_to_string(Self::fingerprint_file_path(dir_path, module_root))
+                .ok()
+                .and_then(|content| serde_json::from_str::<SourceFingerprint>(&content).ok())
+                .is_some_and(|fingerprint| fingerprint.is_current(cli_args, cargo_args));
+            if !fresh {
+                return false;
+            }
+        }
+
+        contexts_found
+    }
+
     /// Load all project contexts from the default .pup directory and return the merged result
     pub fn load_all_contexts() -> Result<ProjectContext> {
         let (context, _) = Self::load_all_contexts_with_crate_names()?;
@@ -198,10 +339,13 @@ impl ProjectContext {
         let entries = fs::read_dir(dir_path)
             .context(format!("Failed to read directory: {}", dir_path.display()))?;
 
+        // Sort the files so the merged result doesn't depend on directory order
+        let mut paths: Vec<PathBuf> = entries.filter_map(Result::ok).map(|e| e.path()).collect();
+        paths.sort();
+
         // Process each file
         let mut contexts_found = false;
-        for entry in entries.filter_map(Result::ok) {
-            let path = entry.path();
+        for path in paths {
             if let Some(filename) = path.file_name().and_then(|f| f.to_str()) {
                 // Look specifically for our deterministic *_context.json pattern
                 if filename.ends_with(CONTEXT_FILE_SUFFIX) {
@@ -252,7 +396,8 @@ impl ProjectContext {
         for entry in entries.filter_map(Result::ok) {
             let path = entry.path();
             if let Some(filename) = path.file_name().and_then(|f| f.to_str())
-                && filename.ends_with(CONTEXT_FILE_SUFFIX)
+                && (filename.ends_with(CONTEXT_FILE_SUFFIX)
+                    || filename.ends_with(FINGERPRINT_FILE_SUFFIX))
             {
                 let _ = fs::remove_file(&path); // Ignore errors on deletion
             }
@@ -269,6 +414,35 @@ impl ProjectContext {
 
     // Private implementation methods
 
+    /// Path of the context file for a crate within a directory
+    fn context_file_path(dir_path: &Path, module_root: &str) -> PathBuf {
+        dir_path.join(format!("{module_root}{CONTEXT_FILE_SUFFIX}"))
+    }
+
+    /// Path of the fingerprint file for a crate within a directory
+    fn fingerprint_file_path(dir_path: &Path, module_root: &str) -> PathBuf {
+        dir_path.join(format!("{module_root}{FINGERPRINT_FILE_SUFFIX}"))
+    }
+
+    /// Write a file by writing to a temporary file in the same directory and renaming
+    /// it into place. Crates are analysed concurrently, so readers must never observe
+    /// a partially written file.
+    fn write_atomically(dir_path: &Path, file_path: &Path, content: &[u8]) -> Result<()> {
+        let mut temp_file = NamedTempFile::new_in(dir_path).context(format!(
+            "Failed to create temporary file in: {}",
+            dir_path.display()
+        ))?;
+        temp_file.write_all(content).context(format!(
+            "Failed to write temporary file for: {}",
+            file_path.display()
+        ))?;
+        temp_file.persist(file_path).context(format!(
+            "Failed to move temporary file into place: {}",
+            file_path.display()
+        ))?;
+        Ok(())
+    }
+
     /// Merge another ProjectContext into this one
     fn merge(&mut self, other: &ProjectContext) {
         // Add the module root if ours is empty
@@ -282,6 +456,14 @@ impl ProjectContext {
         // Add traits (since each trait has a unique fully-qualified name,
         // we can just add them without worrying about duplicates)
         self.traits.extend(other.traits.clone());
+
+        self.module_dependencies
+            .extend(other.module_dependencies.clone());
+
+        self.feature_gated_modules
+            .extend(other.feature_gated_modules.clone());
+
+        self.module_items.extend(other.module_items.clone());
     }
 
     /// Sorts modules and traits for consistent ordering
@@ -291,6 +473,15 @@ impl ProjectContext {
 
         // Sort traits by name
         self.traits.sort_by(|a, b| a.name.cmp(&b.name));
+
+        // Several use items can produce the same module edge
+        self.module_dependencies.sort();
+        self.module_dependencies.dedup();
+
+        self.feature_gated_modules.sort();
+        self.feature_gated_modules.dedup();
+
+        self.module_items.sort_by(|a, b| a.module.cmp(&b.module));
     }
 }
 
@@ -509,4 +700,175 @@ mod tests {
 
         // temp_dir will be automatically cleaned up when it goes out of scope
     }
+
+    #[test]
+    fn test_module_dependencies_are_merged_and_deduplicated() {
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().expect("Failed to create temporary directory");
+        let edge = |from: &str, to: &str| ModuleDependency {
+            from: from.to_string(),
+            to: to.to_string(),
+        };
+
+        let mut context1 = ProjectContext::with_base_dir(temp_dir.path());
+        context1.module_root = "crate1".to_string();
+        context1.module_dependencies = vec![
+            edge("crate1::api", "crate2::db"),
+            edge("crate1::api", "crate1::models"),
+            edge("crate1::api", "crate2::db"),
+        ];
+        context1.serialize_to_file().unwrap();
+
+        let mut context2 = ProjectContext::with_base_dir(temp_dir.path());
+        context2.module_root = "crate2".to_string();
+        context2.module_dependencies = vec![edge("crate2::db", "crate2::models")];
+        context2.serialize_to_file().unwrap();
+
+        let (loaded, _) = ProjectContext::load_all_contexts_from_dir(temp_dir.path()).unwrap();
+        assert_eq!(
+            loaded.module_dependencies,
+            vec![
+                edge("crate1::api", "crate1::models"),
+                edge("crate1::api", "crate2::db"),
+                edge("crate2::db", "crate2::models"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_concurrent_serialization_is_atomic() {
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().expect("Failed to create temporary directory");
+        let test_dir_path = temp_dir.path().to_path_buf();
+
+        // Several writers racing on the same crate's context file
+        let writers: Vec<_> = (0..8)
+            .map(|i| {
+                let dir = test_dir_path.clone();
+                std::thread::spawn(move || {
+                    let mut context = ProjectContext::with_base_dir(&dir);
+                    context.module_root = "crate1".to_string();
+                    context.modules = (0..100)
+                        .map(|m| ModuleInfo {
+                            name: format!("crate1::writer{i}::module{m}"),
+                            applicable_lints: vec![],
+                        })
+                        .collect();
+                    context
+                        .serialize_to_file()
+                        .expect("Failed to serialize context");
+                })
+            })
+            .collect();
+        for writer in writers {
+            writer.join().expect("Writer thread panicked");
+        }
+
+        // Exactly one complete context survives, and no temporary files are left behind
+        let (context, _) = ProjectContext::load_all_contexts_from_dir(&test_dir_path)
+            .expect("Failed to load contexts");
+        assert_eq!(context.modules.len(), 100);
+        assert_eq!(fs::read_dir(&test_dir_path).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_context_freshness_tracks_fingerprint() {
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().expect("Failed to create temporary directory");
+        let test_dir_path = temp_dir.path();
+        let source = test_dir_path.join("lib.rs");
+        fs::write(&source, "pub mod a;").unwrap();
+        let cargo_args = vec!["--all-features".to_string()];
+
+        let mut context = ProjectContext::with_base_dir(test_dir_path);
+        context.module_root = "crate1".to_string();
+
+        // Nothing has been written yet
+        assert!(!ProjectContext::contexts_are_fresh(
+            test_dir_path,
+            "args",
+            &cargo_args
+        ));
+
+        // A context without a fingerprint can't be trusted
+        context
+            .serialize_to_file()
+            .expect("Failed to serialize context");
+        assert!(!ProjectContext::contexts_are_fresh(
+            test_dir_path,
+            "args",
+            &cargo_args
+        ));
+
+        let fingerprint =
+            SourceFingerprint::new("args".to_string(), cargo_args.clone(), [source.clone()]);
+        context
+            .serialize_to_file_with_fingerprint(&fingerprint)
+            .expect("Failed to serialize context");
+
+        // The same arguments over the same files are fresh, other arguments are not
+        assert!(ProjectContext::contexts_are_fresh(
+            test_dir_path,
+            "args",
+            &cargo_args
+        ));
+        assert!(!ProjectContext::contexts_are_fresh(
+            test_dir_path,
+            "other args",
+            &cargo_args
+        ));
+        assert!(!ProjectContext::contexts_are_fresh(
+            test_dir_path,
+            "args",
+            &[]
+        ));
+
+        // Fingerprint files are not picked up as contexts
+        let (_, crate_names) = ProjectContext::load_all_contexts_from_dir(test_dir_path)
+            .expect("Failed to load contexts");
+        assert_eq!(crate_names, vec!["crate1".to_string()]);
+
+        // Changing a source file makes the context stale
+        let modified = fs::metadata(&source).unwrap().modified().unwrap();
+        fs::File::options()
+            .write(true)
+            .open(&source)
+            .unwrap()
+            .set_modified(modified + std::time::Duration::from_secs(1))
+            .unwrap();
+        assert!(!ProjectContext::contexts_are_fresh(
+            test_dir_path,
+            "args",
+            &cargo_args
+        ));
+
+        // So does removing one
+        context
+            .serialize_to_file_with_fingerprint(&SourceFingerprint::new(
+                "args".to_string(),
+                cargo_args.clone(),
+                [source.clone()],
+            ))
+            .expect("Failed to serialize context");
+        assert!(ProjectContext::contexts_are_fresh(
+            test_dir_path,
+            "args",
+            &cargo_args
+        ));
+        fs::remove_file(&source).unwrap();
+        assert!(!ProjectContext::contexts_are_fresh(
+            test_dir_path,
+            "args",
+            &cargo_args
+        ));
+
+        // Cleaning removes the fingerprint too
+        context
+            .clean_context_files()
+            .expect("Failed to clean context files");
+        assert_eq!(fs::read_dir(test_dir_path).unwrap().count(), 0);
+    }
 }
@@ -0,0 +1,192 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//! Aggregate figures about a codebase for `cargo pup stats`, worked out from the
+//! project contexts of its crates. Recording them over time shows how the
+//! architecture drifts.
+
+use crate::project_context::{ModuleItems, ProjectContext};
+use serde::{Deserialize, Serialize};
+
+/// Aggregate metrics across every crate in a project context
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProjectStats {
+    /// Number of crates analysed
+    pub crates: usize,
+    /// Number of modules, not counting the crate roots
+    pub modules: usize,
+    /// Number of traits declared
+    pub traits: usize,
+    /// Number of trait implementations, counting each implementing type once per trait
+    pub trait_impls: usize,
+    /// Number of structs declared
+    pub structs: usize,
+    /// Number of free functions and methods in impl blocks
+    pub functions: usize,
+    /// Number of distinct imports from one module to another
+    pub module_imports: usize,
+    /// Average depth of the modules below their crate root, e.g. 2 for `app::api::v1`
+    pub average_module_depth: f64,
+    /// Depth of the most deeply nested module
+    pub max_module_depth: usize,
+    /// The module declaring the most items; the first by name on a tie
+    pub largest_module: Option<ModuleItems>,
+}
+
+impl ProjectStats {
+    /// Work out the metrics for a merged project context and the crates it was built from
+    pub fn from_context(context: &ProjectContext, crate_names: &[String]) -> Self {
+        let depths: Vec<usize> = context
+            .modules
+            .iter()
+            .map(|module| module.name.matches("::").count())
+            .collect();
+        let average_module_depth = if depths.is_empty() {
+            0.0
+        } else {
+            depths.iter().sum::<usize>() as f64 / depths.len() as f64
+        };
+
+        let largest_module = context
+            .module_items
+            .iter()
+            .filter(|items| items.total > 0)
+            .fold(None::<&ModuleItems>, |largest, items| match largest {
+                Some(largest) if largest.total >= items.total => Some(largest),
+                _ => Some(items),
+            })
+            .cloned();
+
+        ProjectStats {
+            crates: crate_names.len(),
+            modules: context.modules.len(),
+            traits: context.traits.len(),
+            trait_impls: context
+                .traits
+                .iter()
+                .map(|trait_info| trait_info.implementors.len())
+                .sum(),
+            structs: context.module_items.iter().map(|items| items.structs).sum(),
+            functions: context
+                .module_items
+                .iter()
+                .map(|items| items.functions)
+                .sum(),
+            module_imports: context.module_dependencies.len(),
+            average_module_depth,
+            max_module_depth: depths.into_iter().max().unwrap_or(0),
+            largest_module,
+        }
+    }
+
+    /// Render the metrics as an aligned table, one per line
+    pub fn render_text(&self) -> String {
+        let largest = match &self.largest_module {
+            Some(module) => format!("{} ({} items)", module.module, module.total),
+            None => "-".to_string(),
+        };
+        let rows = [
+            ("Crates", self.crates.to_string()),
+            ("Modules", self.modules.to_string()),
+            ("Traits", self.traits.to_string()),
+            ("Trait implementations", self.trait_impls.to_string()),
+            ("Structs", self.structs.to_string()),
+            ("Functions", self.functions.to_string()),
+            ("Module imports", self.module_imports.to_string()),
+            (
+                "Average module depth",
+                format!("{:.2}", self.average_module_depth),
+            ),
+            ("Deepest module depth", self.max_module_depth.to_string()),
+            ("Largest module", largest),
+        ];
+
+        let width = rows.iter().map(|(label, _)| label.len()).max().unwrap_or(0);
+        rows.iter()
+            .map(|(label, value)| format!("{label:width$}  {value}\n"))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project_context::{ModuleDependency, TraitInfo};
+
+    fn items(module: &str, structs: usize, functions: usize, total: usize) -> ModuleItems {
+        ModuleItems {
+            module: module.to_string(),
+            structs,
+            traits: 0,
+            functions,
+            total,
+        }
+    }
+
+    fn sample_context() -> ProjectContext {
+        let mut context = ProjectContext::with_data(
+            vec![
+                "app::api".to_string(),
+                "app::api::v1".to_string(),
+                "models::user".to_string(),
+            ],
+            "app".to_string(),
+            vec![TraitInfo {
+                name: "app::Handler".to_string(),
+                implementors: vec![
+                    "app::api::Users".to_string(),
+                    "app::api::v1::Orders".to_string(),
+                ],
+                applicable_lints: Vec::new(),
+            }],
+        );
+        context.module_items = vec![
+            items("app", 0, 1, 2),
+            items("app::api", 1, 4, 6),
+            items("app::api::v1", 2, 2, 6),
+            items("models", 0, 0, 1),
+            items("models::user", 1, 0, 1),
+        ];
+        context.module_dependencies = vec![ModuleDependency {
+            from: "app::api".to_string(),
+            to: "models::user".to_string(),
+        }];
+        context
+    }
+
+    #[test]
+    fn test_stats_from_context() {
+        let stats = ProjectStats::from_context(
+            &sample_context(),
+            &["app".to_string(), "models".to_string()],
+        );
+
+        assert_eq!(stats.crates, 2);
+        assert_eq!(stats.modules, 3);
+        assert_eq!(stats.traits, 1);
+        assert_eq!(stats.trait_impls, 2);
+        assert_eq!(stats.structs, 4);
+        assert_eq!(stats.functions, 7);
+        assert_eq!(stats.module_imports, 1);
+        assert!((stats.average_module_depth - 4.0 / 3.0).abs() < f64::EPSILON);
+        assert_eq!(stats.max_module_depth, 2);
+        // Ties go to the module that sorts first
+        assert_eq!(stats.largest_module, Some(items("app::api", 1, 4, 6)));
+    }
+
+    #[test]
+    fn test_render_text() {
+        let stats = ProjectStats::from_context(&sample_context(), &["app".to_string()]);
+        let text = stats.render_text();
+
+        assert!(text.starts_with("Crates                 1\n"), "{text}");
+        assert!(text.contains("Average module depth   1.33\n"), "{text}");
+        assert!(
+            text.ends_with("Largest module         app::api (6 items)\n"),
+            "{text}"
+        );
+
+        // With nothing analysed there's no largest module
+        let empty = ProjectStats::from_context(&ProjectContext::new(), &[]);
+        assert!(empty.render_text().ends_with("Largest module         -\n"));
+    }
+}
@@ -0,0 +1,167 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//! Per-lint timings for `cargo pup check --timings`. Each crate's pup-driver adds the
+//! time its lints spent checking items to a shared JSON file, and cargo-pup renders
+//! the totals once the whole workspace has been checked.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Read, Seek, Write};
+use std::path::Path;
+use std::time::Duration;
+
+/// Where the timings of the last `check --timings` are kept, within the `.pup` directory
+pub const TIMINGS_FILE: &str = "timings.json";
+
+/// Seconds spent by each configured lint, keyed by crate and then by lint name
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LintTimings {
+    pub crates: BTreeMap<String, BTreeMap<String, f64>>,
+}
+
+impl LintTimings {
+    /// Add the time each lint spent checking a crate to the timings at `path`, creating
+    /// the file if needed. The file is locked while it's updated, so drivers running in
+    /// parallel don't lose each other's timings.
+    pub fn record(
+        path: &Path,
+        crate_name: &str,
+        lints: impl IntoIterator<Item = (String, Duration)>,
+    ) -> io::Result<()> {
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)?;
+        file.lock()?;
+
+        let mut content = String::new();
+        file.read_to_string(&mut content)?;
+        let mut timings: LintTimings = if content.trim().is_empty() {
+            LintTimings::default()
+        } else {
+            serde_json::from_str(&content).map_err(io::Error::other)?
+        };
+
+        // A crate can be checked more than once, e.g. for its library and its binaries
+        let crate_timings = timings.crates.entry(crate_name.to_string()).or_default();
+        for (lint_name, elapsed) in lints {
+            *crate_timings.entry(lint_name).or_default() += elapsed.as_secs_f64();
+        }
+
+        let content = serde_json::to_string_pretty(&timings).map_err(io::Error::other)?;
+        file.set_len(0)?;
+        file.rewind()?;
+        file.write_all(content.as_bytes())?;
+        file.unlock()
+    }
+
+    /// Read the timings at `path`. Timings that don't exist are empty, as no crate
+    /// was checked.
+    pub fn read(path: &Path) -> io::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(content) => serde_json::from_str(&content).map_err(io::Error::other),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(LintTimings::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// The time each lint spent across all crates, slowest first, with ties in name order
+    pub fn totals(&self) -> Vec<(String, Duration)> {
+        let mut totals: BTreeMap<&str, f64> = BTreeMap::new();
+        for lints in self.crates.values() {
+            for (lint_name, seconds) in lints {
+                *totals.entry(lint_name.as_str()).or_default() += seconds;
+            }
+        }
+        let mut totals: Vec<(String, Duration)> = totals
+            .into_iter()
+            .map(|(name, seconds)| (name.to_string(), Duration::from_secs_f64(seconds)))
+            .collect();
+        totals.sort_by(|a, b| b.1.cmp(&a.1));
+        totals
+    }
+
+    /// Render the totals as an aligned table, slowest lint first, with each lint's share
+    /// of the time spent linting
+    pub fn render_text(&self) -> String {
+        let totals = self.totals();
+        let total: Duration = totals.iter().map(|(_, elapsed)| *elapsed).sum();
+        let width = totals.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+
+        let mut out = format!(
+            "Lint timings across {} crate(s), {total:.2?} in total:\n",
+            self.crates.len()
+        );
+        for (name, elapsed) in &totals {
+            let share = if total.is_zero() {
+                0.0
+            } else {
+                elapsed.as_secs_f64() / total.as_secs_f64() * 100.0
+            };
+            out.push_str(&format!(
+                "  {name:width$}  {:>10}  {share:>5.1}%\n",
+                format!("{elapsed:.2?}")
+            ));
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_records_from_each_crate_are_read_back_together() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join(TIMINGS_FILE);
+
+        // Nothing has been timed yet
+        assert_eq!(LintTimings::read(&path).unwrap(), LintTimings::default());
+
+        let ms = Duration::from_millis;
+        LintTimings::record(&path, "api", [("no_unwrap".to_string(), ms(10))]).unwrap();
+        LintTimings::record(
+            &path,
+            "core",
+            [
+                ("no_unwrap".to_string(), ms(5)),
+                ("no_allocation".to_string(), ms(40)),
+            ],
+        )
+        .unwrap();
+        // A second target of the same crate adds to its timings
+        LintTimings::record(&path, "api", [("no_unwrap".to_string(), ms(10))]).unwrap();
+
+        let timings = LintTimings::read(&path).unwrap();
+        assert_eq!(timings.crates.len(), 2);
+        assert!((timings.crates["api"]["no_unwrap"] - 0.02).abs() < 1e-9);
+
+        let totals = timings.totals();
+        assert_eq!(totals[0].0, "no_allocation");
+        assert_eq!(totals[1].0, "no_unwrap");
+        assert!((totals[1].1.as_secs_f64() - 0.025).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_render_text() {
+        let mut timings = LintTimings::default();
+        timings.crates.insert(
+            "app".to_string(),
+            BTreeMap::from([
+                ("no_unwrap".to_string(), 0.25),
+                ("no_allocation".to_string(), 0.75),
+            ]),
+        );
+
+        assert_eq!(
+            timings.render_text(),
+            "Lint timings across 1 crate(s), 1.00s in total:\n\
+             \x20 no_allocation    750.00ms   75.0%\n\
+             \x20 no_unwrap        250.00ms   25.0%\n"
+        );
+    }
+}
@@ -1,15 +1,154 @@
 // This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
 
 use cargo_metadata::MetadataCommand;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+/// The name of the configuration file pup looks for
+const PUP_RON: &str = "pup.ron";
+
+/// Find the workspace root using cargo metadata
+pub fn find_workspace_root() -> Option<PathBuf> {
+    let metadata = MetadataCommand::new().no_deps().exec().ok()?;
+    Some(metadata.workspace_root.into_std_path_buf())
+}
 
 /// Find pup.ron in workspace root using cargo metadata
 pub fn find_workspace_pup_ron() -> Option<PathBuf> {
-    let metadata = MetadataCommand::new().no_deps().exec().ok()?;
-    let pup_ron = metadata.workspace_root.join("pup.ron");
+    let pup_ron = find_workspace_root()?.join(PUP_RON);
     if pup_ron.exists() {
-        Some(pup_ron.into_std_path_buf())
+        Some(pup_ron)
     } else {
         None
     }
 }
+
+/// Find the pup.ron closest to `start`, looking in `start` itself and then in each of
+/// its parents. If `root` is given and `start` is inside it, the search stops at
+/// `root`, so configuration files above the workspace are never picked up.
+pub fn find_nearest_pup_ron(start: &Path, root: Option<&Path>) -> Option<PathBuf> {
+    // Compare canonical paths, so a relative start like "." or a symlinked root
+    // still finds its parents and stops where it should
+    let start = start.canonicalize().unwrap_or_else(|_| start.to_path_buf());
+    let root = root.map(|root| root.canonicalize().unwrap_or_else(|_| root.to_path_buf()));
+    let root = root.filter(|root| start.starts_with(root));
+
+    for dir in start.ancestors() {
+        let pup_ron = dir.join(PUP_RON);
+        if pup_ron.is_file() {
+            return Some(pup_ron);
+        }
+        if root.as_deref() == Some(dir) {
+            break;
+        }
+    }
+    None
+}
+
+/// Find the pup.ron that applies to the crate whose manifest is in `crate_dir`: the
+/// nearest one, searching no higher than the workspace root
+pub fn find_crate_pup_ron(crate_dir: &Path) -> Option<PathBuf> {
+    find_nearest_pup_ron(crate_dir, find_workspace_root().as_deref())
+}
+
+/// Find the pup.ron files that apply to the workspace members, one for each member
+/// that has one, without duplicates
+pub fn find_member_pup_rons() -> Vec<PathBuf> {
+    let Ok(metadata) = MetadataCommand::new().no_deps().exec() else {
+        return Vec::new();
+    };
+
+    let mut configs = Vec::new();
+    for package in metadata.workspace_packages() {
+        let Some(crate_dir) = package.manifest_path.parent() else {
+            continue;
+        };
+        if let Some(config) = find_nearest_pup_ron(
+            crate_dir.as_std_path(),
+            Some(metadata.workspace_root.as_std_path()),
+        ) && !configs.contains(&config)
+        {
+            configs.push(config);
+        }
+    }
+    configs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    // Lays out a workspace with configuration at two levels:
+    //   workspace/pup.ron
+    //   workspace/crates/api/pup.ron
+    //   workspace/crates/core/          (no configuration of its own)
+    fn workspace_with_configs() -> (tempfile::TempDir, PathBuf) {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let workspace = temp_dir.path().join("workspace");
+        for dir in ["crates/api/src", "crates/core/src"] {
+            fs::create_dir_all(workspace.join(dir)).unwrap();
+        }
+        fs::write(workspace.join(PUP_RON), "(lints: [])").unwrap();
+        fs::write(workspace.join("crates/api").join(PUP_RON), "(lints: [])").unwrap();
+        let workspace = workspace.canonicalize().unwrap();
+        (temp_dir, workspace)
+    }
+
+    #[test]
+    fn test_nearest_config_wins() {
+        let (_temp_dir, workspace) = workspace_with_configs();
+        let api_config = workspace.join("crates/api").join(PUP_RON);
+
+        assert_eq!(
+            find_nearest_pup_ron(&workspace.join("crates/api"), Some(&workspace)),
+            Some(api_config.clone())
+        );
+        // Searching from deeper inside the crate finds the same file
+        assert_eq!(
+            find_nearest_pup_ron(&workspace.join("crates/api/src"), Some(&workspace)),
+            Some(api_config)
+        );
+    }
+
+    #[test]
+    fn test_falls_back_to_parent_config() {
+        let (_temp_dir, workspace) = workspace_with_configs();
+
+        assert_eq!(
+            find_nearest_pup_ron(&workspace.join("crates/core"), Some(&workspace)),
+            Some(workspace.join(PUP_RON))
+        );
+        assert_eq!(
+            find_nearest_pup_ron(&workspace.join("crates/core"), None),
+            Some(workspace.join(PUP_RON))
+        );
+    }
+
+    #[test]
+    fn test_search_stops_at_root() {
+        let (_temp_dir, workspace) = workspace_with_configs();
+        let crates = workspace.join("crates");
+
+        // Configuration above the root is out of bounds
+        assert_eq!(
+            find_nearest_pup_ron(&crates.join("core"), Some(&crates)),
+            None
+        );
+        // A root that doesn't contain the start doesn't limit the search
+        assert_eq!(
+            find_nearest_pup_ron(&crates.join("core"), Some(&crates.join("api"))),
+            Some(workspace.join(PUP_RON))
+        );
+    }
+
+    #[test]
+    fn test_directory_named_like_config_is_ignored() {
+        let (_temp_dir, workspace) = workspace_with_configs();
+        fs::create_dir(workspace.join("crates/core").join(PUP_RON)).unwrap();
+
+        assert_eq!(
+            find_nearest_pup_ron(&workspace.join("crates/core"), Some(&workspace)),
+            Some(workspace.join(PUP_RON))
+        );
+    }
+}
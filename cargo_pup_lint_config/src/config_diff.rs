@@ -0,0 +1,277 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//! Compares two configurations for `cargo pup diff`. Lints are paired up by name, so
+//! a renamed lint shows up as one removed and one added.
+
+use crate::{ConfiguredLint, Severity};
+use serde::Serialize;
+
+/// How a lint differs between two configurations
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LintDiff {
+    /// The lint is only in the new configuration
+    Added { name: String, kind: String },
+    /// The lint is only in the old configuration
+    Removed { name: String, kind: String },
+    /// The lint is in both, with the given differences, one per line
+    Changed {
+        name: String,
+        kind: String,
+        changes: Vec<String>,
+    },
+}
+
+/// Compare the lints of two configurations. Added and changed lints come in the order
+/// of the new configuration, followed by the removed lints in the order of the old one.
+pub fn diff_lints(from: &[ConfiguredLint], to: &[ConfiguredLint]) -> Vec<LintDiff> {
+    let mut diffs = Vec::new();
+    for new in to {
+        let name = new.name().to_string();
+        let kind = new.kind().to_string();
+        match from.iter().find(|old| old.name() == new.name()) {
+            None => diffs.push(LintDiff::Added { name, kind }),
+            Some(old) => {
+                let changes = lint_changes(old, new);
+                if !changes.is_empty() {
+                    diffs.push(LintDiff::Changed {
+                        name,
+                        kind,
+                        changes,
+                    });
+                }
+            }
+        }
+    }
+    for old in from {
+        if !to.iter().any(|new| new.name() == old.name()) {
+            diffs.push(LintDiff::Removed {
+                name: old.name().to_string(),
+                kind: old.kind().to_string(),
+            });
+        }
+    }
+    diffs
+}
+
+/// Render the differences as plain text, grouped into added, removed and changed
+/// lints, followed by a summary
+pub fn render_diff(diffs: &[LintDiff]) -> String {
+    let mut added = String::new();
+    let mut removed = String::new();
+    let mut changed = String::new();
+    for diff in diffs {
+        match diff {
+            LintDiff::Added { name, kind } => added.push_str(&format!("  + {name} ({kind})\n")),
+            LintDiff::Removed { name, kind } => removed.push_str(&format!("  - {name} ({kind})\n")),
+            LintDiff::Changed {
+                name,
+                kind,
+                changes,
+            } => {
+                changed.push_str(&format!("  ~ {name} ({kind})\n"));
+                for change in changes {
+                    changed.push_str(&format!("      {change}\n"));
+                }
+            }
+        }
+    }
+
+    let mut out = String::new();
+    for (heading, lines) in [
+        ("Added", &added),
+        ("Removed", &removed),
+        ("Changed", &changed),
+    ] {
+        if !lines.is_empty() {
+            out.push_str(&format!("{heading}\n{lines}\n"));
+        }
+    }
+
+    let count = |f: fn(&LintDiff) -> bool| diffs.iter().filter(|diff| f(diff)).count();
+    if diffs.is_empty() {
+        out.push_str("No lints were added, removed or changed\n");
+    } else {
+        out.push_str(&format!(
+            "{} added, {} removed, {} changed\n",
+            count(|diff| matches!(diff, LintDiff::Added { .. })),
+            count(|diff| matches!(diff, LintDiff::Removed { .. })),
+            count(|diff| matches!(diff, LintDiff::Changed { .. })),
+        ));
+    }
+    out
+}
+
+// Lists what changed in a lint that's in both configurations. Rules are compared as a
+// whole, so a rule whose severity or settings changed is listed as removed and added.
+fn lint_changes(old: &ConfiguredLint, new: &ConfiguredLint) -> Vec<String> {
+    let mut changes = Vec::new();
+    if old.kind() != new.kind() {
+        changes.push(format!("kind: {} -> {}", old.kind(), new.kind()));
+    }
+    if old.severity() != new.severity() {
+        changes.push(format!(
+            "severity: {} -> {}",
+            severity_name(old.severity()),
+            severity_name(new.severity())
+        ));
+    }
+    if old.escalate_after() != new.escalate_after() {
+        let escalate_after = |lint: &ConfiguredLint| match lint.escalate_after() {
+            Some(after) => after.to_string(),
+            None => "never".to_string(),
+        };
+        changes.push(format!(
+            "escalate_after: {} -> {}",
+            escalate_after(old),
+            escalate_after(new)
+        ));
+    }
+
+    let (old_matches, old_rules) = lint_parts(old);
+    let (new_matches, new_rules) = lint_parts(new);
+    if old_matches != new_matches {
+        changes.push(format!("matches: {old_matches} -> {new_matches}"));
+    }
+    for rule in old_rules.iter().filter(|rule| !new_rules.contains(rule)) {
+        changes.push(format!("- rule {rule}"));
+    }
+    for rule in new_rules.iter().filter(|rule| !old_rules.contains(rule)) {
+        changes.push(format!("+ rule {rule}"));
+    }
+    changes
+}
+
+// The lint's matcher and rules, each written as RON
+fn lint_parts(lint: &ConfiguredLint) -> (String, Vec<String>) {
+    fn ron<T: Serialize>(value: &T) -> String {
+        ron::to_string(value).unwrap_or_else(|e| format!("<{e}>"))
+    }
+    match lint {
+        ConfiguredLint::Module(lint) => (ron(&lint.matches), lint.rules.iter().map(ron).collect()),
+        ConfiguredLint::Struct(lint) => (ron(&lint.matches), lint.rules.iter().map(ron).collect()),
+        ConfiguredLint::Function(lint) => {
+            (ron(&lint.matches), lint.rules.iter().map(ron).collect())
+        }
+        ConfiguredLint::Trait(lint) => (ron(&lint.matches), lint.rules.iter().map(ron).collect()),
+        ConfiguredLint::Const(lint) => (ron(&lint.matches), lint.rules.iter().map(ron).collect()),
+        ConfiguredLint::Enum(lint) => (ron(&lint.matches), lint.rules.iter().map(ron).collect()),
+    }
+}
+
+fn severity_name(severity: Option<Severity>) -> &'static str {
+    match severity {
+        Some(Severity::Error) => "error",
+        Some(Severity::Warn) => "warn",
+        None => "none",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FunctionLintExt, LintBuilder, ModuleLintExt};
+
+    fn from_config() -> LintBuilder {
+        let mut builder = LintBuilder::new();
+        builder
+            .function_lint()
+            .lint_named("no_unwrap")
+            .matching(|m| m.in_module("^app::"))
+            .no_unwrap()
+            .build();
+        builder
+            .module_lint()
+            .lint_named("shallow")
+            .matching(|m| m.module(".*"))
+            .max_depth(3)
+            .build();
+        builder
+            .module_lint()
+            .lint_named("unchanged")
+            .matching(|m| m.module("^app$"))
+            .must_not_be_empty()
+            .build();
+        builder
+    }
+
+    #[test]
+    fn test_diff_lints() {
+        let mut to = LintBuilder::new();
+        to.function_lint()
+            .lint_named("no_unwrap")
+            .matching(|m| m.in_module("^app::api"))
+            .with_severity(Severity::Error)
+            .no_unwrap()
+            .build();
+        to.module_lint()
+            .lint_named("unchanged")
+            .matching(|m| m.module("^app$"))
+            .must_not_be_empty()
+            .build();
+        to.module_lint()
+            .lint_named("no_wildcards")
+            .matching(|m| m.module(".*"))
+            .no_wildcard_imports()
+            .build();
+
+        let diffs = diff_lints(&from_config().lints, &to.lints);
+
+        assert_eq!(
+            diffs,
+            vec![
+                LintDiff::Changed {
+                    name: "no_unwrap".to_string(),
+                    kind: "function".to_string(),
+                    changes: vec![
+                        "severity: warn -> error".to_string(),
+                        r#"matches: InModule("^app::") -> InModule("^app::api")"#.to_string(),
+                        "- rule NoUnwrap(Warn)".to_string(),
+                        "+ rule NoUnwrap(Error)".to_string(),
+                    ],
+                },
+                LintDiff::Added {
+                    name: "no_wildcards".to_string(),
+                    kind: "module".to_string(),
+                },
+                LintDiff::Removed {
+                    name: "shallow".to_string(),
+                    kind: "module".to_string(),
+                },
+            ]
+        );
+
+        // A configuration doesn't differ from itself
+        assert!(diff_lints(&from_config().lints, &from_config().lints).is_empty());
+    }
+
+    #[test]
+    fn test_render_diff() {
+        let diffs = vec![
+            LintDiff::Added {
+                name: "no_wildcards".to_string(),
+                kind: "module".to_string(),
+            },
+            LintDiff::Changed {
+                name: "no_unwrap".to_string(),
+                kind: "function".to_string(),
+                changes: vec!["severity: warn -> error".to_string()],
+            },
+        ];
+
+        assert_eq!(
+            render_diff(&diffs),
+            "Added\n\
+             \x20 + no_wildcards (module)\n\
+             \n\
+             Changed\n\
+             \x20 ~ no_unwrap (function)\n\
+             \x20     severity: warn -> error\n\
+             \n\
+             1 added, 0 removed, 1 changed\n"
+        );
+        assert_eq!(
+            render_diff(&[]),
+            "No lints were added, removed or changed\n"
+        );
+    }
+}
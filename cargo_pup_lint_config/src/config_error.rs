@@ -0,0 +1,298 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//! Describes configuration files that fail to parse in pup's own terms - lint types,
+//! rules and matchers - rather than as raw serde errors.
+
+use ron::error::{Error, SpannedError};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// A lint configuration that couldn't be parsed, pointing at the offending text
+#[derive(Debug)]
+pub struct ConfigError {
+    /// The file the configuration was read from, if known
+    pub path: Option<PathBuf>,
+    /// Line of the offending text, counting from 1
+    pub line: usize,
+    /// Column of the offending text, counting from 1
+    pub column: usize,
+    /// What's wrong, e.g. "unknown function rule `MaxLenght`"
+    pub message: String,
+    /// Suggestions for fixing it, such as the valid names at that position
+    pub help: Vec<String>,
+    // The offending line and how many columns to underline
+    source_line: String,
+    width: usize,
+}
+
+impl ConfigError {
+    /// Describe a RON error in the given configuration content
+    pub fn from_ron(content: &str, error: SpannedError) -> Self {
+        let start = error.span.start;
+        let end = error.span.end;
+        let source_line = content
+            .lines()
+            .nth(start.line.saturating_sub(1))
+            .unwrap_or_default()
+            .to_string();
+        let width = if end.line == start.line {
+            end.col.saturating_sub(start.col).max(1)
+        } else {
+            1
+        };
+
+        let (message, help) = describe(&error.code);
+        ConfigError {
+            path: None,
+            line: start.line,
+            column: start.col,
+            message,
+            help,
+            source_line,
+            width,
+        }
+    }
+
+    /// Attribute the error to the file the configuration was read from
+    pub fn in_file(mut self, path: &Path) -> Self {
+        self.path = Some(path.to_path_buf());
+        self
+    }
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(path) = &self.path {
+            write!(f, "{}:", path.display())?;
+        }
+        writeln!(f, "{}:{}: {}", self.line, self.column, self.message)?;
+
+        let gutter = self.line.to_string().len();
+        writeln!(f, "{:gutter$} |", "")?;
+        writeln!(f, "{} | {}", self.line, self.source_line)?;
+        write!(
+            f,
+            "{:gutter$} | {:indent$}{}",
+            "",
+            "",
+            "^".repeat(self.width),
+            indent = self.column.saturating_sub(1)
+        )?;
+        for help in &self.help {
+            write!(f, "\n{:gutter$} = help: {help}", "")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+// The message and help for a RON error, naming the configuration's types the way
+// the documentation does
+fn describe(error: &Error) -> (String, Vec<String>) {
+    match error {
+        Error::NoSuchEnumVariant {
+            expected,
+            found,
+            outer,
+        } => {
+            let kind = outer
+                .as_deref()
+                .map_or("variant".to_string(), describe_enum);
+            let mut help = Vec::new();
+            if let Some(suggestion) = closest_match(found, expected) {
+                help.push(format!("did you mean `{suggestion}`?"));
+            }
+            help.push(format!("valid {kind}s are {}", list(expected)));
+            (format!("unknown {kind} `{found}`"), help)
+        }
+        Error::NoSuchStructField {
+            expected,
+            found,
+            outer,
+        } => {
+            let mut help = Vec::new();
+            if let Some(suggestion) = closest_match(found, expected) {
+                help.push(format!("did you mean `{suggestion}`?"));
+            }
+            help.push(format!("valid fields are {}", list(expected)));
+            let container = outer.as_deref().map_or("here".to_string(), |outer| {
+                format!("in {}", describe_struct(outer))
+            });
+            (format!("unknown field `{found}` {container}"), help)
+        }
+        Error::MissingStructField { field, outer } => {
+            let container = outer.as_deref().map_or("here".to_string(), |outer| {
+                format!("in {}", describe_struct(outer))
+            });
+            (format!("missing field `{field}` {container}"), Vec::new())
+        }
+        Error::DuplicateStructField { field, outer } => {
+            let container = outer.as_deref().map_or("here".to_string(), |outer| {
+                format!("in {}", describe_struct(outer))
+            });
+            (
+                format!("field `{field}` is given twice {container}"),
+                Vec::new(),
+            )
+        }
+        other => (other.to_string(), Vec::new()),
+    }
+}
+
+// Name an enum from the configuration by what it selects, e.g. "function rule"
+fn describe_enum(name: &str) -> String {
+    let kind = |prefix: &str| prefix.to_lowercase();
+    if name == "ConfiguredLint" {
+        "lint type".to_string()
+    } else if let Some(prefix) = name.strip_suffix("Rule") {
+        format!("{} rule", kind(prefix))
+    } else if let Some(prefix) = name.strip_suffix("Match") {
+        format!("{} matcher", kind(prefix))
+    } else {
+        match name {
+            "Severity" => "severity".to_string(),
+            "ReturnTypePattern" => "return type pattern".to_string(),
+            "CaseStyle" => "case style".to_string(),
+            other => format!("`{other}` variant"),
+        }
+    }
+}
+
+// Name a struct or struct-like rule from the configuration, e.g. "function lint"
+fn describe_struct(name: &str) -> String {
+    match name {
+        "LintBuilder" => "the configuration".to_string(),
+        "ModuleLint" => "module lint".to_string(),
+        "StructLint" => "struct lint".to_string(),
+        "FunctionLint" => "function lint".to_string(),
+        "TraitLint" => "trait lint".to_string(),
+        "ConstLint" => "const lint".to_string(),
+        "EnumLint" => "enum lint".to_string(),
+        other => format!("`{other}`"),
+    }
+}
+
+fn list(names: &[&str]) -> String {
+    names
+        .iter()
+        .map(|name| format!("`{name}`"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+// The candidate closest to `found`, if it's close enough to be a likely typo or
+// an abbreviated or extended form of it, like `Warning` for `Warn`
+fn closest_match<'a>(found: &str, candidates: &[&'a str]) -> Option<&'a str> {
+    let found = found.to_lowercase();
+    candidates
+        .iter()
+        .filter_map(|candidate| {
+            let lowercase = candidate.to_lowercase();
+            let distance = edit_distance(&found, &lowercase);
+            let close = distance <= found.len().max(lowercase.len()) / 3
+                || (!found.is_empty()
+                    && (found.starts_with(&lowercase) || lowercase.starts_with(&found)));
+            close.then_some((distance, *candidate))
+        })
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+// Levenshtein distance between two strings, by character
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::LintBuilder;
+
+    fn parse_error(content: &str) -> String {
+        LintBuilder::parse(content)
+            .expect_err("The configuration should not parse")
+            .to_string()
+    }
+
+    #[test]
+    fn test_unknown_rule_suggests_the_closest_name() {
+        let error = parse_error(
+            r#"(lints: [
+    Function((name: "short", matches: NameRegex(".*"), rules: [MaxLenght(50, Warn)])),
+])"#,
+        );
+        assert!(
+            error.starts_with("2:64: unknown function rule `MaxLenght`"),
+            "{error}"
+        );
+        assert!(error.contains("did you mean `MaxLength`?"), "{error}");
+        assert!(
+            error.contains("valid function rules are `MaxLength`, "),
+            "{error}"
+        );
+        // The offending name is underlined on a copy of its line
+        assert!(
+            error.contains(
+                "2 |     Function((name: \"short\", matches: NameRegex(\".*\"), rules: [MaxLenght(50, Warn)])),\n  |                                                                ^^^^^^^^^\n"
+            ),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn test_unknown_lint_type_and_severity() {
+        let error = parse_error(r#"(lints: [Functon((name: "a", matches: IsAsync, rules: []))])"#);
+        assert!(error.contains("unknown lint type `Functon`"), "{error}");
+        assert!(
+            error.contains("valid lint types are `Module`, `Struct`, `Function`, `Trait`"),
+            "{error}"
+        );
+
+        let error = parse_error(
+            r#"(lints: [Module((name: "a", matches: Module(".*"), rules: [MustNotBeEmpty(Warning)]))])"#,
+        );
+        assert!(error.contains("unknown severity `Warning`"), "{error}");
+        assert!(error.contains("did you mean `Warn`?"), "{error}");
+    }
+
+    #[test]
+    fn test_unrelated_names_get_no_suggestion() {
+        let error = parse_error(
+            r#"(lints: [Trait((name: "a", matches: Name(".*"), rules: [Frobnicate(Warn)]))])"#,
+        );
+        assert!(error.contains("unknown trait rule `Frobnicate`"), "{error}");
+        assert!(!error.contains("did you mean"), "{error}");
+    }
+
+    #[test]
+    fn test_missing_field_names_the_lint() {
+        let error = parse_error(r#"(lints: [Function((name: "a", rules: []))])"#);
+        assert!(
+            error.contains("missing field `matches` in function lint"),
+            "{error}"
+        );
+
+        let error = parse_error("(lintz: [])");
+        assert!(
+            error.contains("missing field `lints` in the configuration"),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(super::edit_distance("maxlenght", "maxlength"), 2);
+        assert_eq!(super::edit_distance("", "abc"), 3);
+        assert_eq!(super::edit_distance("same", "same"), 0);
+    }
+}
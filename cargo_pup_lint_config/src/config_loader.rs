@@ -0,0 +1,335 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//! Loading of configuration files the way a check sees them: `${VAR}` references are
+//! interpolated, the files in `include` are merged in, and a profile's lints are
+//! selected. Every command that reads a configuration goes through here, so they all
+//! agree on which lints it defines.
+
+use crate::ConfiguredLint;
+use crate::lint_builder::LintBuilder;
+use anyhow::Result;
+use regex::Regex;
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Load the configuration file at `path` with its includes merged in. When a `profile`
+/// is given, its lints run in place of the top-level lints. The returned builder has
+/// no includes or profiles left.
+pub fn load_config(path: &Path, profile: Option<&str>) -> Result<LintBuilder> {
+    select_profile(load_file(path, &mut Vec::new(), &mut Vec::new())?, profile)
+}
+
+/// The configuration file at `path` followed by every file it includes, directly or
+/// through other includes. Changing any of them changes the configuration.
+pub fn config_files(path: &Path) -> Result<Vec<PathBuf>> {
+    let mut loaded = Vec::new();
+    load_file(path, &mut Vec::new(), &mut loaded)?;
+    Ok(loaded)
+}
+
+/// Load a configuration given as RON content rather than a file. Includes are resolved
+/// relative to the working directory.
+pub fn load_config_str(content: &str, profile: Option<&str>) -> Result<LintBuilder> {
+    let lint_builder = LintBuilder::parse(&interpolate_env_vars(content)?)
+        .map_err(|e| anyhow::anyhow!("Failed to parse RON content: {}", e))?;
+    let lint_builder = merge_includes(
+        lint_builder,
+        Path::new("."),
+        &mut Vec::new(),
+        &mut Vec::new(),
+    )?;
+    select_profile(lint_builder, profile)
+}
+
+// Replaces the top-level lints, which are all that run without a profile, with the
+// lints of the selected profile. Naming a profile the configuration doesn't define is
+// an error, rather than quietly running the default lints.
+fn select_profile(mut lint_builder: LintBuilder, profile: Option<&str>) -> Result<LintBuilder> {
+    let mut profiles = std::mem::take(&mut lint_builder.profiles);
+    let Some(name) = profile else {
+        return Ok(lint_builder);
+    };
+    let Some(lints) = profiles.remove(name) else {
+        let available = if profiles.is_empty() {
+            "none".to_string()
+        } else {
+            profiles.keys().cloned().collect::<Vec<_>>().join(", ")
+        };
+        return Err(anyhow::anyhow!(
+            "Profile '{}' not found in the configuration (available profiles: {})",
+            name,
+            available
+        ));
+    };
+    lint_builder.lints = lints;
+    Ok(lint_builder)
+}
+
+// Loads the configuration file at `path` along with everything it includes.
+// `loading` holds the files whose includes are currently being resolved, so
+// that an include cycle can be reported rather than recursing forever, and
+// `loaded` collects every file that was read.
+fn load_file(
+    path: &Path,
+    loading: &mut Vec<PathBuf>,
+    loaded: &mut Vec<PathBuf>,
+) -> Result<LintBuilder> {
+    let canonical_path = path
+        .canonicalize()
+        .map_err(|e| anyhow::anyhow!("Failed to read lint file {}: {}", path.display(), e))?;
+    if let Some(start) = loading.iter().position(|p| *p == canonical_path) {
+        let cycle = loading[start..]
+            .iter()
+            .chain(std::iter::once(&canonical_path))
+            .map(|p| p.display().to_string())
+            .collect::<Vec<_>>()
+            .join(" -> ");
+        return Err(anyhow::anyhow!(
+            "Lint configuration includes itself: {}",
+            cycle
+        ));
+    }
+
+    let content = fs::read_to_string(path)
+        .map_err(|e| anyhow::anyhow!("Failed to read/parse lint file: {}", e))?;
+    let lint_builder = LintBuilder::parse(&interpolate_env_vars(&content)?)
+        .map_err(|e| anyhow::anyhow!("Failed to read/parse lint file: {}", e.in_file(path)))?;
+
+    if !loaded.contains(&canonical_path) {
+        loaded.push(canonical_path.clone());
+    }
+    loading.push(canonical_path);
+    let base_dir = path.parent().unwrap_or(Path::new("."));
+    let merged = merge_includes(lint_builder, base_dir, loading, loaded);
+    loading.pop();
+    merged
+}
+
+// Replaces the builder's includes with the lints they define, resolving include
+// paths against `base_dir`. Each file's lints replace same-named lints from the
+// files it includes, and later includes replace earlier ones. Profiles are merged
+// the same way, one profile name at a time.
+fn merge_includes(
+    lint_builder: LintBuilder,
+    base_dir: &Path,
+    loading: &mut Vec<PathBuf>,
+    loaded: &mut Vec<PathBuf>,
+) -> Result<LintBuilder> {
+    let mut lints: Vec<ConfiguredLint> = Vec::new();
+    let mut profiles: BTreeMap<String, Vec<ConfiguredLint>> = BTreeMap::new();
+    for include in &lint_builder.include {
+        let included = load_file(&base_dir.join(include), loading, loaded)?;
+        override_by_name(&mut lints, included.lints);
+        merge_profiles(&mut profiles, included.profiles);
+    }
+    override_by_name(&mut lints, lint_builder.lints);
+    merge_profiles(&mut profiles, lint_builder.profiles);
+
+    Ok(LintBuilder {
+        include: Vec::new(),
+        lints,
+        profiles,
+    })
+}
+
+/// Append `overrides` to `lints`, first removing any lints that share a name with one of them
+fn override_by_name(lints: &mut Vec<ConfiguredLint>, overrides: Vec<ConfiguredLint>) {
+    lints.retain(|lint| !overrides.iter().any(|o| o.name() == lint.name()));
+    lints.extend(overrides);
+}
+
+fn merge_profiles(
+    profiles: &mut BTreeMap<String, Vec<ConfiguredLint>>,
+    overrides: BTreeMap<String, Vec<ConfiguredLint>>,
+) {
+    for (name, lints) in overrides {
+        override_by_name(profiles.entry(name).or_default(), lints);
+    }
+}
+
+/// Replace each `${VAR}` in the raw configuration with the value of the environment
+/// variable `VAR`, so that patterns can adapt to where cargo-pup is run.
+fn interpolate_env_vars(content: &str) -> Result<String> {
+    interpolate_with(content, |name| env::var(name).ok())
+}
+
+/// Interpolate `${VAR}` references using `lookup`. Values are escaped so they stay
+/// valid inside RON string literals. Referencing an unset variable is an error, rather
+/// than silently expanding to an empty pattern that would match far more than intended.
+fn interpolate_with(content: &str, lookup: impl Fn(&str) -> Option<String>) -> Result<String> {
+    let reference = Regex::new(r"\$\{([A-Za-z_][A-Za-z0-9_]*)\}").unwrap();
+
+    let mut missing = Vec::new();
+    let interpolated = reference.replace_all(content, |captures: &regex::Captures<'_>| {
+        let name = &captures[1];
+        match lookup(name) {
+            Some(value) => value.replace('\\', "\\\\").replace('"', "\\\""),
+            None => {
+                missing.push(name.to_string());
+                String::new()
+            }
+        }
+    });
+
+    if !missing.is_empty() {
+        missing.sort();
+        missing.dedup();
+        return Err(anyhow::anyhow!(
+            "Lint configuration references unset environment variable(s): {}",
+            missing.join(", ")
+        ));
+    }
+    Ok(interpolated.into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Severity;
+
+    #[test]
+    fn test_interpolate_set_variable() {
+        let content = r#"Module(( matches: Module("^${CRATE_PREFIX}::internal$") ))"#;
+        let lookup = |name: &str| (name == "CRATE_PREFIX").then(|| "my_app".to_string());
+
+        assert_eq!(
+            interpolate_with(content, lookup).unwrap(),
+            r#"Module(( matches: Module("^my_app::internal$") ))"#
+        );
+    }
+
+    #[test]
+    fn test_interpolate_escapes_values() {
+        let lookup = |_: &str| Some(r#"a"b\d"#.to_string());
+        assert_eq!(
+            interpolate_with(r#""${PATTERN}""#, lookup).unwrap(),
+            r#""a\"b\\d""#
+        );
+    }
+
+    fn module_lint(name: &str, rule: &str) -> String {
+        format!(r#"Module((name: "{name}", matches: Module(".*"), rules: [{rule}]))"#)
+    }
+
+    #[test]
+    fn test_includes_are_merged_with_local_overrides() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir(dir.path().join("shared")).unwrap();
+        fs::write(
+            dir.path().join("shared/pup.base.ron"),
+            format!(
+                "(lints: [{}, {}])",
+                module_lint("no_wildcards", "NoWildcardImports(Warn)"),
+                module_lint("not_empty", "MustNotBeEmpty(Warn)")
+            ),
+        )
+        .unwrap();
+        let local = dir.path().join("pup.ron");
+        fs::write(
+            &local,
+            format!(
+                r#"(include: ["shared/pup.base.ron"], lints: [{}, {}])"#,
+                module_lint("not_empty", "MustNotBeEmpty(Error)"),
+                module_lint("local_only", "NoWildcardImports(Error)")
+            ),
+        )
+        .unwrap();
+
+        let merged = load_config(&local, None).unwrap();
+        let names: Vec<&str> = merged.lints.iter().map(|lint| lint.name()).collect();
+        assert_eq!(names, vec!["no_wildcards", "not_empty", "local_only"]);
+        assert_eq!(
+            merged.lints[1].severity(),
+            Some(Severity::Error),
+            "The local lint should replace the included one"
+        );
+    }
+
+    #[test]
+    fn test_profile_lints_replace_default_lints() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("base.ron"),
+            format!(
+                r#"(lints: [], profiles: {{"strict": [{}]}})"#,
+                module_lint("no_wildcards", "NoWildcardImports(Error)")
+            ),
+        )
+        .unwrap();
+        let local = dir.path().join("pup.ron");
+        fs::write(
+            &local,
+            format!(
+                r#"(include: ["base.ron"], lints: [{}, {}, {}], profiles: {{"strict": [{}]}})"#,
+                module_lint("not_empty", "MustNotBeEmpty(Warn)"),
+                module_lint("no_wildcards", "NoWildcardImports(Warn)"),
+                module_lint("default_only", "NoWildcardImports(Warn)"),
+                module_lint("not_empty", "MustNotBeEmpty(Error)")
+            ),
+        )
+        .unwrap();
+
+        // Without a profile, only the top-level lints run
+        let default = load_config(&local, None).unwrap();
+        assert!(default.profiles.is_empty());
+        assert!(
+            default
+                .lints
+                .iter()
+                .all(|lint| lint.severity() == Some(Severity::Warn))
+        );
+
+        // The strict profile from both files runs instead of the default lints
+        let strict = load_config(&local, Some("strict")).unwrap();
+        let names: Vec<&str> = strict.lints.iter().map(|lint| lint.name()).collect();
+        assert_eq!(names, vec!["no_wildcards", "not_empty"]);
+        assert!(
+            strict
+                .lints
+                .iter()
+                .all(|lint| lint.severity() == Some(Severity::Error))
+        );
+    }
+
+    #[test]
+    fn test_config_files_lists_includes() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("base.ron"), "(lints: [])").unwrap();
+        fs::write(
+            dir.path().join("shared.ron"),
+            r#"(include: ["base.ron"], lints: [])"#,
+        )
+        .unwrap();
+        let local = dir.path().join("pup.ron");
+        fs::write(
+            &local,
+            r#"(include: ["shared.ron", "base.ron"], lints: [])"#,
+        )
+        .unwrap();
+
+        let dir = dir.path().canonicalize().unwrap();
+        assert_eq!(
+            config_files(&local).unwrap(),
+            vec![
+                dir.join("pup.ron"),
+                dir.join("shared.ron"),
+                dir.join("base.ron")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_missing_include_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let local = dir.path().join("pup.ron");
+        fs::write(&local, r#"(include: ["missing.ron"], lints: [])"#).unwrap();
+
+        let error = load_config(&local, None).expect_err("A missing include should fail to load");
+        assert!(
+            error.to_string().contains("missing.ron"),
+            "Unexpected error: {error}"
+        );
+    }
+}
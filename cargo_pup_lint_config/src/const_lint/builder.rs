@@ -0,0 +1,142 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+use super::matcher::{ConstMatchNode, ConstMatcher, matcher};
+use super::types::{ConstLint, ConstMatch, ConstRule};
+use crate::lint_builder::LintBuilder;
+use crate::{ConfiguredLint, Severity};
+
+/// Extension trait that adds const linting capabilities to LintBuilder
+pub trait ConstLintExt {
+    /// Start building a const lint rule
+    fn const_lint(&mut self) -> ConstLintBuilder<'_>;
+}
+
+impl ConstLintExt for LintBuilder {
+    fn const_lint(&mut self) -> ConstLintBuilder<'_> {
+        ConstLintBuilder { parent: self }
+    }
+}
+
+/// Initial builder for creating a const lint
+pub struct ConstLintBuilder<'a> {
+    parent: &'a mut LintBuilder,
+}
+
+impl<'a> ConstLintBuilder<'a> {
+    /// Give the lint a name
+    pub fn lint_named(self, name: impl Into<String>) -> ConstNamedBuilder<'a> {
+        ConstNamedBuilder {
+            parent: self.parent,
+            name: name.into(),
+        }
+    }
+}
+
+/// Builder used after naming the lint
+pub struct ConstNamedBuilder<'a> {
+    parent: &'a mut LintBuilder,
+    name: String,
+}
+
+impl<'a> ConstNamedBuilder<'a> {
+    /// Directly provide a const matcher
+    pub fn matches(self, m: ConstMatch) -> ConstConstraintBuilder<'a> {
+        ConstConstraintBuilder {
+            parent: self.parent,
+            match_: m,
+            rules: Vec::new(),
+            current_severity: Severity::default(),
+            name: self.name,
+            escalate_after: None,
+        }
+    }
+
+    /// Define const matching using the fluent DSL
+    ///
+    /// # Example
+    /// ```
+    /// use cargo_pup_lint_config::{LintBuilder, ConstLintExt};
+    /// let mut lint_builder = LintBuilder::new();
+    /// lint_builder.const_lint()
+    ///     .lint_named("no_global_locks")
+    ///     .matching(|m| m.in_module("::config$"))
+    ///     .forbid_type("Mutex<")
+    ///     .build();
+    /// ```
+    pub fn matching<F>(self, f: F) -> ConstConstraintBuilder<'a>
+    where
+        F: FnOnce(&ConstMatcher) -> ConstMatchNode,
+    {
+        let matcher = matcher(f);
+        self.matches(matcher)
+    }
+}
+
+/// Builder for adding rules to a const lint
+pub struct ConstConstraintBuilder<'a> {
+    parent: &'a mut LintBuilder,
+    match_: ConstMatch,
+    rules: Vec<ConstRule>,
+    current_severity: Severity,
+    name: String,
+    escalate_after: Option<usize>,
+}
+
+impl<'a> ConstConstraintBuilder<'a> {
+    // Private method to add a rule directly to self
+    fn add_rule_internal(&mut self, rule: ConstRule) {
+        self.rules.push(rule);
+    }
+
+    /// Add a custom rule to the const lint
+    pub fn add_rule(mut self, rule: ConstRule) -> Self {
+        self.add_rule_internal(rule);
+        self
+    }
+
+    /// Finalize the const lint and return to the parent builder
+    pub fn build(self) -> &'a mut LintBuilder {
+        let lint = ConfiguredLint::Const(ConstLint {
+            name: self.name,
+            matches: self.match_,
+            rules: self.rules,
+            escalate_after: self.escalate_after,
+        });
+        self.parent.push(lint);
+        self.parent
+    }
+
+    /// Set the severity level for all subsequently added rules
+    pub fn with_severity(mut self, severity: Severity) -> Self {
+        self.current_severity = severity;
+        self
+    }
+
+    /// Fail the run if this lint reports more than `max` violations, whatever their severity
+    pub fn escalate_after(mut self, max: usize) -> Self {
+        self.escalate_after = Some(max);
+        self
+    }
+
+    /// Add a rule requiring the item's name to match the regex pattern, e.g.
+    /// `^[A-Z][A-Z0-9_]*$` for SCREAMING_SNAKE_CASE
+    pub fn naming_pattern(mut self, pattern: impl Into<String>) -> Self {
+        self.add_rule_internal(ConstRule::NamingPattern(
+            pattern.into(),
+            self.current_severity,
+        ));
+        self
+    }
+
+    /// Add a rule forbidding items whose type matches the regex pattern
+    pub fn forbid_type(mut self, pattern: impl Into<String>) -> Self {
+        self.add_rule_internal(ConstRule::ForbidType(pattern.into(), self.current_severity));
+        self
+    }
+
+    /// Add a rule forbidding `static mut` items
+    pub fn forbid_static_mut(mut self) -> Self {
+        self.add_rule_internal(ConstRule::ForbidStaticMut(self.current_severity));
+        self
+    }
+}
@@ -0,0 +1,11 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+use crate::const_lint::ConstLint;
+use crate::{GenerateFromContext, LintBuilder};
+use cargo_pup_common::project_context::ProjectContext;
+
+impl GenerateFromContext for ConstLint {
+    fn generate_from_contexts(_contexts: &[ProjectContext], _builder: &mut LintBuilder) {
+        // The contexts don't record a crate's constants, so there's nothing to infer
+    }
+}
@@ -0,0 +1,69 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+use super::types::ConstMatch;
+
+// === Const Matcher DSL === //
+pub struct ConstMatcher;
+
+impl ConstMatcher {
+    /// Matches `const` and `static` items by name, given a regular expression.
+    /// e.g., "^DEFAULT_"
+    pub fn name(&self, name: impl Into<String>) -> ConstMatchNode {
+        ConstMatchNode::Leaf(ConstMatch::Name(name.into()))
+    }
+
+    /// Matches items declared in modules whose path matches the regular expression.
+    /// e.g., "^my_crate::config$"
+    pub fn in_module(&self, module: impl Into<String>) -> ConstMatchNode {
+        ConstMatchNode::Leaf(ConstMatch::InModule(module.into()))
+    }
+}
+
+#[derive(Clone)]
+pub enum ConstMatchNode {
+    Leaf(ConstMatch),
+    And(Box<ConstMatchNode>, Box<ConstMatchNode>),
+    Or(Box<ConstMatchNode>, Box<ConstMatchNode>),
+    Not(Box<ConstMatchNode>),
+}
+
+impl ConstMatchNode {
+    /// Create a logical AND operation between two matchers
+    pub fn and(self, other: ConstMatchNode) -> Self {
+        ConstMatchNode::And(Box::new(self), Box::new(other))
+    }
+
+    /// Create a logical OR operation between two matchers
+    pub fn or(self, other: ConstMatchNode) -> Self {
+        ConstMatchNode::Or(Box::new(self), Box::new(other))
+    }
+
+    /// Create a logical NOT operation that inverts the matcher
+    #[allow(clippy::should_implement_trait)]
+    pub fn not(self) -> Self {
+        ConstMatchNode::Not(Box::new(self))
+    }
+
+    // Converts the DSL tree to the actual ConstMatch
+    pub fn build(self) -> ConstMatch {
+        match self {
+            ConstMatchNode::Leaf(matcher) => matcher,
+            ConstMatchNode::And(a, b) => {
+                ConstMatch::AndMatches(Box::new(a.build()), Box::new(b.build()))
+            }
+            ConstMatchNode::Or(a, b) => {
+                ConstMatch::OrMatches(Box::new(a.build()), Box::new(b.build()))
+            }
+            ConstMatchNode::Not(m) => ConstMatch::NotMatch(Box::new(m.build())),
+        }
+    }
+}
+
+// Factory function to create a matcher DSL
+pub fn matcher<F>(f: F) -> ConstMatch
+where
+    F: FnOnce(&ConstMatcher) -> ConstMatchNode,
+{
+    let matcher = ConstMatcher;
+    f(&matcher).build()
+}
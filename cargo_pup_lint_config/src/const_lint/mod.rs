@@ -0,0 +1,38 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+mod builder;
+mod generate_config;
+mod matcher;
+mod tests;
+/// Const lint module provides tools for creating and configuring lints on `const`
+/// and `static` items
+///
+/// These lints enforce conventions on the global constants a module is allowed to
+/// declare, e.g. their naming or the types they may hold.
+///
+/// # Example
+/// ```
+/// use cargo_pup_lint_config::{LintBuilder, Severity};
+/// use cargo_pup_lint_config::const_lint::ConstLintExt;
+///
+/// let mut builder = LintBuilder::new();
+///
+/// // Constants are named in SCREAMING_SNAKE_CASE, and nothing is a `static mut`
+/// builder.const_lint()
+///     .lint_named("const_conventions")
+///     .matching(|m| m.in_module("^my_app::"))
+///     .with_severity(Severity::Error)
+///     .naming_pattern("^[A-Z][A-Z0-9_]*$")
+///     .forbid_static_mut()
+///     .build();
+/// ```
+mod types;
+
+// Core types for defining const lints
+pub use types::{ConstLint, ConstMatch, ConstRule};
+
+// Const matcher DSL for creating complex matching rules
+pub use matcher::{ConstMatchNode, ConstMatcher, matcher};
+
+// Builder API for creating const lints
+pub use builder::{ConstConstraintBuilder, ConstLintBuilder, ConstLintExt, ConstNamedBuilder};
@@ -0,0 +1,88 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod tests {
+    use crate::ConfiguredLint;
+    use crate::lint_builder::LintBuilder;
+    use crate::{ConstLintExt, ConstMatch, ConstRule, Severity};
+
+    #[test]
+    fn test_const_lint_builder() {
+        let mut builder = LintBuilder::new();
+
+        builder
+            .const_lint()
+            .lint_named("const_conventions")
+            .matching(|m| m.in_module("^app::").and(m.name("^_").not()))
+            .with_severity(Severity::Error)
+            .naming_pattern("^[A-Z][A-Z0-9_]*$")
+            .forbid_static_mut()
+            .build();
+
+        assert_eq!(builder.lints.len(), 1);
+        if let ConfiguredLint::Const(const_lint) = &builder.lints[0] {
+            assert_eq!(const_lint.name, "const_conventions");
+
+            if let ConstMatch::AndMatches(left, right) = &const_lint.matches {
+                assert!(matches!(&**left, ConstMatch::InModule(pattern) if pattern == "^app::"));
+                assert!(matches!(&**right, ConstMatch::NotMatch(_)));
+            } else {
+                panic!("Expected AndMatches");
+            }
+
+            assert!(matches!(
+                &const_lint.rules[0],
+                ConstRule::NamingPattern(pattern, Severity::Error) if pattern == "^[A-Z][A-Z0-9_]*$"
+            ));
+            assert!(matches!(
+                const_lint.rules[1],
+                ConstRule::ForbidStaticMut(Severity::Error)
+            ));
+        } else {
+            panic!("Unexpected lint type");
+        }
+    }
+
+    #[test]
+    fn test_forbid_type_rule() {
+        let mut builder = LintBuilder::new();
+
+        builder
+            .const_lint()
+            .lint_named("no_global_locks")
+            .matching(|m| m.name(".*"))
+            .forbid_type("Mutex<")
+            .build();
+
+        if let ConfiguredLint::Const(const_lint) = &builder.lints[0] {
+            assert!(matches!(
+                &const_lint.rules[0],
+                ConstRule::ForbidType(pattern, Severity::Warn) if pattern == "Mutex<"
+            ));
+        } else {
+            panic!("Unexpected lint type");
+        }
+    }
+
+    #[test]
+    fn test_const_lint_roundtrip() {
+        let mut builder = LintBuilder::new();
+        builder
+            .const_lint()
+            .lint_named("const_conventions")
+            .matching(|m| m.name(".*"))
+            .forbid_static_mut()
+            .build();
+
+        let serialized = ron::to_string(&builder).unwrap();
+        let deserialized: LintBuilder = ron::from_str(&serialized).unwrap();
+
+        assert!(matches!(
+            &deserialized.lints[0],
+            ConfiguredLint::Const(lint) if lint.name == "const_conventions"
+        ));
+        assert_eq!(deserialized.lints[0].kind(), "const");
+        assert_eq!(deserialized.lints[0].severity(), Some(Severity::Warn));
+    }
+}
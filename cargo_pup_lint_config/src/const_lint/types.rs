@@ -0,0 +1,54 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+use crate::Severity;
+use serde::{Deserialize, Serialize};
+
+/// Specifies how to match `const` and `static` items for linting
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub enum ConstMatch {
+    /// Match items whose name matches this regex pattern
+    Name(String),
+    /// Match items declared in a module whose path matches this regex pattern
+    InModule(String),
+    /// Logical AND - both patterns must match
+    AndMatches(Box<ConstMatch>, Box<ConstMatch>),
+    /// Logical OR - either pattern must match
+    OrMatches(Box<ConstMatch>, Box<ConstMatch>),
+    /// Logical NOT - inverts the match
+    NotMatch(Box<ConstMatch>),
+}
+
+/// A complete const lint definition with matching criteria and rules
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConstLint {
+    pub name: String,
+    pub matches: ConstMatch,
+    pub rules: Vec<ConstRule>,
+    /// Fail the run once this lint reports more than this many violations, even at `Warn`
+    #[serde(default)]
+    pub escalate_after: Option<usize>,
+}
+
+/// Rules that can be applied to `const` and `static` items matching specific criteria
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub enum ConstRule {
+    /// Enforces that the item's name matches the regex pattern, e.g.
+    /// `^[A-Z][A-Z0-9_]*$` for SCREAMING_SNAKE_CASE
+    NamingPattern(String, Severity),
+    /// Forbids items whose rendered type, with full paths such as
+    /// `std::sync::Mutex<u32>`, matches the regex pattern
+    ForbidType(String, Severity),
+    /// Forbids `static mut` items, which can only be used through `unsafe` code
+    ForbidStaticMut(Severity),
+}
+
+impl ConstRule {
+    /// Returns the severity the rule reports violations with
+    pub fn severity(&self) -> Severity {
+        match self {
+            ConstRule::NamingPattern(_, severity)
+            | ConstRule::ForbidType(_, severity)
+            | ConstRule::ForbidStaticMut(severity) => *severity,
+        }
+    }
+}
@@ -0,0 +1,129 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+use super::matcher::{EnumMatchNode, EnumMatcher, matcher};
+use super::types::{EnumLint, EnumMatch, EnumRule};
+use crate::lint_builder::LintBuilder;
+use crate::{ConfiguredLint, Severity};
+
+/// Extension trait that adds enum linting capabilities to LintBuilder
+pub trait EnumLintExt {
+    /// Start building an enum lint rule
+    fn enum_lint(&mut self) -> EnumLintBuilder<'_>;
+}
+
+impl EnumLintExt for LintBuilder {
+    fn enum_lint(&mut self) -> EnumLintBuilder<'_> {
+        EnumLintBuilder { parent: self }
+    }
+}
+
+/// Initial builder for creating an enum lint
+pub struct EnumLintBuilder<'a> {
+    parent: &'a mut LintBuilder,
+}
+
+impl<'a> EnumLintBuilder<'a> {
+    /// Give the lint a name
+    pub fn lint_named(self, name: impl Into<String>) -> EnumNamedBuilder<'a> {
+        EnumNamedBuilder {
+            parent: self.parent,
+            name: name.into(),
+        }
+    }
+}
+
+/// Builder used after naming the lint
+pub struct EnumNamedBuilder<'a> {
+    parent: &'a mut LintBuilder,
+    name: String,
+}
+
+impl<'a> EnumNamedBuilder<'a> {
+    /// Directly provide an enum matcher
+    pub fn matches(self, m: EnumMatch) -> EnumConstraintBuilder<'a> {
+        EnumConstraintBuilder {
+            parent: self.parent,
+            match_: m,
+            rules: Vec::new(),
+            current_severity: Severity::default(),
+            name: self.name,
+            escalate_after: None,
+        }
+    }
+
+    /// Define enum matching using the fluent DSL
+    ///
+    /// # Example
+    /// ```
+    /// use cargo_pup_lint_config::{LintBuilder, EnumLintExt};
+    /// let mut lint_builder = LintBuilder::new();
+    /// lint_builder.enum_lint()
+    ///     .lint_named("ffi_enums")
+    ///     .matching(|m| m.in_module("::ffi$"))
+    ///     .require_explicit_discriminants()
+    ///     .build();
+    /// ```
+    pub fn matching<F>(self, f: F) -> EnumConstraintBuilder<'a>
+    where
+        F: FnOnce(&EnumMatcher) -> EnumMatchNode,
+    {
+        let matcher = matcher(f);
+        self.matches(matcher)
+    }
+}
+
+/// Builder for adding rules to an enum lint
+pub struct EnumConstraintBuilder<'a> {
+    parent: &'a mut LintBuilder,
+    match_: EnumMatch,
+    rules: Vec<EnumRule>,
+    current_severity: Severity,
+    name: String,
+    escalate_after: Option<usize>,
+}
+
+impl<'a> EnumConstraintBuilder<'a> {
+    // Private method to add a rule directly to self
+    fn add_rule_internal(&mut self, rule: EnumRule) {
+        self.rules.push(rule);
+    }
+
+    /// Add a custom rule to the enum lint
+    pub fn add_rule(mut self, rule: EnumRule) -> Self {
+        self.add_rule_internal(rule);
+        self
+    }
+
+    /// Finalize the enum lint and return to the parent builder
+    pub fn build(self) -> &'a mut LintBuilder {
+        let lint = ConfiguredLint::Enum(EnumLint {
+            name: self.name,
+            matches: self.match_,
+            rules: self.rules,
+            escalate_after: self.escalate_after,
+        });
+        self.parent.push(lint);
+        self.parent
+    }
+
+    /// Set the severity level for all subsequently added rules
+    pub fn with_severity(mut self, severity: Severity) -> Self {
+        self.current_severity = severity;
+        self
+    }
+
+    /// Fail the run if this lint reports more than `max` violations, whatever their severity
+    pub fn escalate_after(mut self, max: usize) -> Self {
+        self.escalate_after = Some(max);
+        self
+    }
+
+    /// Add a rule requiring every variant of a C-style enum to have an explicit
+    /// discriminant, e.g. for enums passed across an FFI boundary
+    pub fn require_explicit_discriminants(mut self) -> Self {
+        self.add_rule_internal(EnumRule::RequireExplicitDiscriminants(
+            self.current_severity,
+        ));
+        self
+    }
+}
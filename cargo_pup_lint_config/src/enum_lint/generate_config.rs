@@ -0,0 +1,12 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+use crate::enum_lint::EnumLint;
+use crate::{GenerateFromContext, LintBuilder};
+use cargo_pup_common::project_context::ProjectContext;
+
+impl GenerateFromContext for EnumLint {
+    fn generate_from_contexts(_contexts: &[ProjectContext], _builder: &mut LintBuilder) {
+        // The contexts don't record which enums cross an FFI boundary, so there's
+        // nothing to infer
+    }
+}
@@ -0,0 +1,69 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+use super::types::EnumMatch;
+
+// === Enum Matcher DSL === //
+pub struct EnumMatcher;
+
+impl EnumMatcher {
+    /// Matches enums by name, given a regular expression.
+    /// e.g., "Kind$"
+    pub fn name(&self, name: impl Into<String>) -> EnumMatchNode {
+        EnumMatchNode::Leaf(EnumMatch::Name(name.into()))
+    }
+
+    /// Matches enums declared in modules whose path matches the regular expression.
+    /// e.g., "^my_crate::ffi$"
+    pub fn in_module(&self, module: impl Into<String>) -> EnumMatchNode {
+        EnumMatchNode::Leaf(EnumMatch::InModule(module.into()))
+    }
+}
+
+#[derive(Clone)]
+pub enum EnumMatchNode {
+    Leaf(EnumMatch),
+    And(Box<EnumMatchNode>, Box<EnumMatchNode>),
+    Or(Box<EnumMatchNode>, Box<EnumMatchNode>),
+    Not(Box<EnumMatchNode>),
+}
+
+impl EnumMatchNode {
+    /// Create a logical AND operation between two matchers
+    pub fn and(self, other: EnumMatchNode) -> Self {
+        EnumMatchNode::And(Box::new(self), Box::new(other))
+    }
+
+    /// Create a logical OR operation between two matchers
+    pub fn or(self, other: EnumMatchNode) -> Self {
+        EnumMatchNode::Or(Box::new(self), Box::new(other))
+    }
+
+    /// Create a logical NOT operation that inverts the matcher
+    #[allow(clippy::should_implement_trait)]
+    pub fn not(self) -> Self {
+        EnumMatchNode::Not(Box::new(self))
+    }
+
+    // Converts the DSL tree to the actual EnumMatch
+    pub fn build(self) -> EnumMatch {
+        match self {
+            EnumMatchNode::Leaf(matcher) => matcher,
+            EnumMatchNode::And(a, b) => {
+                EnumMatch::AndMatches(Box::new(a.build()), Box::new(b.build()))
+            }
+            EnumMatchNode::Or(a, b) => {
+                EnumMatch::OrMatches(Box::new(a.build()), Box::new(b.build()))
+            }
+            EnumMatchNode::Not(m) => EnumMatch::NotMatch(Box::new(m.build())),
+        }
+    }
+}
+
+// Factory function to create a matcher DSL
+pub fn matcher<F>(f: F) -> EnumMatch
+where
+    F: FnOnce(&EnumMatcher) -> EnumMatchNode,
+{
+    let matcher = EnumMatcher;
+    f(&matcher).build()
+}
@@ -0,0 +1,37 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+mod builder;
+mod generate_config;
+mod matcher;
+mod tests;
+/// Enum lint module provides tools for creating and configuring lints on enums
+///
+/// Until now enums could only be forbidden outright, through a module lint's
+/// `DeniedItems`. These lints check the enums themselves, e.g. that the ones passed
+/// across an FFI boundary spell out their discriminants.
+///
+/// # Example
+/// ```
+/// use cargo_pup_lint_config::{LintBuilder, Severity};
+/// use cargo_pup_lint_config::enum_lint::EnumLintExt;
+///
+/// let mut builder = LintBuilder::new();
+///
+/// // Every variant of an FFI-facing enum has an explicit `= N`
+/// builder.enum_lint()
+///     .lint_named("ffi_enums")
+///     .matching(|m| m.in_module("^my_app::ffi"))
+///     .with_severity(Severity::Error)
+///     .require_explicit_discriminants()
+///     .build();
+/// ```
+mod types;
+
+// Core types for defining enum lints
+pub use types::{EnumLint, EnumMatch, EnumRule};
+
+// Enum matcher DSL for creating complex matching rules
+pub use matcher::{EnumMatchNode, EnumMatcher, matcher};
+
+// Builder API for creating enum lints
+pub use builder::{EnumConstraintBuilder, EnumLintBuilder, EnumLintExt, EnumNamedBuilder};
@@ -0,0 +1,63 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod tests {
+    use crate::ConfiguredLint;
+    use crate::lint_builder::LintBuilder;
+    use crate::{EnumLintExt, EnumMatch, EnumRule, Severity};
+
+    #[test]
+    fn test_enum_lint_builder() {
+        let mut builder = LintBuilder::new();
+
+        builder
+            .enum_lint()
+            .lint_named("ffi_enums")
+            .matching(|m| m.in_module("::ffi$").and(m.name("^Internal").not()))
+            .with_severity(Severity::Error)
+            .require_explicit_discriminants()
+            .build();
+
+        assert_eq!(builder.lints.len(), 1);
+        if let ConfiguredLint::Enum(enum_lint) = &builder.lints[0] {
+            assert_eq!(enum_lint.name, "ffi_enums");
+
+            if let EnumMatch::AndMatches(left, right) = &enum_lint.matches {
+                assert!(matches!(&**left, EnumMatch::InModule(pattern) if pattern == "::ffi$"));
+                assert!(matches!(&**right, EnumMatch::NotMatch(_)));
+            } else {
+                panic!("Expected AndMatches");
+            }
+
+            assert!(matches!(
+                enum_lint.rules[..],
+                [EnumRule::RequireExplicitDiscriminants(Severity::Error)]
+            ));
+        } else {
+            panic!("Unexpected lint type");
+        }
+    }
+
+    #[test]
+    fn test_enum_lint_roundtrip() {
+        let mut builder = LintBuilder::new();
+        builder
+            .enum_lint()
+            .lint_named("ffi_enums")
+            .matching(|m| m.name(".*"))
+            .require_explicit_discriminants()
+            .build();
+
+        let serialized = ron::to_string(&builder).unwrap();
+        assert!(serialized.contains("RequireExplicitDiscriminants(Warn)"));
+        let deserialized: LintBuilder = ron::from_str(&serialized).unwrap();
+
+        assert!(matches!(
+            &deserialized.lints[0],
+            ConfiguredLint::Enum(lint) if lint.name == "ffi_enums"
+        ));
+        assert_eq!(deserialized.lints[0].kind(), "enum");
+        assert_eq!(deserialized.lints[0].severity(), Some(Severity::Warn));
+    }
+}
@@ -0,0 +1,49 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+use crate::Severity;
+use serde::{Deserialize, Serialize};
+
+/// Specifies how to match enums for linting
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub enum EnumMatch {
+    /// Match enums whose name matches this regex pattern
+    Name(String),
+    /// Match enums declared in a module whose path matches this regex pattern
+    InModule(String),
+    /// Logical AND - both patterns must match
+    AndMatches(Box<EnumMatch>, Box<EnumMatch>),
+    /// Logical OR - either pattern must match
+    OrMatches(Box<EnumMatch>, Box<EnumMatch>),
+    /// Logical NOT - inverts the match
+    NotMatch(Box<EnumMatch>),
+}
+
+/// A complete enum lint definition with matching criteria and rules
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnumLint {
+    pub name: String,
+    pub matches: EnumMatch,
+    pub rules: Vec<EnumRule>,
+    /// Fail the run once this lint reports more than this many violations, even at `Warn`
+    #[serde(default)]
+    pub escalate_after: Option<usize>,
+}
+
+/// Rules that can be applied to enums matching specific criteria
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub enum EnumRule {
+    /// Requires every variant of a C-style enum to give its discriminant explicitly,
+    /// as in `Ready = 1`, so reordering or inserting variants can't silently change
+    /// the values seen across an FFI boundary. Enums with variants that hold data
+    /// are not checked.
+    RequireExplicitDiscriminants(Severity),
+}
+
+impl EnumRule {
+    /// Returns the severity the rule reports violations with
+    pub fn severity(&self) -> Severity {
+        match self {
+            EnumRule::RequireExplicitDiscriminants(severity) => *severity,
+        }
+    }
+}
@@ -1,7 +1,7 @@
 // This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
 
 use super::matcher::{FunctionMatchNode, FunctionMatcher, matcher};
-use super::types::{FunctionLint, FunctionMatch, FunctionRule};
+use super::types::{FunctionLint, FunctionMatch, FunctionRule, ReturnTypePattern};
 use crate::lint_builder::LintBuilder;
 use crate::{ConfiguredLint, Severity};
 
@@ -47,6 +47,7 @@ impl<'a> FunctionNamedBuilder<'a> {
             rules: Vec::new(),
             current_severity: Severity::default(),
             name: self.name,
+            escalate_after: None,
         }
     }
 
@@ -78,6 +79,7 @@ pub struct FunctionConstraintBuilder<'a> {
     rules: Vec<FunctionRule>,
     current_severity: Severity,
     name: String,
+    escalate_after: Option<usize>,
 }
 
 impl<'a> FunctionConstraintBuilder<'a> {
@@ -98,6 +100,7 @@ impl<'a> FunctionConstraintBuilder<'a> {
             name: self.name,
             matches: self.match_,
             rules: self.rules,
+            escalate_after: self.escalate_after,
         });
         self.parent.push(lint);
         self.parent
@@ -109,6 +112,12 @@ impl<'a> FunctionConstraintBuilder<'a> {
         self
     }
 
+    /// Fail the run if this lint reports more than `max` violations, whatever their severity
+    pub fn escalate_after(mut self, max: usize) -> Self {
+        self.escalate_after = Some(max);
+        self
+    }
+
     /// Limit function length to the specified number of lines
     pub fn max_length(mut self, length: usize) -> Self {
         self.add_rule_internal(FunctionRule::MaxLength(length, self.current_severity));
@@ -169,6 +178,226 @@ impl<'a> FunctionConstraintBuilder<'a> {
         self
     }
 
+    /// Limit the number of generic type and const parameters the function declares.
+    /// Parameters inherited from an enclosing impl or trait are not counted.
+    pub fn max_generics(mut self, max: usize) -> Self {
+        self.add_rule_internal(FunctionRule::MaxGenerics {
+            max,
+            count_lifetimes: false,
+            severity: self.current_severity,
+        });
+        self
+    }
+
+    /// Include explicitly declared lifetimes in the count of the preceding `max_generics` rule
+    pub fn count_lifetimes(mut self) -> Self {
+        if let Some(FunctionRule::MaxGenerics {
+            count_lifetimes, ..
+        }) = self
+            .rules
+            .iter_mut()
+            .rev()
+            .find(|rule| matches!(rule, FunctionRule::MaxGenerics { .. }))
+        {
+            *count_lifetimes = true;
+        }
+        self
+    }
+
+    /// Add a rule requiring public functions to have a doc comment.
+    /// Methods in trait impls are exempt.
+    pub fn require_doc_comment(mut self) -> Self {
+        self.add_rule_internal(FunctionRule::RequireDocComment(self.current_severity));
+        self
+    }
+
+    /// Add a rule forbidding unsafe blocks and `unsafe fn` declarations
+    pub fn forbid_unsafe(mut self) -> Self {
+        self.add_rule_internal(FunctionRule::ForbidUnsafe(self.current_severity));
+        self
+    }
+
+    /// Add a rule forbidding any access to a `static mut`
+    pub fn forbid_static_mut_access(mut self) -> Self {
+        self.add_rule_internal(FunctionRule::ForbidStaticMutAccess(self.current_severity));
+        self
+    }
+
+    /// Add a rule requiring `.context(...)` or `.with_context(...)` wherever `?` turns
+    /// another error type into an `anyhow::Error`.
+    /// See [`FunctionRule::RequireErrorContext`] for what is reported.
+    pub fn require_error_context(mut self) -> Self {
+        self.add_rule_internal(FunctionRule::RequireErrorContext(self.current_severity));
+        self
+    }
+
+    /// Add a rule forbidding indexing expressions such as `a[i]`, which can panic, in
+    /// favour of `.get()`. See [`FunctionRule::ForbidIndexing`] for what is reported.
+    pub fn forbid_indexing(mut self) -> Self {
+        self.add_rule_internal(FunctionRule::ForbidIndexing {
+            allow_constant_indices: false,
+            severity: self.current_severity,
+        });
+        self
+    }
+
+    /// Allow indices that are integer literals or constants in the preceding
+    /// `forbid_indexing` rule
+    pub fn allow_constant_indices(mut self) -> Self {
+        if let Some(FunctionRule::ForbidIndexing {
+            allow_constant_indices,
+            ..
+        }) = self
+            .rules
+            .iter_mut()
+            .rev()
+            .find(|rule| matches!(rule, FunctionRule::ForbidIndexing { .. }))
+        {
+            *allow_constant_indices = true;
+        }
+        self
+    }
+
+    /// Add a rule requiring parameter names to match a regular expression, such as
+    /// `^[a-z_][a-z0-9_]{1,}$`. `self` and destructured parameters are not checked.
+    pub fn parameter_naming_pattern(mut self, pattern: impl Into<String>) -> Self {
+        self.add_rule_internal(FunctionRule::ParameterNamingPattern(
+            pattern.into(),
+            self.current_severity,
+        ));
+        self
+    }
+
+    /// Add a rule limiting the cyclomatic complexity of matching functions.
+    /// See [`FunctionRule::MaxComplexity`] for the exact counting rules.
+    pub fn max_complexity(mut self, max: usize) -> Self {
+        self.add_rule_internal(FunctionRule::MaxComplexity(max, self.current_severity));
+        self
+    }
+
+    /// Add a rule limiting the number of statements in matching functions.
+    /// See [`FunctionRule::MaxStatements`] for what is counted.
+    pub fn max_statements(mut self, max: usize) -> Self {
+        self.add_rule_internal(FunctionRule::MaxStatements(max, self.current_severity));
+        self
+    }
+
+    /// Add a rule limiting the number of local variables bound in matching functions.
+    /// See [`FunctionRule::MaxLocals`] for what is counted.
+    pub fn max_locals(mut self, max: usize) -> Self {
+        self.add_rule_internal(FunctionRule::MaxLocals {
+            max,
+            count_match_bindings: false,
+            severity: self.current_severity,
+        });
+        self
+    }
+
+    /// Include bindings in `match` arm patterns in the count of the preceding `max_locals` rule
+    pub fn count_match_bindings(mut self) -> Self {
+        if let Some(FunctionRule::MaxLocals {
+            count_match_bindings,
+            ..
+        }) = self
+            .rules
+            .iter_mut()
+            .rev()
+            .find(|rule| matches!(rule, FunctionRule::MaxLocals { .. }))
+        {
+            *count_match_bindings = true;
+        }
+        self
+    }
+
+    /// Add a rule forbidding parameter or return types that match any of the given
+    /// regular expressions, e.g. `vec!["^std::rc::Rc<"]`. Raw pointers need the `*`
+    /// escaped, as in `"^\\*mut "`.
+    pub fn forbid_type_in_signature(mut self, type_patterns: Vec<impl Into<String>>) -> Self {
+        self.add_rule_internal(FunctionRule::ForbidTypeInSignature {
+            type_patterns: type_patterns.into_iter().map(Into::into).collect(),
+            severity: self.current_severity,
+        });
+        self
+    }
+
+    /// Add a rule requiring matching functions to be `async fn`
+    pub fn must_be_async(mut self) -> Self {
+        self.add_rule_internal(FunctionRule::MustBeAsync(self.current_severity));
+        self
+    }
+
+    /// Add a rule forbidding calls to the functions whose path matches one of the given
+    /// regular expressions, e.g. `vec!["^std::process::exit$"]`. Without the anchors,
+    /// `std::env::var` would also forbid `std::env::vars`.
+    pub fn forbid_call_to(mut self, paths: Vec<impl Into<String>>) -> Self {
+        self.add_rule_internal(FunctionRule::ForbidCallTo {
+            paths: paths.into_iter().map(Into::into).collect(),
+            severity: self.current_severity,
+        });
+        self
+    }
+
+    /// Add a rule forbidding matching functions from calling themselves. Mutual
+    /// recursion isn't detected.
+    pub fn no_recursion(mut self) -> Self {
+        self.add_rule_internal(FunctionRule::NoRecursion(self.current_severity));
+        self
+    }
+
+    /// Add a rule limiting the number of parameters matching functions take by value,
+    /// rather than by reference
+    pub fn max_by_value_parameters(mut self, max: usize) -> Self {
+        self.add_rule_internal(FunctionRule::MaxByValueParameters(
+            max,
+            self.current_severity,
+        ));
+        self
+    }
+
+    /// Require matching functions to return a type matching the pattern, e.g.
+    /// `ReturnTypePattern::Result`
+    pub fn must_return(mut self, pattern: ReturnTypePattern) -> Self {
+        self.add_rule_internal(FunctionRule::MustReturnType(pattern, self.current_severity));
+        self
+    }
+
+    /// Require the error type of a returned `Result` to be an enum
+    pub fn result_error_must_be_enum(mut self) -> Self {
+        self.add_rule_internal(FunctionRule::ResultErrorMustBeEnum {
+            allow_boxed: false,
+            allow_anyhow: false,
+            severity: self.current_severity,
+        });
+        self
+    }
+
+    /// Accept boxed errors, such as `Box<dyn Error>`, in the preceding
+    /// `result_error_must_be_enum` rule
+    pub fn allow_boxed_errors(mut self) -> Self {
+        if let Some(FunctionRule::ResultErrorMustBeEnum { allow_boxed, .. }) = self
+            .rules
+            .iter_mut()
+            .rev()
+            .find(|rule| matches!(rule, FunctionRule::ResultErrorMustBeEnum { .. }))
+        {
+            *allow_boxed = true;
+        }
+        self
+    }
+
+    /// Accept `anyhow::Error` in the preceding `result_error_must_be_enum` rule
+    pub fn allow_anyhow_errors(mut self) -> Self {
+        if let Some(FunctionRule::ResultErrorMustBeEnum { allow_anyhow, .. }) = self
+            .rules
+            .iter_mut()
+            .rev()
+            .find(|rule| matches!(rule, FunctionRule::ResultErrorMustBeEnum { .. }))
+        {
+            *allow_anyhow = true;
+        }
+        self
+    }
+
     /// Create a new MaxLength rule with the current severity
     pub fn create_max_length_rule(&self, length: usize) -> FunctionRule {
         FunctionRule::MaxLength(length, self.current_severity)
@@ -14,6 +14,7 @@ impl GenerateFromContext for FunctionLint {
             name: "function_length_limit".to_string(),
             matches: FunctionMatch::NameRegex(".*".to_string()),
             rules: vec![FunctionRule::MaxLength(50, Severity::Error)],
+            escalate_after: None,
         };
         builder.push(ConfiguredLint::Function(function_length_lint));
 
@@ -22,6 +23,7 @@ impl GenerateFromContext for FunctionLint {
             name: "result_error_must_implement_error".to_string(),
             matches: FunctionMatch::ReturnsType(ReturnTypePattern::Result),
             rules: vec![FunctionRule::ResultErrorMustImplementError(Severity::Error)],
+            escalate_after: None,
         };
         builder.push(ConfiguredLint::Function(result_error_lint));
 
@@ -35,6 +37,7 @@ impl GenerateFromContext for FunctionLint {
                         name: format!("func_rules_for_{}", context.module_root),
                         matches: FunctionMatch::InModule(format!("{}::*", context.module_root)),
                         rules: vec![FunctionRule::MaxLength(30, Severity::Warn)],
+                        escalate_after: None,
                     };
                     builder.push(ConfiguredLint::Function(module_functions_rule));
                 }
@@ -18,6 +18,16 @@ impl FunctionMatcher {
         FunctionMatchNode::Leaf(FunctionMatch::NameRegex(pattern.into()))
     }
 
+    /// Match functions whose names start with this prefix, e.g. `"get_"`
+    pub fn name_starts_with(&self, prefix: impl Into<String>) -> FunctionMatchNode {
+        FunctionMatchNode::Leaf(FunctionMatch::NameStartsWith(prefix.into()))
+    }
+
+    /// Match functions whose names end with this suffix, e.g. `"_mut"`
+    pub fn name_ends_with(&self, suffix: impl Into<String>) -> FunctionMatchNode {
+        FunctionMatchNode::Leaf(FunctionMatch::NameEndsWith(suffix.into()))
+    }
+
     /// Matches functions in a specific module, with the module
     /// name given as a regular expression.
     ///
@@ -76,6 +86,14 @@ impl FunctionMatcher {
         FunctionMatchNode::Leaf(FunctionMatch::ReturnsType(ReturnTypePattern::SelfMutRef))
     }
 
+    /// Matches functions that take a parameter whose type matches a regex pattern.
+    ///
+    /// The pattern is matched against the rendered parameter type, so references
+    /// and generic arguments are included, e.g. "&mut .*Connection"
+    pub fn takes_parameter_type(&self, pattern: impl Into<String>) -> FunctionMatchNode {
+        FunctionMatchNode::Leaf(FunctionMatch::ParameterType(pattern.into()))
+    }
+
     /// Matches async functions
     pub fn is_async(&self) -> FunctionMatchNode {
         FunctionMatchNode::Leaf(FunctionMatch::IsAsync)
@@ -85,6 +103,52 @@ impl FunctionMatcher {
     pub fn is_unsafe(&self) -> FunctionMatchNode {
         FunctionMatchNode::Leaf(FunctionMatch::IsUnsafe)
     }
+
+    /// Matches `async fn`s and functions returning `impl Future`
+    pub fn returns_impl_future(&self) -> FunctionMatchNode {
+        FunctionMatchNode::Leaf(FunctionMatch::ReturnsImplFuture)
+    }
+
+    /// Matches functions whose body is at least `lines` lines long
+    pub fn min_length(&self, lines: usize) -> FunctionMatchNode {
+        FunctionMatchNode::Leaf(FunctionMatch::MinLength(lines))
+    }
+
+    /// Matches associated functions, i.e. those in impl blocks and traits, including
+    /// constructors that don't take `self`
+    pub fn is_method(&self) -> FunctionMatchNode {
+        FunctionMatchNode::Leaf(FunctionMatch::IsMethod)
+    }
+
+    /// Matches free functions, declared directly in a module
+    pub fn is_free_function(&self) -> FunctionMatchNode {
+        FunctionMatchNode::Leaf(FunctionMatch::IsFreeFunction)
+    }
+
+    /// Matches trait impl methods that override one of the trait's default methods
+    pub fn overrides_default(&self) -> FunctionMatchNode {
+        FunctionMatchNode::Leaf(FunctionMatch::OverridesTraitMethod)
+    }
+
+    /// Invert a matcher. This is the same as calling `.not()` on it, but reads
+    /// more naturally in the middle of an `and`/`or` chain.
+    ///
+    /// # Example
+    /// ```
+    /// use cargo_pup_lint_config::{FunctionMatch, function_matcher};
+    ///
+    /// // Public API functions, leaving out the test helpers
+    /// let matches = function_matcher(|m| {
+    ///     m.in_module("^my_app::api").and(m.not(m.name_starts_with("test_")))
+    /// });
+    /// assert!(matches!(
+    ///     matches,
+    ///     FunctionMatch::AndMatches(_, excluded) if matches!(*excluded, FunctionMatch::NotMatch(_))
+    /// ));
+    /// ```
+    pub fn not(&self, node: FunctionMatchNode) -> FunctionMatchNode {
+        node.not()
+    }
 }
 
 /// Node in the matcher expression tree
@@ -1,12 +1,15 @@
 // This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
 
 #[cfg(test)]
+#[allow(clippy::module_inception)]
 mod tests {
     use crate::ConfiguredLint;
     use crate::GenerateFromContext;
     use crate::LintBuilder;
     use crate::Severity;
-    use crate::function_lint::{FunctionLint, FunctionLintExt, FunctionMatch, FunctionRule};
+    use crate::function_lint::{
+        FunctionLint, FunctionLintExt, FunctionMatch, FunctionRule, ReturnTypePattern,
+    };
     use cargo_pup_common::project_context::{ModuleInfo, ProjectContext};
 
     // Helper function to verify default severity
@@ -208,6 +211,575 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_function_lint_max_generics() {
+        let mut builder = LintBuilder::new();
+
+        builder
+            .function_lint()
+            .lint_named("max_generics_lint")
+            .matching(|m| m.in_module("^api$"))
+            .max_generics(2)
+            .with_severity(Severity::Error)
+            .max_generics(3)
+            .count_lifetimes()
+            .build();
+
+        if let ConfiguredLint::Function(function_lint) = &builder.lints[0] {
+            assert_eq!(function_lint.rules.len(), 2);
+
+            // The toggle only applies to the most recent max_generics rule
+            if let FunctionRule::MaxGenerics {
+                max,
+                count_lifetimes,
+                severity,
+            } = &function_lint.rules[0]
+            {
+                assert_eq!(*max, 2);
+                assert!(!count_lifetimes);
+                assert_default_severity(severity);
+            } else {
+                panic!("Expected MaxGenerics rule");
+            }
+
+            if let FunctionRule::MaxGenerics {
+                max,
+                count_lifetimes,
+                severity,
+            } = &function_lint.rules[1]
+            {
+                assert_eq!(*max, 3);
+                assert!(count_lifetimes);
+                assert_eq!(severity, &Severity::Error);
+            } else {
+                panic!("Expected MaxGenerics rule");
+            }
+        } else {
+            panic!("Unexpected lint type");
+        }
+    }
+
+    #[test]
+    fn test_function_lint_forbid_unsafe() {
+        let mut builder = LintBuilder::new();
+
+        builder
+            .function_lint()
+            .lint_named("no_unsafe_outside_ffi")
+            .matching(|m| m.in_module(".*").and(m.in_module("::ffi$").not()))
+            .with_severity(Severity::Error)
+            .forbid_unsafe()
+            .build();
+
+        if let ConfiguredLint::Function(function_lint) = &builder.lints[0] {
+            assert!(matches!(
+                function_lint.matches,
+                FunctionMatch::AndMatches(_, ref right) if matches!(**right, FunctionMatch::NotMatch(_))
+            ));
+            assert!(matches!(
+                function_lint.rules[0],
+                FunctionRule::ForbidUnsafe(Severity::Error)
+            ));
+        } else {
+            panic!("Unexpected lint type");
+        }
+    }
+
+    #[test]
+    fn test_function_lint_forbid_static_mut_access() {
+        let mut builder = LintBuilder::new();
+
+        builder
+            .function_lint()
+            .lint_named("no_static_mut")
+            .matching(|m| m.in_module(".*"))
+            .forbid_static_mut_access()
+            .build();
+
+        if let ConfiguredLint::Function(function_lint) = &builder.lints[0] {
+            assert!(matches!(
+                function_lint.rules[0],
+                FunctionRule::ForbidStaticMutAccess(Severity::Warn)
+            ));
+        } else {
+            panic!("Unexpected lint type");
+        }
+    }
+
+    #[test]
+    fn test_function_lint_require_error_context() {
+        let mut builder = LintBuilder::new();
+
+        builder
+            .function_lint()
+            .lint_named("errors_have_context")
+            .matching(|m| m.in_module("^my_app::commands"))
+            .with_severity(Severity::Error)
+            .require_error_context()
+            .build();
+
+        if let ConfiguredLint::Function(function_lint) = &builder.lints[0] {
+            assert!(matches!(
+                function_lint.rules[0],
+                FunctionRule::RequireErrorContext(Severity::Error)
+            ));
+        } else {
+            panic!("Unexpected lint type");
+        }
+    }
+
+    #[test]
+    fn test_function_matcher_negation_builds_nested_tree() {
+        use crate::function_lint::matcher;
+
+        let matches = matcher(|m| {
+            m.in_module("^api$")
+                .and(m.not(m.name_starts_with("test_").or(m.is_unsafe())))
+        });
+        assert_eq!(
+            format!("{matches:?}"),
+            r#"AndMatches(InModule("^api$"), NotMatch(OrMatches(NameStartsWith("test_"), IsUnsafe)))"#
+        );
+
+        // Negating twice keeps both levels, rather than cancelling out
+        let matches = matcher(|m| m.not(m.not(m.is_async())));
+        assert_eq!(format!("{matches:?}"), "NotMatch(NotMatch(IsAsync))");
+    }
+
+    #[test]
+    fn test_function_lint_max_complexity() {
+        let mut builder = LintBuilder::new();
+
+        builder
+            .function_lint()
+            .lint_named("simple_handlers")
+            .matching(|m| m.in_module("::handlers$"))
+            .with_severity(Severity::Warn)
+            .max_complexity(10)
+            .build();
+
+        if let ConfiguredLint::Function(function_lint) = &builder.lints[0] {
+            assert!(matches!(
+                function_lint.rules[0],
+                FunctionRule::MaxComplexity(10, Severity::Warn)
+            ));
+        } else {
+            panic!("Unexpected lint type");
+        }
+    }
+
+    #[test]
+    fn test_function_lint_max_statements() {
+        let mut builder = LintBuilder::new();
+
+        builder
+            .function_lint()
+            .lint_named("short_handlers")
+            .matching(|m| m.in_module("::handlers$"))
+            .with_severity(Severity::Error)
+            .max_statements(20)
+            .build();
+
+        if let ConfiguredLint::Function(function_lint) = &builder.lints[0] {
+            assert!(matches!(
+                function_lint.rules[0],
+                FunctionRule::MaxStatements(20, Severity::Error)
+            ));
+        } else {
+            panic!("Unexpected lint type");
+        }
+    }
+
+    #[test]
+    fn test_function_lint_forbid_indexing() {
+        let mut builder = LintBuilder::new();
+
+        builder
+            .function_lint()
+            .lint_named("no_indexing")
+            .matching(|m| m.in_module("::codec$"))
+            .with_severity(Severity::Error)
+            .forbid_indexing()
+            .allow_constant_indices()
+            .build();
+
+        if let ConfiguredLint::Function(function_lint) = &builder.lints[0] {
+            assert!(matches!(
+                function_lint.rules[0],
+                FunctionRule::ForbidIndexing {
+                    allow_constant_indices: true,
+                    severity: Severity::Error,
+                }
+            ));
+        } else {
+            panic!("Unexpected lint type");
+        }
+    }
+
+    #[test]
+    fn test_function_lint_parameter_naming_pattern() {
+        let mut builder = LintBuilder::new();
+
+        builder
+            .function_lint()
+            .lint_named("descriptive_parameters")
+            .matching(|m| m.in_module("::api$"))
+            .parameter_naming_pattern("^[a-z_][a-z0-9_]{1,}$")
+            .build();
+
+        if let ConfiguredLint::Function(function_lint) = &builder.lints[0] {
+            assert!(matches!(
+                &function_lint.rules[0],
+                FunctionRule::ParameterNamingPattern(pattern, Severity::Warn)
+                    if pattern == "^[a-z_][a-z0-9_]{1,}$"
+            ));
+        } else {
+            panic!("Unexpected lint type");
+        }
+    }
+
+    #[test]
+    fn test_function_lint_max_locals() {
+        let mut builder = LintBuilder::new();
+
+        builder
+            .function_lint()
+            .lint_named("few_locals")
+            .matching(|m| m.in_module("::handlers$"))
+            .with_severity(Severity::Warn)
+            .max_locals(8)
+            .max_locals(12)
+            .count_match_bindings()
+            .build();
+
+        if let ConfiguredLint::Function(function_lint) = &builder.lints[0] {
+            assert!(matches!(
+                function_lint.rules[0],
+                FunctionRule::MaxLocals {
+                    max: 8,
+                    count_match_bindings: false,
+                    severity: Severity::Warn,
+                }
+            ));
+            // Only the preceding rule counts match bindings
+            assert!(matches!(
+                function_lint.rules[1],
+                FunctionRule::MaxLocals {
+                    max: 12,
+                    count_match_bindings: true,
+                    severity: Severity::Warn,
+                }
+            ));
+        } else {
+            panic!("Unexpected lint type");
+        }
+    }
+
+    #[test]
+    fn test_function_lint_forbid_type_in_signature() {
+        let mut builder = LintBuilder::new();
+
+        builder
+            .function_lint()
+            .lint_named("no_rc_in_api")
+            .matching(|m| m.in_module("::api$"))
+            .with_severity(Severity::Error)
+            .forbid_type_in_signature(vec!["^std::rc::Rc<", "^\\*mut "])
+            .build();
+
+        if let ConfiguredLint::Function(function_lint) = &builder.lints[0] {
+            match &function_lint.rules[0] {
+                FunctionRule::ForbidTypeInSignature {
+                    type_patterns,
+                    severity,
+                } => {
+                    assert_eq!(type_patterns, &vec!["^std::rc::Rc<", "^\\*mut "]);
+                    assert_eq!(severity, &Severity::Error);
+                }
+                _ => panic!("Expected ForbidTypeInSignature rule"),
+            }
+        } else {
+            panic!("Unexpected lint type");
+        }
+    }
+
+    #[test]
+    fn test_function_name_prefix_and_suffix_matchers() {
+        let mut builder = LintBuilder::new();
+
+        builder
+            .function_lint()
+            .lint_named("accessor_naming")
+            .matching(|m| m.name_starts_with("get_").or(m.name_ends_with("_mut")))
+            .must_not_exist()
+            .build();
+
+        if let ConfiguredLint::Function(function_lint) = &builder.lints[0] {
+            match &function_lint.matches {
+                FunctionMatch::OrMatches(left, right) => {
+                    assert!(matches!(&**left, FunctionMatch::NameStartsWith(p) if p == "get_"));
+                    assert!(matches!(&**right, FunctionMatch::NameEndsWith(s) if s == "_mut"));
+                }
+                other => panic!("Expected OrMatches, got {other:?}"),
+            }
+        } else {
+            panic!("Unexpected lint type");
+        }
+    }
+
+    #[test]
+    fn test_function_lint_must_be_async() {
+        let mut builder = LintBuilder::new();
+
+        builder
+            .function_lint()
+            .lint_named("async_handlers")
+            .matching(|m| m.in_module("::handlers$"))
+            .with_severity(Severity::Error)
+            .must_be_async()
+            .build();
+
+        if let ConfiguredLint::Function(function_lint) = &builder.lints[0] {
+            assert!(matches!(
+                function_lint.rules[0],
+                FunctionRule::MustBeAsync(Severity::Error)
+            ));
+        } else {
+            panic!("Unexpected lint type");
+        }
+    }
+
+    #[test]
+    fn test_function_lint_no_recursion() {
+        let mut builder = LintBuilder::new();
+
+        builder
+            .function_lint()
+            .lint_named("bounded_stack")
+            .matching(|m| m.in_module("::embedded$"))
+            .with_severity(Severity::Error)
+            .no_recursion()
+            .build();
+
+        if let ConfiguredLint::Function(function_lint) = &builder.lints[0] {
+            assert!(matches!(
+                function_lint.rules[0],
+                FunctionRule::NoRecursion(Severity::Error)
+            ));
+        } else {
+            panic!("Unexpected lint type");
+        }
+    }
+
+    #[test]
+    fn test_function_lint_max_by_value_parameters() {
+        let mut builder = LintBuilder::new();
+
+        builder
+            .function_lint()
+            .lint_named("borrow_arguments")
+            .matching(|m| m.in_module("::api$"))
+            .max_by_value_parameters(2)
+            .build();
+
+        if let ConfiguredLint::Function(function_lint) = &builder.lints[0] {
+            assert!(matches!(
+                function_lint.rules[0],
+                FunctionRule::MaxByValueParameters(2, Severity::Warn)
+            ));
+        } else {
+            panic!("Unexpected lint type");
+        }
+    }
+
+    #[test]
+    fn test_function_lint_must_return() {
+        let mut builder = LintBuilder::new();
+
+        builder
+            .function_lint()
+            .lint_named("fallible_handlers")
+            .matching(|m| m.in_module("::handlers$"))
+            .with_severity(Severity::Error)
+            .must_return(ReturnTypePattern::Result)
+            .build();
+
+        if let ConfiguredLint::Function(function_lint) = &builder.lints[0] {
+            assert!(matches!(
+                function_lint.rules[0],
+                FunctionRule::MustReturnType(ReturnTypePattern::Result, Severity::Error)
+            ));
+        } else {
+            panic!("Unexpected lint type");
+        }
+    }
+
+    #[test]
+    fn test_function_lint_forbid_call_to() {
+        let mut builder = LintBuilder::new();
+
+        builder
+            .function_lint()
+            .lint_named("no_process_exit")
+            .matching(|m| m.name_regex(".*"))
+            .forbid_call_to(vec!["std::process::exit", "std::env::var"])
+            .build();
+
+        if let ConfiguredLint::Function(function_lint) = &builder.lints[0] {
+            match &function_lint.rules[0] {
+                FunctionRule::ForbidCallTo { paths, severity } => {
+                    assert_eq!(paths, &vec!["std::process::exit", "std::env::var"]);
+                    assert_eq!(severity, &Severity::Warn);
+                }
+                _ => panic!("Expected ForbidCallTo rule"),
+            }
+        } else {
+            panic!("Unexpected lint type");
+        }
+    }
+
+    #[test]
+    fn test_function_lint_result_error_must_be_enum() {
+        let mut builder = LintBuilder::new();
+
+        builder
+            .function_lint()
+            .lint_named("enum_errors")
+            .matching(|m| m.in_module("^api$"))
+            .with_severity(Severity::Error)
+            .result_error_must_be_enum()
+            .allow_boxed_errors()
+            .build();
+
+        if let ConfiguredLint::Function(function_lint) = &builder.lints[0] {
+            assert!(matches!(
+                function_lint.rules[0],
+                FunctionRule::ResultErrorMustBeEnum {
+                    allow_boxed: true,
+                    allow_anyhow: false,
+                    severity: Severity::Error,
+                }
+            ));
+        } else {
+            panic!("Unexpected lint type");
+        }
+
+        // Both exemptions default to off when left out of pup.ron
+        let rule: FunctionRule = ron::from_str("ResultErrorMustBeEnum(severity: Warn)").unwrap();
+        assert!(matches!(
+            rule,
+            FunctionRule::ResultErrorMustBeEnum {
+                allow_boxed: false,
+                allow_anyhow: false,
+                severity: Severity::Warn,
+            }
+        ));
+    }
+
+    #[test]
+    fn test_function_lint_returns_impl_future_matcher() {
+        let mut builder = LintBuilder::new();
+
+        builder
+            .function_lint()
+            .lint_named("async_handlers")
+            .matching(|m| m.returns_impl_future().and(m.in_module("::handlers$")))
+            .no_panic()
+            .build();
+
+        if let ConfiguredLint::Function(function_lint) = &builder.lints[0] {
+            assert!(matches!(
+                &function_lint.matches,
+                FunctionMatch::AndMatches(left, _) if matches!(**left, FunctionMatch::ReturnsImplFuture)
+            ));
+        } else {
+            panic!("Unexpected lint type");
+        }
+
+        // Round trip through RON
+        let serialized = ron::to_string(&builder).unwrap();
+        assert!(serialized.contains("ReturnsImplFuture"));
+        let deserialized: LintBuilder = ron::from_str(&serialized).unwrap();
+        assert_eq!(deserialized.lints.len(), 1);
+    }
+
+    #[test]
+    fn test_function_lint_min_length_matcher() {
+        let mut builder = LintBuilder::new();
+
+        builder
+            .function_lint()
+            .lint_named("long_functions_documented")
+            .matching(|m| m.min_length(20).and(m.in_module("^my_crate::api")))
+            .require_doc_comment()
+            .build();
+
+        if let ConfiguredLint::Function(function_lint) = &builder.lints[0] {
+            assert!(matches!(
+                &function_lint.matches,
+                FunctionMatch::AndMatches(left, _) if matches!(**left, FunctionMatch::MinLength(20))
+            ));
+        } else {
+            panic!("Unexpected lint type");
+        }
+
+        let serialized = ron::to_string(&builder).unwrap();
+        assert!(serialized.contains("MinLength(20)"));
+        let deserialized: LintBuilder = ron::from_str(&serialized).unwrap();
+        assert_eq!(deserialized.lints.len(), 1);
+    }
+
+    #[test]
+    fn test_function_lint_method_matchers() {
+        let mut builder = LintBuilder::new();
+
+        builder
+            .function_lint()
+            .lint_named("few_parameters")
+            .matching(|m| m.is_free_function().and(m.not(m.is_method())))
+            .max_by_value_parameters(3)
+            .build();
+
+        if let ConfiguredLint::Function(function_lint) = &builder.lints[0] {
+            assert!(matches!(
+                &function_lint.matches,
+                FunctionMatch::AndMatches(left, right)
+                    if matches!(**left, FunctionMatch::IsFreeFunction)
+                        && matches!(&**right, FunctionMatch::NotMatch(inner) if matches!(**inner, FunctionMatch::IsMethod))
+            ));
+        } else {
+            panic!("Unexpected lint type");
+        }
+
+        let serialized = ron::to_string(&builder).unwrap();
+        assert!(serialized.contains("AndMatches(IsFreeFunction,NotMatch(IsMethod))"));
+        let deserialized: LintBuilder = ron::from_str(&serialized).unwrap();
+        assert_eq!(deserialized.lints.len(), 1);
+    }
+
+    #[test]
+    fn test_function_lint_overrides_default_matcher() {
+        let mut builder = LintBuilder::new();
+
+        builder
+            .function_lint()
+            .lint_named("keep_default_methods")
+            .matching(|m| m.in_module("::model$").and(m.overrides_default()))
+            .with_severity(Severity::Error)
+            .must_not_exist()
+            .build();
+
+        if let ConfiguredLint::Function(function_lint) = &builder.lints[0] {
+            assert!(matches!(
+                &function_lint.matches,
+                FunctionMatch::AndMatches(_, right)
+                    if matches!(**right, FunctionMatch::OverridesTraitMethod)
+            ));
+        } else {
+            panic!("Unexpected lint type");
+        }
+    }
+
     #[test]
     fn test_function_lint_generate_from_empty_contexts() {
         // Test with empty contexts
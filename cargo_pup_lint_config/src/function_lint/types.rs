@@ -31,14 +31,34 @@ pub enum FunctionMatch {
     NameEquals(String),
     /// Match functions whose name matches this regex pattern
     NameRegex(String),
+    /// Match functions whose name starts with this prefix
+    NameStartsWith(String),
+    /// Match functions whose name ends with this suffix
+    NameEndsWith(String),
     /// Match functions inside a specific module
     InModule(String),
     /// Match functions that return a specific type pattern
     ReturnsType(ReturnTypePattern),
+    /// Match functions where any parameter's rendered type matches this regex pattern
+    ParameterType(String),
     /// Match async functions
     IsAsync,
     /// Match unsafe functions
     IsUnsafe,
+    /// Match functions that are `async fn`, or that return an opaque `impl Future`.
+    /// Boxed futures such as `Pin<Box<dyn Future>>` are not matched.
+    ReturnsImplFuture,
+    /// Match functions whose body spans at least this many source lines, counted
+    /// the same way as the `MaxLength` rule
+    MinLength(usize),
+    /// Match associated functions, in an impl block or a trait, with or without `self`
+    IsMethod,
+    /// Match functions that aren't associated with a type or trait
+    IsFreeFunction,
+    /// Match methods in a trait impl that override a method the trait provides a default
+    /// body for, such as `Iterator::size_hint`. Implementations of required methods,
+    /// like `Iterator::next`, are not matched.
+    OverridesTraitMethod,
     /// Logical AND - both patterns must match
     AndMatches(Box<FunctionMatch>, Box<FunctionMatch>),
     /// Logical OR - either pattern must match
@@ -53,6 +73,9 @@ pub struct FunctionLint {
     pub name: String,
     pub matches: FunctionMatch,
     pub rules: Vec<FunctionRule>,
+    /// Fail the run once this lint reports more than this many violations, even at `Warn`
+    #[serde(default)]
+    pub escalate_after: Option<usize>,
 }
 
 /// Rules that can be applied to functions matching specific criteria
@@ -75,10 +98,150 @@ pub enum FunctionRule {
     NoPanic(Severity),
     /// Enforces that a function must not trigger index bounds panics
     NoIndexPanic(Severity),
+    /// Enforces a maximum number of generic parameters declared by the function itself.
+    /// Type and const parameters (including `impl Trait` arguments) are counted;
+    /// explicitly declared lifetimes are only counted when `count_lifetimes` is set.
+    MaxGenerics {
+        max: usize,
+        #[serde(default)]
+        count_lifetimes: bool,
+        severity: Severity,
+    },
+    /// Enforces that public functions have a doc comment. Methods in trait impls are
+    /// exempt, as they inherit the trait's documentation.
+    RequireDocComment(Severity),
+    /// Forbids `unsafe` blocks in the function body, and declaring the function `unsafe fn`
+    ForbidUnsafe(Severity),
+    /// Enforces a maximum cyclomatic complexity for the function body. The complexity
+    /// starts at 1 and each of the following adds one:
+    /// - an `if` or `if let` expression (each `else if` counts separately)
+    /// - every arm of a `match` after the first
+    /// - a `loop`, `while`, `while let` or `for` loop
+    /// - a `&&` or `||` operator
+    /// - a `?` operator
+    ///
+    /// Closures are counted as part of the enclosing function, while code produced by
+    /// macro expansion is not counted. Match guards, `let ... else` and early returns
+    /// do not add to the complexity.
+    MaxComplexity(usize, Severity),
+    /// Enforces a maximum number of statements in the function body, which unlike
+    /// `MaxLength` isn't affected by comments, blank lines or formatting. Statements in
+    /// nested blocks and closures are counted, and a block's trailing expression counts
+    /// as a statement. Nested items and code produced by macro expansion are not counted.
+    MaxStatements(usize, Severity),
+    /// Enforces a maximum number of local variables bound in the function body. Every
+    /// binding in a `let` statement or `let` expression counts, so `let (a, b) = ...`
+    /// binds two, including in nested blocks. Closures, nested items and code produced
+    /// by macro expansion are not counted. Bindings in `match` arm patterns are only
+    /// counted when `count_match_bindings` is set.
+    MaxLocals {
+        max: usize,
+        #[serde(default)]
+        count_match_bindings: bool,
+        severity: Severity,
+    },
+    /// Forbids parameter and return types whose rendered type, with full paths such as
+    /// `std::rc::Rc<u32>` or `*mut u8`, matches any of the given regular expressions.
+    /// A pattern that isn't a valid regular expression, such as `*mut`, is reported and
+    /// then only matches a type rendered exactly as it.
+    ForbidTypeInSignature {
+        type_patterns: Vec<String>,
+        severity: Severity,
+    },
+    /// Enforces that the function is declared `async fn`
+    MustBeAsync(Severity),
+    /// Forbids calls to functions or methods whose full path, such as
+    /// `std::process::exit`, matches one of the given regular expressions. As with the
+    /// matchers, a pattern matches anywhere in the path unless anchored with `^` and `$`.
+    /// Methods are named through their type, as in `std::vec::Vec::<T, A>::push`.
+    ForbidCallTo {
+        paths: Vec<String>,
+        severity: Severity,
+    },
+    /// Enforces that the error type of a returned `Result` is an enum. Boxed errors,
+    /// such as `Box<dyn Error>`, and `anyhow::Error` are only accepted when allowed.
+    ResultErrorMustBeEnum {
+        #[serde(default)]
+        allow_boxed: bool,
+        #[serde(default)]
+        allow_anyhow: bool,
+        severity: Severity,
+    },
+    /// Forbids the function from calling itself directly, including from closures in
+    /// its body. Only direct recursion is found: functions that call each other in a
+    /// cycle, and calls through trait methods or function pointers, are not reported.
+    NoRecursion(Severity),
+    /// Enforces a maximum number of parameters taken by value, i.e. whose type isn't a
+    /// reference. `self` counts when it's taken by value; references, however many,
+    /// are not limited.
+    MaxByValueParameters(usize, Severity),
+    /// Enforces that the function's return type matches the pattern, e.g. that every
+    /// handler returns a `Result`. The pattern is evaluated as it is for the
+    /// `ReturnsType` matcher.
+    MustReturnType(ReturnTypePattern, Severity),
+    /// Forbids reading or writing a `static mut` in the function body, including from
+    /// closures and through `&raw mut`. Each access is reported, whether or not it's
+    /// inside an `unsafe` block that `ForbidUnsafe` would otherwise allow.
+    ForbidStaticMutAccess(Severity),
+    /// Requires errors to be given context before `?` turns them into an
+    /// `anyhow::Error`. A `?` is reported when it converts into an `anyhow::Result`,
+    /// its operand's error type is anything other than `anyhow::Error`, and the
+    /// operand isn't a `.context(...)` or `.with_context(...)` call. Propagating an
+    /// `anyhow::Error` as it is, with context already attached, is fine.
+    ///
+    /// Each `?` is judged by what it converts into, so closures and async blocks that
+    /// return an `anyhow::Result` are checked as well. The rule is opinionated: scope
+    /// it with matchers to the application code where the context is worth having,
+    /// rather than to libraries with their own error types.
+    RequireErrorContext(Severity),
+    /// Forbids indexing expressions such as `a[i]` or `a[1..]` in the function body,
+    /// including in closures, as they panic when the index is out of bounds. Unlike
+    /// `NoIndexPanic`, each indexing written in the function is reported, whether or not
+    /// the compiler can prove it in bounds. Indices that are integer literals or
+    /// constants are allowed when `allow_constant_indices` is set.
+    ForbidIndexing {
+        #[serde(default)]
+        allow_constant_indices: bool,
+        severity: Severity,
+    },
+    /// Enforces that the name of each parameter matches a regular expression, e.g. to
+    /// forbid single-letter names. `self` isn't checked, and neither are parameters
+    /// bound by a pattern, such as `(a, b): (u32, u32)`, or ignored with `_`.
+    ParameterNamingPattern(String, Severity),
 }
 
 // Helper methods for FunctionRule
 impl FunctionRule {
+    /// Returns the severity this rule reports with
+    pub fn severity(&self) -> Severity {
+        match self {
+            FunctionRule::MaxLength(_, severity)
+            | FunctionRule::ResultErrorMustImplementError(severity)
+            | FunctionRule::MustNotExist(severity)
+            | FunctionRule::NoAllocation(severity)
+            | FunctionRule::NoUnwrap(severity)
+            | FunctionRule::NoPanic(severity)
+            | FunctionRule::NoIndexPanic(severity)
+            | FunctionRule::MaxGenerics { severity, .. }
+            | FunctionRule::RequireDocComment(severity)
+            | FunctionRule::ForbidUnsafe(severity)
+            | FunctionRule::MaxComplexity(_, severity)
+            | FunctionRule::MaxStatements(_, severity)
+            | FunctionRule::MaxLocals { severity, .. }
+            | FunctionRule::ForbidTypeInSignature { severity, .. }
+            | FunctionRule::MustBeAsync(severity)
+            | FunctionRule::ForbidCallTo { severity, .. }
+            | FunctionRule::ResultErrorMustBeEnum { severity, .. }
+            | FunctionRule::NoRecursion(severity)
+            | FunctionRule::MaxByValueParameters(_, severity)
+            | FunctionRule::MustReturnType(_, severity)
+            | FunctionRule::ForbidStaticMutAccess(severity)
+            | FunctionRule::RequireErrorContext(severity)
+            | FunctionRule::ForbidIndexing { severity, .. }
+            | FunctionRule::ParameterNamingPattern(_, severity) => *severity,
+        }
+    }
+
     // pub fn and(self, other: FunctionRule) -> Self {
     //     FunctionRule::And(Box::new(self), Box::new(other))
     // }
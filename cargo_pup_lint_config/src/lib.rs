@@ -1,15 +1,29 @@
 // This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
 
+pub mod config_diff;
+pub mod config_error;
+pub mod config_loader;
+pub mod const_lint;
+pub mod enum_lint;
 pub mod function_lint;
 pub mod lint_builder;
 pub mod lint_builder_ext;
 pub mod module_lint;
+pub mod report;
 pub mod struct_lint;
+pub mod trait_lint;
 
+pub use config_error::ConfigError;
 pub use lint_builder::LintBuilder;
-pub use lint_builder_ext::LintBuilderExt;
+pub use lint_builder_ext::{LintBuilderExt, LintViolation, parse_violations};
 
 // Make sure our extensions are visible
+pub use const_lint::{
+    ConstLintExt, ConstMatch, ConstMatchNode, ConstMatcher, ConstRule, matcher as const_matcher,
+};
+pub use enum_lint::{
+    EnumLintExt, EnumMatch, EnumMatchNode, EnumMatcher, EnumRule, matcher as enum_matcher,
+};
 pub use function_lint::{
     FunctionLintExt, FunctionMatch, FunctionMatchNode, FunctionMatcher, FunctionRule,
     ReturnTypePattern, matcher as function_matcher,
@@ -22,10 +36,16 @@ pub use struct_lint::{
     StructLintExt, StructMatch, StructMatchNode, StructMatcher, StructRule,
     matcher as struct_matcher,
 };
+pub use trait_lint::{
+    TraitLintExt, TraitMatch, TraitMatchNode, TraitMatcher, TraitRule, matcher as trait_matcher,
+};
 
+use crate::const_lint::ConstLint;
+use crate::enum_lint::EnumLint;
 use crate::function_lint::FunctionLint;
 use crate::module_lint::ModuleLint;
 use crate::struct_lint::StructLint;
+use crate::trait_lint::TraitLint;
 use cargo_pup_common::project_context::ProjectContext;
 use serde::{Deserialize, Serialize};
 
@@ -40,7 +60,7 @@ pub trait GenerateFromContext {
 }
 
 /// Severity level for lint rules
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Default)]
 pub enum Severity {
     /// Warning - prints a warning but doesn't cause failure
     #[default]
@@ -49,9 +69,107 @@ pub enum Severity {
     Error,
 }
 
+/// Naming convention for identifiers, as an alternative to spelling out a regex
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CaseStyle {
+    /// Lowercase ASCII letters and digits separated by single underscores, e.g. `http_client2`
+    Snake,
+    /// A lowercase ASCII letter followed by ASCII letters and digits, e.g. `httpClient`
+    Camel,
+    /// An uppercase ASCII letter followed by ASCII letters and digits, e.g. `HttpClient`
+    Pascal,
+}
+
+impl CaseStyle {
+    /// Returns true if the name follows this convention
+    pub fn matches(&self, name: &str) -> bool {
+        let mut chars = name.chars();
+        let Some(first) = chars.next() else {
+            return false;
+        };
+        match self {
+            CaseStyle::Snake => {
+                first.is_ascii_lowercase()
+                    && !name.ends_with('_')
+                    && !name.contains("__")
+                    && chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+            }
+            CaseStyle::Camel => {
+                first.is_ascii_lowercase() && chars.all(|c| c.is_ascii_alphanumeric())
+            }
+            CaseStyle::Pascal => {
+                first.is_ascii_uppercase() && chars.all(|c| c.is_ascii_alphanumeric())
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for CaseStyle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CaseStyle::Snake => write!(f, "snake_case"),
+            CaseStyle::Camel => write!(f, "camelCase"),
+            CaseStyle::Pascal => write!(f, "PascalCase"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ConfiguredLint {
     Module(ModuleLint),
     Struct(StructLint),
     Function(FunctionLint),
+    Trait(TraitLint),
+    Const(ConstLint),
+    Enum(EnumLint),
+}
+
+impl ConfiguredLint {
+    /// Returns the configured name of the lint
+    pub fn name(&self) -> &str {
+        match self {
+            ConfiguredLint::Module(lint) => &lint.name,
+            ConfiguredLint::Struct(lint) => &lint.name,
+            ConfiguredLint::Function(lint) => &lint.name,
+            ConfiguredLint::Trait(lint) => &lint.name,
+            ConfiguredLint::Const(lint) => &lint.name,
+            ConfiguredLint::Enum(lint) => &lint.name,
+        }
+    }
+
+    /// Returns the kind of item the lint applies to, e.g. "module"
+    pub fn kind(&self) -> &'static str {
+        match self {
+            ConfiguredLint::Module(_) => "module",
+            ConfiguredLint::Struct(_) => "struct",
+            ConfiguredLint::Function(_) => "function",
+            ConfiguredLint::Trait(_) => "trait",
+            ConfiguredLint::Const(_) => "const",
+            ConfiguredLint::Enum(_) => "enum",
+        }
+    }
+
+    /// Returns the highest severity across the lint's rules, or `None` if it has no rules
+    pub fn severity(&self) -> Option<Severity> {
+        match self {
+            ConfiguredLint::Module(lint) => lint.rules.iter().map(|r| r.severity()).max(),
+            ConfiguredLint::Struct(lint) => lint.rules.iter().map(|r| r.severity()).max(),
+            ConfiguredLint::Function(lint) => lint.rules.iter().map(|r| r.severity()).max(),
+            ConfiguredLint::Trait(lint) => lint.rules.iter().map(|r| r.severity()).max(),
+            ConfiguredLint::Const(lint) => lint.rules.iter().map(|r| r.severity()).max(),
+            ConfiguredLint::Enum(lint) => lint.rules.iter().map(|r| r.severity()).max(),
+        }
+    }
+
+    /// Returns the violation count past which the lint fails the run, if one is configured
+    pub fn escalate_after(&self) -> Option<usize> {
+        match self {
+            ConfiguredLint::Module(lint) => lint.escalate_after,
+            ConfiguredLint::Struct(lint) => lint.escalate_after,
+            ConfiguredLint::Function(lint) => lint.escalate_after,
+            ConfiguredLint::Trait(lint) => lint.escalate_after,
+            ConfiguredLint::Const(lint) => lint.escalate_after,
+            ConfiguredLint::Enum(lint) => lint.escalate_after,
+        }
+    }
 }
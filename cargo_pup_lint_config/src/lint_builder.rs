@@ -1,20 +1,32 @@
 // This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
 
-use ron::de::from_reader;
 use ron::ser::{PrettyConfig, to_writer_pretty};
 use std::fs::File;
 use std::io::{self, Write};
 // lint_builder.rs
+use crate::const_lint::ConstLint;
+use crate::enum_lint::EnumLint;
 use crate::function_lint::FunctionLint;
 use crate::module_lint::ModuleLint;
 use crate::struct_lint::StructLint;
-use crate::{ConfiguredLint, GenerateFromContext};
+use crate::trait_lint::TraitLint;
+use crate::{ConfigError, ConfiguredLint, GenerateFromContext};
 use cargo_pup_common::project_context::ProjectContext;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 #[derive(Debug, Default, Serialize, Deserialize)]
 pub struct LintBuilder {
+    /// Other configuration files whose lints are merged in before these, given
+    /// relative to this file. Lints defined here replace included lints of the same name.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub include: Vec<String>,
     pub lints: Vec<ConfiguredLint>,
+    /// Named sets of lints, one of which can be selected with `--pup-profile` or
+    /// `PUP_PROFILE`. The selected profile's lints run instead of `lints`, e.g. to run
+    /// stricter versions of them in CI. Without a profile, only `lints` run.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub profiles: BTreeMap<String, Vec<ConfiguredLint>>,
 }
 
 impl LintBuilder {
@@ -44,6 +56,9 @@ impl LintBuilder {
         ModuleLint::generate_from_contexts(contexts, &mut builder);
         StructLint::generate_from_contexts(contexts, &mut builder);
         FunctionLint::generate_from_contexts(contexts, &mut builder);
+        TraitLint::generate_from_contexts(contexts, &mut builder);
+        ConstLint::generate_from_contexts(contexts, &mut builder);
+        EnumLint::generate_from_contexts(contexts, &mut builder);
 
         builder
     }
@@ -75,11 +90,15 @@ impl LintBuilder {
 
     // Method to read the LintBuilder from a file
     pub fn read_from_file<P: AsRef<std::path::Path>>(path: P) -> io::Result<Self> {
-        let file = File::open(path).map_err(io::Error::other)?; // Map any io::Error
+        let content = std::fs::read_to_string(&path)?;
 
-        let lint_builder: LintBuilder = from_reader(file).map_err(io::Error::other)?; // Map ron::de::SpannedError to io::Error
+        Self::parse(&content).map_err(|e| io::Error::other(e.in_file(path.as_ref())))
+    }
 
-        Ok(lint_builder)
+    /// Parse a configuration from RON, describing any error in terms of the lints,
+    /// rules and matchers it was meant to configure
+    pub fn parse(content: &str) -> Result<Self, ConfigError> {
+        ron::from_str(content).map_err(|e| ConfigError::from_ron(content, e))
     }
 }
 
@@ -178,6 +197,39 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_profiles() {
+        let builder = LintBuilder::parse(
+            r#"(
+    lints: [
+        Module((name: "not_empty", matches: Module(".*"), rules: [MustNotBeEmpty(Warn)])),
+    ],
+    profiles: {
+        "strict": [
+            Module((name: "not_empty", matches: Module(".*"), rules: [MustNotBeEmpty(Error)])),
+        ],
+        "relaxed": [],
+    },
+)"#,
+        )
+        .unwrap();
+
+        assert_eq!(builder.lints.len(), 1);
+        assert_eq!(
+            builder.profiles.keys().collect::<Vec<_>>(),
+            vec!["relaxed", "strict"]
+        );
+        assert_eq!(
+            builder.profiles["strict"][0].severity(),
+            Some(Severity::Error)
+        );
+
+        // Configurations without profiles are written as before
+        let mut content = String::new();
+        to_writer_pretty(&mut content, &LintBuilder::new(), PrettyConfig::default()).unwrap();
+        assert!(!content.contains("profiles"));
+    }
+
     /// Test that ensures the full LintBuilder structure is correctly serialized and deserialized,
     /// preserving the exact structure and format expected by cargo-pup.
     #[test]
@@ -262,6 +314,121 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_escalate_after() {
+        let mut builder = LintBuilder::new();
+        builder
+            .module_lint()
+            .lint_named("escalating")
+            .matching(|m| m.module(".*"))
+            .escalate_after(3)
+            .no_wildcard_imports()
+            .build();
+        builder
+            .function_lint()
+            .lint_named("not_escalating")
+            .matching(|m| m.name_regex(".*"))
+            .max_length(50)
+            .build();
+
+        let serialized = ron::to_string(&builder).expect("Failed to serialize to RON string");
+        let from_ron: LintBuilder =
+            ron::from_str(&serialized).expect("Failed to deserialize from RON string");
+        assert_eq!(from_ron.lints[0].escalate_after(), Some(3));
+        assert_eq!(from_ron.lints[1].escalate_after(), None);
+
+        // Configurations written before the option existed still load
+        let legacy: LintBuilder = ron::from_str(
+            r#"(lints: [Module((name: "legacy", matches: Module(".*"), rules: []))])"#,
+        )
+        .expect("Failed to deserialize config without escalate_after");
+        assert_eq!(legacy.lints[0].escalate_after(), None);
+    }
+
+    #[test]
+    fn test_empty_module_rules_ignoring_test_code() {
+        // Only the most recently added empty-module rule ignores test code
+        let mut builder = LintBuilder::new();
+        builder
+            .module_lint()
+            .lint_named("facades")
+            .matching(|m| m.module("^app::api$"))
+            .must_be_empty()
+            .must_have_empty_mod_file()
+            .ignoring_test_code()
+            .build();
+        let serialized = ron::to_string(&builder).unwrap();
+        let from_ron = LintBuilder::parse(&serialized).unwrap();
+        if let ConfiguredLint::Module(module_lint) = &from_ron.lints[0] {
+            assert!(matches!(
+                module_lint.rules[..],
+                [
+                    ModuleRule::MustBeEmpty {
+                        severity: Severity::Warn,
+                        ignore_test_code: false,
+                    },
+                    ModuleRule::MustHaveEmptyModFile {
+                        severity: Severity::Warn,
+                        ignore_test_code: true,
+                    },
+                ]
+            ));
+        } else {
+            panic!("Unexpected lint type");
+        }
+
+        // Configurations written before the toggle still count test code, and the
+        // toggle is only written out when it's set
+        let rule: ModuleRule = ron::from_str("MustBeEmpty(Error)").unwrap();
+        assert!(matches!(
+            rule,
+            ModuleRule::MustBeEmpty {
+                severity: Severity::Error,
+                ignore_test_code: false,
+            }
+        ));
+        assert_eq!(ron::to_string(&rule).unwrap(), "MustBeEmpty(Error)");
+
+        let rule = ModuleRule::MustHaveEmptyModFile {
+            severity: Severity::Error,
+            ignore_test_code: true,
+        };
+        let serialized = ron::to_string(&rule).unwrap();
+        assert!(matches!(
+            ron::from_str(&serialized).unwrap(),
+            ModuleRule::MustHaveEmptyModFile {
+                severity: Severity::Error,
+                ignore_test_code: true,
+            }
+        ));
+
+        // The fields may also be named, and `ignore_test_code` left out
+        let builder = LintBuilder::parse(
+            r#"(lints: [Module((name: "facade", matches: Module("^app::api$"), rules: [
+                MustBeEmpty((severity: Error, ignore_test_code: true)),
+                MustHaveEmptyModFile((severity: Warn)),
+            ]))])"#,
+        )
+        .unwrap();
+        if let ConfiguredLint::Module(module_lint) = &builder.lints[0] {
+            assert!(matches!(
+                module_lint.rules[..],
+                [
+                    ModuleRule::MustBeEmpty {
+                        severity: Severity::Error,
+                        ignore_test_code: true,
+                    },
+                    ModuleRule::MustHaveEmptyModFile {
+                        severity: Severity::Warn,
+                        ignore_test_code: false,
+                    },
+                ]
+            ));
+        } else {
+            panic!("Unexpected lint type");
+        }
+    }
+
     #[test]
     fn test_generate_from_contexts() {
         // Create test project contexts
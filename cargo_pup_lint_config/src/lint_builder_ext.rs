@@ -1,11 +1,12 @@
 // This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
 
 use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
 use std::path::Path;
 use std::process::{Command, Output};
 use tempfile::NamedTempFile;
 
-use crate::LintBuilder;
+use crate::{LintBuilder, Severity};
 
 /// Extension trait for LintBuilder that provides unit testing capabilities.
 pub trait LintBuilderExt {
@@ -89,38 +90,100 @@ pub trait LintBuilderExt {
     /// }
     /// ```
     fn assert_lints(&self, project_path: Option<&str>) -> Result<Output>;
-}
 
-impl LintBuilderExt for LintBuilder {
-    fn assert_lints(&self, project_path: Option<&str>) -> Result<Output> {
-        // Determine which path to validate
-        let path_to_validate = match project_path {
-            Some(path) => path.to_string(),
-            None => std::env::current_dir()
-                .context("Failed to get current working directory")?
-                .to_str()
-                .context("Current working directory path is not valid UTF-8")?
-                .to_string(),
-        };
+    /// Executes lint rules against a Cargo project and returns the violations found.
+    ///
+    /// Unlike [`assert_lints`](LintBuilderExt::assert_lints), this method does not panic
+    /// when rules are violated. It runs `cargo pup check` with JSON diagnostics enabled and
+    /// parses the output into a list of [`LintViolation`]s, so tests can make precise
+    /// assertions about which rules fired and where.
+    ///
+    /// # Arguments
+    ///
+    /// * `project_path` - Optional path to the project to lint, with the same meaning as
+    ///   for [`assert_lints`](LintBuilderExt::assert_lints)
+    ///
+    /// # Returns
+    ///
+    /// Returns the violations reported by cargo-pup, or an error if the project path is
+    /// invalid, the cargo-pup command fails to execute, or the build fails for a reason
+    /// other than a lint violation, even when violations were reported as well.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use cargo_pup_lint_config::{FunctionLintExt, LintBuilder, LintBuilderExt};
+    /// # fn test_lint_rules() -> anyhow::Result<()> {
+    /// let mut builder = LintBuilder::new();
+    /// builder.function_lint()
+    ///     .lint_named("no_unwrap")
+    ///     .matching(|m| m.name_regex(".*"))
+    ///     .no_unwrap()
+    ///     .build();
+    ///
+    /// let violations = builder.run_lints(None)?;
+    /// let count = violations.iter().filter(|v| v.lint_name == "no_unwrap").count();
+    /// assert_eq!(count, 2);
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn run_lints(&self, project_path: Option<&str>) -> Result<Vec<LintViolation>>;
 
-        // Validate the project path
-        validate_project_path(&path_to_validate).with_context(|| {
-            if project_path.is_some() {
-                format!("Invalid manifest path: {path_to_validate}")
-            } else {
-                format!(
-                    "Current working directory is not a valid Cargo project: {path_to_validate}"
-                )
-            }
-        })?;
+    /// Executes lint rules against a snippet of source code and returns the violations
+    /// found.
+    ///
+    /// The code is written as the `src/lib.rs` of a throwaway library crate named
+    /// `snippet`, which is then checked as [`run_lints`](LintBuilderExt::run_lints)
+    /// would. This lets a rule be tried out without a fixture project. Module paths in
+    /// the snippet start with `snippet`, e.g. `snippet::api`.
+    ///
+    /// # Returns
+    ///
+    /// Returns the violations reported by cargo-pup, or an error if the throwaway crate
+    /// can't be created, the cargo-pup command fails to execute, or the snippet fails
+    /// to build for a reason other than a lint violation.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use cargo_pup_lint_config::{FunctionLintExt, LintBuilder, LintBuilderExt};
+    /// # fn test_lint_rules() -> anyhow::Result<()> {
+    /// let mut builder = LintBuilder::new();
+    /// builder.function_lint()
+    ///     .lint_named("short_functions")
+    ///     .matching(|m| m.in_module("^snippet$"))
+    ///     .max_length(3)
+    ///     .build();
+    ///
+    /// let violations = builder.assert_lints_on_source(
+    ///     "pub fn long() {\n    let a = 1;\n    let b = 2;\n    let _ = a + b;\n}\n",
+    /// )?;
+    /// assert_eq!(violations.len(), 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn assert_lints_on_source(&self, code: &str) -> Result<Vec<LintViolation>>;
+}
 
-        let args = if let Some(path) = project_path {
-            vec!["--manifest-path", path]
-        } else {
-            vec![]
-        };
+/// A single lint violation reported by cargo-pup.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LintViolation {
+    /// Name of the configured lint that produced the violation
+    pub lint_name: String,
+    /// Severity the violation was reported with
+    pub severity: Severity,
+    /// Source file containing the violation, relative to the workspace root
+    pub file: String,
+    /// 1-based line number of the violation
+    pub line: usize,
+    /// The diagnostic message
+    pub message: String,
+}
 
-        let output = run_with_args(self, &args.to_vec())?;
+impl LintBuilderExt for LintBuilder {
+    fn assert_lints(&self, project_path: Option<&str>) -> Result<Output> {
+        let args = project_args(project_path)?;
+        let output = run_with_args(self, &args)?;
 
         // Check if the command failed (non-zero exit status)
         if !output.status.success() {
@@ -138,6 +201,170 @@ impl LintBuilderExt for LintBuilder {
 
         Ok(output)
     }
+
+    fn run_lints(&self, project_path: Option<&str>) -> Result<Vec<LintViolation>> {
+        let mut args = project_args(project_path)?;
+        args.push("--message-format=json");
+
+        let output = run_with_args(self, &args)?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let violations = parse_violations(&stdout);
+
+        // A failing build without any pup violations means something else went wrong,
+        // e.g. a compile error in the project itself. So do errors alongside them, as
+        // the violations found are then only those from before the build broke.
+        let errors = compile_errors(&stdout);
+        if !errors.is_empty() {
+            anyhow::bail!(
+                "cargo pup check failed with errors that aren't lint violations:\n{}",
+                errors.concat()
+            );
+        }
+        if !output.status.success() && violations.is_empty() {
+            anyhow::bail!(
+                "cargo pup check failed without reporting lint violations:\n{}",
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+
+        Ok(violations)
+    }
+
+    fn assert_lints_on_source(&self, code: &str) -> Result<Vec<LintViolation>> {
+        let crate_dir =
+            tempfile::tempdir().context("Failed to create temporary crate directory")?;
+        let manifest_path = write_snippet_crate(crate_dir.path(), code)?;
+        let manifest_path = manifest_path
+            .to_str()
+            .context("Temporary crate path is not valid UTF-8")?;
+
+        // Keep crate_dir alive until the check has finished
+        self.run_lints(Some(manifest_path))
+    }
+}
+
+/// Writes a library crate named `snippet` with `code` as its `src/lib.rs` into `dir`,
+/// returning the path of its manifest
+fn write_snippet_crate(dir: &Path, code: &str) -> Result<std::path::PathBuf> {
+    let src_dir = dir.join("src");
+    std::fs::create_dir_all(&src_dir).context("Failed to create snippet src directory")?;
+    std::fs::write(src_dir.join("lib.rs"), code).context("Failed to write snippet source")?;
+
+    // An empty workspace table keeps the crate out of any workspace it's created under
+    let manifest_path = dir.join("Cargo.toml");
+    std::fs::write(
+        &manifest_path,
+        r#"[package]
+name = "snippet"
+version = "0.1.0"
+edition = "2024"
+
+[workspace]
+"#,
+    )
+    .context("Failed to write snippet Cargo.toml")?;
+
+    Ok(manifest_path)
+}
+
+/// Validates the project path and builds the cargo arguments that select it
+fn project_args(project_path: Option<&str>) -> Result<Vec<&str>> {
+    // Determine which path to validate
+    let path_to_validate = match project_path {
+        Some(path) => path.to_string(),
+        None => std::env::current_dir()
+            .context("Failed to get current working directory")?
+            .to_str()
+            .context("Current working directory path is not valid UTF-8")?
+            .to_string(),
+    };
+
+    // Validate the project path
+    validate_project_path(&path_to_validate).with_context(|| {
+        if project_path.is_some() {
+            format!("Invalid manifest path: {path_to_validate}")
+        } else {
+            format!("Current working directory is not a valid Cargo project: {path_to_validate}")
+        }
+    })?;
+
+    Ok(match project_path {
+        Some(path) => vec!["--manifest-path", path],
+        None => vec![],
+    })
+}
+
+/// Extracts cargo-pup violations from cargo's JSON message stream.
+///
+/// Lines that aren't JSON (cargo-pup's own output) and diagnostics that weren't
+/// produced by a cargo-pup rule are ignored.
+pub fn parse_violations(stdout: &str) -> Vec<LintViolation> {
+    stdout
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .filter(|msg| msg["reason"] == "compiler-message")
+        .filter_map(|msg| parse_diagnostic(&msg["message"]))
+        .collect()
+}
+
+// The rendered text of the error-level compiler messages in cargo's JSON message stream
+// that aren't cargo-pup violations, like compile errors from the project itself. rustc's
+// closing "aborting due to .. previous errors" doesn't count, as violations end with it too.
+fn compile_errors(stdout: &str) -> Vec<String> {
+    stdout
+        .lines()
+        .filter(|line| parse_violations(line).is_empty())
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .filter(|msg| msg["reason"] == "compiler-message")
+        .map(|msg| msg["message"].clone())
+        .filter(|diagnostic| {
+            diagnostic["level"]
+                .as_str()
+                .is_some_and(|level| level.starts_with("error"))
+                && !diagnostic["message"]
+                    .as_str()
+                    .is_some_and(|message| message.starts_with("aborting due to"))
+        })
+        .map(|diagnostic| {
+            diagnostic["rendered"]
+                .as_str()
+                .or(diagnostic["message"].as_str())
+                .unwrap_or_default()
+                .to_string()
+        })
+        .collect()
+}
+
+fn parse_diagnostic(diagnostic: &serde_json::Value) -> Option<LintViolation> {
+    let severity = match diagnostic["level"].as_str()? {
+        "error" => Severity::Error,
+        "warning" => Severity::Warn,
+        _ => return None,
+    };
+
+    // Every cargo-pup diagnostic carries a note naming the rule that produced it
+    let lint_name = diagnostic["children"]
+        .as_array()?
+        .iter()
+        .filter_map(|child| child["message"].as_str())
+        .find_map(|note| {
+            note.strip_prefix("Applied by cargo-pup rule '")?
+                .strip_suffix("'.")
+        })?
+        .to_string();
+
+    let span = diagnostic["spans"]
+        .as_array()?
+        .iter()
+        .find(|span| span["is_primary"].as_bool() == Some(true))?;
+
+    Some(LintViolation {
+        lint_name,
+        severity,
+        file: span["file_name"].as_str()?.to_string(),
+        line: span["line_start"].as_u64()? as usize,
+        message: diagnostic["message"].as_str()?.to_string(),
+    })
 }
 
 fn run_with_args(lint_builder: &LintBuilder, args: &[&str]) -> Result<Output> {
@@ -721,4 +948,91 @@ edition = "2021"
             error_str
         );
     }
+
+    #[test]
+    fn test_run_lints_with_non_existent_path() {
+        let mut builder = LintBuilder::new();
+        builder
+            .module_lint()
+            .lint_named("test_rule")
+            .matching(|m| m.module("test::module"))
+            .must_not_be_empty()
+            .build();
+
+        let result = builder.run_lints(Some("/non/existent/path"));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_write_snippet_crate() {
+        let temp_dir = TempDir::new().expect("Failed to create temp directory");
+        let code = "pub mod api {}\n";
+
+        let manifest_path = write_snippet_crate(temp_dir.path(), code).unwrap();
+
+        assert_eq!(manifest_path, temp_dir.path().join("Cargo.toml"));
+        assert!(validate_project_path(temp_dir.path().to_str().unwrap()).is_ok());
+        let manifest = fs::read_to_string(&manifest_path).unwrap();
+        assert!(manifest.contains("name = \"snippet\""));
+        assert!(manifest.contains("[workspace]"));
+        assert_eq!(
+            fs::read_to_string(temp_dir.path().join("src").join("lib.rs")).unwrap(),
+            code
+        );
+    }
+
+    #[test]
+    fn test_parse_violations() {
+        let stdout = [
+            "Running cargo-pup checks",
+            r#"{"reason":"compiler-artifact","package_id":"test 0.1.0"}"#,
+            r#"{"reason":"compiler-message","message":{"level":"error","message":"Use of unwrap() is not allowed","spans":[{"file_name":"src/lib.rs","line_start":12,"is_primary":true}],"children":[{"level":"note","message":"Applied by cargo-pup rule 'no_unwrap'.","spans":[],"children":[]}]}}"#,
+            r#"{"reason":"compiler-message","message":{"level":"warning","message":"Module must not be empty","spans":[{"file_name":"src/other.rs","line_start":3,"is_primary":false},{"file_name":"src/empty.rs","line_start":1,"is_primary":true}],"children":[{"level":"note","message":"Applied by cargo-pup rule 'not_empty'.","spans":[],"children":[]}]}}"#,
+            r#"{"reason":"compiler-message","message":{"level":"warning","message":"unused variable: `x`","spans":[{"file_name":"src/lib.rs","line_start":4,"is_primary":true}],"children":[]}}"#,
+            r#"{"reason":"compiler-message","message":{"level":"error","message":"aborting due to 1 previous error","spans":[],"children":[]}}"#,
+        ]
+        .join("\n");
+
+        let violations = parse_violations(&stdout);
+
+        assert_eq!(
+            violations,
+            vec![
+                LintViolation {
+                    lint_name: "no_unwrap".to_string(),
+                    severity: Severity::Error,
+                    file: "src/lib.rs".to_string(),
+                    line: 12,
+                    message: "Use of unwrap() is not allowed".to_string(),
+                },
+                LintViolation {
+                    lint_name: "not_empty".to_string(),
+                    severity: Severity::Warn,
+                    file: "src/empty.rs".to_string(),
+                    line: 1,
+                    message: "Module must not be empty".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_compile_errors() {
+        let violation = r#"{"reason":"compiler-message","message":{"level":"error","message":"Use of unwrap() is not allowed","rendered":"error: Use of unwrap() is not allowed\n","spans":[{"file_name":"src/lib.rs","line_start":12,"is_primary":true}],"children":[{"level":"note","message":"Applied by cargo-pup rule 'no_unwrap'.","spans":[],"children":[]}]}}"#;
+        let warning = r#"{"reason":"compiler-message","message":{"level":"warning","message":"unused variable: `x`","rendered":"warning: unused variable: `x`\n","spans":[{"file_name":"src/lib.rs","line_start":4,"is_primary":true}],"children":[]}}"#;
+        let aborting = r#"{"reason":"compiler-message","message":{"level":"error","message":"aborting due to 2 previous errors","rendered":"error: aborting due to 2 previous errors\n","spans":[],"children":[]}}"#;
+        let compile_error = r#"{"reason":"compiler-message","message":{"level":"error","message":"cannot find value `y`","rendered":"error: cannot find value `y`\n","spans":[{"file_name":"src/lib.rs","line_start":7,"is_primary":true}],"children":[]}}"#;
+
+        // Violations, and the errors rustc closes them with, are all fine
+        let violations_only = [violation, warning, aborting].join("\n");
+        assert!(compile_errors(&violations_only).is_empty());
+
+        // A compile error alongside the violations isn't
+        let with_compile_error = [violation, compile_error, aborting].join("\n");
+        assert_eq!(
+            compile_errors(&with_compile_error),
+            vec!["error: cannot find value `y`\n".to_string()]
+        );
+        assert_eq!(parse_violations(&with_compile_error).len(), 1);
+    }
 }
@@ -3,7 +3,7 @@
 use super::matcher::{ModuleMatchNode, ModuleMatcher, matcher};
 use super::types::{ModuleLint, ModuleMatch, ModuleRule};
 use crate::lint_builder::LintBuilder;
-use crate::{ConfiguredLint, Severity};
+use crate::{CaseStyle, ConfiguredLint, Severity};
 
 /// Extension trait that adds module linting capabilities to LintBuilder
 pub trait ModuleLintExt {
@@ -47,6 +47,7 @@ impl<'a> ModuleNamedBuilder<'a> {
             rules: Vec::new(),
             current_severity: Severity::default(),
             name: self.name,
+            escalate_after: None,
         }
     }
 
@@ -78,6 +79,7 @@ pub struct ModuleConstraintBuilder<'a> {
     rules: Vec<ModuleRule>,
     current_severity: Severity,
     name: String,
+    escalate_after: Option<usize>,
 }
 
 impl<'a> ModuleConstraintBuilder<'a> {
@@ -98,6 +100,7 @@ impl<'a> ModuleConstraintBuilder<'a> {
             name: self.name,
             matches: self.match_,
             rules: self.rules,
+            escalate_after: self.escalate_after,
         });
         self.parent.push(lint);
         self.parent
@@ -109,6 +112,12 @@ impl<'a> ModuleConstraintBuilder<'a> {
         self
     }
 
+    /// Fail the run if this lint reports more than `max` violations, whatever their severity
+    pub fn escalate_after(mut self, max: usize) -> Self {
+        self.escalate_after = Some(max);
+        self
+    }
+
     /// Add a rule requiring the module to have at least one item
     pub fn must_not_be_empty(mut self) -> Self {
         self.add_rule_internal(ModuleRule::MustNotBeEmpty(self.current_severity));
@@ -117,13 +126,19 @@ impl<'a> ModuleConstraintBuilder<'a> {
 
     /// Add a rule requiring the module to be empty
     pub fn must_be_empty(mut self) -> Self {
-        self.add_rule_internal(ModuleRule::MustBeEmpty(self.current_severity));
+        self.add_rule_internal(ModuleRule::MustBeEmpty {
+            severity: self.current_severity,
+            ignore_test_code: false,
+        });
         self
     }
 
     /// Add a rule requiring the module.rs file to only re-export other modules
     pub fn must_have_empty_mod_file(mut self) -> Self {
-        self.add_rule_internal(ModuleRule::MustHaveEmptyModFile(self.current_severity));
+        self.add_rule_internal(ModuleRule::MustHaveEmptyModFile {
+            severity: self.current_severity,
+            ignore_test_code: false,
+        });
         self
     }
 
@@ -133,7 +148,8 @@ impl<'a> ModuleConstraintBuilder<'a> {
         self
     }
 
-    /// Add a rule to restrict imports by specifying allowed/denied modules
+    /// Add a rule to restrict imports by specifying allowed/denied modules. Patterns
+    /// are matched against both the imported path and what it resolves to.
     ///
     /// @param allowed_only - If provided, only these imports are allowed
     /// @param denied - If provided, these imports are explicitly prohibited
@@ -150,6 +166,38 @@ impl<'a> ModuleConstraintBuilder<'a> {
         self
     }
 
+    /// Add a rule forbidding modules matching any of `source_patterns` from importing
+    /// paths matching any of `target_patterns`, e.g. keeping everything outside
+    /// `::internal` from reaching into it. Only modules the lint matches are checked.
+    pub fn forbid_import_from(
+        mut self,
+        source_patterns: Vec<impl Into<String>>,
+        target_patterns: Vec<impl Into<String>>,
+    ) -> Self {
+        self.add_rule_internal(ModuleRule::ForbidImportFrom {
+            source_patterns: source_patterns.into_iter().map(Into::into).collect(),
+            target_patterns: target_patterns.into_iter().map(Into::into).collect(),
+            severity: self.current_severity,
+        });
+        self
+    }
+
+    /// Add a rule forbidding `pub use` re-exports of paths matching any of the patterns,
+    /// e.g. `crate::internal::.*`, so internal items stay out of the public API
+    pub fn forbid_reexport_from(mut self, patterns: Vec<impl Into<String>>) -> Self {
+        self.add_rule_internal(ModuleRule::ForbidReexportFrom {
+            patterns: patterns.into_iter().map(Into::into).collect(),
+            severity: self.current_severity,
+        });
+        self
+    }
+
+    /// Add a rule forbidding the module from using `std`, to keep it `no_std`
+    pub fn forbid_std(mut self) -> Self {
+        self.add_rule_internal(ModuleRule::ForbidStd(self.current_severity));
+        self
+    }
+
     /// Add a rule requiring the module to have a specific name
     pub fn must_be_named(mut self, name: String) -> Self {
         self.add_rule_internal(ModuleRule::MustBeNamed(name, self.current_severity));
@@ -170,4 +218,161 @@ impl<'a> ModuleConstraintBuilder<'a> {
         });
         self
     }
+
+    /// Add a rule restricting the module's direct submodules to the given names
+    ///
+    /// Each entry is a regular expression matched against the child module's name,
+    /// e.g. `allowed_submodules_only(["routes", "handlers", "dto"])`
+    pub fn allowed_submodules_only(
+        mut self,
+        allowed: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.add_rule_internal(ModuleRule::AllowedSubmodulesOnly {
+            allowed: allowed.into_iter().map(Into::into).collect(),
+            severity: self.current_severity,
+        });
+        self
+    }
+
+    /// Add a rule limiting the number of imports in the module.
+    /// Re-exports (`pub use`) don't count towards the limit.
+    pub fn max_imports(mut self, max: usize) -> Self {
+        self.add_rule_internal(ModuleRule::MaxImports(max, self.current_severity));
+        self
+    }
+
+    /// Add a rule limiting the number of public items declared in the module.
+    /// See [`ModuleRule::MaxPublicItems`] for what is counted.
+    pub fn max_public_items(mut self, max: usize) -> Self {
+        self.add_rule_internal(ModuleRule::MaxPublicItems {
+            max,
+            count_reexports: false,
+            severity: self.current_severity,
+        });
+        self
+    }
+
+    /// Count re-exports (`pub use`) towards the `max_public_items` rules added so far
+    pub fn count_reexports(mut self) -> Self {
+        for rule in &mut self.rules {
+            if let ModuleRule::MaxPublicItems {
+                count_reexports, ..
+            } = rule
+            {
+                *count_reexports = true;
+            }
+        }
+        self
+    }
+
+    /// Add a rule requiring the module to have a `#[cfg(test)]` test module
+    pub fn require_tests(mut self) -> Self {
+        self.add_rule_internal(ModuleRule::RequireTests(self.current_severity));
+        self
+    }
+
+    /// Add a rule requiring the module's name to follow a naming convention
+    pub fn naming_case(mut self, style: CaseStyle) -> Self {
+        self.add_rule_internal(ModuleRule::NamingCaseStyle(style, self.current_severity));
+        self
+    }
+
+    /// Add a rule requiring the module to `pub use` each of the given item paths
+    pub fn require_reexport(mut self, items: Vec<impl Into<String>>) -> Self {
+        self.add_rule_internal(ModuleRule::RequireReexport {
+            items: items.into_iter().map(Into::into).collect(),
+            severity: self.current_severity,
+        });
+        self
+    }
+
+    /// Add a rule limiting a crate root `lib.rs` or `main.rs` to `mod` and `use` items
+    pub fn forbid_items_in_root(mut self) -> Self {
+        self.add_rule_internal(ModuleRule::ForbidItemsInRoot {
+            allowed_kinds: vec!["module".into(), "use".into()],
+            severity: self.current_severity,
+        });
+        self
+    }
+
+    /// Add a rule requiring each of the module's direct submodules to be named with
+    /// the given prefix
+    pub fn require_submodule_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.add_rule_internal(ModuleRule::RequirePrefix(
+            prefix.into(),
+            self.current_severity,
+        ));
+        self
+    }
+
+    /// Add a rule requiring a module with the given name in the same parent as each
+    /// matched module, e.g. an `error` module next to them
+    pub fn require_sibling_module(mut self, name: impl Into<String>) -> Self {
+        self.add_rule_internal(ModuleRule::RequireSiblingModule(
+            name.into(),
+            self.current_severity,
+        ));
+        self
+    }
+
+    /// Add a rule limiting how deeply the module is nested below the crate root
+    pub fn max_depth(mut self, max: usize) -> Self {
+        self.add_rule_internal(ModuleRule::MaxDepth(max, self.current_severity));
+        self
+    }
+
+    /// Add a rule restricting which external crates the module may import from.
+    /// The standard library crates and the current crate are always allowed.
+    pub fn allowed_crates(mut self, crates: Vec<impl Into<String>>) -> Self {
+        self.add_rule_internal(ModuleRule::AllowedCrates {
+            crates: crates.into_iter().map(Into::into).collect(),
+            severity: self.current_severity,
+        });
+        self
+    }
+
+    /// Add a rule forbidding inline `mod` declarations inside the module
+    pub fn forbid_nested_modules(mut self) -> Self {
+        self.add_rule_internal(ModuleRule::ForbidNestedModules {
+            allow_test_modules: false,
+            severity: self.current_severity,
+        });
+        self
+    }
+
+    /// Exempt `#[cfg(test)]` and `tests` modules from the `forbid_nested_modules`
+    /// rules added so far
+    pub fn allow_test_modules(mut self) -> Self {
+        for rule in &mut self.rules {
+            if let ModuleRule::ForbidNestedModules {
+                allow_test_modules, ..
+            } = rule
+            {
+                *allow_test_modules = true;
+            }
+        }
+        self
+    }
+
+    /// Leave items gated on `#[cfg(test)]` out of the most recently added
+    /// `must_be_empty` or `must_have_empty_mod_file` rule, so a façade module can keep
+    /// its inline tests
+    pub fn ignoring_test_code(mut self) -> Self {
+        if let Some(
+            ModuleRule::MustBeEmpty {
+                ignore_test_code, ..
+            }
+            | ModuleRule::MustHaveEmptyModFile {
+                ignore_test_code, ..
+            },
+        ) = self.rules.iter_mut().rev().find(|rule| {
+            matches!(
+                rule,
+                ModuleRule::MustBeEmpty { .. } | ModuleRule::MustHaveEmptyModFile { .. }
+            )
+        }) {
+            *ignore_test_code = true;
+        }
+        self
+    }
 }
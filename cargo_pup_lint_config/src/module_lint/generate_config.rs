@@ -10,7 +10,11 @@ impl GenerateFromContext for ModuleLint {
         let empty_mod_lint = ModuleLint {
             name: "empty_mod_rule".to_string(),
             matches: ModuleMatch::Module(".*".to_string()),
-            rules: vec![ModuleRule::MustHaveEmptyModFile(Severity::Error)],
+            rules: vec![ModuleRule::MustHaveEmptyModFile {
+                severity: Severity::Error,
+                ignore_test_code: false,
+            }],
+            escalate_after: None,
         };
         builder.push(ConfiguredLint::Module(empty_mod_lint));
     }
@@ -16,6 +16,44 @@ impl ModuleMatcher {
     pub fn module(&self, module: impl Into<String>) -> ModuleMatchNode {
         ModuleMatchNode::Leaf(ModuleMatch::Module(module.into()))
     }
+
+    /// Match the direct submodules of a module
+    ///
+    /// The parent parameter is matched against the module's immediate parent path
+    /// only, so `has_parent("^crate::api$")` matches `crate::api::v1` but not
+    /// `crate::api` or `crate::api::v1::handlers`.
+    pub fn has_parent(&self, parent: impl Into<String>) -> ModuleMatchNode {
+        ModuleMatchNode::Leaf(ModuleMatch::HasParent(parent.into()))
+    }
+
+    /// Match items by the source file they're defined in
+    ///
+    /// The pattern is a regular expression matched against the file path as the
+    /// compiler sees it, e.g. `in_file("src/generated/.*")`. Useful when a rule
+    /// should follow the physical layout of the code rather than its module tree.
+    pub fn in_file(&self, pattern: impl Into<String>) -> ModuleMatchNode {
+        ModuleMatchNode::Leaf(ModuleMatch::InFile(pattern.into()))
+    }
+
+    /// Invert a matcher. This is the same as calling `.not()` on it, but reads
+    /// more naturally in the middle of an `and`/`or` chain.
+    ///
+    /// # Example
+    /// ```
+    /// use cargo_pup_lint_config::{ModuleMatch, module_matcher};
+    ///
+    /// // Everything under the API, apart from its internal modules
+    /// let matches = module_matcher(|m| {
+    ///     m.module("^my_app::api").and(m.not(m.module("^my_app::api::internal")))
+    /// });
+    /// assert!(matches!(
+    ///     matches,
+    ///     ModuleMatch::AndMatches(_, excluded) if matches!(*excluded, ModuleMatch::NotMatch(_))
+    /// ));
+    /// ```
+    pub fn not(&self, node: ModuleMatchNode) -> ModuleMatchNode {
+        node.not()
+    }
 }
 
 /// Node in the matcher expression tree
@@ -5,7 +5,7 @@ mod builder_tests {
     use crate::lint_builder::LintBuilder;
     use crate::ConfiguredLint;
     use crate::module_lint::{ModuleRule, ModuleMatch, ModuleLintExt};
-    use crate::Severity;
+    use crate::{CaseStyle, Severity};
     use tempfile::NamedTempFile;
     
     // Helper function to verify default severity
@@ -63,6 +63,387 @@ mod builder_tests {
         }
     }
     
+    #[test]
+    fn test_max_imports_rule() {
+        let mut builder = LintBuilder::new();
+
+        builder
+            .module_lint()
+            .lint_named("import_limit")
+            .matching(|m| m.module("^app::services$"))
+            .with_severity(Severity::Error)
+            .max_imports(10)
+            .build();
+
+        if let ConfiguredLint::Module(module_lint) = &builder.lints[0] {
+            assert_eq!(module_lint.rules.len(), 1);
+            if let ModuleRule::MaxImports(max, severity) = &module_lint.rules[0] {
+                assert_eq!(*max, 10);
+                assert_eq!(severity, &Severity::Error);
+            } else {
+                panic!("Expected MaxImports rule");
+            }
+        } else {
+            panic!("Unexpected lint type");
+        }
+    }
+
+    #[test]
+    fn test_require_tests_rule() {
+        let mut builder = LintBuilder::new();
+
+        builder
+            .module_lint()
+            .lint_named("tested_services")
+            .matching(|m| m.module("^app::services::.*$"))
+            .require_tests()
+            .build();
+
+        if let ConfiguredLint::Module(module_lint) = &builder.lints[0] {
+            assert!(matches!(
+                module_lint.rules[0],
+                ModuleRule::RequireTests(Severity::Warn)
+            ));
+        } else {
+            panic!("Unexpected lint type");
+        }
+    }
+
+    #[test]
+    fn test_naming_case_rule() {
+        let mut builder = LintBuilder::new();
+
+        builder
+            .module_lint()
+            .lint_named("snake_case_modules")
+            .matching(|m| m.module(".*"))
+            .with_severity(Severity::Error)
+            .naming_case(CaseStyle::Snake)
+            .build();
+
+        if let ConfiguredLint::Module(module_lint) = &builder.lints[0] {
+            assert!(matches!(
+                module_lint.rules[0],
+                ModuleRule::NamingCaseStyle(CaseStyle::Snake, Severity::Error)
+            ));
+        } else {
+            panic!("Unexpected lint type");
+        }
+    }
+
+    #[test]
+    fn test_require_reexport_rule() {
+        let mut builder = LintBuilder::new();
+
+        builder
+            .module_lint()
+            .lint_named("api_facade")
+            .matching(|m| m.module("^app::api$"))
+            .require_reexport(vec!["crate::internal::Client", "crate::internal::Config"])
+            .build();
+
+        if let ConfiguredLint::Module(module_lint) = &builder.lints[0] {
+            if let ModuleRule::RequireReexport { items, severity } = &module_lint.rules[0] {
+                assert_eq!(items, &vec!["crate::internal::Client".to_string(), "crate::internal::Config".to_string()]);
+                assert_eq!(severity, &Severity::Warn);
+            } else {
+                panic!("Expected RequireReexport rule");
+            }
+        } else {
+            panic!("Unexpected lint type");
+        }
+    }
+
+    #[test]
+    fn test_forbid_items_in_root_rule() {
+        let mut builder = LintBuilder::new();
+
+        builder
+            .module_lint()
+            .lint_named("thin_lib_rs")
+            .matching(|m| m.module("^app$"))
+            .with_severity(Severity::Error)
+            .forbid_items_in_root()
+            .build();
+
+        if let ConfiguredLint::Module(module_lint) = &builder.lints[0] {
+            if let ModuleRule::ForbidItemsInRoot { allowed_kinds, severity } = &module_lint.rules[0] {
+                assert_eq!(allowed_kinds, &vec!["module".to_string(), "use".to_string()]);
+                assert_eq!(severity, &Severity::Error);
+            } else {
+                panic!("Expected ForbidItemsInRoot rule");
+            }
+        } else {
+            panic!("Unexpected lint type");
+        }
+    }
+
+    #[test]
+    fn test_max_depth_rule() {
+        let mut builder = LintBuilder::new();
+
+        builder
+            .module_lint()
+            .lint_named("shallow_modules")
+            .matching(|m| m.module(".*"))
+            .with_severity(Severity::Error)
+            .max_depth(3)
+            .build();
+
+        if let ConfiguredLint::Module(module_lint) = &builder.lints[0] {
+            assert!(matches!(module_lint.rules[0], ModuleRule::MaxDepth(3, Severity::Error)));
+        } else {
+            panic!("Unexpected lint type");
+        }
+    }
+
+    #[test]
+    fn test_max_public_items_rule() {
+        let mut builder = LintBuilder::new();
+
+        builder
+            .module_lint()
+            .lint_named("narrow_api")
+            .matching(|m| m.module("^my_app::api$"))
+            .max_public_items(10)
+            .count_reexports()
+            .build();
+
+        if let ConfiguredLint::Module(module_lint) = &builder.lints[0] {
+            assert!(matches!(
+                module_lint.rules[0],
+                ModuleRule::MaxPublicItems {
+                    max: 10,
+                    count_reexports: true,
+                    severity: Severity::Warn,
+                }
+            ));
+        } else {
+            panic!("Unexpected lint type");
+        }
+    }
+
+    #[test]
+    fn test_require_submodule_prefix_rule() {
+        let mut builder = LintBuilder::new();
+
+        builder
+            .module_lint()
+            .lint_named("event_modules")
+            .matching(|m| m.module("::events$"))
+            .require_submodule_prefix("evt_")
+            .build();
+
+        if let ConfiguredLint::Module(module_lint) = &builder.lints[0] {
+            assert!(matches!(
+                &module_lint.rules[0],
+                ModuleRule::RequirePrefix(prefix, Severity::Warn) if prefix == "evt_"
+            ));
+        } else {
+            panic!("Unexpected lint type");
+        }
+    }
+
+    #[test]
+    fn test_require_sibling_module_rule() {
+        let mut builder = LintBuilder::new();
+
+        builder
+            .module_lint()
+            .lint_named("error_modules")
+            .matching(|m| m.module("^my_app::services::[^:]+$"))
+            .with_severity(Severity::Error)
+            .require_sibling_module("error")
+            .build();
+
+        if let ConfiguredLint::Module(module_lint) = &builder.lints[0] {
+            assert!(matches!(
+                &module_lint.rules[0],
+                ModuleRule::RequireSiblingModule(name, Severity::Error) if name == "error"
+            ));
+        } else {
+            panic!("Unexpected lint type");
+        }
+    }
+
+    #[test]
+    fn test_forbid_import_from_rule() {
+        let mut builder = LintBuilder::new();
+
+        builder
+            .module_lint()
+            .lint_named("internal_boundary")
+            .matching(|m| m.module(".*"))
+            .with_severity(Severity::Error)
+            .forbid_import_from(vec!["^my_app::api"], vec!["^crate::internal", "^my_db::"])
+            .build();
+
+        if let ConfiguredLint::Module(module_lint) = &builder.lints[0] {
+            assert!(matches!(
+                &module_lint.rules[0],
+                ModuleRule::ForbidImportFrom {
+                    source_patterns,
+                    target_patterns,
+                    severity: Severity::Error,
+                } if source_patterns == &["^my_app::api"]
+                    && target_patterns == &["^crate::internal", "^my_db::"]
+            ));
+        } else {
+            panic!("Unexpected lint type");
+        }
+    }
+
+    #[test]
+    fn test_forbid_reexport_from_rule() {
+        let mut builder = LintBuilder::new();
+
+        builder
+            .module_lint()
+            .lint_named("no_leaked_internals")
+            .matching(|m| m.module("^my_app::api"))
+            .with_severity(Severity::Warn)
+            .forbid_reexport_from(vec!["crate::internal::.*"])
+            .build();
+
+        if let ConfiguredLint::Module(module_lint) = &builder.lints[0] {
+            assert!(matches!(
+                &module_lint.rules[0],
+                ModuleRule::ForbidReexportFrom {
+                    patterns,
+                    severity: Severity::Warn,
+                } if patterns == &["crate::internal::.*"]
+            ));
+        } else {
+            panic!("Unexpected lint type");
+        }
+    }
+
+    #[test]
+    fn test_forbid_std_rule() {
+        let mut builder = LintBuilder::new();
+
+        builder
+            .module_lint()
+            .lint_named("no_std_drivers")
+            .matching(|m| m.module("^firmware::drivers"))
+            .with_severity(Severity::Error)
+            .forbid_std()
+            .build();
+
+        if let ConfiguredLint::Module(module_lint) = &builder.lints[0] {
+            assert!(matches!(
+                module_lint.rules[0],
+                ModuleRule::ForbidStd(Severity::Error)
+            ));
+        } else {
+            panic!("Unexpected lint type");
+        }
+    }
+
+    #[test]
+    fn test_allowed_crates_rule() {
+        let mut builder = LintBuilder::new();
+
+        builder
+            .module_lint()
+            .lint_named("domain_dependencies")
+            .matching(|m| m.module("^app::domain(::.*)?$"))
+            .allowed_crates(vec!["serde", "chrono"])
+            .build();
+
+        if let ConfiguredLint::Module(module_lint) = &builder.lints[0] {
+            if let ModuleRule::AllowedCrates { crates, severity } = &module_lint.rules[0] {
+                assert_eq!(crates, &vec!["serde".to_string(), "chrono".to_string()]);
+                assert_default_severity(severity);
+            } else {
+                panic!("Expected AllowedCrates rule");
+            }
+        } else {
+            panic!("Unexpected lint type");
+        }
+    }
+
+    #[test]
+    fn test_forbid_nested_modules_rule() {
+        let mut builder = LintBuilder::new();
+
+        builder
+            .module_lint()
+            .lint_named("flat_modules")
+            .matching(|m| m.module(".*"))
+            .forbid_nested_modules()
+            .build();
+        builder
+            .module_lint()
+            .lint_named("flat_modules_except_tests")
+            .matching(|m| m.module(".*"))
+            .forbid_nested_modules()
+            .allow_test_modules()
+            .build();
+
+        for (lint, expected) in builder.lints.iter().zip([false, true]) {
+            if let ConfiguredLint::Module(module_lint) = lint {
+                assert!(matches!(
+                    module_lint.rules[0],
+                    ModuleRule::ForbidNestedModules { allow_test_modules, .. } if allow_test_modules == expected
+                ));
+            } else {
+                panic!("Unexpected lint type");
+            }
+        }
+    }
+
+    #[test]
+    fn test_empty_module_rules_ignoring_test_code() {
+        let mut builder = LintBuilder::new();
+
+        builder
+            .module_lint()
+            .lint_named("facades")
+            .matching(|m| m.module("^app::api$"))
+            .must_be_empty()
+            .must_have_empty_mod_file()
+            .ignoring_test_code()
+            .build();
+
+        if let ConfiguredLint::Module(module_lint) = &builder.lints[0] {
+            // Only the most recent empty-module rule is changed
+            assert!(matches!(
+                module_lint.rules[..],
+                [
+                    ModuleRule::MustBeEmpty {
+                        severity: Severity::Warn,
+                        ignore_test_code: false,
+                    },
+                    ModuleRule::MustHaveEmptyModFile {
+                        severity: Severity::Warn,
+                        ignore_test_code: true,
+                    },
+                ]
+            ));
+        } else {
+            panic!("Unexpected lint type");
+        }
+    }
+
+    #[test]
+    fn test_case_style_matches() {
+        for name in ["api", "http_client", "v2_routes"] {
+            assert!(CaseStyle::Snake.matches(name), "{name} should be snake_case");
+        }
+        for name in ["", "Api", "httpClient", "_private", "trailing_", "double__underscore", "2fa"] {
+            assert!(!CaseStyle::Snake.matches(name), "{name} should not be snake_case");
+        }
+
+        assert!(CaseStyle::Camel.matches("httpClient"));
+        assert!(!CaseStyle::Camel.matches("HttpClient"));
+        assert!(!CaseStyle::Camel.matches("http_client"));
+
+        assert!(CaseStyle::Pascal.matches("HttpClient"));
+        assert!(!CaseStyle::Pascal.matches("httpClient"));
+        assert!(!CaseStyle::Pascal.matches("Http_Client"));
+    }
+
     #[test]
     fn test_restrict_imports_rule() {
         let mut builder = LintBuilder::new();
@@ -156,6 +537,48 @@ mod builder_tests {
         assert_eq!(builder.lints.len(), 1);
         assert!(matches!(&builder.lints[0], ConfiguredLint::Module(_)));
     }
+
+    #[test]
+    fn test_matcher_negation_builds_nested_tree() {
+        use crate::module_lint::matcher;
+
+        // The prefix and postfix forms of `not` build the same tree
+        let prefix = matcher(|m| m.module("api").and(m.not(m.module("api::internal"))));
+        let postfix = matcher(|m| m.module("api").and(m.module("api::internal").not()));
+        let expected = r#"AndMatches(Module("api"), NotMatch(Module("api::internal")))"#;
+        assert_eq!(format!("{prefix:?}"), expected);
+        assert_eq!(format!("{postfix:?}"), expected);
+
+        // Chains nest from the left, and `not` applies to the whole node it's given
+        let matches = matcher(|m| {
+            m.not(m.module("legacy").or(m.in_file("generated/.*")))
+                .and(m.has_parent("^app$"))
+                .or(m.module("app::core"))
+        });
+        assert_eq!(
+            format!("{matches:?}"),
+            r#"OrMatches(AndMatches(NotMatch(OrMatches(Module("legacy"), InFile("generated/.*"))), HasParent("^app$")), Module("app::core"))"#
+        );
+    }
+
+    #[test]
+    fn test_in_file_matcher() {
+        let mut builder = LintBuilder::new();
+
+        builder
+            .module_lint()
+            .lint_named("generated_code")
+            .matching(|m| m.in_file("src/generated/.*"))
+            .no_wildcard_imports()
+            .build();
+
+        match &builder.lints[0] {
+            ConfiguredLint::Module(lint) => {
+                assert!(matches!(&lint.matches, ModuleMatch::InFile(p) if p == "src/generated/.*"));
+            }
+            _ => panic!("Expected Module lint"),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -228,7 +651,7 @@ mod context_generation_tests {
                         // Check the rule
                         assert_eq!(module_lint.rules.len(), 1);
                         match &module_lint.rules[0] {
-                            ModuleRule::MustBeEmpty(severity) => {
+                            ModuleRule::MustBeEmpty { severity, .. } => {
                                 assert_eq!(*severity, crate::Severity::Error);
                             },
                             _ => panic!("Expected MustBeEmpty rule"),
@@ -1,13 +1,17 @@
 // This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
 
-use crate::Severity;
-use serde::{Deserialize, Serialize};
+use crate::{CaseStyle, Severity};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 /// Specifies how to match modules for linting
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub enum ModuleMatch {
     /// Match modules by path (exact path or regex pattern)
     Module(String),
+    /// Match modules whose immediate parent path matches the pattern (regex)
+    HasParent(String),
+    /// Match modules and items by the path of the source file they're defined in (regex)
+    InFile(String),
     /// Logical AND - both patterns must match
     AndMatches(Box<ModuleMatch>, Box<ModuleMatch>),
     /// Logical OR - either pattern must match
@@ -22,6 +26,9 @@ pub struct ModuleLint {
     pub name: String,
     pub matches: ModuleMatch,
     pub rules: Vec<ModuleRule>,
+    /// Fail the run once this lint reports more than this many violations, even at `Warn`
+    #[serde(default)]
+    pub escalate_after: Option<usize>,
 }
 
 /// Rules that can be applied to modules matching specific criteria
@@ -33,11 +40,32 @@ pub enum ModuleRule {
     MustNotBeNamed(String, Severity),
     /// Enforces that the module contains at least one item
     MustNotBeEmpty(Severity),
-    /// Enforces that the module contains no items
-    MustBeEmpty(Severity),
-    /// Enforces that the module.rs file only re-exports other modules
-    MustHaveEmptyModFile(Severity),
-    /// Controls which modules can be imported
+    /// Enforces that the module contains no items. With `ignore_test_code`, items gated
+    /// on `#[cfg(test)]` aren't counted, so inline test code is allowed. Written as
+    /// `MustBeEmpty((severity: Error, ignore_test_code: true))`, or with just the
+    /// severity, `MustBeEmpty(Error)`, as before the toggle was added.
+    #[serde(
+        serialize_with = "serialize_empty_rule",
+        deserialize_with = "deserialize_empty_rule"
+    )]
+    MustBeEmpty {
+        severity: Severity,
+        ignore_test_code: bool,
+    },
+    /// Enforces that the module.rs file only re-exports other modules. Takes the same
+    /// fields as `MustBeEmpty`.
+    #[serde(
+        serialize_with = "serialize_empty_rule",
+        deserialize_with = "deserialize_empty_rule"
+    )]
+    MustHaveEmptyModFile {
+        severity: Severity,
+        ignore_test_code: bool,
+    },
+    /// Controls which modules can be imported. Each import is checked against the path
+    /// as written and against what it resolves to, named `crate::...` for the current
+    /// crate, so an `as` alias or a re-export doesn't get around the rule. Grouped
+    /// imports like `use a::{b, c}` are checked one by one.
     RestrictImports {
         allowed_only: Option<Vec<String>>,
         denied: Option<Vec<String>>,
@@ -50,6 +78,96 @@ pub enum ModuleRule {
         items: Vec<String>,
         severity: Severity,
     },
+    /// Restricts the direct submodules of the module to names matching one of the
+    /// allowed patterns (regex). Deeper descendants are not checked.
+    AllowedSubmodulesOnly {
+        allowed: Vec<String>,
+        severity: Severity,
+    },
+    /// Limits the number of imports in the module. Re-exports (`pub use`) are
+    /// not counted.
+    MaxImports(usize, Severity),
+    /// Limits the number of public items declared directly in the module: functions,
+    /// structs, enums, unions, traits, type aliases, consts and statics. Items
+    /// restricted with `pub(crate)` and the like don't count, nor do submodules or
+    /// items in them. Re-exports (`pub use`) count, one per name, when
+    /// `count_reexports` is set.
+    MaxPublicItems {
+        max: usize,
+        #[serde(default)]
+        count_reexports: bool,
+        severity: Severity,
+    },
+    /// Requires the module to contain a `#[cfg(test)]` module, or one gated on any
+    /// other predicate that mentions `test`, such as `all(test, feature = "x")`. Test
+    /// modules, and modules that only declare submodules or re-export, are exempt.
+    RequireTests(Severity),
+    /// Enforces that the module's own name (the last path segment) follows the
+    /// given naming convention
+    NamingCaseStyle(CaseStyle, Severity),
+    /// Requires the module to re-export each of the given items with a single-item
+    /// `pub use`. Paths into the current crate may start with `crate::` or the crate's
+    /// name. Glob re-exports are not taken into account.
+    RequireReexport {
+        items: Vec<String>,
+        severity: Severity,
+    },
+    /// Forbids items other than the allowed kinds directly in a crate root `lib.rs` or
+    /// `main.rs`. Kinds use the names from `DeniedItems`, plus `use` and `extern_crate`.
+    ForbidItemsInRoot {
+        allowed_kinds: Vec<String>,
+        severity: Severity,
+    },
+    /// Restricts the external crates the module may import from to the given crate
+    /// names. `std`, `core`, `alloc` and the current crate are always allowed.
+    AllowedCrates {
+        crates: Vec<String>,
+        severity: Severity,
+    },
+    /// Limits how deeply the module is nested. The crate root is depth 0, a top-level
+    /// module depth 1, and so on.
+    MaxDepth(usize, Severity),
+    /// Forbids inline `mod` declarations directly inside the module, to keep to one
+    /// module per file. Test modules may be exempted.
+    ForbidNestedModules {
+        #[serde(default)]
+        allow_test_modules: bool,
+        severity: Severity,
+    },
+    /// Requires the names of the module's direct submodules to start with the given
+    /// prefix, e.g. `evt_` for the submodules of `events`. The module's own name is
+    /// not checked.
+    RequirePrefix(String, Severity),
+    /// Forbids modules matching one of `source_patterns` from importing anything
+    /// matching one of `target_patterns`, so several boundaries can share one lint.
+    /// Source patterns are matched against the importing module's full path, e.g.
+    /// `^my_app::api`, and target patterns against the imported path and what it
+    /// resolves to, where items of the current crate are named `crate::...`. Modules
+    /// that match a target pattern themselves are inside the boundary and may import
+    /// from it.
+    ForbidImportFrom {
+        source_patterns: Vec<String>,
+        target_patterns: Vec<String>,
+        severity: Severity,
+    },
+    /// Forbids public re-exports (`pub use`) of anything matching one of the patterns,
+    /// so a public module can't leak the crate's internals. Patterns are matched against
+    /// the path as written and what it resolves to, named as for `ForbidImportFrom`;
+    /// a glob re-export is matched with `::*` at the end, as in `crate::internal::*`.
+    /// `pub(crate)` and other restricted re-exports are not checked.
+    ForbidReexportFrom {
+        patterns: Vec<String>,
+        severity: Severity,
+    },
+    /// Forbids the module from depending on `std`, for modules that must stay `no_std`.
+    /// Imports and paths written from `std` are reported, as are paths resolving to
+    /// items only `std` provides and uses of macros defined in `std`, like `println!`.
+    /// Items `std` re-exports from `core` or `alloc` are fine when imported from there.
+    ForbidStd(Severity),
+    /// Requires a module with the given name, e.g. `error`, alongside the matched
+    /// modules, in the same parent. A parent missing it is reported once, however many
+    /// of its submodules match.
+    RequireSiblingModule(String, Severity),
     /// Logical AND - both rules must pass
     And(Box<ModuleRule>, Box<ModuleRule>),
     /// Logical OR - either rule must pass
@@ -57,3 +175,84 @@ pub enum ModuleRule {
     /// Logical NOT - inverts the rule check
     Not(Box<ModuleRule>),
 }
+
+impl ModuleRule {
+    /// Returns the severity this rule reports with. Composite rules report
+    /// the highest severity of their children.
+    pub fn severity(&self) -> Severity {
+        match self {
+            ModuleRule::MustBeNamed(_, severity)
+            | ModuleRule::MustNotBeNamed(_, severity)
+            | ModuleRule::MustNotBeEmpty(severity)
+            | ModuleRule::MustBeEmpty { severity, .. }
+            | ModuleRule::MustHaveEmptyModFile { severity, .. }
+            | ModuleRule::RestrictImports { severity, .. }
+            | ModuleRule::NoWildcardImports(severity)
+            | ModuleRule::DeniedItems { severity, .. }
+            | ModuleRule::AllowedSubmodulesOnly { severity, .. }
+            | ModuleRule::MaxImports(_, severity)
+            | ModuleRule::MaxPublicItems { severity, .. }
+            | ModuleRule::RequireTests(severity)
+            | ModuleRule::NamingCaseStyle(_, severity)
+            | ModuleRule::RequireReexport { severity, .. }
+            | ModuleRule::ForbidItemsInRoot { severity, .. }
+            | ModuleRule::AllowedCrates { severity, .. }
+            | ModuleRule::MaxDepth(_, severity)
+            | ModuleRule::ForbidNestedModules { severity, .. }
+            | ModuleRule::RequirePrefix(_, severity)
+            | ModuleRule::ForbidImportFrom { severity, .. }
+            | ModuleRule::ForbidReexportFrom { severity, .. }
+            | ModuleRule::ForbidStd(severity)
+            | ModuleRule::RequireSiblingModule(_, severity) => *severity,
+            ModuleRule::And(left, right) | ModuleRule::Or(left, right) => {
+                left.severity().max(right.severity())
+            }
+            ModuleRule::Not(inner) => inner.severity(),
+        }
+    }
+}
+
+/// The fields of `MustBeEmpty` and `MustHaveEmptyModFile`, or the bare severity they
+/// were configured with before `ignore_test_code` was added
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum EmptyRuleFields {
+    Named {
+        severity: Severity,
+        #[serde(default)]
+        ignore_test_code: bool,
+    },
+    Severity(Severity),
+}
+
+/// Writes the bare severity unless `ignore_test_code` is set, so configurations that
+/// don't use it read as before
+fn serialize_empty_rule<S: Serializer>(
+    severity: &Severity,
+    ignore_test_code: &bool,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    if *ignore_test_code {
+        EmptyRuleFields::Named {
+            severity: *severity,
+            ignore_test_code: true,
+        }
+        .serialize(serializer)
+    } else {
+        severity.serialize(serializer)
+    }
+}
+
+fn deserialize_empty_rule<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<(Severity, bool), D::Error> {
+    match EmptyRuleFields::deserialize(deserializer).map_err(|_| {
+        serde::de::Error::custom("expected a severity, or `severity` and `ignore_test_code` fields")
+    })? {
+        EmptyRuleFields::Named {
+            severity,
+            ignore_test_code,
+        } => Ok((severity, ignore_test_code)),
+        EmptyRuleFields::Severity(severity) => Ok((severity, false)),
+    }
+}
@@ -0,0 +1,201 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//! The workspace report behind `cargo pup check --report`. Each crate's pup-driver
+//! appends the violations it found to a shared JSON lines file, one entry per line,
+//! and cargo-pup renders them together once the whole workspace has been checked.
+//! The same entries are how cargo-pup counts each lint's violations across the
+//! workspace against its `escalate_after` limit.
+
+use crate::Severity;
+use crate::lint_builder_ext::LintViolation;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+
+/// A violation in the workspace report, along with the crate it was found in
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReportEntry {
+    /// Name of the crate being checked when the violation was reported
+    pub crate_name: String,
+    #[serde(flatten)]
+    pub violation: LintViolation,
+}
+
+/// Append entries to the report at `path`, creating it if needed. The file is locked
+/// while the entries are written, so drivers running in parallel never interleave
+/// their lines.
+pub fn append_report_entries(path: &Path, entries: &[ReportEntry]) -> io::Result<()> {
+    let mut lines = String::new();
+    for entry in entries {
+        lines.push_str(&serde_json::to_string(entry).map_err(io::Error::other)?);
+        lines.push('\n');
+    }
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    file.lock()?;
+    file.write_all(lines.as_bytes())?;
+    file.unlock()
+}
+
+/// Read every entry from the report at `path`. A report that doesn't exist has no
+/// entries, as no crate had anything to report.
+pub fn read_report(path: &Path) -> io::Result<Vec<ReportEntry>> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| serde_json::from_str(line).map_err(io::Error::other))
+        .collect()
+}
+
+/// Render the report as plain text, with the violations listed under each crate in
+/// file and line order, followed by a summary
+pub fn render_report(entries: &[ReportEntry]) -> String {
+    let mut crates: BTreeMap<&str, Vec<&LintViolation>> = BTreeMap::new();
+    for entry in entries {
+        crates
+            .entry(entry.crate_name.as_str())
+            .or_default()
+            .push(&entry.violation);
+    }
+
+    let mut out = String::new();
+    for (crate_name, violations) in &mut crates {
+        violations.sort_by(|a, b| (&a.file, a.line).cmp(&(&b.file, b.line)));
+        out.push_str(&format!("{crate_name} ({})\n", count(violations.len())));
+        for violation in violations.iter() {
+            let severity = match violation.severity {
+                Severity::Error => "error",
+                Severity::Warn => "warning",
+            };
+            out.push_str(&format!(
+                "  {}:{}: {severity} [{}] {}\n",
+                violation.file, violation.line, violation.lint_name, violation.message
+            ));
+        }
+        out.push('\n');
+    }
+
+    let errors = entries
+        .iter()
+        .filter(|entry| entry.violation.severity == Severity::Error)
+        .count();
+    let crate_noun = if crates.len() == 1 { "crate" } else { "crates" };
+    out.push_str(&format!(
+        "{} across {} {crate_noun}, {errors} error(s)\n",
+        count(entries.len()),
+        crates.len()
+    ));
+    out
+}
+
+/// Count the violations each lint reported across every crate, keyed by lint name. A
+/// violation is only counted once, even when more than one crate reported it, e.g. a
+/// file that's compiled into both a library and its tests.
+pub fn violation_counts(entries: &[ReportEntry]) -> BTreeMap<&str, usize> {
+    let mut violations: BTreeMap<&str, BTreeSet<(&str, usize, &str)>> = BTreeMap::new();
+    for entry in entries {
+        let violation = &entry.violation;
+        violations
+            .entry(violation.lint_name.as_str())
+            .or_default()
+            .insert((&violation.file, violation.line, &violation.message));
+    }
+    violations
+        .into_iter()
+        .map(|(lint_name, locations)| (lint_name, locations.len()))
+        .collect()
+}
+
+fn count(violations: usize) -> String {
+    match violations {
+        1 => "1 violation".to_string(),
+        n => format!("{n} violations"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(crate_name: &str, file: &str, line: usize, severity: Severity) -> ReportEntry {
+        ReportEntry {
+            crate_name: crate_name.to_string(),
+            violation: LintViolation {
+                lint_name: "no_unwrap".to_string(),
+                severity,
+                file: file.to_string(),
+                line,
+                message: "Use of unwrap() is forbidden".to_string(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_appends_from_each_crate_are_read_back_together() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("report.jsonl");
+
+        // Nothing has been reported yet
+        assert!(read_report(&path).unwrap().is_empty());
+
+        let api = vec![entry("api", "api/src/lib.rs", 3, Severity::Error)];
+        let core = vec![
+            entry("core", "core/src/lib.rs", 9, Severity::Warn),
+            entry("core", "core/src/lib.rs", 4, Severity::Warn),
+        ];
+        append_report_entries(&path, &api).unwrap();
+        append_report_entries(&path, &core).unwrap();
+        // A crate with nothing to report leaves the file as it was
+        append_report_entries(&path, &[]).unwrap();
+
+        let entries = read_report(&path).unwrap();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0], api[0]);
+        assert_eq!(entries[1..], core[..]);
+    }
+
+    #[test]
+    fn test_render_groups_by_crate_in_line_order() {
+        let report = render_report(&[
+            entry("core", "core/src/lib.rs", 9, Severity::Warn),
+            entry("api", "api/src/lib.rs", 3, Severity::Error),
+            entry("core", "core/src/lib.rs", 4, Severity::Warn),
+        ]);
+        assert_eq!(
+            report,
+            "api (1 violation)\n\
+             \x20 api/src/lib.rs:3: error [no_unwrap] Use of unwrap() is forbidden\n\
+             \n\
+             core (2 violations)\n\
+             \x20 core/src/lib.rs:4: warning [no_unwrap] Use of unwrap() is forbidden\n\
+             \x20 core/src/lib.rs:9: warning [no_unwrap] Use of unwrap() is forbidden\n\
+             \n\
+             3 violations across 2 crates, 1 error(s)\n"
+        );
+    }
+
+    #[test]
+    fn test_violation_counts_total_across_crates() {
+        let mut other_lint = entry("core", "core/src/lib.rs", 4, Severity::Error);
+        other_lint.violation.lint_name = "no_panic".to_string();
+        let entries = [
+            entry("api", "api/src/lib.rs", 3, Severity::Warn),
+            entry("core", "core/src/lib.rs", 4, Severity::Warn),
+            entry("core", "core/src/lib.rs", 9, Severity::Warn),
+            // The library's tests report what the library itself did
+            entry("core", "core/src/lib.rs", 9, Severity::Warn),
+            other_lint,
+        ];
+        let counts = violation_counts(&entries);
+        assert_eq!(counts.len(), 2);
+        assert_eq!(counts["no_unwrap"], 3);
+        assert_eq!(counts["no_panic"], 1);
+    }
+}
@@ -47,6 +47,7 @@ impl<'a> StructNamedBuilder<'a> {
             rules: Vec::new(),
             current_severity: Severity::default(),
             name: self.name,
+            escalate_after: None,
         }
     }
 
@@ -78,6 +79,7 @@ pub struct StructConstraintBuilder<'a> {
     rules: Vec<StructRule>,
     current_severity: Severity,
     name: String,
+    escalate_after: Option<usize>,
 }
 
 impl<'a> StructConstraintBuilder<'a> {
@@ -98,6 +100,7 @@ impl<'a> StructConstraintBuilder<'a> {
             name: self.name,
             matches: self.match_,
             rules: self.rules,
+            escalate_after: self.escalate_after,
         });
         self.parent.push(lint);
         self.parent
@@ -109,6 +112,12 @@ impl<'a> StructConstraintBuilder<'a> {
         self
     }
 
+    /// Fail the run if this lint reports more than `max` violations, whatever their severity
+    pub fn escalate_after(mut self, max: usize) -> Self {
+        self.escalate_after = Some(max);
+        self
+    }
+
     /// Add a rule requiring the struct to have a specific name
     pub fn must_be_named(mut self, name: String) -> Self {
         self.add_rule_internal(StructRule::MustBeNamed(name, self.current_severity));
@@ -154,4 +163,119 @@ impl<'a> StructConstraintBuilder<'a> {
         self.add_rule_internal(StructRule::MustBePubCrate(self.current_severity));
         self
     }
+
+    /// Add a rule requiring the struct name to contain the name of the traits it was
+    /// matched on. Only traits selected by `implements_trait` in the matcher are considered.
+    pub fn name_must_contain_trait_name(mut self) -> Self {
+        self.add_rule_internal(StructRule::NameMustContainTraitName(self.current_severity));
+        self
+    }
+
+    /// Add a rule requiring public structs to be marked `#[non_exhaustive]`
+    pub fn must_be_non_exhaustive(mut self) -> Self {
+        self.add_rule_internal(StructRule::MustBeNonExhaustive(self.current_severity));
+        self
+    }
+
+    /// Add a rule forbidding the struct from deriving any of the given traits
+    pub fn forbid_derive(mut self, traits: Vec<impl Into<String>>) -> Self {
+        let traits = traits.into_iter().map(Into::into).collect();
+        self.add_rule_internal(StructRule::ForbidDerive(traits, self.current_severity));
+        self
+    }
+
+    /// Add a rule limiting the struct's size in bytes
+    pub fn max_size_bytes(mut self, max: u64) -> Self {
+        self.add_rule_internal(StructRule::MaxMemorySize(max, self.current_severity));
+        self
+    }
+
+    /// Add a rule requiring each public field of the struct to have a doc comment
+    pub fn require_field_docs(mut self) -> Self {
+        self.add_rule_internal(StructRule::RequireFieldDocs(self.current_severity));
+        self
+    }
+
+    /// Add a rule requiring the struct's fields to be either all public or all non-public
+    pub fn consistent_field_visibility(mut self) -> Self {
+        self.add_rule_internal(StructRule::ConsistentFieldVisibility(self.current_severity));
+        self
+    }
+
+    /// Add a rule requiring the struct to implement `std::fmt::Debug`
+    pub fn must_implement_debug(mut self) -> Self {
+        self.add_rule_internal(StructRule::MustImplementDebug(self.current_severity));
+        self
+    }
+
+    /// Add a rule requiring the struct to implement all of the given traits, e.g.
+    /// `vec!["serde::Serialize", "serde::Deserialize"]`
+    pub fn must_implement(mut self, traits: Vec<impl Into<String>>) -> Self {
+        let traits = traits.into_iter().map(Into::into).collect();
+        self.add_rule_internal(StructRule::MustImplement(traits, self.current_severity));
+        self
+    }
+
+    /// Add a rule requiring the struct to have a `new` associated function
+    pub fn require_constructor(self) -> Self {
+        self.require_constructor_named("^new$")
+    }
+
+    /// Add a rule requiring the struct to have an associated function whose name
+    /// matches the given pattern, e.g. `"^(new|builder)$"`
+    pub fn require_constructor_named(mut self, name_pattern: impl Into<String>) -> Self {
+        self.add_rule_internal(StructRule::RequireConstructor {
+            name_pattern: name_pattern.into(),
+            severity: self.current_severity,
+        });
+        self
+    }
+
+    /// Limit the number of generic type and const parameters the struct declares
+    pub fn max_generics(mut self, max: usize) -> Self {
+        self.add_rule_internal(StructRule::MaxGenerics {
+            max,
+            count_lifetimes: false,
+            severity: self.current_severity,
+        });
+        self
+    }
+
+    /// Include lifetimes in the count of the preceding `max_generics` rule
+    pub fn count_lifetimes(mut self) -> Self {
+        if let Some(StructRule::MaxGenerics {
+            count_lifetimes, ..
+        }) = self
+            .rules
+            .iter_mut()
+            .rev()
+            .find(|rule| matches!(rule, StructRule::MaxGenerics { .. }))
+        {
+            *count_lifetimes = true;
+        }
+        self
+    }
+
+    /// Require each file with a matched struct to define one named after it, as `Client`
+    /// in `client.rs`
+    pub fn name_must_match_file(mut self) -> Self {
+        self.add_rule_internal(StructRule::NameMustMatchFile(self.current_severity));
+        self
+    }
+
+    /// Limit the number of lifetime parameters the struct declares
+    pub fn max_lifetimes(mut self, max: usize) -> Self {
+        self.add_rule_internal(StructRule::MaxLifetimes(max, self.current_severity));
+        self
+    }
+
+    /// Forbid fields whose type matches any of the given regular expressions,
+    /// e.g. `vec!["std::sync::Mutex"]`
+    pub fn forbid_field_type(mut self, type_patterns: Vec<impl Into<String>>) -> Self {
+        self.add_rule_internal(StructRule::ForbidFieldType {
+            type_patterns: type_patterns.into_iter().map(Into::into).collect(),
+            severity: self.current_severity,
+        });
+        self
+    }
 }
@@ -20,13 +20,41 @@ impl StructMatcher {
         StructMatchNode::Leaf(StructMatch::HasAttribute(attr.into()))
     }
 
-    /// Matches structs that implement a specific trait. The trait name
-    /// is given as a regular expression,
-    /// e.g. "^(Read|Write)$"
+    /// Matches structs that implement a trait whose full path matches the
+    /// given regular expression, e.g. "^serde::.*Serialize$". Traits from
+    /// other crates are resolved too, so any struct rule can target every
+    /// type that implements them.
     ///
     pub fn implements_trait(&self, trait_name: impl Into<String>) -> StructMatchNode {
         StructMatchNode::Leaf(StructMatch::ImplementsTrait(trait_name.into()))
     }
+
+    /// Matches structs that have a field whose name matches the given
+    /// regular expression, e.g. "^password$"
+    ///
+    pub fn has_field_named(&self, name: impl Into<String>) -> StructMatchNode {
+        StructMatchNode::Leaf(StructMatch::HasField {
+            name: Some(name.into()),
+            type_pattern: None,
+        })
+    }
+
+    /// Matches structs that have a field whose type matches the given
+    /// regular expression, e.g. ".*Secret"
+    ///
+    pub fn has_field_of_type(&self, type_pattern: impl Into<String>) -> StructMatchNode {
+        StructMatchNode::Leaf(StructMatch::HasField {
+            name: None,
+            type_pattern: Some(type_pattern.into()),
+        })
+    }
+
+    /// Matches structs declared in a module whose full path matches the given
+    /// regular expression, e.g. "^my_crate::domain(::.*)?$"
+    ///
+    pub fn in_module(&self, module: impl Into<String>) -> StructMatchNode {
+        StructMatchNode::Leaf(StructMatch::InModule(module.into()))
+    }
 }
 
 #[derive(Clone)]
@@ -38,14 +66,17 @@ pub enum StructMatchNode {
 }
 
 impl StructMatchNode {
+    /// Create a logical AND operation between two matchers
     pub fn and(self, other: StructMatchNode) -> Self {
         StructMatchNode::And(Box::new(self), Box::new(other))
     }
 
+    /// Create a logical OR operation between two matchers
     pub fn or(self, other: StructMatchNode) -> Self {
         StructMatchNode::Or(Box::new(self), Box::new(other))
     }
 
+    /// Create a logical NOT operation that inverts the matcher
     #[allow(clippy::should_implement_trait)]
     pub fn not(self) -> Self {
         StructMatchNode::Not(Box::new(self))
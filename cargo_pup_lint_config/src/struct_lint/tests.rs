@@ -1,6 +1,7 @@
 // This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
 
 #[cfg(test)]
+#[allow(clippy::module_inception)]
 mod tests {
     use crate::ConfiguredLint;
     use crate::lint_builder::LintBuilder;
@@ -131,6 +132,334 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_has_field_matcher() {
+        let mut builder = LintBuilder::new();
+
+        builder
+            .struct_lint()
+            .lint_named("sensitive_structs")
+            .matching(|m| {
+                m.has_field_named("^password$")
+                    .or(m.has_field_of_type(".*Secret"))
+            })
+            .must_be_private()
+            .build();
+
+        assert_eq!(builder.lints.len(), 1);
+        if let ConfiguredLint::Struct(struct_lint) = &builder.lints[0] {
+            if let StructMatch::OrMatches(left, right) = &struct_lint.matches {
+                assert!(matches!(
+                    &**left,
+                    StructMatch::HasField { name: Some(name), type_pattern: None }
+                        if name == "^password$"
+                ));
+                assert!(matches!(
+                    &**right,
+                    StructMatch::HasField { name: None, type_pattern: Some(pattern) }
+                        if pattern == ".*Secret"
+                ));
+            } else {
+                panic!("Expected OrMatches");
+            }
+        } else {
+            panic!("Unexpected lint type");
+        }
+    }
+
+    #[test]
+    fn test_in_module_matcher() {
+        let mut builder = LintBuilder::new();
+
+        builder
+            .struct_lint()
+            .lint_named("domain_structs")
+            .matching(|m| m.in_module("^my_crate::domain(::.*)?$"))
+            .must_be_private()
+            .build();
+
+        assert_eq!(builder.lints.len(), 1);
+        if let ConfiguredLint::Struct(struct_lint) = &builder.lints[0] {
+            assert!(matches!(
+                &struct_lint.matches,
+                StructMatch::InModule(pattern) if pattern == "^my_crate::domain(::.*)?$"
+            ));
+        } else {
+            panic!("Unexpected lint type");
+        }
+    }
+
+    #[test]
+    fn test_forbid_derive_rule() {
+        let mut builder = LintBuilder::new();
+
+        builder
+            .struct_lint()
+            .lint_named("no_debug_secrets")
+            .matching(|m| m.has_field_named("^secret$"))
+            .with_severity(Severity::Error)
+            .forbid_derive(vec!["Debug", "Clone"])
+            .build();
+
+        if let ConfiguredLint::Struct(struct_lint) = &builder.lints[0] {
+            if let StructRule::ForbidDerive(traits, severity) = &struct_lint.rules[0] {
+                assert_eq!(traits, &vec!["Debug".to_string(), "Clone".to_string()]);
+                assert_eq!(severity, &Severity::Error);
+            } else {
+                panic!("Expected ForbidDerive rule");
+            }
+        } else {
+            panic!("Unexpected lint type");
+        }
+    }
+
+    #[test]
+    fn test_max_size_bytes_rule() {
+        let mut builder = LintBuilder::new();
+
+        builder
+            .struct_lint()
+            .lint_named("small_messages")
+            .matching(|m| m.name(".*Message$"))
+            .max_size_bytes(64)
+            .build();
+
+        if let ConfiguredLint::Struct(struct_lint) = &builder.lints[0] {
+            assert!(matches!(
+                struct_lint.rules[0],
+                StructRule::MaxMemorySize(64, Severity::Warn)
+            ));
+        } else {
+            panic!("Unexpected lint type");
+        }
+    }
+
+    #[test]
+    fn test_require_field_docs_rule() {
+        let mut builder = LintBuilder::new();
+
+        builder
+            .struct_lint()
+            .lint_named("documented_dtos")
+            .matching(|m| m.name(".*Dto$"))
+            .with_severity(Severity::Error)
+            .require_field_docs()
+            .build();
+
+        if let ConfiguredLint::Struct(struct_lint) = &builder.lints[0] {
+            assert!(matches!(
+                struct_lint.rules[0],
+                StructRule::RequireFieldDocs(Severity::Error)
+            ));
+        } else {
+            panic!("Unexpected lint type");
+        }
+    }
+
+    #[test]
+    fn test_consistent_field_visibility_rule() {
+        let mut builder = LintBuilder::new();
+
+        builder
+            .struct_lint()
+            .lint_named("no_partial_leaks")
+            .matching(|m| m.name(".*"))
+            .consistent_field_visibility()
+            .build();
+
+        if let ConfiguredLint::Struct(struct_lint) = &builder.lints[0] {
+            assert!(matches!(
+                struct_lint.rules[0],
+                StructRule::ConsistentFieldVisibility(Severity::Warn)
+            ));
+        } else {
+            panic!("Unexpected lint type");
+        }
+    }
+
+    #[test]
+    fn test_must_implement_debug_rule() {
+        let mut builder = LintBuilder::new();
+
+        builder
+            .struct_lint()
+            .lint_named("debuggable_errors")
+            .matching(|m| m.name(".*Error$"))
+            .with_severity(Severity::Error)
+            .must_implement_debug()
+            .build();
+
+        if let ConfiguredLint::Struct(struct_lint) = &builder.lints[0] {
+            assert!(matches!(
+                struct_lint.rules[0],
+                StructRule::MustImplementDebug(Severity::Error)
+            ));
+        } else {
+            panic!("Unexpected lint type");
+        }
+    }
+
+    #[test]
+    fn test_must_implement_rule() {
+        let mut builder = LintBuilder::new();
+
+        builder
+            .struct_lint()
+            .lint_named("serializable_dtos")
+            .matching(|m| m.name(".*Dto$"))
+            .must_implement(vec!["serde::Serialize", "serde::Deserialize"])
+            .build();
+
+        if let ConfiguredLint::Struct(struct_lint) = &builder.lints[0] {
+            match &struct_lint.rules[0] {
+                StructRule::MustImplement(traits, Severity::Warn) => {
+                    assert_eq!(traits, &["serde::Serialize", "serde::Deserialize"]);
+                }
+                rule => panic!("Unexpected rule: {rule:?}"),
+            }
+        } else {
+            panic!("Unexpected lint type");
+        }
+    }
+
+    #[test]
+    fn test_require_constructor_rule() {
+        let mut builder = LintBuilder::new();
+
+        builder
+            .struct_lint()
+            .lint_named("constructible")
+            .matching(|m| m.name(".*"))
+            .require_constructor()
+            .with_severity(Severity::Error)
+            .require_constructor_named("^(new|builder)$")
+            .build();
+
+        if let ConfiguredLint::Struct(struct_lint) = &builder.lints[0] {
+            assert!(matches!(
+                &struct_lint.rules[0],
+                StructRule::RequireConstructor { name_pattern, severity: Severity::Warn }
+                    if name_pattern == "^new$"
+            ));
+            assert!(matches!(
+                &struct_lint.rules[1],
+                StructRule::RequireConstructor { name_pattern, severity: Severity::Error }
+                    if name_pattern == "^(new|builder)$"
+            ));
+        } else {
+            panic!("Unexpected lint type");
+        }
+    }
+
+    #[test]
+    fn test_max_generics_rule() {
+        let mut builder = LintBuilder::new();
+
+        builder
+            .struct_lint()
+            .lint_named("simple_types")
+            .matching(|m| m.name(".*"))
+            .max_generics(2)
+            .build();
+        builder
+            .struct_lint()
+            .lint_named("simple_borrowing_types")
+            .matching(|m| m.name(".*"))
+            .max_generics(3)
+            .count_lifetimes()
+            .build();
+
+        for (lint, expected_max, expected_lifetimes) in
+            [(&builder.lints[0], 2, false), (&builder.lints[1], 3, true)]
+        {
+            if let ConfiguredLint::Struct(struct_lint) = lint {
+                assert!(matches!(
+                    struct_lint.rules[0],
+                    StructRule::MaxGenerics { max, count_lifetimes, .. }
+                        if max == expected_max && count_lifetimes == expected_lifetimes
+                ));
+            } else {
+                panic!("Unexpected lint type");
+            }
+        }
+    }
+
+    #[test]
+    fn test_max_lifetimes_rule() {
+        let mut builder = LintBuilder::new();
+
+        builder
+            .struct_lint()
+            .lint_named("few_lifetimes")
+            .matching(|m| m.in_module("::views$"))
+            .with_severity(Severity::Error)
+            .max_generics(3)
+            .max_lifetimes(1)
+            .build();
+
+        if let ConfiguredLint::Struct(struct_lint) = &builder.lints[0] {
+            assert_eq!(struct_lint.rules.len(), 2);
+            assert!(matches!(
+                struct_lint.rules[1],
+                StructRule::MaxLifetimes(1, Severity::Error)
+            ));
+        } else {
+            panic!("Unexpected lint type");
+        }
+    }
+
+    #[test]
+    fn test_name_must_match_file_rule() {
+        let mut builder = LintBuilder::new();
+
+        builder
+            .struct_lint()
+            .lint_named("one_type_per_file")
+            .matching(|m| m.in_module("::clients::"))
+            .name_must_match_file()
+            .build();
+
+        if let ConfiguredLint::Struct(struct_lint) = &builder.lints[0] {
+            assert!(matches!(
+                struct_lint.rules[0],
+                StructRule::NameMustMatchFile(Severity::Warn)
+            ));
+        } else {
+            panic!("Unexpected lint type");
+        }
+    }
+
+    #[test]
+    fn test_forbid_field_type_rule() {
+        let mut builder = LintBuilder::new();
+
+        builder
+            .struct_lint()
+            .lint_named("lock_free")
+            .matching(|m| m.in_module("::lock_free$"))
+            .with_severity(Severity::Error)
+            .forbid_field_type(vec!["std::sync::Mutex", "std::sync::RwLock"])
+            .build();
+
+        if let ConfiguredLint::Struct(struct_lint) = &builder.lints[0] {
+            match &struct_lint.rules[0] {
+                StructRule::ForbidFieldType {
+                    type_patterns,
+                    severity,
+                } => {
+                    assert_eq!(
+                        type_patterns,
+                        &vec!["std::sync::Mutex", "std::sync::RwLock"]
+                    );
+                    assert_eq!(severity, &Severity::Error);
+                }
+                _ => panic!("Expected ForbidFieldType rule"),
+            }
+        } else {
+            panic!("Unexpected lint type");
+        }
+    }
+
     #[test]
     fn test_struct_visibility_rules() {
         let mut builder = LintBuilder::new();
@@ -178,6 +507,34 @@ mod tests {
             panic!("Expected Struct lint type");
         }
     }
+
+    #[test]
+    fn test_name_must_contain_trait_name_rule() {
+        let mut builder = LintBuilder::new();
+
+        builder
+            .struct_lint()
+            .lint_named("repository_naming")
+            .matching(|m| m.implements_trait("^crate::Repository$"))
+            .with_severity(Severity::Error)
+            .name_must_contain_trait_name()
+            .build();
+
+        if let ConfiguredLint::Struct(struct_lint) = &builder.lints[0] {
+            assert!(matches!(
+                struct_lint.matches,
+                StructMatch::ImplementsTrait(ref pattern) if pattern == "^crate::Repository$"
+            ));
+            assert_eq!(struct_lint.rules.len(), 1);
+            if let StructRule::NameMustContainTraitName(severity) = &struct_lint.rules[0] {
+                assert_eq!(severity, &Severity::Error);
+            } else {
+                panic!("Expected NameMustContainTraitName rule");
+            }
+        } else {
+            panic!("Expected Struct lint type");
+        }
+    }
 }
 
 #[cfg(test)]
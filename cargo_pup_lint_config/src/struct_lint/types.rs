@@ -10,8 +10,16 @@ pub enum StructMatch {
     Name(String),
     /// Match structs that have a specific attribute (e.g., #[derive(Debug)])
     HasAttribute(String),
-    /// Match structs that implement a specific trait
+    /// Match structs that implement a trait whose full path matches a regular expression
     ImplementsTrait(String),
+    /// Match structs with at least one field whose name and rendered type match the
+    /// given regular expressions. A pattern left as `None` matches any field.
+    HasField {
+        name: Option<String>,
+        type_pattern: Option<String>,
+    },
+    /// Match structs whose enclosing module path matches a regular expression
+    InModule(String),
     /// Logical AND - both patterns must match
     AndMatches(Box<StructMatch>, Box<StructMatch>),
     /// Logical OR - either pattern must match
@@ -26,6 +34,9 @@ pub struct StructLint {
     pub name: String,
     pub matches: StructMatch,
     pub rules: Vec<StructRule>,
+    /// Fail the run once this lint reports more than this many violations, even at `Warn`
+    #[serde(default)]
+    pub escalate_after: Option<usize>,
 }
 
 /// Rules that can be applied to structs matching specific criteria
@@ -43,6 +54,65 @@ pub enum StructRule {
     MustBePubCrate(Severity),
     /// Enforces that the struct implements a specific trait
     ImplementsTrait(String, Severity),
+    /// Enforces that the struct name contains the simple name of each trait it implements
+    /// that is selected by an `ImplementsTrait` matcher (e.g. `UserRepositoryImpl` for
+    /// `Repository`)
+    NameMustContainTraitName(Severity),
+    /// Enforces that public structs are marked `#[non_exhaustive]`, so that adding
+    /// fields isn't a breaking change. Non-public structs are not checked.
+    MustBeNonExhaustive(Severity),
+    /// Forbids deriving any of the given traits, by simple name (e.g. `Debug`, `Clone`)
+    ForbidDerive(Vec<String>, Severity),
+    /// Enforces a maximum size in bytes for the struct, as laid out by the compiler.
+    /// Generic structs whose layout depends on their parameters are skipped.
+    MaxMemorySize(u64, Severity),
+    /// Enforces that every public named field has a doc comment. Private fields and
+    /// tuple struct fields are not checked.
+    RequireFieldDocs(Severity),
+    /// Enforces that a struct's named fields are either all public or all non-public,
+    /// as a mix is often an accidental leak. Tuple structs are not checked.
+    ConsistentFieldVisibility(Severity),
+    /// Enforces that the struct implements `std::fmt::Debug`, whether derived or
+    /// implemented by hand. Unlike `ImplementsTrait("Debug")`, the trait is resolved by
+    /// the compiler rather than matched by name.
+    MustImplementDebug(Severity),
+    /// Enforces a maximum number of generic type and const parameters on the struct.
+    /// Lifetimes are only counted when `count_lifetimes` is set.
+    MaxGenerics {
+        max: usize,
+        #[serde(default)]
+        count_lifetimes: bool,
+        severity: Severity,
+    },
+    /// Enforces a maximum number of lifetime parameters declared on the struct, as
+    /// structs borrowing through many lifetimes are hard to use. Type and const
+    /// parameters are left to `MaxGenerics`.
+    MaxLifetimes(usize, Severity),
+    /// Forbids fields whose rendered type, with full paths such as
+    /// `std::sync::Mutex<u32>`, matches any of the given regular expressions
+    ForbidFieldType {
+        type_patterns: Vec<String>,
+        severity: Severity,
+    },
+    /// Enforces that the struct implements every one of the given traits, named by
+    /// the path they are imported by (e.g. `serde::Serialize`, or `crate::Entity` for
+    /// local traits). Each path is resolved to a trait and checked by the compiler, so
+    /// derives, hand-written and blanket impls all count. A trait that can't be found
+    /// in the crate's dependencies is reported as missing.
+    MustImplement(Vec<String>, Severity),
+    /// Enforces that the struct has an associated function without a `self` receiver
+    /// whose name matches `name_pattern`, e.g. `^new$`, in one of its inherent impls, so
+    /// types with private fields can still be constructed
+    RequireConstructor {
+        name_pattern: String,
+        severity: Severity,
+    },
+    /// Enforces that each file with a matched struct has one named after the file,
+    /// ignoring case and underscores, so `http_client.rs` defines `HttpClient`. Other
+    /// matched structs, such as helpers, can sit alongside it, and a file without one
+    /// is reported once, at its first matched struct. A `mod.rs` file is named after its
+    /// directory, and structs in `lib.rs` or `main.rs` are not checked.
+    NameMustMatchFile(Severity),
     /// Logical AND - both rules must pass
     And(Box<StructRule>, Box<StructRule>),
     /// Logical OR - either rule must pass
@@ -50,3 +120,35 @@ pub enum StructRule {
     /// Logical NOT - inverts the rule check
     Not(Box<StructRule>),
 }
+
+impl StructRule {
+    /// Returns the severity this rule reports with. Composite rules report
+    /// the highest severity of their children.
+    pub fn severity(&self) -> Severity {
+        match self {
+            StructRule::MustBeNamed(_, severity)
+            | StructRule::MustNotBeNamed(_, severity)
+            | StructRule::MustBePrivate(severity)
+            | StructRule::MustBePublic(severity)
+            | StructRule::MustBePubCrate(severity)
+            | StructRule::ImplementsTrait(_, severity)
+            | StructRule::NameMustContainTraitName(severity)
+            | StructRule::MustBeNonExhaustive(severity)
+            | StructRule::ForbidDerive(_, severity)
+            | StructRule::MaxMemorySize(_, severity)
+            | StructRule::RequireFieldDocs(severity)
+            | StructRule::ConsistentFieldVisibility(severity)
+            | StructRule::MustImplementDebug(severity)
+            | StructRule::MaxGenerics { severity, .. }
+            | StructRule::MaxLifetimes(_, severity)
+            | StructRule::ForbidFieldType { severity, .. }
+            | StructRule::MustImplement(_, severity)
+            | StructRule::RequireConstructor { severity, .. }
+            | StructRule::NameMustMatchFile(severity) => *severity,
+            StructRule::And(left, right) | StructRule::Or(left, right) => {
+                left.severity().max(right.severity())
+            }
+            StructRule::Not(inner) => inner.severity(),
+        }
+    }
+}
@@ -0,0 +1,144 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+use super::matcher::{TraitMatchNode, TraitMatcher, matcher};
+use super::types::{TraitLint, TraitMatch, TraitRule};
+use crate::lint_builder::LintBuilder;
+use crate::{ConfiguredLint, Severity};
+
+/// Extension trait that adds trait linting capabilities to LintBuilder
+pub trait TraitLintExt {
+    /// Start building a trait lint rule
+    fn trait_lint(&mut self) -> TraitLintBuilder<'_>;
+}
+
+impl TraitLintExt for LintBuilder {
+    fn trait_lint(&mut self) -> TraitLintBuilder<'_> {
+        TraitLintBuilder { parent: self }
+    }
+}
+
+/// Initial builder for creating a trait lint
+pub struct TraitLintBuilder<'a> {
+    parent: &'a mut LintBuilder,
+}
+
+impl<'a> TraitLintBuilder<'a> {
+    /// Give the lint a name
+    pub fn lint_named(self, name: impl Into<String>) -> TraitNamedBuilder<'a> {
+        TraitNamedBuilder {
+            parent: self.parent,
+            name: name.into(),
+        }
+    }
+}
+
+/// Builder used after naming the lint
+pub struct TraitNamedBuilder<'a> {
+    parent: &'a mut LintBuilder,
+    name: String,
+}
+
+impl<'a> TraitNamedBuilder<'a> {
+    /// Directly provide a trait matcher
+    pub fn matches(self, m: TraitMatch) -> TraitConstraintBuilder<'a> {
+        TraitConstraintBuilder {
+            parent: self.parent,
+            match_: m,
+            rules: Vec::new(),
+            current_severity: Severity::default(),
+            name: self.name,
+            escalate_after: None,
+        }
+    }
+
+    /// Define trait matching using the fluent DSL
+    ///
+    /// # Example
+    /// ```
+    /// use cargo_pup_lint_config::{LintBuilder, TraitLintExt};
+    /// let mut lint_builder = LintBuilder::new();
+    /// lint_builder.trait_lint()
+    ///     .lint_named("hidden_repositories")
+    ///     .matching(|m| m.name("::Repository$"))
+    ///     .require_implementors_private()
+    ///     .build();
+    /// ```
+    pub fn matching<F>(self, f: F) -> TraitConstraintBuilder<'a>
+    where
+        F: FnOnce(&TraitMatcher) -> TraitMatchNode,
+    {
+        let matcher = matcher(f);
+        self.matches(matcher)
+    }
+}
+
+/// Builder for adding rules to a trait lint
+pub struct TraitConstraintBuilder<'a> {
+    parent: &'a mut LintBuilder,
+    match_: TraitMatch,
+    rules: Vec<TraitRule>,
+    current_severity: Severity,
+    name: String,
+    escalate_after: Option<usize>,
+}
+
+impl<'a> TraitConstraintBuilder<'a> {
+    // Private method to add a rule directly to self
+    fn add_rule_internal(&mut self, rule: TraitRule) {
+        self.rules.push(rule);
+    }
+
+    /// Add a custom rule to the trait lint
+    pub fn add_rule(mut self, rule: TraitRule) -> Self {
+        self.add_rule_internal(rule);
+        self
+    }
+
+    /// Finalize the trait lint and return to the parent builder
+    pub fn build(self) -> &'a mut LintBuilder {
+        let lint = ConfiguredLint::Trait(TraitLint {
+            name: self.name,
+            matches: self.match_,
+            rules: self.rules,
+            escalate_after: self.escalate_after,
+        });
+        self.parent.push(lint);
+        self.parent
+    }
+
+    /// Set the severity level for all subsequently added rules
+    pub fn with_severity(mut self, severity: Severity) -> Self {
+        self.current_severity = severity;
+        self
+    }
+
+    /// Fail the run if this lint reports more than `max` violations, whatever their severity
+    pub fn escalate_after(mut self, max: usize) -> Self {
+        self.escalate_after = Some(max);
+        self
+    }
+
+    /// Add a rule requiring every type implementing the trait to be private
+    pub fn require_implementors_private(mut self) -> Self {
+        self.add_rule_internal(TraitRule::RequireImplementorsPrivate(self.current_severity));
+        self
+    }
+
+    /// Add a rule limiting how many methods each impl of the trait may define
+    pub fn max_impl_methods(mut self, max: usize) -> Self {
+        self.add_rule_internal(TraitRule::MaxImplMethods(max, self.current_severity));
+        self
+    }
+
+    /// Add a rule requiring each method the trait declares to have a doc comment
+    pub fn require_method_docs(mut self) -> Self {
+        self.add_rule_internal(TraitRule::RequireMethodDocs(self.current_severity));
+        self
+    }
+
+    /// Add a rule requiring the trait to be object safe, so it can be used as `dyn Trait`
+    pub fn must_be_object_safe(mut self) -> Self {
+        self.add_rule_internal(TraitRule::MustBeObjectSafe(self.current_severity));
+        self
+    }
+}
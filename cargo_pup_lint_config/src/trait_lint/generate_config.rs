@@ -0,0 +1,11 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+use crate::trait_lint::TraitLint;
+use crate::{GenerateFromContext, LintBuilder};
+use cargo_pup_common::project_context::ProjectContext;
+
+impl GenerateFromContext for TraitLint {
+    fn generate_from_contexts(_contexts: &[ProjectContext], _builder: &mut LintBuilder) {
+        // Trait rules encode design decisions, so there's nothing sensible to infer
+    }
+}
@@ -0,0 +1,63 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+use super::types::TraitMatch;
+
+// === Trait Matcher DSL === //
+pub struct TraitMatcher;
+
+impl TraitMatcher {
+    /// Matches traits by their fully qualified path, given a regular expression.
+    /// e.g., "^my_crate::storage::(Reader|Writer)$"
+    pub fn name(&self, name: impl Into<String>) -> TraitMatchNode {
+        TraitMatchNode::Leaf(TraitMatch::Name(name.into()))
+    }
+}
+
+#[derive(Clone)]
+pub enum TraitMatchNode {
+    Leaf(TraitMatch),
+    And(Box<TraitMatchNode>, Box<TraitMatchNode>),
+    Or(Box<TraitMatchNode>, Box<TraitMatchNode>),
+    Not(Box<TraitMatchNode>),
+}
+
+impl TraitMatchNode {
+    /// Create a logical AND operation between two matchers
+    pub fn and(self, other: TraitMatchNode) -> Self {
+        TraitMatchNode::And(Box::new(self), Box::new(other))
+    }
+
+    /// Create a logical OR operation between two matchers
+    pub fn or(self, other: TraitMatchNode) -> Self {
+        TraitMatchNode::Or(Box::new(self), Box::new(other))
+    }
+
+    /// Create a logical NOT operation that inverts the matcher
+    #[allow(clippy::should_implement_trait)]
+    pub fn not(self) -> Self {
+        TraitMatchNode::Not(Box::new(self))
+    }
+
+    // Converts the DSL tree to the actual TraitMatch
+    pub fn build(self) -> TraitMatch {
+        match self {
+            TraitMatchNode::Leaf(matcher) => matcher,
+            TraitMatchNode::And(a, b) => {
+                TraitMatch::AndMatches(Box::new(a.build()), Box::new(b.build()))
+            }
+            TraitMatchNode::Or(a, b) => {
+                TraitMatch::OrMatches(Box::new(a.build()), Box::new(b.build()))
+            }
+            TraitMatchNode::Not(m) => TraitMatch::NotMatch(Box::new(m.build())),
+        }
+    }
+}
+
+// Factory function to create a matcher DSL
+pub fn matcher<F>(f: F) -> TraitMatch
+where
+    F: FnOnce(&TraitMatcher) -> TraitMatchNode,
+{
+    let matcher = TraitMatcher;
+    f(&matcher).build()
+}
@@ -0,0 +1,36 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+mod builder;
+mod generate_config;
+mod matcher;
+mod tests;
+/// Trait lint module provides tools for creating and configuring trait-level lints
+///
+/// These lints constrain traits and the types that implement them, e.g.
+/// requiring that implementors stay private so that only the trait is exposed.
+///
+/// # Example
+/// ```
+/// use cargo_pup_lint_config::{LintBuilder, Severity};
+/// use cargo_pup_lint_config::trait_lint::TraitLintExt;
+///
+/// let mut builder = LintBuilder::new();
+///
+/// // Repositories are only ever used through the trait
+/// builder.trait_lint()
+///     .lint_named("private_repositories")
+///     .matching(|m| m.name("^my_app::storage::Repository$"))
+///     .with_severity(Severity::Error)
+///     .require_implementors_private()
+///     .build();
+/// ```
+mod types;
+
+// Core types for defining trait lints
+pub use types::{TraitLint, TraitMatch, TraitRule};
+
+// Trait matcher DSL for creating complex matching rules
+pub use matcher::{TraitMatchNode, TraitMatcher, matcher};
+
+// Builder API for creating trait lints
+pub use builder::{TraitConstraintBuilder, TraitLintBuilder, TraitLintExt, TraitNamedBuilder};
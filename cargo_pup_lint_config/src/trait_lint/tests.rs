@@ -0,0 +1,127 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod tests {
+    use crate::ConfiguredLint;
+    use crate::lint_builder::LintBuilder;
+    use crate::{Severity, TraitLintExt, TraitMatch, TraitRule};
+
+    #[test]
+    fn test_trait_lint_builder() {
+        let mut builder = LintBuilder::new();
+
+        builder
+            .trait_lint()
+            .lint_named("private_implementors")
+            .matching(|m| m.name("::Repository$").and(m.name("^test::legacy").not()))
+            .with_severity(Severity::Error)
+            .require_implementors_private()
+            .build();
+
+        assert_eq!(builder.lints.len(), 1);
+        if let ConfiguredLint::Trait(trait_lint) = &builder.lints[0] {
+            assert_eq!(trait_lint.name, "private_implementors");
+
+            if let TraitMatch::AndMatches(left, right) = &trait_lint.matches {
+                assert!(matches!(&**left, TraitMatch::Name(pattern) if pattern == "::Repository$"));
+                assert!(matches!(&**right, TraitMatch::NotMatch(_)));
+            } else {
+                panic!("Expected AndMatches");
+            }
+
+            assert!(matches!(
+                trait_lint.rules[0],
+                TraitRule::RequireImplementorsPrivate(Severity::Error)
+            ));
+        } else {
+            panic!("Unexpected lint type");
+        }
+    }
+
+    #[test]
+    fn test_max_impl_methods_rule() {
+        let mut builder = LintBuilder::new();
+
+        builder
+            .trait_lint()
+            .lint_named("small_handlers")
+            .matching(|m| m.name("::Handler$"))
+            .with_severity(Severity::Error)
+            .max_impl_methods(5)
+            .build();
+
+        if let ConfiguredLint::Trait(trait_lint) = &builder.lints[0] {
+            assert!(matches!(
+                trait_lint.rules[0],
+                TraitRule::MaxImplMethods(5, Severity::Error)
+            ));
+        } else {
+            panic!("Unexpected lint type");
+        }
+    }
+
+    #[test]
+    fn test_require_method_docs_rule() {
+        let mut builder = LintBuilder::new();
+
+        builder
+            .trait_lint()
+            .lint_named("documented_contracts")
+            .matching(|m| m.name("^my_lib::"))
+            .require_method_docs()
+            .build();
+
+        if let ConfiguredLint::Trait(trait_lint) = &builder.lints[0] {
+            assert!(matches!(
+                trait_lint.rules[0],
+                TraitRule::RequireMethodDocs(Severity::Warn)
+            ));
+        } else {
+            panic!("Unexpected lint type");
+        }
+    }
+
+    #[test]
+    fn test_must_be_object_safe_rule() {
+        let mut builder = LintBuilder::new();
+
+        builder
+            .trait_lint()
+            .lint_named("dyn_plugins")
+            .matching(|m| m.name("::plugins::"))
+            .with_severity(Severity::Error)
+            .must_be_object_safe()
+            .build();
+
+        if let ConfiguredLint::Trait(trait_lint) = &builder.lints[0] {
+            assert!(matches!(
+                trait_lint.rules[0],
+                TraitRule::MustBeObjectSafe(Severity::Error)
+            ));
+        } else {
+            panic!("Unexpected lint type");
+        }
+    }
+
+    #[test]
+    fn test_trait_lint_roundtrip() {
+        let mut builder = LintBuilder::new();
+        builder
+            .trait_lint()
+            .lint_named("private_implementors")
+            .matching(|m| m.name("::Repository$"))
+            .require_implementors_private()
+            .build();
+
+        let serialized = ron::to_string(&builder).unwrap();
+        let deserialized: LintBuilder = ron::from_str(&serialized).unwrap();
+
+        assert!(matches!(
+            &deserialized.lints[0],
+            ConfiguredLint::Trait(lint) if lint.name == "private_implementors"
+        ));
+        assert_eq!(deserialized.lints[0].kind(), "trait");
+        assert_eq!(deserialized.lints[0].severity(), Some(Severity::Warn));
+    }
+}
@@ -0,0 +1,60 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+use crate::Severity;
+use serde::{Deserialize, Serialize};
+
+/// Specifies how to match traits for linting
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub enum TraitMatch {
+    /// Match traits by their fully qualified path, given as a regular expression
+    /// (e.g. "^my_crate::repository::Repository$")
+    Name(String),
+    /// Logical AND - both patterns must match
+    AndMatches(Box<TraitMatch>, Box<TraitMatch>),
+    /// Logical OR - either pattern must match
+    OrMatches(Box<TraitMatch>, Box<TraitMatch>),
+    /// Logical NOT - inverts the match
+    NotMatch(Box<TraitMatch>),
+}
+
+/// A complete trait lint definition with matching criteria and rules
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraitLint {
+    pub name: String,
+    pub matches: TraitMatch,
+    pub rules: Vec<TraitRule>,
+    /// Fail the run once this lint reports more than this many violations, even at `Warn`
+    #[serde(default)]
+    pub escalate_after: Option<usize>,
+}
+
+/// Rules that can be applied to traits matching specific criteria
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub enum TraitRule {
+    /// Enforces that every type in the local crate implementing the trait is private,
+    /// so that callers only ever see the trait
+    RequireImplementorsPrivate(Severity),
+    /// Limits the number of methods in each local impl of the trait. Impls generated
+    /// by derives and other macros are not checked.
+    MaxImplMethods(usize, Severity),
+    /// Requires every method declared by a public trait, whether required or provided,
+    /// to have a doc comment
+    RequireMethodDocs(Severity),
+    /// Requires the trait to be object safe (dyn compatible), so it can be used as
+    /// `dyn Trait`, e.g. for plugins loaded behind trait objects. Each reason the
+    /// compiler gives for it not being, such as a generic method or one returning
+    /// `Self`, is reported.
+    MustBeObjectSafe(Severity),
+}
+
+impl TraitRule {
+    /// Returns the severity the rule reports violations with
+    pub fn severity(&self) -> Severity {
+        match self {
+            TraitRule::RequireImplementorsPrivate(severity)
+            | TraitRule::MaxImplMethods(_, severity)
+            | TraitRule::RequireMethodDocs(severity)
+            | TraitRule::MustBeObjectSafe(severity) => *severity,
+        }
+    }
+}
@@ -1,14 +1,61 @@
 // This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
 
 use crate::ArchitectureLintCollection;
-use cargo_pup_common::project_context::{ModuleInfo, ProjectContext, TraitInfo};
+use crate::helpers::lint_helpers::{PUP_TOOL_NAME, errors_emitted};
+use crate::helpers::queries::{get_full_module_name, get_module_name_from_def_id};
+use cargo_pup_common::project_context::{
+    FeatureGatedModule, ModuleDependency, ModuleInfo, ModuleItems, PUP_DIR, ProjectContext,
+    SourceFingerprint, TraitInfo,
+};
+use cargo_pup_common::timings::LintTimings;
 use rustc_driver::Callbacks;
-use rustc_hir::ItemKind;
-use rustc_hir::def_id::LocalModDefId;
+use rustc_errors::DiagMessage;
+use rustc_hir::attrs::{AttributeKind, CfgEntry};
+use rustc_hir::def::{DefKind, Res};
+use rustc_hir::def_id::{LOCAL_CRATE, LocalModDefId};
+use rustc_hir::{Attribute, ImplItemKind, ItemKind, OwnerId, UseKind};
 use rustc_middle::ty::TyCtxt;
-use rustc_span::Symbol;
-use std::sync::Arc;
-use std::{collections::BTreeSet, path::Path};
+use rustc_span::{Ident, Span, Symbol, sym};
+use std::collections::{BTreeMap, BTreeSet};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+/// Diagnostics emitted so far during this compilation, keyed by lint name, primary span
+/// and message. Rules that walk MIR can reach the same source location more than once,
+/// e.g. through several instantiations of a generic function.
+static EMITTED: LazyLock<Mutex<HashSet<(String, Span, DiagMessage)>>> =
+    LazyLock::new(|| Mutex::new(HashSet::new()));
+
+/// Time spent by each configured lint checking items during this compilation, keyed by
+/// lint name. Only collected when the runner is verbose or recording timings.
+static LINT_TIMINGS: LazyLock<Mutex<HashMap<String, Duration>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Set when the runner is verbose or recording timings, so the lint passes time themselves
+static TIMING_ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Set with --fail-fast, so the first error-severity violation aborts the compilation
+static FAIL_FAST: AtomicBool = AtomicBool::new(false);
+
+/// Adds the time since it was started to a lint's total when dropped.
+/// See [`ArchitectureLintRunner::time_lint`].
+pub struct LintTimer {
+    lint_name: String,
+    start: Instant,
+}
+
+impl Drop for LintTimer {
+    fn drop(&mut self) {
+        *LINT_TIMINGS
+            .lock()
+            .unwrap()
+            .entry(std::mem::take(&mut self.lint_name))
+            .or_default() += self.start.elapsed();
+    }
+}
 
 ///
 /// The mode our lint runner should operate in
@@ -26,6 +73,15 @@ pub enum Mode {
 
     /// Generate configuration
     GenerateConfig,
+
+    /// Export the module import graph
+    Graph,
+
+    /// Summarize the size and shape of the codebase
+    Stats,
+
+    /// Export the project context for other tools
+    ExportContext,
 }
 
 ///
@@ -44,6 +100,24 @@ pub struct ArchitectureLintRunner {
     // Cargo arguments that were passed through
     cargo_args: Vec<String>,
 
+    // The configuration files the lints were loaded from, including the files
+    // they include. They're tracked as dependencies of the crate, so editing
+    // one triggers a re-check and makes the project context stale.
+    config_files: Vec<PathBuf>,
+
+    // With --verbose, a summary of the lints run over the crate and the time
+    // each took is added to the results.
+    verbose: bool,
+
+    // With --timings, the file each lint's time over the crate is added to
+    timings_file: Option<PathBuf>,
+
+    // With --exit-zero, errors from our lints don't fail the compilation
+    exit_zero: bool,
+
+    // Where project contexts are cached, .pup unless --output-dir moved it
+    pup_dir: PathBuf,
+
     // Because we gather our output within the compiler
     // Callback mechanism, we need somewhere we can stash our
     // results internally.
@@ -58,6 +132,11 @@ impl ArchitectureLintRunner {
             result_text: String::new(),
             cli_args,
             cargo_args: Vec::new(),
+            config_files: Vec::new(),
+            verbose: false,
+            timings_file: None,
+            exit_zero: false,
+            pup_dir: PathBuf::from(PUP_DIR),
         }
     }
 
@@ -66,6 +145,124 @@ impl ArchitectureLintRunner {
         self.cargo_args = args;
     }
 
+    /// Set the configuration files the lints were loaded from
+    pub fn set_config_files(&mut self, files: Vec<PathBuf>) {
+        self.config_files = files;
+    }
+
+    /// Report which lints ran and how long they took, along with the results
+    pub fn set_verbose(&mut self, verbose: bool) {
+        self.verbose = verbose;
+        TIMING_ENABLED.store(verbose || self.timings_file.is_some(), Ordering::Relaxed);
+    }
+
+    /// Add the time each lint spent checking the crate to this file once analysis is done
+    pub fn set_timings_file(&mut self, path: PathBuf) {
+        self.timings_file = Some(path);
+        TIMING_ENABLED.store(true, Ordering::Relaxed);
+    }
+
+    /// Don't fail the compilation over errors from our lints, as long as there are no
+    /// other errors
+    pub fn set_exit_zero(&mut self, exit_zero: bool) {
+        self.exit_zero = exit_zero;
+    }
+
+    /// Abort the compilation as soon as a lint reports a violation as an error, rather
+    /// than reporting every violation in the crate. Warnings don't stop it.
+    pub fn set_fail_fast(&mut self, fail_fast: bool) {
+        FAIL_FAST.store(fail_fast, Ordering::Relaxed);
+    }
+
+    /// Returns true if the compilation should stop at its first error-severity violation
+    pub fn fail_fast() -> bool {
+        FAIL_FAST.load(Ordering::Relaxed)
+    }
+
+    /// Write project contexts to this directory instead of .pup
+    pub fn set_pup_dir(&mut self, pup_dir: PathBuf) {
+        self.pup_dir = pup_dir;
+    }
+
+    /// Start timing the named lint, which is charged for the time until the timer is
+    /// dropped. Returns None, and costs nothing, unless the runner is verbose or
+    /// recording timings.
+    pub fn time_lint(lint_name: &str) -> Option<LintTimer> {
+        TIMING_ENABLED.load(Ordering::Relaxed).then(|| LintTimer {
+            lint_name: lint_name.to_string(),
+            start: Instant::now(),
+        })
+    }
+
+    /// Returns true the first time the named lint reports this message at this span,
+    /// and false for every repeat, which should not be emitted again.
+    pub fn first_emission(lint_name: &str, span: Span, msg: &DiagMessage) -> bool {
+        EMITTED
+            .lock()
+            .unwrap()
+            .insert((lint_name.to_string(), span, msg.clone()))
+    }
+
+    /// With --exit-zero, forget the errors our lints emitted if they're the only errors in
+    /// the crate, so rustc goes on to write its metadata for the crates that depend on it
+    /// and exits successfully. Compile errors, and errors from rustc's own lints, still
+    /// fail the compilation.
+    fn clear_lint_errors(tcx: TyCtxt<'_>) {
+        let errors = errors_emitted();
+        if errors == 0 || tcx.dcx().err_count() != errors {
+            return;
+        }
+        tcx.dcx().reset_err_count();
+        tcx.dcx().note(format!(
+            "{errors} cargo-pup lint error(s) don't fail the build because of --exit-zero"
+        ));
+    }
+
+    /// Summarise the lints run over this crate, listing each one with the time it
+    /// spent checking items
+    fn timing_summary(&self, tcx: TyCtxt<'_>) -> String {
+        let timings = LINT_TIMINGS.lock().unwrap();
+        let lints = self.lint_collection.lints();
+        let files = tcx
+            .sess
+            .source_map()
+            .files()
+            .iter()
+            .filter(|file| file.cnum == LOCAL_CRATE && file.name.is_real())
+            .count();
+        let total: Duration = timings.values().sum();
+
+        let mut summary = format!(
+            "cargo-pup checked crate `{}` ({files} file(s)) with {} lint(s) in {total:.2?}",
+            tcx.crate_name(LOCAL_CRATE),
+            lints.len()
+        );
+        for lint in lints {
+            let name = lint.name();
+            let elapsed = timings.get(&name).copied().unwrap_or_default();
+            summary.push_str(&format!("\n  {name}: {elapsed:.2?}"));
+        }
+        summary
+    }
+
+    /// Add the time each configured lint spent checking this crate to the timings file.
+    /// Lints that didn't check any items are recorded too, so every lint is listed.
+    fn record_timings(&self, tcx: TyCtxt<'_>, path: &Path) {
+        let timings = LINT_TIMINGS.lock().unwrap();
+        let lints = self.lint_collection.lints().iter().map(|lint| {
+            let name = lint.name();
+            let elapsed = timings.get(&name).copied().unwrap_or_default();
+            (name, elapsed)
+        });
+        let crate_name = tcx.crate_name(LOCAL_CRATE).to_string();
+        if let Err(e) = LintTimings::record(path, &crate_name, lints) {
+            eprintln!(
+                "Warning: couldn't record lint timings to {}: {e}",
+                path.display()
+            );
+        }
+    }
+
     ///
     /// Borrow the lint results in formatted text style.
     ///
@@ -82,47 +279,86 @@ impl ArchitectureLintRunner {
                 let _ = self.lint_collection.lints();
                 Ok(())
             }
-            Mode::PrintModules | Mode::PrintTraits => {
+            Mode::PrintModules
+            | Mode::PrintTraits
+            | Mode::Graph
+            | Mode::Stats
+            | Mode::ExportContext => {
                 // For these modes, we build the project context, then serialize it
                 // out to .pup. The outer call - e.g. cargo-pup - then grabs it all
                 // and uses it to produce a complete view of all the nested projects.
                 //
                 // We don't print any of our own output! We're just discovering project
                 // structure info for cargo-pup.
-                let context = self
-                    .build_project_context(tcx)
+                self.write_project_context(tcx)
                     .context("Failed to build project context for print-modules mode")?;
 
-                // Serialize the context to a file
-                if let Err(e) = context.serialize_to_file() {
-                    eprintln!("Warning: Failed to serialize project context: {e}");
-                }
-
                 Ok(())
             }
             Mode::GenerateConfig => {
                 // For config generation, just build the project context and serialize it
                 // like we do for PrintModules and PrintTraits. The cargo-pup tool will handle
                 // generating the config from the serialized context.
-                let context = self
-                    .build_project_context(tcx)
+                let module_root = self
+                    .write_project_context(tcx)
                     .context("Failed to build project context for generate-config mode")?;
 
-                // Serialize the context to a file
-                if let Err(e) = context.serialize_to_file() {
-                    eprintln!("Warning: Failed to serialize project context: {e}");
-                }
-
                 // Set a simple success message
-                self.result_text = format!(
-                    "Project context successfully generated for crate {}",
-                    context.module_root
-                );
+                self.result_text =
+                    format!("Project context successfully generated for crate {module_root}");
                 Ok(())
             }
         }
     }
 
+    /// Build the project context and serialize it to the .pup directory, along with the
+    /// fingerprint cargo-pup checks to skip rebuilding it. Returns the module root of the
+    /// context.
+    fn write_project_context(&self, tcx: TyCtxt<'_>) -> anyhow::Result<String> {
+        let fingerprint = self.source_fingerprint(tcx);
+        let context = self.build_project_context(tcx)?;
+
+        // Serialize the context to a file
+        if let Err(e) = context.serialize_to_file_with_fingerprint(&fingerprint) {
+            eprintln!("Warning: Failed to serialize project context: {e}");
+        }
+
+        Ok(context.module_root)
+    }
+
+    /// Fingerprint everything the project context depends on: the cargo-pup and cargo
+    /// arguments, the local crate's source files as loaded by the compiler, the lint
+    /// configuration, and the manifests that decide what the workspace builds.
+    fn source_fingerprint(&self, tcx: TyCtxt<'_>) -> SourceFingerprint {
+        // Cargo runs rustc from the workspace root, but cargo-pup may check the
+        // fingerprint from elsewhere, so every path is made absolute
+        let absolute = |path: PathBuf| path.canonicalize().unwrap_or(path);
+
+        let sources = tcx
+            .sess
+            .source_map()
+            .files()
+            .iter()
+            .filter(|file| file.cnum == LOCAL_CRATE)
+            .filter_map(|file| file.name.clone().into_local_path())
+            .collect::<Vec<_>>();
+        let manifest_dir = std::env::var_os("CARGO_MANIFEST_DIR").map(PathBuf::from);
+        let manifests = [Some(PathBuf::from(".")), manifest_dir]
+            .into_iter()
+            .flatten()
+            .flat_map(|dir| [dir.join("Cargo.toml"), dir.join("Cargo.lock")]);
+
+        SourceFingerprint::new(
+            self.cli_args.clone(),
+            self.cargo_args.clone(),
+            sources
+                .into_iter()
+                .chain(self.config_files.iter().cloned())
+                .chain(manifests)
+                .map(absolute),
+        )
+    }
+
     /// Build ProjectContext. This includes module and trait information - and is typically
     /// used by cargo-pup - on the outside of the pup-driver execution - to display project
     /// info to the user.
@@ -238,15 +474,157 @@ impl ArchitectureLintRunner {
             .collect();
 
         // Build and return the context
-        let mut context = ProjectContext::new();
+        let mut context = ProjectContext::with_base_dir(&self.pup_dir);
         context.module_root = module_root;
         context.modules = module_infos;
         context.traits = traits;
+        context.module_dependencies = collect_module_dependencies(tcx);
+        context.feature_gated_modules = collect_feature_gated_modules(tcx);
+        context.module_items = collect_module_items(tcx);
 
         Ok(context)
     }
 }
 
+/// Collect an edge for every `use` item in the crate, from the module containing it to
+/// the module that owns whatever it resolves to. Imports of modules themselves point at
+/// that module.
+fn collect_module_dependencies(tcx: TyCtxt<'_>) -> Vec<ModuleDependency> {
+    let mut dependencies = BTreeSet::new();
+
+    for item_id in tcx.hir_crate_items(()).free_items() {
+        let item = tcx.hir_item(item_id);
+        let ItemKind::Use(path, kind) = item.kind else {
+            continue;
+        };
+        // List stems (`use a::{b, c}`) are lowered into separate items for each leaf
+        if kind == UseKind::ListStem || item.span.from_expansion() {
+            continue;
+        }
+
+        let parent = tcx.hir_get_parent_item(item.hir_id());
+        let from = get_full_module_name(&tcx, &parent);
+
+        for res in path.res.present_items() {
+            let Res::Def(def_kind, def_id) = res else {
+                continue;
+            };
+            let module_def_id = if def_kind == DefKind::Mod {
+                def_id
+            } else {
+                let mut current = def_id;
+                loop {
+                    current = tcx.parent(current);
+                    if tcx.def_kind(current) == DefKind::Mod {
+                        break current;
+                    }
+                }
+            };
+
+            let to = get_module_name_from_def_id(&tcx, module_def_id);
+            if to != from {
+                dependencies.insert(ModuleDependency {
+                    from: from.clone(),
+                    to,
+                });
+            }
+        }
+    }
+
+    dependencies.into_iter().collect()
+}
+
+/// Collect the modules whose `#[cfg]` depends on a cargo feature. Modules whose cfg
+/// doesn't hold have been removed by now, so only those enabled in this build are seen.
+fn collect_feature_gated_modules(tcx: TyCtxt<'_>) -> Vec<FeatureGatedModule> {
+    fn mentions_feature(entry: &CfgEntry) -> bool {
+        match entry {
+            CfgEntry::All(entries, _) | CfgEntry::Any(entries, _) => {
+                entries.iter().any(mentions_feature)
+            }
+            CfgEntry::Not(entry, _) => mentions_feature(entry),
+            CfgEntry::NameValue { name, .. } => *name == sym::feature,
+            CfgEntry::Bool(..) | CfgEntry::Version(..) => false,
+        }
+    }
+
+    let mut modules = Vec::new();
+    for item_id in tcx.hir_crate_items(()).free_items() {
+        let item = tcx.hir_item(item_id);
+        if !matches!(item.kind, ItemKind::Mod(..)) {
+            continue;
+        }
+
+        for attr in tcx.hir_attrs(item.hir_id()) {
+            if let Attribute::Parsed(AttributeKind::CfgTrace(entries)) = attr {
+                for (entry, _) in entries.iter().filter(|(entry, _)| mentions_feature(entry)) {
+                    modules.push(FeatureGatedModule {
+                        module: get_full_module_name(&tcx, &item.owner_id),
+                        cfg: entry.to_string(),
+                    });
+                }
+            }
+        }
+    }
+    modules
+}
+
+/// Count the items each module of the crate declares directly. Items generated by
+/// macros, such as derived impls, aren't counted.
+fn collect_module_items(tcx: TyCtxt<'_>) -> Vec<ModuleItems> {
+    let module_name = |def_id: LocalModDefId| {
+        get_full_module_name(
+            &tcx,
+            &OwnerId {
+                def_id: def_id.to_local_def_id(),
+            },
+        )
+    };
+
+    // Start from every module, so that empty ones are counted too
+    let mut modules: BTreeMap<String, ModuleItems> = BTreeMap::new();
+    tcx.hir_for_each_module(|module| {
+        let name = module_name(module);
+        modules.insert(
+            name.clone(),
+            ModuleItems {
+                module: name,
+                ..ModuleItems::default()
+            },
+        );
+    });
+
+    for item_id in tcx.hir_crate_items(()).free_items() {
+        let item = tcx.hir_item(item_id);
+        if matches!(item.kind, ItemKind::Use(..)) || item.span.from_expansion() {
+            continue;
+        }
+
+        let name = module_name(tcx.parent_module_from_def_id(item.owner_id.def_id));
+        let Some(counts) = modules.get_mut(&name) else {
+            continue;
+        };
+        counts.total += 1;
+        match &item.kind {
+            ItemKind::Struct(..) => counts.structs += 1,
+            ItemKind::Trait(..) => counts.traits += 1,
+            ItemKind::Fn { .. } => counts.functions += 1,
+            ItemKind::Impl(impl_data) => {
+                let methods = impl_data
+                    .items
+                    .iter()
+                    .filter(|id| matches!(tcx.hir_impl_item(**id).kind, ImplItemKind::Fn(..)))
+                    .count();
+                counts.functions += methods;
+                counts.total += methods;
+            }
+            _ => {}
+        }
+    }
+
+    modules.into_values().collect()
+}
+
 ///
 /// Adapt rustc's callbacks mechanism to our lints, collecting
 /// lint results as we go.
@@ -256,6 +634,7 @@ impl Callbacks for ArchitectureLintRunner {
         let cli_args = self.cli_args.clone();
         let mode = self.mode.clone();
         let cargo_args = self.cargo_args.clone();
+        let config_files = self.config_files.clone();
 
         let lint_collection = Arc::clone(&self.lint_collection);
         config.register_lints = Some(Box::new(move |_sess, lint_store| {
@@ -267,6 +646,18 @@ impl Callbacks for ArchitectureLintRunner {
             }
         }));
 
+        // Register `pup` as a lint tool, so that `#[allow(pup::rule_name)]` is
+        // accepted. The attributes themselves are interpreted by span_lint_and_help.
+        config.override_queries = Some(|_sess, providers| {
+            providers.queries.registered_tools = |tcx, ()| {
+                let mut tools = (rustc_interface::DEFAULT_QUERY_PROVIDERS
+                    .queries
+                    .registered_tools)(tcx, ());
+                tools.insert(Ident::from_str(PUP_TOOL_NAME));
+                tools
+            };
+        });
+
         config.psess_created = Some(Box::new(move |psess| {
             // track CLI args
             psess
@@ -283,12 +674,12 @@ impl Callbacks for ArchitectureLintRunner {
                 ));
             }
 
-            // Track config file
-            if Path::new("../../../pup.ron").exists() {
+            // Track config files
+            for config_file in &config_files {
                 psess
                     .file_depinfo
                     .get_mut()
-                    .insert(Symbol::intern("pup.ron"));
+                    .insert(Symbol::intern(&config_file.to_string_lossy()));
             }
 
             // Add our test lint
@@ -313,18 +704,25 @@ impl Callbacks for ArchitectureLintRunner {
     }
 
     ///
-    /// We can use this to filter
-    /// lint results, probably, if we need
-    /// to do this dynamically (e.g., raising level if we cross some threshold).
-    ///
-    /// TODO - we should check this to make sure it works and we can keep it up our
-    /// sleeve.
+    /// Our lints have all run by now, so this is where their timings are
+    /// collected, and where --exit-zero forgives their errors.
     ///
     fn after_analysis(
         &mut self,
         _compiler: &rustc_interface::interface::Compiler,
-        _tcx: TyCtxt<'_>,
+        tcx: TyCtxt<'_>,
     ) -> rustc_driver::Compilation {
+        if self.mode == Mode::Check && self.verbose {
+            self.result_text = self.timing_summary(tcx);
+        }
+        if self.mode == Mode::Check
+            && let Some(timings_file) = &self.timings_file
+        {
+            self.record_timings(tcx, timings_file);
+        }
+        if self.mode == Mode::Check && self.exit_zero {
+            Self::clear_lint_errors(tcx);
+        }
         rustc_driver::Compilation::Continue
     }
 }
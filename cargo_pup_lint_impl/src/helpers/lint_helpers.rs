@@ -6,9 +6,79 @@
 //! Apache 2.0 and MIT licenses. Original copyright:
 //! Copyright (c) 2014 The Rust Project Developers
 
+use crate::ArchitectureLintRunner;
+use cargo_pup_lint_config::report::ReportEntry;
+use cargo_pup_lint_config::{LintViolation, Severity};
+use rustc_ast::MetaItem;
 use rustc_errors::{DiagMessage, MultiSpan, SubdiagMessage};
-use rustc_lint::{Lint, LintContext};
-use rustc_span::Span;
+use rustc_hir::HirId;
+use rustc_hir::def_id::LOCAL_CRATE;
+use rustc_lint::{LateContext, Level, Lint, LintContext};
+use rustc_span::{DUMMY_SP, FileName, RemapPathScopeComponents, Span, Symbol, sym};
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Mutex, OnceLock};
+
+/// The tool name under which configured cargo-pup rules can be referenced in
+/// lint attributes, e.g. `#[allow(pup::my_rule)]`.
+pub const PUP_TOOL_NAME: &str = "pup";
+
+/// Set once a cargo-pup diagnostic has been emitted at warning level by this process
+static WARNING_EMITTED: AtomicBool = AtomicBool::new(false);
+
+/// Returns true if any cargo-pup diagnostic has been emitted as a warning so far.
+/// Used by `--deny-warnings` to fail the compilation without changing how the
+/// diagnostics render. rustc deduplicates identical diagnostics, so we only track
+/// whether there were any, not how many.
+pub fn warning_emitted() -> bool {
+    WARNING_EMITTED.load(Ordering::Relaxed)
+}
+
+/// How many cargo-pup diagnostics have been emitted at error level by this process
+static ERRORS_EMITTED: AtomicUsize = AtomicUsize::new(0);
+
+/// Returns how many cargo-pup diagnostics have been emitted as errors so far. Each one
+/// counts as an error towards rustc's own total too, even when rustc deduplicates it,
+/// so `--exit-zero` can tell whether they're the only errors in the crate.
+pub fn errors_emitted() -> usize {
+    ERRORS_EMITTED.load(Ordering::Relaxed)
+}
+
+/// Every cargo-pup violation emitted by this process, for the workspace report
+static REPORT_ENTRIES: Mutex<Vec<ReportEntry>> = Mutex::new(Vec::new());
+
+/// Take the violations emitted so far, for `check --report` to append to the
+/// workspace report
+pub fn take_report_entries() -> Vec<ReportEntry> {
+    std::mem::take(&mut REPORT_ENTRIES.lock().unwrap())
+}
+
+/// The files diagnostics may be reported in, when `check --since` limits them
+static CHANGED_FILES: OnceLock<HashSet<PathBuf>> = OnceLock::new();
+
+/// Only report diagnostics in the given files from now on. The paths should be
+/// absolute and canonical. Only the first call has any effect.
+pub fn restrict_to_files(files: impl IntoIterator<Item = PathBuf>) {
+    let _ = CHANGED_FILES.set(files.into_iter().collect());
+}
+
+/// Returns true if diagnostics at this span should be reported, given any
+/// restriction set by [`restrict_to_files`]
+fn is_in_reported_files(cx: &LateContext<'_>, span: Span) -> bool {
+    let Some(files) = CHANGED_FILES.get() else {
+        return true;
+    };
+    let FileName::Real(filename) = cx.sess().source_map().span_to_filename(span) else {
+        return false;
+    };
+    // rustc sees paths relative to the directory cargo runs it in
+    let path = filename.path(RemapPathScopeComponents::DIAGNOSTICS);
+    let path = std::env::current_dir()
+        .map(|dir| dir.join(path))
+        .unwrap_or_else(|_| path.to_path_buf());
+    files.contains(&path.canonicalize().unwrap_or(path))
+}
 
 /// Emit a lint message with an extra `help` message.
 ///
@@ -16,8 +86,14 @@ use rustc_span::Span;
 /// can't provide a specific machine applicable suggestion.
 ///
 /// The `help` message can be optionally attached to a `Span`.
-pub fn span_lint_and_help<T: LintContext>(
-    cx: &T,
+///
+/// Nothing is emitted if the rule is suppressed at the node being linted with
+/// `#[allow(pup::<rule_name>)]` or `#[expect(..)]` (see [`is_rule_allowed`]), if the
+/// rule has already reported the same
+/// message at the same span during this compilation, or if the span is outside
+/// the files set by [`restrict_to_files`].
+pub fn span_lint_and_help(
+    cx: &LateContext<'_>,
     lint: &'static Lint,
     rule_name: &str,
     span: impl Into<MultiSpan>,
@@ -25,6 +101,38 @@ pub fn span_lint_and_help<T: LintContext>(
     help_span: Option<Span>,
     help: impl Into<SubdiagMessage>,
 ) {
+    if is_rule_allowed(cx, cx.last_node_with_lint_attrs, rule_name) {
+        return;
+    }
+
+    let span = span.into();
+    let msg = msg.into();
+    let primary_span = span.primary_span().unwrap_or(DUMMY_SP);
+    if !is_in_reported_files(cx, primary_span) {
+        return;
+    }
+    if !ArchitectureLintRunner::first_emission(rule_name, primary_span, &msg) {
+        return;
+    }
+
+    let level = cx
+        .tcx
+        .lint_level_at_node(lint, cx.last_node_with_lint_attrs)
+        .level;
+    // rustc silently drops lints inside external macro expansions, such as the
+    // injected prelude import, so those don't count as violations either
+    let dropped = primary_span.in_external_macro(cx.sess().source_map());
+    if !matches!(level, Level::Allow | Level::Expect) && !dropped {
+        record_report_entry(cx, rule_name, primary_span, &msg, level);
+    }
+
+    // Only a warning that's actually printed fails the build with --deny-warnings
+    if matches!(level, Level::Warn | Level::ForceWarn) && !dropped {
+        WARNING_EMITTED.store(true, Ordering::Relaxed);
+    }
+    if matches!(level, Level::Deny | Level::Forbid) && !dropped {
+        ERRORS_EMITTED.fetch_add(1, Ordering::Relaxed);
+    }
     cx.span_lint(lint, span, |diag| {
         diag.primary_message(msg);
         if let Some(help_span) = help_span {
@@ -34,4 +142,78 @@ pub fn span_lint_and_help<T: LintContext>(
         }
         diag.note(format!("Applied by cargo-pup rule '{rule_name}'."));
     });
+
+    // With --fail-fast, an error stops the compilation here. Lints capped to warnings
+    // don't emit an error, so they carry on.
+    if matches!(level, Level::Deny | Level::Forbid) && ArchitectureLintRunner::fail_fast() {
+        cx.sess().dcx().abort_if_errors();
+    }
+}
+
+// Record a violation for the workspace report, located the way cargo's JSON messages
+// locate it: by the file as rustc sees it and the 1-based line
+fn record_report_entry(
+    cx: &LateContext<'_>,
+    rule_name: &str,
+    span: Span,
+    msg: &DiagMessage,
+    level: Level,
+) {
+    let severity = match level {
+        Level::Warn | Level::ForceWarn => Severity::Warn,
+        _ => Severity::Error,
+    };
+    let location = cx.sess().source_map().lookup_char_pos(span.lo());
+    let file = match &location.file.name {
+        FileName::Real(filename) => filename
+            .path(RemapPathScopeComponents::DIAGNOSTICS)
+            .display()
+            .to_string(),
+        other => other.prefer_local_unconditionally().to_string(),
+    };
+    REPORT_ENTRIES.lock().unwrap().push(ReportEntry {
+        crate_name: cx.tcx.crate_name(LOCAL_CRATE).to_string(),
+        violation: LintViolation {
+            lint_name: rule_name.to_string(),
+            severity,
+            file,
+            line: location.line,
+            message: msg.as_str().unwrap_or_default().to_string(),
+        },
+    });
+}
+
+/// Returns true if the configured rule is suppressed at the given node: the nearest
+/// `allow`, `expect`, `warn`, `deny` or `forbid` attribute naming `pup::<rule_name>`,
+/// on the node or one of its parents, is an `allow` or an `expect`. A `warn` or `deny`
+/// closer to the node turns the rule back on.
+///
+/// rustc only knows about the internal lint symbols, and several configured rules
+/// share each one - so we resolve suppression by configured rule name ourselves.
+pub fn is_rule_allowed(cx: &LateContext<'_>, hir_id: HirId, rule_name: &str) -> bool {
+    const LEVELS: [Symbol; 5] = [sym::allow, sym::expect, sym::warn, sym::deny, sym::forbid];
+
+    let names_rule = |meta: &MetaItem| {
+        let segments = &meta.path.segments;
+        segments.len() == 2
+            && segments[0].ident.name.as_str() == PUP_TOOL_NAME
+            && segments[1].ident.name.as_str() == rule_name
+    };
+
+    for id in std::iter::once(hir_id).chain(cx.tcx.hir_parent_id_iter(hir_id)) {
+        // Of several attributes on the same node, the last one wins, as it does in rustc
+        let level = cx.tcx.hir_attrs(id).iter().rev().find_map(|attr| {
+            let level = LEVELS.into_iter().find(|level| attr.has_name(*level))?;
+            let items = attr.meta_item_list()?;
+            items
+                .iter()
+                .filter_map(|item| item.meta_item())
+                .any(names_rule)
+                .then_some(level)
+        });
+        if let Some(level) = level {
+            return level == sym::allow || level == sym::expect;
+        }
+    }
+    false
 }
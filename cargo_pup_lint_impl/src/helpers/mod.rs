@@ -5,4 +5,5 @@ pub mod architecture_lint_collection;
 pub mod architecture_lint_runner;
 mod declare_lint;
 pub mod lint_helpers;
+pub mod patterns;
 pub mod queries;
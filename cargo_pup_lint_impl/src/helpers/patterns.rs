@@ -0,0 +1,44 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+use regex::Regex;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// The patterns a lint's matchers and rules are configured with, each compiled once,
+/// the first time it's used.
+///
+/// A pattern is a regular expression searched for anywhere in the text, so `^` and `$`
+/// are needed to match a whole name or path. A pattern that isn't a valid regular
+/// expression is reported, and then only matches text that equals it.
+pub struct Patterns {
+    lint_name: String,
+    compiled: Mutex<HashMap<String, Option<Regex>>>,
+}
+
+impl Patterns {
+    pub fn new(lint_name: impl Into<String>) -> Self {
+        Self {
+            lint_name: lint_name.into(),
+            compiled: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns true if `text` matches `pattern`
+    pub fn is_match(&self, pattern: &str, text: &str) -> bool {
+        let mut compiled = self.compiled.lock().unwrap();
+        let regex = compiled.entry(pattern.to_string()).or_insert_with(|| {
+            Regex::new(pattern)
+                .inspect_err(|_| {
+                    eprintln!(
+                        "Invalid regex pattern in lint '{}': {pattern} (only text equal to it matches)",
+                        self.lint_name
+                    )
+                })
+                .ok()
+        });
+        match regex {
+            Some(regex) => regex.is_match(text),
+            None => text == pattern,
+        }
+    }
+}
@@ -4,10 +4,11 @@ use rustc_hir::OwnerId;
 use rustc_hir::def_id::DefId;
 use rustc_infer::infer::TyCtxtInferExt;
 use rustc_middle::ty::{self, ParamEnv, Ty, TyCtxt, TypingMode};
-use rustc_span::symbol::sym;
+use rustc_span::symbol::{kw, sym};
 use rustc_trait_selection::traits::query::evaluate_obligation::InferCtxtExt;
 use rustc_trait_selection::traits::{Obligation, ObligationCause};
 use rustc_type_ir::TypeVisitableExt;
+use std::collections::HashMap;
 
 ///
 /// Returns the name for a module. If the module is the root module, returns just the crate name.
@@ -28,6 +29,20 @@ pub fn get_full_module_name(tcx: &TyCtxt<'_>, module_def_id: &OwnerId) -> String
     }
 }
 
+/// Like [`get_full_module_name`], but for modules in any crate. Paths outside the local
+/// crate already start with their crate name.
+pub fn get_module_name_from_def_id(tcx: &TyCtxt<'_>, module_def_id: DefId) -> String {
+    match module_def_id.as_local() {
+        Some(local_def_id) => get_full_module_name(
+            tcx,
+            &OwnerId {
+                def_id: local_def_id,
+            },
+        ),
+        None => tcx.def_path_str(module_def_id),
+    }
+}
+
 pub fn implements_trait<'tcx>(
     tcx: TyCtxt<'tcx>,
     param_env: ParamEnv<'tcx>,
@@ -92,6 +107,61 @@ pub fn implements_error_trait<'tcx>(
     }
 }
 
+/// Like [`implements_error_trait`], remembering the answer in `cache` for error types
+/// without generic arguments, keyed on their def id. Whether such a type implements
+/// `Error` can't depend on the generics in scope, so the answer holds in every param
+/// environment. Other types, like `Wrapper<T>`, are checked afresh each time.
+pub fn implements_error_trait_cached<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    param_env: ParamEnv<'tcx>,
+    ty: Ty<'tcx>,
+    cache: &mut HashMap<DefId, bool>,
+) -> bool {
+    let cache_key = match ty.kind() {
+        ty::TyKind::Adt(adt_def, args) if args.is_empty() => Some(adt_def.did()),
+        _ => None,
+    };
+    let Some(def_id) = cache_key else {
+        return implements_error_trait(tcx, param_env, ty);
+    };
+    *cache
+        .entry(def_id)
+        .or_insert_with(|| implements_error_trait(tcx, param_env, ty))
+}
+
+/// Counts the generic parameters an item declares itself, as `generics_of` sees them, so
+/// parameters inherited from an enclosing impl or trait are left out, while the
+/// anonymous type parameters that `impl Trait` arguments desugar to are kept. Lifetimes are
+/// only counted when `count_lifetimes` is set, including the late-bound lifetimes a function
+/// names in its signature, but not elided ones.
+pub fn count_own_generics(tcx: TyCtxt<'_>, def_id: DefId, count_lifetimes: bool) -> usize {
+    let declared = tcx
+        .generics_of(def_id)
+        .own_params
+        .iter()
+        .filter(|param| match param.kind {
+            ty::GenericParamDefKind::Type { .. } | ty::GenericParamDefKind::Const { .. } => true,
+            ty::GenericParamDefKind::Lifetime => {
+                count_lifetimes && param.name != kw::UnderscoreLifetime
+            }
+        })
+        .count();
+
+    // Late-bound lifetimes live on the function's signature rather than its generics
+    let late_bound = if count_lifetimes && tcx.def_kind(def_id).is_fn_like() {
+        tcx.fn_sig(def_id)
+            .skip_binder()
+            .bound_vars()
+            .iter()
+            .filter(|var| matches!(var, ty::BoundVariableKind::Region(kind) if kind.is_named(tcx)))
+            .count()
+    } else {
+        0
+    };
+
+    declared + late_bound
+}
+
 /// Creates a canonical trait name from a potentially generic trait name.
 /// This removes any generic parameters (including lifetimes) from the trait name.
 ///
@@ -1,55 +1,37 @@
 // This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
 
 use crate::ArchitectureLintRule;
+use crate::lints::const_lint::ConstLint;
+use crate::lints::enum_lint::EnumLint;
 use crate::lints::function_lint::FunctionLint;
 use crate::lints::module_lint::ModuleLint;
 use crate::lints::struct_lint::StructLint;
+use crate::lints::trait_lint::TraitLint;
 use anyhow::Result;
 use cargo_pup_common::project_context::ProjectContext;
 use cargo_pup_lint_config::ConfiguredLint;
+use cargo_pup_lint_config::config_loader::{load_config, load_config_str};
 use cargo_pup_lint_config::lint_builder::LintBuilder;
-use ron;
 use std::path::Path;
 
 pub struct LintConfigurationFactory {}
 
 impl LintConfigurationFactory {
-    pub fn from_file(file: String) -> Result<Vec<Box<dyn ArchitectureLintRule + Send>>> {
+    /// Load the lints configured in `file`, which is either a path or the configuration
+    /// itself. When a `profile` is given, its lints run instead of the configuration's
+    /// top-level lints, and a profile the configuration doesn't define is an error.
+    pub fn from_file(
+        file: String,
+        profile: Option<&str>,
+    ) -> Result<Vec<Box<dyn ArchitectureLintRule + Send>>> {
         // Check if this is a file path or actual content
         let path = Path::new(&file);
-        if path.exists() {
-            // Use LintBuilder for file deserialization
-            let lint_builder = LintBuilder::read_from_file(file)
-                .map_err(|e| anyhow::anyhow!("Failed to read/parse lint file: {}", e))?;
-
-            // Convert to architecture lint rules
-            Self::from_lint_builder(lint_builder)
+        let lint_builder = if path.exists() {
+            load_config(path, profile)?
         } else {
-            // Try parsing as direct content
-            match ron::from_str::<LintBuilder>(&file) {
-                Ok(lint_builder) => Self::from_lint_builder(lint_builder),
-                Err(e) => {
-                    // Extract an error line preview
-                    let error_preview = match file
-                        .lines()
-                        .enumerate()
-                        .take(10)
-                        .map(|(i, line)| format!("{}: {}", i + 1, line))
-                        .collect::<Vec<_>>()
-                    {
-                        lines if !lines.is_empty() => {
-                            format!("\nFirst few lines of the content:\n{}", lines.join("\n"))
-                        }
-                        _ => String::new(),
-                    };
-                    Err(anyhow::anyhow!(
-                        "Failed to parse RON content: {}{}",
-                        e,
-                        error_preview
-                    ))
-                }
-            }
-        }
+            load_config_str(&file, profile)?
+        };
+        Self::from_lint_builder(lint_builder)
     }
 
     // Converts a LintBuilder to a collection of ArchitectureLintRules
@@ -63,6 +45,9 @@ impl LintConfigurationFactory {
                 ConfiguredLint::Module(_) => ModuleLint::new(l),
                 ConfiguredLint::Struct(_) => StructLint::new(l),
                 ConfiguredLint::Function(_) => FunctionLint::new(l),
+                ConfiguredLint::Trait(_) => TraitLint::new(l),
+                ConfiguredLint::Const(_) => ConstLint::new(l),
+                ConfiguredLint::Enum(_) => EnumLint::new(l),
             })
             .collect())
     }
@@ -71,3 +56,81 @@ impl LintConfigurationFactory {
         panic!("Not implemented!");
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_unset_variable_is_an_error() {
+        let content = r#"(
+    lints: [
+        Module((
+            name: "internal",
+            matches: Module("^${PUP_TEST_SURELY_UNSET_PREFIX}::internal$"),
+            rules: [MustNotBeEmpty(Warn)],
+        )),
+    ],
+)"#;
+
+        let error = LintConfigurationFactory::from_file(content.to_string(), None)
+            .err()
+            .expect("An unset variable should fail to load");
+        assert!(
+            error
+                .to_string()
+                .contains("unset environment variable(s): PUP_TEST_SURELY_UNSET_PREFIX"),
+            "Unexpected error: {error}"
+        );
+    }
+
+    fn module_lint(name: &str, rule: &str) -> String {
+        format!(r#"Module((name: "{name}", matches: Module(".*"), rules: [{rule}]))"#)
+    }
+
+    #[test]
+    fn test_include_cycle_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("a.ron"),
+            r#"(include: ["b.ron"], lints: [])"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("b.ron"),
+            r#"(include: ["a.ron"], lints: [])"#,
+        )
+        .unwrap();
+
+        let error = LintConfigurationFactory::from_file(
+            dir.path().join("a.ron").to_str().unwrap().to_string(),
+            None,
+        )
+        .err()
+        .expect("An include cycle should fail to load");
+        let message = error.to_string();
+        assert!(
+            message.contains("includes itself") && message.contains("b.ron"),
+            "Unexpected error: {message}"
+        );
+    }
+
+    #[test]
+    fn test_unknown_profile_is_an_error() {
+        let content = format!(
+            r#"(lints: [], profiles: {{"strict": [], "ci": [{}]}})"#,
+            module_lint("not_empty", "MustNotBeEmpty(Error)")
+        );
+
+        let error = LintConfigurationFactory::from_file(content, Some("nightly"))
+            .err()
+            .expect("An unknown profile should fail to load");
+        assert!(
+            error.to_string().contains(
+                "Profile 'nightly' not found in the configuration (available profiles: ci, strict)"
+            ),
+            "Unexpected error: {error}"
+        );
+    }
+}
@@ -0,0 +1,189 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+use crate::ArchitectureLintRule;
+use crate::ArchitectureLintRunner;
+use crate::declare_variable_severity_lint;
+use crate::helpers::lint_helpers::span_lint_and_help;
+use crate::helpers::patterns::Patterns;
+use crate::helpers::queries::get_full_module_name;
+use cargo_pup_lint_config::{ConfiguredLint, ConstMatch, ConstRule};
+use rustc_hir::{Item, ItemKind, Mutability};
+use rustc_lint::{LateContext, LateLintPass, LintStore};
+use rustc_middle::ty::print::with_no_trimmed_paths;
+use rustc_session::impl_lint_pass;
+
+pub struct ConstLint {
+    name: String,
+    matches: ConstMatch,
+    const_rules: Vec<ConstRule>,
+    // The patterns of the matcher and rules, compiled as they're first used
+    patterns: Patterns,
+}
+
+impl ConstLint {
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new(config: &ConfiguredLint) -> Box<dyn ArchitectureLintRule + Send> {
+        if let ConfiguredLint::Const(c) = config {
+            Box::new(Self {
+                name: c.name.clone(),
+                matches: c.matches.clone(),
+                const_rules: c.rules.to_vec(),
+                patterns: Patterns::new(c.name.clone()),
+            })
+        } else {
+            panic!("Expected a Const lint configuration")
+        }
+    }
+}
+
+// Evaluates the matcher against an item's name and the path of its module
+fn evaluate_const_match(
+    patterns: &Patterns,
+    matcher: &ConstMatch,
+    module_path: &str,
+    item_name: &str,
+) -> bool {
+    match matcher {
+        ConstMatch::Name(pattern) => patterns.is_match(pattern, item_name),
+        ConstMatch::InModule(pattern) => patterns.is_match(pattern, module_path),
+        ConstMatch::AndMatches(left, right) => {
+            evaluate_const_match(patterns, left, module_path, item_name)
+                && evaluate_const_match(patterns, right, module_path, item_name)
+        }
+        ConstMatch::OrMatches(left, right) => {
+            evaluate_const_match(patterns, left, module_path, item_name)
+                || evaluate_const_match(patterns, right, module_path, item_name)
+        }
+        ConstMatch::NotMatch(inner) => {
+            !evaluate_const_match(patterns, inner, module_path, item_name)
+        }
+    }
+}
+
+declare_variable_severity_lint!(
+    pub,
+    CONST_LINT_NAMING_PATTERN,
+    CONST_LINT_NAMING_PATTERN_DENY,
+    CONST_LINT_NAMING_PATTERN_WARN,
+    "Constant names must match a pattern"
+);
+
+declare_variable_severity_lint!(
+    pub,
+    CONST_LINT_FORBID_TYPE,
+    CONST_LINT_FORBID_TYPE_DENY,
+    CONST_LINT_FORBID_TYPE_WARN,
+    "Constant has a forbidden type"
+);
+
+declare_variable_severity_lint!(
+    pub,
+    CONST_LINT_FORBID_STATIC_MUT,
+    CONST_LINT_FORBID_STATIC_MUT_DENY,
+    CONST_LINT_FORBID_STATIC_MUT_WARN,
+    "Mutable statics are forbidden"
+);
+
+impl_lint_pass!(ConstLint => [
+    CONST_LINT_NAMING_PATTERN_DENY,
+    CONST_LINT_NAMING_PATTERN_WARN,
+    CONST_LINT_FORBID_TYPE_DENY,
+    CONST_LINT_FORBID_TYPE_WARN,
+    CONST_LINT_FORBID_STATIC_MUT_DENY,
+    CONST_LINT_FORBID_STATIC_MUT_WARN
+]);
+
+impl ArchitectureLintRule for ConstLint {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn applies_to_module(&self, _namespace: &str) -> bool {
+        false
+    }
+
+    fn register_late_pass(&self, lint_store: &mut LintStore) {
+        let name = self.name.clone();
+        let matches = self.matches.clone();
+        let const_rules = self.const_rules.clone();
+
+        lint_store.register_late_pass(move |_| {
+            Box::new(ConstLint {
+                name: name.clone(),
+                matches: matches.clone(),
+                const_rules: const_rules.clone(),
+                patterns: Patterns::new(name.clone()),
+            })
+        });
+    }
+}
+
+impl<'tcx> LateLintPass<'tcx> for ConstLint {
+    fn check_item(&mut self, ctx: &LateContext<'tcx>, item: &'tcx Item<'tcx>) {
+        let _timer = ArchitectureLintRunner::time_lint(&self.name);
+        let (kind, ident, ty, mutability) = match item.kind {
+            ItemKind::Const(ident, _, ty, _) => ("Constant", ident, ty, Mutability::Not),
+            ItemKind::Static(mutability, ident, ty, _) => ("Static", ident, ty, mutability),
+            _ => return,
+        };
+        // Macros, derives among them, often expand to `const _` items that nobody wrote
+        if item.span.from_expansion() || ident.name.as_str() == "_" {
+            return;
+        }
+
+        let item_name = ident.to_string();
+        let parent_item = ctx.tcx.hir_get_parent_item(item.hir_id());
+        let module_path = get_full_module_name(&ctx.tcx, &parent_item);
+        if !evaluate_const_match(&self.patterns, &self.matches, &module_path, &item_name) {
+            return;
+        }
+
+        for rule in &self.const_rules {
+            match rule {
+                ConstRule::NamingPattern(pattern, severity) => {
+                    if !self.patterns.is_match(pattern, &item_name) {
+                        span_lint_and_help(
+                            ctx,
+                            CONST_LINT_NAMING_PATTERN::get_by_severity(*severity),
+                            self.name().as_str(),
+                            ident.span,
+                            format!(
+                                "{kind} '{item_name}' doesn't match the naming pattern '{pattern}'"
+                            ),
+                            None,
+                            "Rename it to match the pattern",
+                        );
+                    }
+                }
+                ConstRule::ForbidType(pattern, severity) => {
+                    let item_ty = ctx.tcx.type_of(item.owner_id).instantiate_identity();
+                    let rendered = with_no_trimmed_paths!(item_ty.to_string());
+                    if self.patterns.is_match(pattern, &rendered) {
+                        span_lint_and_help(
+                            ctx,
+                            CONST_LINT_FORBID_TYPE::get_by_severity(*severity),
+                            self.name().as_str(),
+                            ty.span,
+                            format!("{kind} '{item_name}' has forbidden type '{rendered}'"),
+                            None,
+                            format!("'{rendered}' matches the forbidden type pattern '{pattern}'"),
+                        );
+                    }
+                }
+                ConstRule::ForbidStaticMut(severity) => {
+                    if mutability == Mutability::Mut {
+                        span_lint_and_help(
+                            ctx,
+                            CONST_LINT_FORBID_STATIC_MUT::get_by_severity(*severity),
+                            self.name().as_str(),
+                            item.span.with_hi(ident.span.hi()),
+                            format!("Static '{item_name}' is declared `static mut`"),
+                            None,
+                            "Use an atomic, a Mutex or a OnceLock instead, so the static can be shared without `unsafe`",
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
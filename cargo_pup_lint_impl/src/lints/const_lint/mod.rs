@@ -0,0 +1,5 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+mod lint;
+
+pub use lint::ConstLint;
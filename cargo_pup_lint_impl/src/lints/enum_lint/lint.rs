@@ -0,0 +1,157 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+use crate::ArchitectureLintRule;
+use crate::ArchitectureLintRunner;
+use crate::declare_variable_severity_lint;
+use crate::helpers::lint_helpers::span_lint_and_help;
+use crate::helpers::patterns::Patterns;
+use crate::helpers::queries::get_full_module_name;
+use cargo_pup_lint_config::{ConfiguredLint, EnumMatch, EnumRule};
+use rustc_hir::{Item, ItemKind, VariantData};
+use rustc_lint::{LateContext, LateLintPass, LintStore};
+use rustc_session::impl_lint_pass;
+
+pub struct EnumLint {
+    name: String,
+    matches: EnumMatch,
+    enum_rules: Vec<EnumRule>,
+    // The patterns of the matcher, compiled as they're first used
+    patterns: Patterns,
+}
+
+impl EnumLint {
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new(config: &ConfiguredLint) -> Box<dyn ArchitectureLintRule + Send> {
+        if let ConfiguredLint::Enum(e) = config {
+            Box::new(Self {
+                name: e.name.clone(),
+                matches: e.matches.clone(),
+                enum_rules: e.rules.to_vec(),
+                patterns: Patterns::new(e.name.clone()),
+            })
+        } else {
+            panic!("Expected an Enum lint configuration")
+        }
+    }
+}
+
+// Evaluates the matcher against an enum's name and the path of its module
+fn evaluate_enum_match(
+    patterns: &Patterns,
+    matcher: &EnumMatch,
+    module_path: &str,
+    enum_name: &str,
+) -> bool {
+    match matcher {
+        EnumMatch::Name(pattern) => patterns.is_match(pattern, enum_name),
+        EnumMatch::InModule(pattern) => patterns.is_match(pattern, module_path),
+        EnumMatch::AndMatches(left, right) => {
+            evaluate_enum_match(patterns, left, module_path, enum_name)
+                && evaluate_enum_match(patterns, right, module_path, enum_name)
+        }
+        EnumMatch::OrMatches(left, right) => {
+            evaluate_enum_match(patterns, left, module_path, enum_name)
+                || evaluate_enum_match(patterns, right, module_path, enum_name)
+        }
+        EnumMatch::NotMatch(inner) => !evaluate_enum_match(patterns, inner, module_path, enum_name),
+    }
+}
+
+declare_variable_severity_lint!(
+    pub,
+    ENUM_LINT_REQUIRE_EXPLICIT_DISCRIMINANTS,
+    ENUM_LINT_REQUIRE_EXPLICIT_DISCRIMINANTS_DENY,
+    ENUM_LINT_REQUIRE_EXPLICIT_DISCRIMINANTS_WARN,
+    "Enum variants must have explicit discriminants"
+);
+
+impl_lint_pass!(EnumLint => [
+    ENUM_LINT_REQUIRE_EXPLICIT_DISCRIMINANTS_DENY,
+    ENUM_LINT_REQUIRE_EXPLICIT_DISCRIMINANTS_WARN
+]);
+
+impl ArchitectureLintRule for EnumLint {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn applies_to_module(&self, _namespace: &str) -> bool {
+        false
+    }
+
+    fn register_late_pass(&self, lint_store: &mut LintStore) {
+        let name = self.name.clone();
+        let matches = self.matches.clone();
+        let enum_rules = self.enum_rules.clone();
+
+        lint_store.register_late_pass(move |_| {
+            Box::new(EnumLint {
+                name: name.clone(),
+                matches: matches.clone(),
+                enum_rules: enum_rules.clone(),
+                patterns: Patterns::new(name.clone()),
+            })
+        });
+    }
+}
+
+impl<'tcx> LateLintPass<'tcx> for EnumLint {
+    fn check_item(&mut self, ctx: &LateContext<'tcx>, item: &'tcx Item<'tcx>) {
+        let _timer = ArchitectureLintRunner::time_lint(&self.name);
+        let ItemKind::Enum(ident, _, enum_def) = item.kind else {
+            return;
+        };
+        if item.span.from_expansion() {
+            return;
+        }
+
+        let enum_name = ident.to_string();
+        let parent_item = ctx.tcx.hir_get_parent_item(item.hir_id());
+        let module_path = get_full_module_name(&ctx.tcx, &parent_item);
+        if !evaluate_enum_match(&self.patterns, &self.matches, &module_path, &enum_name) {
+            return;
+        }
+
+        for rule in &self.enum_rules {
+            match rule {
+                EnumRule::RequireExplicitDiscriminants(severity) => {
+                    // Only C-style enums have discriminants that mean anything outside Rust
+                    let is_c_style = enum_def
+                        .variants
+                        .iter()
+                        .all(|variant| matches!(variant.data, VariantData::Unit(..)));
+                    if !is_c_style {
+                        continue;
+                    }
+                    // The values the variants have now, in declaration order
+                    let discriminants = ctx
+                        .tcx
+                        .adt_def(item.owner_id)
+                        .discriminants(ctx.tcx)
+                        .map(|(_, discr)| discr);
+                    for (variant, discr) in enum_def.variants.iter().zip(discriminants) {
+                        if variant.disr_expr.is_none() {
+                            span_lint_and_help(
+                                ctx,
+                                ENUM_LINT_REQUIRE_EXPLICIT_DISCRIMINANTS::get_by_severity(
+                                    *severity,
+                                ),
+                                self.name().as_str(),
+                                variant.span,
+                                format!(
+                                    "Variant '{enum_name}::{}' has no explicit discriminant",
+                                    variant.ident
+                                ),
+                                None,
+                                format!(
+                                    "Spell out the value it has now, `{} = {discr}`, so it can't change when variants are reordered",
+                                    variant.ident
+                                ),
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,16 +1,30 @@
 // This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
 
 use crate::ArchitectureLintRule;
+use crate::ArchitectureLintRunner;
 use crate::declare_variable_severity_lint;
 use crate::helpers::lint_helpers::span_lint_and_help;
-use crate::helpers::queries::{get_full_module_name, implements_error_trait};
+use crate::helpers::patterns::Patterns;
+use crate::helpers::queries::{
+    count_own_generics, get_full_module_name, implements_error_trait_cached,
+};
 use cargo_pup_lint_config::{ConfiguredLint, FunctionMatch, FunctionRule, ReturnTypePattern};
 use regex::Regex;
-use rustc_hir::{ImplItem, ImplItemKind, Item, ItemKind, def_id::LOCAL_CRATE};
+use rustc_ast::LitKind;
+use rustc_hir::def::{DefKind, Res};
+use rustc_hir::intravisit::{self, Visitor};
+use rustc_hir::{
+    Arm, BinOpKind, Block, BlockCheckMode, BodyId, Expr, ExprKind, FnRetTy, FnSig, ImplItem,
+    ImplItemKind, Item, ItemKind, LetStmt, MatchSource, Mutability, Pat, PatKind, StmtKind,
+    UnsafeSource, def_id::DefId,
+};
 use rustc_lint::{LateContext, LateLintPass, LintStore};
-use rustc_middle::ty::TyKind;
+use rustc_middle::hir::nested_filter;
+use rustc_middle::ty::print::with_no_trimmed_paths;
+use rustc_middle::ty::{AliasTyKind, TyCtxt, TyKind, TypeckResults};
 use rustc_session::impl_lint_pass;
-use rustc_span::BytePos;
+use rustc_span::symbol::kw;
+use rustc_span::{BytePos, DesugaringKind, Span};
 use std::collections::{HashMap, HashSet};
 use std::sync::Mutex;
 
@@ -33,6 +47,11 @@ pub struct FunctionLint {
     function_rules: Vec<FunctionRule>,
     // Cache for allocation detection to avoid re-analyzing the same functions
     allocation_cache: Mutex<HashMap<rustc_hir::def_id::DefId, bool>>,
+    // Cache of whether non-generic error types implement `Error`, keyed on the type's
+    // def id, as many functions tend to share an error type
+    error_trait_cache: Mutex<HashMap<rustc_hir::def_id::DefId, bool>>,
+    // The patterns of the matchers and rules, compiled as they're first used
+    patterns: Patterns,
 }
 
 impl FunctionLint {
@@ -44,6 +63,8 @@ impl FunctionLint {
                 matches: f.matches.clone(),
                 function_rules: f.rules.clone(),
                 allocation_cache: Mutex::new(HashMap::new()),
+                error_trait_cache: Mutex::new(HashMap::new()),
+                patterns: Patterns::new(f.name.clone()),
             })
         } else {
             panic!("Expected a Function lint configuration")
@@ -58,7 +79,14 @@ impl FunctionLint {
         function_name: &str,
         fn_def_id: rustc_hir::def_id::DefId,
     ) -> bool {
-        evaluate_function_match(&self.matches, ctx, module_path, function_name, fn_def_id)
+        evaluate_function_match(
+            &self.matches,
+            ctx,
+            module_path,
+            function_name,
+            fn_def_id,
+            &self.error_trait_cache,
+        )
     }
 
     /// Helper method to check a single panic category and emit a lint if found
@@ -88,6 +116,927 @@ impl FunctionLint {
             }
         }
     }
+
+    /// Helper method to count the function's own generic parameters and emit a lint if
+    /// there are too many, counted the same way as for structs
+    fn check_max_generics(
+        &self,
+        ctx: &LateContext<'_>,
+        fn_def_id: rustc_hir::def_id::DefId,
+        max: usize,
+        count_lifetimes: bool,
+        severity: cargo_pup_lint_config::Severity,
+        sig_span: rustc_span::Span,
+    ) {
+        let count = count_own_generics(ctx.tcx, fn_def_id, count_lifetimes);
+        if count > max {
+            let counted = if count_lifetimes {
+                "generic parameters"
+            } else {
+                "generic type and const parameters"
+            };
+            span_lint_and_help(
+                ctx,
+                FUNCTION_LINT::get_by_severity(severity),
+                self.name().as_str(),
+                sig_span,
+                format!("Function has {count} {counted}, exceeding the maximum of {max}"),
+                None,
+                "Consider reducing the number of generic parameters, e.g. by using trait objects or concrete types",
+            );
+        }
+    }
+
+    /// Helper method to check that a public function has a doc comment, covering
+    /// both `///` comments and `#[doc = "..."]` attributes
+    fn check_doc_comment(
+        &self,
+        ctx: &LateContext<'_>,
+        hir_id: rustc_hir::HirId,
+        fn_def_id: rustc_hir::def_id::DefId,
+        severity: cargo_pup_lint_config::Severity,
+        sig_span: rustc_span::Span,
+    ) {
+        if !ctx.tcx.visibility(fn_def_id).is_public() {
+            return;
+        }
+
+        let has_doc = ctx
+            .tcx
+            .hir_attrs(hir_id)
+            .iter()
+            .filter_map(|attr| attr.doc_str())
+            .any(|doc| !doc.as_str().trim().is_empty());
+
+        if !has_doc {
+            span_lint_and_help(
+                ctx,
+                FUNCTION_LINT::get_by_severity(severity),
+                self.name().as_str(),
+                sig_span,
+                "Public function is missing a doc comment",
+                None,
+                "Add a /// doc comment describing what this function does",
+            );
+        }
+    }
+
+    /// Helper method to report a function declared `unsafe fn`, and each unsafe block
+    /// written in its body. Unsafe blocks generated by macros are not reported.
+    fn check_unsafe(
+        &self,
+        ctx: &LateContext<'_>,
+        item_name: &str,
+        sig: &FnSig<'_>,
+        body_id: BodyId,
+        severity: cargo_pup_lint_config::Severity,
+    ) {
+        struct UnsafeBlockVisitor {
+            spans: Vec<rustc_span::Span>,
+        }
+
+        impl<'tcx> Visitor<'tcx> for UnsafeBlockVisitor {
+            fn visit_expr(&mut self, expr: &'tcx Expr<'tcx>) {
+                if let ExprKind::Block(block, _) = expr.kind
+                    && block.rules == BlockCheckMode::UnsafeBlock(UnsafeSource::UserProvided)
+                {
+                    self.spans.push(block.span);
+                }
+                intravisit::walk_expr(self, expr);
+            }
+        }
+
+        if sig.header.is_unsafe() {
+            span_lint_and_help(
+                ctx,
+                FUNCTION_LINT::get_by_severity(severity),
+                self.name().as_str(),
+                sig.span,
+                format!("Function '{item_name}' is declared unsafe"),
+                None,
+                "Provide a safe interface, or move this function to a module where unsafe is allowed",
+            );
+        }
+
+        let mut visitor = UnsafeBlockVisitor { spans: Vec::new() };
+        visitor.visit_body(ctx.tcx.hir_body(body_id));
+        for span in visitor.spans {
+            span_lint_and_help(
+                ctx,
+                FUNCTION_LINT::get_by_severity(severity),
+                self.name().as_str(),
+                span,
+                format!("Function '{item_name}' contains an unsafe block"),
+                None,
+                "Remove the unsafe block, or move this code to a module where unsafe is allowed",
+            );
+        }
+    }
+
+    /// Helper method to check the cyclomatic complexity of a function body against
+    /// the configured maximum. The counting rules are documented on
+    /// `FunctionRule::MaxComplexity`.
+    fn check_complexity(
+        &self,
+        ctx: &LateContext<'_>,
+        body_id: BodyId,
+        max: usize,
+        severity: cargo_pup_lint_config::Severity,
+        sig_span: rustc_span::Span,
+    ) {
+        let complexity = cyclomatic_complexity(ctx.tcx, body_id);
+        if complexity > max {
+            span_lint_and_help(
+                ctx,
+                FUNCTION_LINT::get_by_severity(severity),
+                self.name().as_str(),
+                sig_span,
+                format!(
+                    "Function has a cyclomatic complexity of {complexity}, exceeding the maximum of {max}"
+                ),
+                None,
+                "Split the function into smaller functions, or simplify its branching",
+            );
+        }
+    }
+
+    /// Helper method to check the number of statements in a function body against the
+    /// configured maximum. The counting rules are documented on `FunctionRule::MaxStatements`.
+    fn check_statements(
+        &self,
+        ctx: &LateContext<'_>,
+        body_id: BodyId,
+        max: usize,
+        severity: cargo_pup_lint_config::Severity,
+        sig_span: rustc_span::Span,
+    ) {
+        let count = statement_count(ctx.tcx, body_id);
+        if count > max {
+            span_lint_and_help(
+                ctx,
+                FUNCTION_LINT::get_by_severity(severity),
+                self.name().as_str(),
+                sig_span,
+                format!("Function has {count} statements, exceeding the maximum of {max}"),
+                None,
+                "Split the function into smaller functions",
+            );
+        }
+    }
+
+    /// Helper method to check the number of local variables bound in a function body
+    /// against the configured maximum, as documented on `FunctionRule::MaxLocals`
+    fn check_locals(
+        &self,
+        ctx: &LateContext<'_>,
+        body_id: BodyId,
+        max: usize,
+        count_match_bindings: bool,
+        severity: cargo_pup_lint_config::Severity,
+        sig_span: rustc_span::Span,
+    ) {
+        let count = local_count(ctx.tcx, body_id, count_match_bindings);
+        if count > max {
+            span_lint_and_help(
+                ctx,
+                FUNCTION_LINT::get_by_severity(severity),
+                self.name().as_str(),
+                sig_span,
+                format!("Function has {count} local variables, exceeding the maximum of {max}"),
+                None,
+                "Split the function into smaller functions, or inline values that are used once",
+            );
+        }
+    }
+
+    /// Helper method to count the parameters taken by value, rather than by reference,
+    /// and emit a lint on the signature if there are too many
+    fn check_by_value_parameters(
+        &self,
+        ctx: &LateContext<'_>,
+        item_name: &str,
+        fn_def_id: rustc_hir::def_id::DefId,
+        sig: &FnSig<'_>,
+        max: usize,
+        severity: cargo_pup_lint_config::Severity,
+    ) {
+        let fn_sig = ctx
+            .tcx
+            .fn_sig(fn_def_id)
+            .instantiate_identity()
+            .skip_binder();
+        let count = fn_sig
+            .inputs()
+            .iter()
+            .filter(|ty| !matches!(ty.kind(), TyKind::Ref(..)))
+            .count();
+
+        if count > max {
+            span_lint_and_help(
+                ctx,
+                FUNCTION_LINT::get_by_severity(severity),
+                self.name().as_str(),
+                sig.span,
+                format!(
+                    "Function '{item_name}' takes {count} parameters by value, exceeding the maximum of {max}"
+                ),
+                None,
+                "Consider passing large parameters by reference instead",
+            );
+        }
+    }
+
+    /// Helper method to report a function whose return type doesn't match the pattern
+    fn check_return_type(
+        &self,
+        ctx: &LateContext<'_>,
+        item_name: &str,
+        fn_def_id: rustc_hir::def_id::DefId,
+        sig: &FnSig<'_>,
+        pattern: &ReturnTypePattern,
+        severity: cargo_pup_lint_config::Severity,
+    ) {
+        if return_type_matches(pattern, ctx, fn_def_id, &self.error_trait_cache) {
+            return;
+        }
+
+        let return_ty = ctx.tcx.instantiate_bound_regions_with_erased(
+            ctx.tcx.fn_sig(fn_def_id).instantiate_identity(),
+        );
+        let expected = describe_return_type(pattern);
+        // Without a `->` there's no return type to point at, so point at the signature
+        let span = match sig.decl.output {
+            FnRetTy::Return(hir_ty) => hir_ty.span,
+            FnRetTy::DefaultReturn(_) => sig.span,
+        };
+        span_lint_and_help(
+            ctx,
+            FUNCTION_LINT::get_by_severity(severity),
+            self.name().as_str(),
+            span,
+            format!(
+                "Function '{item_name}' returns '{}', but must return {expected}",
+                return_ty.output()
+            ),
+            None,
+            format!("Change the return type to {expected}"),
+        );
+    }
+
+    /// Helper method to report each parameter type, and the return type, whose rendered
+    /// form matches one of the forbidden type patterns
+    fn check_signature_types(
+        &self,
+        ctx: &LateContext<'_>,
+        item_name: &str,
+        fn_def_id: rustc_hir::def_id::DefId,
+        sig: &FnSig<'_>,
+        type_patterns: &[String],
+        severity: cargo_pup_lint_config::Severity,
+    ) {
+        let fn_sig = ctx.tcx.instantiate_bound_regions_with_erased(
+            ctx.tcx.fn_sig(fn_def_id).instantiate_identity(),
+        );
+        let forbidden_pattern = |ty: rustc_middle::ty::Ty<'_>| {
+            let rendered = with_no_trimmed_paths!(ty.to_string());
+            type_patterns
+                .iter()
+                .find(|pattern| self.patterns.is_match(pattern, &rendered))
+                .map(|pattern| (rendered, pattern))
+        };
+
+        let positions = fn_sig
+            .inputs()
+            .iter()
+            .zip(sig.decl.inputs)
+            .enumerate()
+            .map(|(index, (ty, hir_ty))| (*ty, hir_ty.span, format!("parameter {}", index + 1)))
+            .chain(std::iter::once((
+                fn_sig.output(),
+                sig.decl.output.span(),
+                "return type".to_string(),
+            )));
+
+        for (ty, span, position) in positions {
+            if let Some((rendered, pattern)) = forbidden_pattern(ty) {
+                span_lint_and_help(
+                    ctx,
+                    FUNCTION_LINT::get_by_severity(severity),
+                    self.name().as_str(),
+                    span,
+                    format!(
+                        "Function '{item_name}' uses forbidden type '{rendered}' in its {position}"
+                    ),
+                    None,
+                    format!("'{rendered}' matches the forbidden type pattern '{pattern}'"),
+                );
+            }
+        }
+    }
+
+    /// Helper method to report each call in the function body to a function or method
+    /// whose path is forbidden
+    fn check_forbidden_calls(
+        &self,
+        ctx: &LateContext<'_>,
+        item_name: &str,
+        body_id: BodyId,
+        paths: &[String],
+        severity: cargo_pup_lint_config::Severity,
+    ) {
+        for (span, path) in called_paths(ctx.tcx, body_id) {
+            if let Some(pattern) = paths
+                .iter()
+                .find(|pattern| self.patterns.is_match(pattern, &path))
+            {
+                span_lint_and_help(
+                    ctx,
+                    FUNCTION_LINT::get_by_severity(severity),
+                    self.name().as_str(),
+                    span,
+                    format!("Function '{item_name}' calls forbidden function '{path}'"),
+                    None,
+                    format!("'{path}' is forbidden by the pattern '{pattern}'"),
+                );
+            }
+        }
+    }
+
+    /// Helper method to report each read or write of a `static mut` in the function body
+    fn check_static_mut_access(
+        &self,
+        ctx: &LateContext<'_>,
+        item_name: &str,
+        body_id: BodyId,
+        severity: cargo_pup_lint_config::Severity,
+    ) {
+        for (span, def_id) in static_mut_accesses(ctx.tcx, body_id) {
+            let path = with_no_trimmed_paths!(ctx.tcx.def_path_str(def_id));
+            span_lint_and_help(
+                ctx,
+                FUNCTION_LINT::get_by_severity(severity),
+                self.name().as_str(),
+                span,
+                format!("Function '{item_name}' accesses static mut '{path}'"),
+                None,
+                "Replace the static mut with an atomic, a lock or a thread-local",
+            );
+        }
+    }
+
+    /// Helper method to report each `?` in the function body that turns another error
+    /// into an `anyhow::Error` without adding context first
+    fn check_error_context(
+        &self,
+        ctx: &LateContext<'_>,
+        item_name: &str,
+        body_id: BodyId,
+        severity: cargo_pup_lint_config::Severity,
+    ) {
+        for (span, error_ty) in contextless_question_marks(ctx.tcx, body_id) {
+            span_lint_and_help(
+                ctx,
+                FUNCTION_LINT::get_by_severity(severity),
+                self.name().as_str(),
+                span,
+                format!(
+                    "Function '{item_name}' propagates '{error_ty}' with `?` without adding context"
+                ),
+                None,
+                "Call `.context(...)` or `.with_context(|| ...)` before the `?` to say what was being done",
+            );
+        }
+    }
+
+    /// Helper method to report each indexing expression in the function body
+    fn check_indexing(
+        &self,
+        ctx: &LateContext<'_>,
+        item_name: &str,
+        body_id: BodyId,
+        allow_constant_indices: bool,
+        severity: cargo_pup_lint_config::Severity,
+    ) {
+        for span in indexing_expressions(ctx.tcx, body_id, allow_constant_indices) {
+            span_lint_and_help(
+                ctx,
+                FUNCTION_LINT::get_by_severity(severity),
+                self.name().as_str(),
+                span,
+                format!("Function '{item_name}' uses indexing, which panics when out of bounds"),
+                None,
+                "Use `.get()` and handle the `None` case instead",
+            );
+        }
+    }
+
+    /// Helper method to report each parameter whose name doesn't match the pattern.
+    /// `self` and parameters bound by a pattern other than a plain name are skipped.
+    fn check_parameter_names(
+        &self,
+        ctx: &LateContext<'_>,
+        item_name: &str,
+        body_id: BodyId,
+        pattern: &str,
+        severity: cargo_pup_lint_config::Severity,
+    ) {
+        for param in ctx.tcx.hir_body(body_id).params {
+            let PatKind::Binding(_, _, ident, None) = param.pat.kind else {
+                continue;
+            };
+            if ident.name == kw::SelfLower {
+                continue;
+            }
+            let param_name = ident.as_str();
+            if !self.patterns.is_match(pattern, param_name) {
+                span_lint_and_help(
+                    ctx,
+                    FUNCTION_LINT::get_by_severity(severity),
+                    self.name().as_str(),
+                    ident.span,
+                    format!(
+                        "Parameter '{param_name}' of function '{item_name}' doesn't match the naming pattern '{pattern}'"
+                    ),
+                    None,
+                    "Rename the parameter to match the pattern",
+                );
+            }
+        }
+    }
+
+    /// Helper method to report each call in the function body to the function itself
+    fn check_recursion(
+        &self,
+        ctx: &LateContext<'_>,
+        item_name: &str,
+        fn_def_id: rustc_hir::def_id::DefId,
+        body_id: BodyId,
+        severity: cargo_pup_lint_config::Severity,
+    ) {
+        for (span, _) in called_functions(ctx.tcx, body_id)
+            .into_iter()
+            .filter(|(_, callee)| *callee == fn_def_id)
+        {
+            span_lint_and_help(
+                ctx,
+                FUNCTION_LINT::get_by_severity(severity),
+                self.name().as_str(),
+                span,
+                format!("Function '{item_name}' calls itself recursively"),
+                None,
+                "Rewrite the recursion as a loop, so the stack depth stays bounded",
+            );
+        }
+    }
+
+    /// Helper method to report a function returning a `Result` whose error type isn't an
+    /// enum, unless it's a boxed or `anyhow` error that the rule allows
+    fn check_result_error_is_enum(
+        &self,
+        ctx: &LateContext<'_>,
+        item_name: &str,
+        fn_def_id: rustc_hir::def_id::DefId,
+        (allow_boxed, allow_anyhow): (bool, bool),
+        severity: cargo_pup_lint_config::Severity,
+        sig_span: rustc_span::Span,
+    ) {
+        let Some(error_ty) = result_error_type(ctx.tcx, fn_def_id) else {
+            return;
+        };
+        let allowed = match error_ty.kind() {
+            TyKind::Adt(adt_def, _) if adt_def.is_enum() => true,
+            TyKind::Adt(adt_def, _) if adt_def.is_box() => allow_boxed,
+            TyKind::Adt(adt_def, _) => {
+                allow_anyhow
+                    && with_no_trimmed_paths!(ctx.tcx.def_path_str(adt_def.did()))
+                        == "anyhow::Error"
+            }
+            _ => false,
+        };
+
+        if !allowed {
+            span_lint_and_help(
+                ctx,
+                FUNCTION_LINT::get_by_severity(severity),
+                self.name().as_str(),
+                sig_span,
+                format!(
+                    "Function '{item_name}' returns error type '{error_ty}', which is not an enum"
+                ),
+                None,
+                "Return an enum with a variant for each way this function can fail",
+            );
+        }
+    }
+
+    /// Helper method to report a function that isn't declared `async fn`, using the
+    /// same detection as `FunctionMatch::IsAsync`
+    fn check_must_be_async(
+        &self,
+        ctx: &LateContext<'_>,
+        module_path: &str,
+        item_name: &str,
+        fn_def_id: rustc_hir::def_id::DefId,
+        severity: cargo_pup_lint_config::Severity,
+        sig_span: rustc_span::Span,
+    ) {
+        if !evaluate_function_match(
+            &FunctionMatch::IsAsync,
+            ctx,
+            module_path,
+            item_name,
+            fn_def_id,
+            &self.error_trait_cache,
+        ) {
+            span_lint_and_help(
+                ctx,
+                FUNCTION_LINT::get_by_severity(severity),
+                self.name().as_str(),
+                sig_span,
+                format!("Function '{item_name}' must be async"),
+                None,
+                "Declare this function as `async fn`",
+            );
+        }
+    }
+}
+
+// The error type of the `Result` a function returns, if it returns one
+fn result_error_type<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    fn_def_id: rustc_hir::def_id::DefId,
+) -> Option<rustc_middle::ty::Ty<'tcx>> {
+    result_error_of(
+        tcx,
+        tcx.fn_sig(fn_def_id).skip_binder().output().skip_binder(),
+    )
+}
+
+// The error type of a `Result` type, or None for any other type
+fn result_error_of<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    ty: rustc_middle::ty::Ty<'tcx>,
+) -> Option<rustc_middle::ty::Ty<'tcx>> {
+    if let TyKind::Adt(adt_def, substs) = ty.kind()
+        && tcx.def_path_str(adt_def.did()).contains("result::Result")
+        && substs.len() >= 2
+    {
+        Some(substs[1].expect_ty())
+    } else {
+        None
+    }
+}
+
+fn is_anyhow_error(tcx: TyCtxt<'_>, ty: rustc_middle::ty::Ty<'_>) -> bool {
+    matches!(ty.kind(), TyKind::Adt(adt_def, _)
+        if with_no_trimmed_paths!(tcx.def_path_str(adt_def.did())) == "anyhow::Error")
+}
+
+// Finds the `?` operators in a body, including in closures, that convert an error
+// other than `anyhow::Error` into one, along with that error type. Operands that are
+// `.context(...)` or `.with_context(...)` calls are skipped, as are `?` operators
+// produced by macro expansion.
+fn contextless_question_marks<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    body_id: BodyId,
+) -> Vec<(rustc_span::Span, rustc_middle::ty::Ty<'tcx>)> {
+    struct QuestionMarkVisitor<'tcx> {
+        tcx: TyCtxt<'tcx>,
+        typeck_results: &'tcx TypeckResults<'tcx>,
+        found: Vec<(rustc_span::Span, rustc_middle::ty::Ty<'tcx>)>,
+    }
+
+    impl<'tcx> Visitor<'tcx> for QuestionMarkVisitor<'tcx> {
+        // Closures and async blocks return through their own `?` operators
+        type NestedFilter = nested_filter::OnlyBodies;
+
+        fn maybe_tcx(&mut self) -> Self::MaybeTyCtxt {
+            self.tcx
+        }
+
+        fn visit_expr(&mut self, expr: &'tcx Expr<'tcx>) {
+            // `x?` is lowered to `match Try::branch(x) { Break(r) => return
+            // FromResidual::from_residual(r), Continue(v) => v }`, and what the
+            // residual is converted into is the type of the returned expression
+            if let ExprKind::Match(scrutinee, [break_arm, _], MatchSource::TryDesugar(_)) =
+                expr.kind
+                && let ExprKind::Call(_, [operand]) = scrutinee.kind
+                && !operand.span.from_expansion()
+                && let ExprKind::Ret(Some(converted)) | ExprKind::Break(_, Some(converted)) =
+                    break_arm.body.kind
+                && result_error_of(self.tcx, self.typeck_results.expr_ty(converted))
+                    .is_some_and(|target| is_anyhow_error(self.tcx, target))
+                && !matches!(operand.kind, ExprKind::MethodCall(segment, ..)
+                    if matches!(segment.ident.as_str(), "context" | "with_context"))
+                && let Some(error_ty) =
+                    result_error_of(self.tcx, self.typeck_results.expr_ty(operand))
+                && !is_anyhow_error(self.tcx, error_ty)
+            {
+                self.found.push((expr.span, error_ty));
+            }
+            intravisit::walk_expr(self, expr);
+        }
+    }
+
+    let mut visitor = QuestionMarkVisitor {
+        tcx,
+        typeck_results: tcx.typeck_body(body_id),
+        found: Vec::new(),
+    };
+    visitor.visit_body(tcx.hir_body(body_id));
+    visitor.found
+}
+
+// Finds the indexing expressions in a body, including in closures, other than those
+// produced by macro expansion. With `allow_constant_indices`, indexing by an integer
+// literal or a constant is left out.
+fn indexing_expressions(
+    tcx: TyCtxt<'_>,
+    body_id: BodyId,
+    allow_constant_indices: bool,
+) -> Vec<rustc_span::Span> {
+    struct IndexingVisitor<'tcx> {
+        tcx: TyCtxt<'tcx>,
+        typeck_results: &'tcx TypeckResults<'tcx>,
+        allow_constant_indices: bool,
+        found: Vec<rustc_span::Span>,
+    }
+
+    impl<'tcx> Visitor<'tcx> for IndexingVisitor<'tcx> {
+        // Closures index just as the function does
+        type NestedFilter = nested_filter::OnlyBodies;
+
+        fn maybe_tcx(&mut self) -> Self::MaybeTyCtxt {
+            self.tcx
+        }
+
+        fn visit_expr(&mut self, expr: &'tcx Expr<'tcx>) {
+            if let ExprKind::Index(_, index, _) = expr.kind
+                && !expr.span.from_expansion()
+                && !(self.allow_constant_indices && self.is_constant(index))
+            {
+                self.found.push(expr.span);
+            }
+            intravisit::walk_expr(self, expr);
+        }
+    }
+
+    impl IndexingVisitor<'_> {
+        fn is_constant(&self, index: &Expr<'_>) -> bool {
+            match index.kind {
+                ExprKind::Lit(lit) => matches!(lit.node, LitKind::Int(..)),
+                ExprKind::Path(ref qpath) => matches!(
+                    self.typeck_results.qpath_res(qpath, index.hir_id),
+                    Res::Def(DefKind::Const | DefKind::AssocConst, _)
+                ),
+                _ => false,
+            }
+        }
+    }
+
+    let mut visitor = IndexingVisitor {
+        tcx,
+        typeck_results: tcx.typeck_body(body_id),
+        allow_constant_indices,
+        found: Vec::new(),
+    };
+    visitor.visit_body(tcx.hir_body(body_id));
+    visitor.found
+}
+
+// Computes the cyclomatic complexity of a body by counting its decision points plus one
+fn cyclomatic_complexity(tcx: TyCtxt<'_>, body_id: BodyId) -> usize {
+    struct ComplexityVisitor<'tcx> {
+        tcx: TyCtxt<'tcx>,
+        decision_points: usize,
+    }
+
+    impl<'tcx> Visitor<'tcx> for ComplexityVisitor<'tcx> {
+        // Closure bodies are nested bodies, and count towards the enclosing function
+        type NestedFilter = nested_filter::OnlyBodies;
+
+        fn maybe_tcx(&mut self) -> Self::MaybeTyCtxt {
+            self.tcx
+        }
+
+        fn visit_expr(&mut self, expr: &'tcx Expr<'tcx>) {
+            // Skip branches generated by macros, but keep compiler desugarings such as `?`
+            let from_macro = expr.span.from_expansion() && expr.span.desugaring_kind().is_none();
+            if !from_macro {
+                self.decision_points += match expr.kind {
+                    // The `if` a `while` loop lowers to is counted through the loop itself
+                    ExprKind::If(..) if expr.span.is_desugaring(DesugaringKind::WhileLoop) => 0,
+                    ExprKind::If(..) => 1,
+                    ExprKind::Match(_, arms, MatchSource::Normal | MatchSource::Postfix) => {
+                        arms.len().saturating_sub(1)
+                    }
+                    ExprKind::Match(_, _, MatchSource::TryDesugar(_)) => 1,
+                    ExprKind::Loop(..) => 1,
+                    ExprKind::Binary(op, _, _)
+                        if matches!(op.node, BinOpKind::And | BinOpKind::Or) =>
+                    {
+                        1
+                    }
+                    _ => 0,
+                };
+            }
+            intravisit::walk_expr(self, expr);
+        }
+    }
+
+    let mut visitor = ComplexityVisitor {
+        tcx,
+        decision_points: 0,
+    };
+    visitor.visit_body(tcx.hir_body(body_id));
+    visitor.decision_points + 1
+}
+
+// Counts the statements in a body's blocks, including nested blocks and closures. A
+// block's trailing expression counts as a statement; nested items and code produced
+// by macro expansion or desugaring do not.
+fn statement_count(tcx: TyCtxt<'_>, body_id: BodyId) -> usize {
+    struct StatementVisitor<'tcx> {
+        tcx: TyCtxt<'tcx>,
+        statements: usize,
+    }
+
+    impl<'tcx> Visitor<'tcx> for StatementVisitor<'tcx> {
+        // Closure bodies are nested bodies, and count towards the enclosing function
+        type NestedFilter = nested_filter::OnlyBodies;
+
+        fn maybe_tcx(&mut self) -> Self::MaybeTyCtxt {
+            self.tcx
+        }
+
+        fn visit_block(&mut self, block: &'tcx Block<'tcx>) {
+            if !block.span.from_expansion() {
+                self.statements += block
+                    .stmts
+                    .iter()
+                    .filter(|stmt| !matches!(stmt.kind, StmtKind::Item(_)))
+                    .count();
+                self.statements += usize::from(block.expr.is_some());
+            }
+            intravisit::walk_block(self, block);
+        }
+    }
+
+    let mut visitor = StatementVisitor { tcx, statements: 0 };
+    visitor.visit_body(tcx.hir_body(body_id));
+    visitor.statements
+}
+
+// Counts the variables bound by `let` statements and `let` expressions in a body,
+// and by match arm patterns when `count_match_bindings` is set. Closures and nested
+// items are separate bodies and aren't visited; code produced by macro expansion or
+// desugaring, such as that of `for` loops and `?`, isn't counted.
+fn local_count(tcx: TyCtxt<'_>, body_id: BodyId, count_match_bindings: bool) -> usize {
+    struct LocalVisitor {
+        count_match_bindings: bool,
+        locals: usize,
+    }
+
+    impl LocalVisitor {
+        fn count_bindings(&mut self, pat: &Pat<'_>) {
+            pat.each_binding(|_, _, _, _| self.locals += 1);
+        }
+    }
+
+    impl<'tcx> Visitor<'tcx> for LocalVisitor {
+        fn visit_local(&mut self, local: &'tcx LetStmt<'tcx>) {
+            if !local.span.from_expansion() {
+                self.count_bindings(local.pat);
+            }
+            intravisit::walk_local(self, local);
+        }
+
+        fn visit_expr(&mut self, expr: &'tcx Expr<'tcx>) {
+            if let ExprKind::Let(let_expr) = expr.kind
+                && !let_expr.span.from_expansion()
+            {
+                self.count_bindings(let_expr.pat);
+            }
+            intravisit::walk_expr(self, expr);
+        }
+
+        fn visit_arm(&mut self, arm: &'tcx Arm<'tcx>) {
+            if self.count_match_bindings && !arm.span.from_expansion() {
+                self.count_bindings(arm.pat);
+            }
+            intravisit::walk_arm(self, arm);
+        }
+    }
+
+    let mut visitor = LocalVisitor {
+        count_match_bindings,
+        locals: 0,
+    };
+    visitor.visit_body(tcx.hir_body(body_id));
+    visitor.locals
+}
+
+// Finds the calls in a body, including in closures, along with the full path of the
+// function or method each one calls
+fn called_paths(tcx: TyCtxt<'_>, body_id: BodyId) -> Vec<(rustc_span::Span, String)> {
+    called_functions(tcx, body_id)
+        .into_iter()
+        .map(|(span, def_id)| (span, with_no_trimmed_paths!(tcx.def_path_str(def_id))))
+        .collect()
+}
+
+// Finds the calls in a body, including in closures, along with the function or method
+// each one resolves to. Calls through function pointers and closures don't resolve to
+// a function, and are skipped.
+fn called_functions(
+    tcx: TyCtxt<'_>,
+    body_id: BodyId,
+) -> Vec<(rustc_span::Span, rustc_hir::def_id::DefId)> {
+    struct CallVisitor<'tcx> {
+        tcx: TyCtxt<'tcx>,
+        typeck_results: &'tcx TypeckResults<'tcx>,
+        calls: Vec<(rustc_span::Span, rustc_hir::def_id::DefId)>,
+    }
+
+    impl<'tcx> Visitor<'tcx> for CallVisitor<'tcx> {
+        // Closure bodies are nested bodies, and count towards the enclosing function
+        type NestedFilter = nested_filter::OnlyBodies;
+
+        fn maybe_tcx(&mut self) -> Self::MaybeTyCtxt {
+            self.tcx
+        }
+
+        fn visit_expr(&mut self, expr: &'tcx Expr<'tcx>) {
+            // Free functions and associated functions are resolved through their path,
+            // method calls through the type-checked receiver
+            let callee = match expr.kind {
+                ExprKind::Call(callee, _) => match callee.kind {
+                    ExprKind::Path(ref qpath) => self
+                        .typeck_results
+                        .qpath_res(qpath, callee.hir_id)
+                        .opt_def_id(),
+                    _ => None,
+                },
+                ExprKind::MethodCall(..) => self.typeck_results.type_dependent_def_id(expr.hir_id),
+                _ => None,
+            };
+            if let Some(def_id) = callee.filter(|def_id| self.tcx.def_kind(*def_id).is_fn_like()) {
+                self.calls.push((expr.span, def_id));
+            }
+            intravisit::walk_expr(self, expr);
+        }
+    }
+
+    let mut visitor = CallVisitor {
+        tcx,
+        typeck_results: tcx.typeck_body(body_id),
+        calls: Vec::new(),
+    };
+    visitor.visit_body(tcx.hir_body(body_id));
+    visitor.calls
+}
+
+// Finds the paths in a body, including in closures, that refer to a `static mut`
+fn static_mut_accesses(
+    tcx: TyCtxt<'_>,
+    body_id: BodyId,
+) -> Vec<(rustc_span::Span, rustc_hir::def_id::DefId)> {
+    struct StaticMutVisitor<'tcx> {
+        tcx: TyCtxt<'tcx>,
+        typeck_results: &'tcx TypeckResults<'tcx>,
+        accesses: Vec<(rustc_span::Span, rustc_hir::def_id::DefId)>,
+    }
+
+    impl<'tcx> Visitor<'tcx> for StaticMutVisitor<'tcx> {
+        type NestedFilter = nested_filter::OnlyBodies;
+
+        fn maybe_tcx(&mut self) -> Self::MaybeTyCtxt {
+            self.tcx
+        }
+
+        fn visit_expr(&mut self, expr: &'tcx Expr<'tcx>) {
+            if let ExprKind::Path(ref qpath) = expr.kind
+                && let Res::Def(
+                    DefKind::Static {
+                        mutability: Mutability::Mut,
+                        ..
+                    },
+                    def_id,
+                ) = self.typeck_results.qpath_res(qpath, expr.hir_id)
+            {
+                self.accesses.push((expr.span, def_id));
+            }
+            intravisit::walk_expr(self, expr);
+        }
+    }
+
+    let mut visitor = StaticMutVisitor {
+        tcx,
+        typeck_results: tcx.typeck_body(body_id),
+        accesses: Vec::new(),
+    };
+    visitor.visit_body(tcx.hir_body(body_id));
+    visitor.accesses
 }
 
 fn evaluate_function_match(
@@ -96,9 +1045,12 @@ fn evaluate_function_match(
     module_path: &str,
     function_name: &str,
     fn_def_id: rustc_hir::def_id::DefId,
+    error_trait_cache: &Mutex<HashMap<rustc_hir::def_id::DefId, bool>>,
 ) -> bool {
     match matcher {
         FunctionMatch::NameEquals(name) => function_name == name,
+        FunctionMatch::NameStartsWith(prefix) => function_name.starts_with(prefix.as_str()),
+        FunctionMatch::NameEndsWith(suffix) => function_name.ends_with(suffix.as_str()),
         FunctionMatch::NameRegex(pattern) => match Regex::new(pattern) {
             Ok(regex) => regex.is_match(function_name),
             Err(_) => false,
@@ -108,104 +1060,21 @@ fn evaluate_function_match(
             Err(_) => module_path == pattern,
         },
         FunctionMatch::ReturnsType(pattern) => {
-            // Get the correct return type from the function signature
-            let fn_sig = ctx.tcx.fn_sig(fn_def_id).skip_binder();
-            let return_ty = fn_sig.output().skip_binder();
-
-            match pattern {
-                ReturnTypePattern::Result => {
-                    // Check for Adt with Result path
-                    if let TyKind::Adt(adt_def, _) = return_ty.kind() {
-                        let path = ctx.tcx.def_path_str(adt_def.did());
-                        return path.contains("result::Result");
-                    }
-
-                    // Fallback: use the string representation
-                    let type_string = return_ty.to_string();
-                    type_string.contains("Result<")
-                }
-                ReturnTypePattern::ResultWithErrorImpl => {
-                    // First check if it's a Result type
-                    if let TyKind::Adt(adt_def, substs) = return_ty.kind() {
-                        let path = ctx.tcx.def_path_str(adt_def.did());
-
-                        // If it's a Result type
-                        if path.contains("result::Result") && substs.len() >= 2 {
-                            // Get the error type (second type parameter)
-                            let error_ty = substs[1].expect_ty();
-
-                            // Check if the error type implements Error trait
-                            let param_env = ctx.param_env;
-                            return implements_error_trait(ctx.tcx, param_env, error_ty);
-                        }
-                    }
-
-                    // Not a Result type or couldn't determine if error type implements Error
-                    false
-                }
-                ReturnTypePattern::Option => {
-                    // Check for Adt with Option path
-                    if let TyKind::Adt(adt_def, _) = return_ty.kind() {
-                        let path = ctx.tcx.def_path_str(adt_def.did());
-                        return path.contains("option::Option");
-                    }
-
-                    // Fallback: use the string representation
-                    let type_string = return_ty.to_string();
-                    type_string.contains("Option<")
-                }
-                ReturnTypePattern::Named(name) => {
-                    // Check for Adt with the exact name
-                    if let TyKind::Adt(adt_def, _) = return_ty.kind() {
-                        let path = ctx.tcx.def_path_str(adt_def.did());
-
-                        // Try to match the simple name at the end of the path
-                        if path.ends_with(&name.to_string()) || path == *name {
-                            return true;
-                        }
-
-                        // Extract the type name without module path
-                        if let Some(last_segment) = path.split("::").last()
-                            && last_segment == *name
-                        {
-                            return true;
-                        }
-                    }
-
-                    // Fallback: use the string representation
-                    let type_string = return_ty.to_string();
-                    type_string == *name || type_string.ends_with(&name.to_string())
-                }
-                ReturnTypePattern::Regex(regex_pattern) => {
-                    // Try to compile and use the regex pattern
-                    match Regex::new(regex_pattern) {
-                        Ok(regex) => {
-                            // Check the string representation of the type against the regex
-                            let type_string = return_ty.to_string();
-                            regex.is_match(&type_string)
-                        }
-                        Err(_) => false,
-                    }
-                }
-                ReturnTypePattern::SelfValue => get_self_type(ctx, fn_def_id) == Some(return_ty),
-                ReturnTypePattern::SelfRef => {
-                    match (get_self_type(ctx, fn_def_id), return_ty.kind()) {
-                        (Some(self_ty), &TyKind::Ref(_, inner, rustc_hir::Mutability::Not)) => {
-                            inner == self_ty
-                        }
-                        _ => false,
-                    }
-                }
-                ReturnTypePattern::SelfMutRef => {
-                    match (get_self_type(ctx, fn_def_id), return_ty.kind()) {
-                        (Some(self_ty), &TyKind::Ref(_, inner, rustc_hir::Mutability::Mut)) => {
-                            inner == self_ty
-                        }
-                        _ => false,
-                    }
-                }
-            }
+            return_type_matches(pattern, ctx, fn_def_id, error_trait_cache)
         }
+        FunctionMatch::ParameterType(pattern) => match Regex::new(pattern) {
+            Ok(regex) => {
+                // Erase lifetimes so `&'a mut T` renders as `&mut T`
+                let fn_sig = ctx.tcx.fn_sig(fn_def_id).instantiate_identity();
+                let fn_sig = ctx.tcx.instantiate_bound_regions_with_erased(fn_sig);
+                fn_sig
+                    .inputs()
+                    .iter()
+                    .map(|input_ty| ctx.tcx.erase_and_anonymize_regions(*input_ty))
+                    .any(|input_ty| regex.is_match(&input_ty.to_string()))
+            }
+            Err(_) => false,
+        },
         FunctionMatch::IsAsync => {
             // Check if the function is async by examining the HIR
             if let Some(local_def_id) = fn_def_id.as_local() {
@@ -265,17 +1134,221 @@ fn evaluate_function_match(
             }
             false
         }
+        FunctionMatch::ReturnsImplFuture => {
+            // `async fn` sugar is the common case, so check the header first
+            if evaluate_function_match(
+                &FunctionMatch::IsAsync,
+                ctx,
+                module_path,
+                function_name,
+                fn_def_id,
+                error_trait_cache,
+            ) {
+                return true;
+            }
+
+            // Otherwise look for `Future` among the bounds of an opaque return type
+            let return_ty = ctx
+                .tcx
+                .fn_sig(fn_def_id)
+                .skip_binder()
+                .output()
+                .skip_binder();
+            let future_trait = ctx.tcx.lang_items().future_trait();
+            match return_ty.kind() {
+                TyKind::Alias(AliasTyKind::Opaque, opaque) => ctx
+                    .tcx
+                    .explicit_item_bounds(opaque.def_id)
+                    .iter_identity_copied()
+                    .filter_map(|(clause, _)| clause.as_trait_clause())
+                    .any(|bound| Some(bound.def_id()) == future_trait),
+                _ => false,
+            }
+        }
+        FunctionMatch::MinLength(min_lines) => {
+            // Only local functions with a body have a length to measure
+            let Some(body) = fn_def_id
+                .as_local()
+                .and_then(|local_def_id| ctx.tcx.hir_maybe_body_owned_by(local_def_id))
+            else {
+                return false;
+            };
+            ctx.tcx
+                .sess
+                .source_map()
+                .span_to_lines(body.value.span)
+                .is_ok_and(|file_lines| file_lines.lines.len() >= *min_lines)
+        }
+        // Impl and trait functions are associated items, wherever they're checked from
+        FunctionMatch::IsMethod => ctx.tcx.opt_associated_item(fn_def_id).is_some(),
+        FunctionMatch::IsFreeFunction => ctx.tcx.opt_associated_item(fn_def_id).is_none(),
+        // Only trait impl items have a trait item they implement
+        FunctionMatch::OverridesTraitMethod => ctx
+            .tcx
+            .opt_associated_item(fn_def_id)
+            .and_then(|item| item.trait_item_def_id())
+            .is_some_and(|trait_item| ctx.tcx.defaultness(trait_item).has_value()),
         FunctionMatch::AndMatches(left, right) => {
-            evaluate_function_match(left, ctx, module_path, function_name, fn_def_id)
-                && evaluate_function_match(right, ctx, module_path, function_name, fn_def_id)
+            evaluate_function_match(
+                left,
+                ctx,
+                module_path,
+                function_name,
+                fn_def_id,
+                error_trait_cache,
+            ) && evaluate_function_match(
+                right,
+                ctx,
+                module_path,
+                function_name,
+                fn_def_id,
+                error_trait_cache,
+            )
         }
         FunctionMatch::OrMatches(left, right) => {
-            evaluate_function_match(left, ctx, module_path, function_name, fn_def_id)
-                || evaluate_function_match(right, ctx, module_path, function_name, fn_def_id)
+            evaluate_function_match(
+                left,
+                ctx,
+                module_path,
+                function_name,
+                fn_def_id,
+                error_trait_cache,
+            ) || evaluate_function_match(
+                right,
+                ctx,
+                module_path,
+                function_name,
+                fn_def_id,
+                error_trait_cache,
+            )
+        }
+        FunctionMatch::NotMatch(inner) => !evaluate_function_match(
+            inner,
+            ctx,
+            module_path,
+            function_name,
+            fn_def_id,
+            error_trait_cache,
+        ),
+    }
+}
+
+/// Whether the function's return type matches the pattern, for both the `ReturnsType`
+/// matcher and the `MustReturnType` rule
+fn return_type_matches(
+    pattern: &ReturnTypePattern,
+    ctx: &LateContext<'_>,
+    fn_def_id: rustc_hir::def_id::DefId,
+    error_trait_cache: &Mutex<HashMap<rustc_hir::def_id::DefId, bool>>,
+) -> bool {
+    // Get the correct return type from the function signature
+    let fn_sig = ctx.tcx.fn_sig(fn_def_id).skip_binder();
+    let return_ty = fn_sig.output().skip_binder();
+
+    match pattern {
+        ReturnTypePattern::Result => {
+            // Check for Adt with Result path
+            if let TyKind::Adt(adt_def, _) = return_ty.kind() {
+                let path = ctx.tcx.def_path_str(adt_def.did());
+                return path.contains("result::Result");
+            }
+
+            // Fallback: use the string representation
+            let type_string = return_ty.to_string();
+            type_string.contains("Result<")
+        }
+        ReturnTypePattern::ResultWithErrorImpl => {
+            // First check if it's a Result type
+            if let TyKind::Adt(adt_def, substs) = return_ty.kind() {
+                let path = ctx.tcx.def_path_str(adt_def.did());
+
+                // If it's a Result type
+                if path.contains("result::Result") && substs.len() >= 2 {
+                    // Get the error type (second type parameter)
+                    let error_ty = substs[1].expect_ty();
+
+                    // Check if the error type implements Error trait
+                    return implements_error_trait_cached(
+                        ctx.tcx,
+                        ctx.param_env,
+                        error_ty,
+                        &mut error_trait_cache.lock().unwrap(),
+                    );
+                }
+            }
+
+            // Not a Result type or couldn't determine if error type implements Error
+            false
+        }
+        ReturnTypePattern::Option => {
+            // Check for Adt with Option path
+            if let TyKind::Adt(adt_def, _) = return_ty.kind() {
+                let path = ctx.tcx.def_path_str(adt_def.did());
+                return path.contains("option::Option");
+            }
+
+            // Fallback: use the string representation
+            let type_string = return_ty.to_string();
+            type_string.contains("Option<")
+        }
+        ReturnTypePattern::Named(name) => {
+            // Check for Adt with the exact name
+            if let TyKind::Adt(adt_def, _) = return_ty.kind() {
+                let path = ctx.tcx.def_path_str(adt_def.did());
+
+                // Try to match the simple name at the end of the path
+                if path.ends_with(&name.to_string()) || path == *name {
+                    return true;
+                }
+
+                // Extract the type name without module path
+                if let Some(last_segment) = path.split("::").last()
+                    && last_segment == *name
+                {
+                    return true;
+                }
+            }
+
+            // Fallback: use the string representation
+            let type_string = return_ty.to_string();
+            type_string == *name || type_string.ends_with(&name.to_string())
         }
-        FunctionMatch::NotMatch(inner) => {
-            !evaluate_function_match(inner, ctx, module_path, function_name, fn_def_id)
+        ReturnTypePattern::Regex(regex_pattern) => {
+            // Try to compile and use the regex pattern
+            match Regex::new(regex_pattern) {
+                Ok(regex) => {
+                    // Check the string representation of the type against the regex
+                    let type_string = return_ty.to_string();
+                    regex.is_match(&type_string)
+                }
+                Err(_) => false,
+            }
         }
+        ReturnTypePattern::SelfValue => get_self_type(ctx, fn_def_id) == Some(return_ty),
+        ReturnTypePattern::SelfRef => match (get_self_type(ctx, fn_def_id), return_ty.kind()) {
+            (Some(self_ty), &TyKind::Ref(_, inner, rustc_hir::Mutability::Not)) => inner == self_ty,
+            _ => false,
+        },
+        ReturnTypePattern::SelfMutRef => match (get_self_type(ctx, fn_def_id), return_ty.kind()) {
+            (Some(self_ty), &TyKind::Ref(_, inner, rustc_hir::Mutability::Mut)) => inner == self_ty,
+            _ => false,
+        },
+    }
+}
+
+/// How a return type pattern reads in a diagnostic, e.g. "a `Result`"
+fn describe_return_type(pattern: &ReturnTypePattern) -> String {
+    match pattern {
+        ReturnTypePattern::Result => "a `Result`".to_string(),
+        ReturnTypePattern::Option => "an `Option`".to_string(),
+        ReturnTypePattern::Named(name) => format!("`{name}`"),
+        ReturnTypePattern::Regex(pattern) => format!("a type matching '{pattern}'"),
+        ReturnTypePattern::ResultWithErrorImpl => {
+            "a `Result` whose error type implements `std::error::Error`".to_string()
+        }
+        ReturnTypePattern::SelfValue => "`Self`".to_string(),
+        ReturnTypePattern::SelfRef => "`&Self`".to_string(),
+        ReturnTypePattern::SelfMutRef => "`&mut Self`".to_string(),
     }
 }
 
@@ -314,168 +1387,300 @@ impl ArchitectureLintRule for FunctionLint {
                 matches: matches.clone(),
                 function_rules: function_rules.clone(),
                 allocation_cache: Mutex::new(HashMap::new()),
+                error_trait_cache: Mutex::new(HashMap::new()),
+                patterns: Patterns::new(name.clone()),
             })
         });
     }
 }
 
-impl<'tcx> LateLintPass<'tcx> for FunctionLint {
-    fn check_item(&mut self, ctx: &LateContext<'tcx>, item: &'tcx Item<'tcx>) {
-        // Only care about functions
-        if let ItemKind::Fn { body, .. } = item.kind {
-            let item_name = ctx
-                .tcx
-                .item_name(item.owner_id.def_id.to_def_id())
-                .to_string();
-            let _crate_name = ctx.tcx.crate_name(LOCAL_CRATE).to_string();
-            let parent_item = ctx.tcx.hir_get_parent_item(item.hir_id());
-            let module_path = get_full_module_name(&ctx.tcx, &parent_item);
-            let fn_def_id = item.owner_id.to_def_id();
-
-            // Check if this function matches our patterns
-            if !self.matches_function(ctx, &module_path, &item_name, fn_def_id) {
-                return;
-            }
-
-            // Apply rules
-            for rule in &self.function_rules {
-                match rule {
-                    FunctionRule::MaxLength(max_lines, severity) => {
-                        let body = ctx.tcx.hir_body(body);
-                        let source_map = ctx.tcx.sess.source_map();
-
-                        if let Ok(file_lines) = source_map.span_to_lines(body.value.span)
-                            && file_lines.lines.len() > *max_lines
-                        {
-                            // Create a span that only covers the function signature
-                            let sig_span = item.span.with_hi(
-                                item.span.lo() + BytePos((item_name.len() + 5) as u32), // "fn name"
-                            );
-
-                            span_lint_and_help(
-                                ctx,
-                                FUNCTION_LINT::get_by_severity(*severity),
-                                self.name().as_str(),
-                                sig_span,
-                                format!(
-                                    "Function exceeds maximum length of {} lines with {} lines",
-                                    max_lines,
-                                    file_lines.lines.len()
-                                ),
-                                None,
-                                "Consider breaking this function into smaller parts",
-                            );
-                        }
-                    }
-                    FunctionRule::ResultErrorMustImplementError(severity) => {
-                        // Get the return type
-                        let fn_sig = ctx.tcx.fn_sig(fn_def_id).skip_binder();
-                        let return_ty = fn_sig.output().skip_binder();
-
-                        // Check if it's a Result type
-                        if let TyKind::Adt(adt_def, substs) = return_ty.kind() {
-                            let path = ctx.tcx.def_path_str(adt_def.did());
-
-                            // If it's a Result type
-                            if path.contains("result::Result") && substs.len() >= 2 {
-                                let error_ty = substs[1].expect_ty();
-                                let param_env = ctx.param_env;
-
-                                // Check if error type does NOT implement Error trait
-                                if !implements_error_trait(ctx.tcx, param_env, error_ty) {
-                                    let error_type_name = error_ty.to_string();
-
-                                    // Create a span that only covers the function signature
-                                    let sig_span = item.span.with_hi(
-                                        item.span.lo() + BytePos((item_name.len() + 5) as u32), // "fn name"
-                                    );
-
-                                    span_lint_and_help(
-                                        ctx,
-                                        FUNCTION_LINT::get_by_severity(*severity),
-                                        self.name().as_str(),
-                                        sig_span,
-                                        format!(
-                                            "Error type '{error_type_name}' in Result does not implement Error trait"
-                                        ),
-                                        None,
-                                        "Consider implementing the Error trait for this type or using a type that already implements it",
-                                    );
-                                }
-                            }
-                        }
-                    }
-                    FunctionRule::MustNotExist(severity) => {
-                        let sig_span = item
-                            .span
-                            .with_hi(item.span.lo() + BytePos((item_name.len() + 5) as u32));
+impl FunctionLint {
+    /// Apply every rule to a function or method, given the span of its signature. Trait
+    /// impl methods are left out of `RequireDocComment`, as the trait documents them.
+    #[allow(clippy::too_many_arguments)]
+    fn apply_rules<'tcx>(
+        &self,
+        ctx: &LateContext<'tcx>,
+        fn_def_id: DefId,
+        item_name: &str,
+        module_path: &str,
+        body_id: BodyId,
+        sig: &FnSig<'tcx>,
+        sig_span: Span,
+    ) {
+        for rule in &self.function_rules {
+            match rule {
+                FunctionRule::MaxLength(max_lines, severity) => {
+                    let body = ctx.tcx.hir_body(body_id);
+                    let source_map = ctx.tcx.sess.source_map();
 
+                    if let Ok(file_lines) = source_map.span_to_lines(body.value.span)
+                        && file_lines.lines.len() > *max_lines
+                    {
                         span_lint_and_help(
                             ctx,
                             FUNCTION_LINT::get_by_severity(*severity),
                             self.name().as_str(),
                             sig_span,
-                            format!("Function '{item_name}' is forbidden by lint rule"),
+                            format!(
+                                "Function exceeds maximum length of {} lines with {} lines",
+                                max_lines,
+                                file_lines.lines.len()
+                            ),
                             None,
-                            "Remove this function to satisfy the architectural rule",
+                            "Consider breaking this function into smaller parts",
                         );
                     }
-                    FunctionRule::NoAllocation(severity) => {
-                        if ctx.tcx.is_mir_available(fn_def_id) {
-                            let mir = ctx.tcx.optimized_mir(fn_def_id);
-
-                            if let Some(violation) = detect_allocation_in_mir(
-                                ctx.tcx,
-                                mir,
-                                fn_def_id,
-                                &mut self.allocation_cache.lock().unwrap(),
-                            ) {
-                                span_lint_and_help(
-                                    ctx,
-                                    FUNCTION_LINT::get_by_severity(*severity),
-                                    self.name().as_str(),
-                                    violation.span,
-                                    format!("Function allocates heap memory: {}", violation.reason),
-                                    None,
-                                    "Remove heap allocations to satisfy the NoAllocation rule",
-                                );
-                            }
-                        }
-                    }
-                    FunctionRule::NoUnwrap(severity) => {
-                        self.check_panic_category(
+                }
+                FunctionRule::ResultErrorMustImplementError(severity) => {
+                    // Check if the error type of a returned Result does NOT implement Error trait
+                    if let Some(error_ty) = result_error_type(ctx.tcx, fn_def_id)
+                        && !implements_error_trait_cached(
+                            ctx.tcx,
+                            ctx.param_env,
+                            error_ty,
+                            &mut self.error_trait_cache.lock().unwrap(),
+                        )
+                    {
+                        let error_type_name = error_ty.to_string();
+
+                        span_lint_and_help(
                             ctx,
-                            fn_def_id,
-                            *severity,
-                            PanicCategory::Unwrap,
-                            "NoUnwrap",
+                            FUNCTION_LINT::get_by_severity(*severity),
+                            self.name().as_str(),
+                            sig_span,
+                            format!(
+                                "Error type '{error_type_name}' in Result does not implement Error trait"
+                            ),
+                            None,
+                            "Consider implementing the Error trait for this type or using a type that already implements it",
                         );
                     }
-                    FunctionRule::NoPanic(severity) => {
-                        self.check_panic_category(
-                            ctx,
+                }
+                FunctionRule::MustNotExist(severity) => {
+                    span_lint_and_help(
+                        ctx,
+                        FUNCTION_LINT::get_by_severity(*severity),
+                        self.name().as_str(),
+                        sig_span,
+                        format!("Function '{item_name}' is forbidden by lint rule"),
+                        None,
+                        "Remove this function to satisfy the architectural rule",
+                    );
+                }
+                FunctionRule::NoAllocation(severity) => {
+                    if ctx.tcx.is_mir_available(fn_def_id) {
+                        let mir = ctx.tcx.optimized_mir(fn_def_id);
+
+                        if let Some(violation) = detect_allocation_in_mir(
+                            ctx.tcx,
+                            mir,
                             fn_def_id,
-                            *severity,
-                            PanicCategory::ExplicitPanic,
-                            "NoPanic",
-                        );
+                            &mut self.allocation_cache.lock().unwrap(),
+                        ) {
+                            span_lint_and_help(
+                                ctx,
+                                FUNCTION_LINT::get_by_severity(*severity),
+                                self.name().as_str(),
+                                violation.span,
+                                format!("Function allocates heap memory: {}", violation.reason),
+                                None,
+                                "Remove heap allocations to satisfy the NoAllocation rule",
+                            );
+                        }
                     }
-                    FunctionRule::NoIndexPanic(severity) => {
-                        self.check_panic_category(
+                }
+                FunctionRule::NoUnwrap(severity) => {
+                    self.check_panic_category(
+                        ctx,
+                        fn_def_id,
+                        *severity,
+                        PanicCategory::Unwrap,
+                        "NoUnwrap",
+                    );
+                }
+                FunctionRule::NoPanic(severity) => {
+                    self.check_panic_category(
+                        ctx,
+                        fn_def_id,
+                        *severity,
+                        PanicCategory::ExplicitPanic,
+                        "NoPanic",
+                    );
+                }
+                FunctionRule::NoIndexPanic(severity) => {
+                    self.check_panic_category(
+                        ctx,
+                        fn_def_id,
+                        *severity,
+                        PanicCategory::IndexBounds,
+                        "NoIndexPanic",
+                    );
+                }
+                FunctionRule::MaxGenerics {
+                    max,
+                    count_lifetimes,
+                    severity,
+                } => {
+                    self.check_max_generics(
+                        ctx,
+                        fn_def_id,
+                        *max,
+                        *count_lifetimes,
+                        *severity,
+                        sig_span,
+                    );
+                }
+                FunctionRule::RequireDocComment(severity) => {
+                    // Trait impl methods are documented by the trait itself
+                    if ctx.tcx.trait_impl_of_assoc(fn_def_id).is_none() {
+                        self.check_doc_comment(
                             ctx,
+                            ctx.tcx.local_def_id_to_hir_id(fn_def_id.expect_local()),
                             fn_def_id,
                             *severity,
-                            PanicCategory::IndexBounds,
-                            "NoIndexPanic",
+                            sig_span,
                         );
                     }
                 }
+                FunctionRule::ForbidUnsafe(severity) => {
+                    self.check_unsafe(ctx, item_name, sig, body_id, *severity);
+                }
+                FunctionRule::ForbidStaticMutAccess(severity) => {
+                    self.check_static_mut_access(ctx, item_name, body_id, *severity);
+                }
+                FunctionRule::RequireErrorContext(severity) => {
+                    self.check_error_context(ctx, item_name, body_id, *severity);
+                }
+                FunctionRule::ForbidIndexing {
+                    allow_constant_indices,
+                    severity,
+                } => {
+                    self.check_indexing(
+                        ctx,
+                        item_name,
+                        body_id,
+                        *allow_constant_indices,
+                        *severity,
+                    );
+                }
+                FunctionRule::ParameterNamingPattern(pattern, severity) => {
+                    self.check_parameter_names(ctx, item_name, body_id, pattern, *severity);
+                }
+                FunctionRule::MaxComplexity(max, severity) => {
+                    self.check_complexity(ctx, body_id, *max, *severity, sig_span);
+                }
+                FunctionRule::MaxStatements(max, severity) => {
+                    self.check_statements(ctx, body_id, *max, *severity, sig_span);
+                }
+                FunctionRule::MaxLocals {
+                    max,
+                    count_match_bindings,
+                    severity,
+                } => {
+                    self.check_locals(
+                        ctx,
+                        body_id,
+                        *max,
+                        *count_match_bindings,
+                        *severity,
+                        sig_span,
+                    );
+                }
+                FunctionRule::ForbidTypeInSignature {
+                    type_patterns,
+                    severity,
+                } => {
+                    self.check_signature_types(
+                        ctx,
+                        item_name,
+                        fn_def_id,
+                        sig,
+                        type_patterns,
+                        *severity,
+                    );
+                }
+                FunctionRule::ForbidCallTo { paths, severity } => {
+                    self.check_forbidden_calls(ctx, item_name, body_id, paths, *severity);
+                }
+                FunctionRule::ResultErrorMustBeEnum {
+                    allow_boxed,
+                    allow_anyhow,
+                    severity,
+                } => {
+                    self.check_result_error_is_enum(
+                        ctx,
+                        item_name,
+                        fn_def_id,
+                        (*allow_boxed, *allow_anyhow),
+                        *severity,
+                        sig_span,
+                    );
+                }
+                FunctionRule::MustBeAsync(severity) => {
+                    self.check_must_be_async(
+                        ctx,
+                        module_path,
+                        item_name,
+                        fn_def_id,
+                        *severity,
+                        sig_span,
+                    );
+                }
+                FunctionRule::NoRecursion(severity) => {
+                    self.check_recursion(ctx, item_name, fn_def_id, body_id, *severity);
+                }
+                FunctionRule::MaxByValueParameters(max, severity) => {
+                    self.check_by_value_parameters(ctx, item_name, fn_def_id, sig, *max, *severity);
+                }
+                FunctionRule::MustReturnType(pattern, severity) => {
+                    self.check_return_type(ctx, item_name, fn_def_id, sig, pattern, *severity);
+                }
+            }
+        }
+    }
+}
+
+/// The span of `fn name` at the start of an item, which violations of rules about the
+/// whole function point at rather than the entire body
+fn signature_span(item_span: Span, item_name: &str) -> Span {
+    item_span.with_hi(item_span.lo() + BytePos((item_name.len() + 5) as u32))
+}
+
+impl<'tcx> LateLintPass<'tcx> for FunctionLint {
+    fn check_item(&mut self, ctx: &LateContext<'tcx>, item: &'tcx Item<'tcx>) {
+        let _timer = ArchitectureLintRunner::time_lint(&self.name);
+        // Only care about functions
+        if let ItemKind::Fn { sig, body, .. } = item.kind {
+            let item_name = ctx
+                .tcx
+                .item_name(item.owner_id.def_id.to_def_id())
+                .to_string();
+            let parent_item = ctx.tcx.hir_get_parent_item(item.hir_id());
+            let module_path = get_full_module_name(&ctx.tcx, &parent_item);
+            let fn_def_id = item.owner_id.to_def_id();
+
+            // Check if this function matches our patterns
+            if !self.matches_function(ctx, &module_path, &item_name, fn_def_id) {
+                return;
             }
+
+            let sig_span = signature_span(item.span, &item_name);
+            self.apply_rules(
+                ctx,
+                fn_def_id,
+                &item_name,
+                &module_path,
+                body,
+                &sig,
+                sig_span,
+            );
         }
     }
 
     fn check_impl_item(&mut self, ctx: &LateContext<'tcx>, impl_item: &'tcx ImplItem<'tcx>) {
-        if let ImplItemKind::Fn(_, body_id) = &impl_item.kind {
+        let _timer = ArchitectureLintRunner::time_lint(&self.name);
+        if let ImplItemKind::Fn(sig, body_id) = &impl_item.kind {
             let item_name = impl_item.ident.to_string();
 
             // Get the module path using the original code's approach
@@ -489,140 +1694,16 @@ impl<'tcx> LateLintPass<'tcx> for FunctionLint {
                 return;
             }
 
-            // Apply rules
-            for rule in &self.function_rules {
-                match rule {
-                    FunctionRule::MaxLength(max_lines, severity) => {
-                        let body = ctx.tcx.hir_body(*body_id);
-                        let source_map = ctx.tcx.sess.source_map();
-
-                        if let Ok(file_lines) = source_map.span_to_lines(body.value.span)
-                            && file_lines.lines.len() > *max_lines
-                        {
-                            // Create a span that only covers the method signature
-                            let sig_span = impl_item.span.with_hi(
-                                impl_item.span.lo() + BytePos((item_name.len() + 5) as u32), // "fn name"
-                            );
-
-                            span_lint_and_help(
-                                ctx,
-                                FUNCTION_LINT::get_by_severity(*severity),
-                                self.name().as_str(),
-                                sig_span,
-                                format!(
-                                    "Function exceeds maximum length of {} lines with {} lines",
-                                    max_lines,
-                                    file_lines.lines.len()
-                                ),
-                                None,
-                                "Consider breaking this function into smaller parts",
-                            );
-                        }
-                    }
-                    FunctionRule::ResultErrorMustImplementError(severity) => {
-                        // Get the return type
-                        let fn_sig = ctx.tcx.fn_sig(fn_def_id).skip_binder();
-                        let return_ty = fn_sig.output().skip_binder();
-
-                        // Check if it's a Result type
-                        if let TyKind::Adt(adt_def, substs) = return_ty.kind() {
-                            let path = ctx.tcx.def_path_str(adt_def.did());
-
-                            // If it's a Result type
-                            if path.contains("result::Result") && substs.len() >= 2 {
-                                let error_ty = substs[1].expect_ty();
-                                let param_env = ctx.param_env;
-
-                                // Check if error type does NOT implement Error trait
-                                if !implements_error_trait(ctx.tcx, param_env, error_ty) {
-                                    let error_type_name = error_ty.to_string();
-
-                                    // Create a span that only covers the method signature
-                                    let sig_span = impl_item.span.with_hi(
-                                        impl_item.span.lo() + BytePos((item_name.len() + 5) as u32), // "fn name"
-                                    );
-
-                                    span_lint_and_help(
-                                        ctx,
-                                        FUNCTION_LINT::get_by_severity(*severity),
-                                        self.name().as_str(),
-                                        sig_span,
-                                        format!(
-                                            "Error type '{error_type_name}' in Result does not implement Error trait"
-                                        ),
-                                        None,
-                                        "Consider implementing the Error trait for this type or using a type that already implements it",
-                                    );
-                                }
-                            }
-                        }
-                    }
-                    FunctionRule::MustNotExist(severity) => {
-                        let sig_span = impl_item
-                            .span
-                            .with_hi(impl_item.span.lo() + BytePos((item_name.len() + 5) as u32));
-
-                        span_lint_and_help(
-                            ctx,
-                            FUNCTION_LINT::get_by_severity(*severity),
-                            self.name().as_str(),
-                            sig_span,
-                            format!("Function '{item_name}' is forbidden by lint rule"),
-                            None,
-                            "Remove this function to satisfy the architectural rule",
-                        );
-                    }
-                    FunctionRule::NoAllocation(severity) => {
-                        if ctx.tcx.is_mir_available(fn_def_id) {
-                            let mir = ctx.tcx.optimized_mir(fn_def_id);
-
-                            if let Some(violation) = detect_allocation_in_mir(
-                                ctx.tcx,
-                                mir,
-                                fn_def_id,
-                                &mut self.allocation_cache.lock().unwrap(),
-                            ) {
-                                span_lint_and_help(
-                                    ctx,
-                                    FUNCTION_LINT::get_by_severity(*severity),
-                                    self.name().as_str(),
-                                    violation.span,
-                                    format!("Function allocates heap memory: {}", violation.reason),
-                                    None,
-                                    "Remove heap allocations to satisfy the NoAllocation rule",
-                                );
-                            }
-                        }
-                    }
-                    FunctionRule::NoUnwrap(severity) => {
-                        self.check_panic_category(
-                            ctx,
-                            fn_def_id,
-                            *severity,
-                            PanicCategory::Unwrap,
-                            "NoUnwrap",
-                        );
-                    }
-                    FunctionRule::NoPanic(severity) => {
-                        self.check_panic_category(
-                            ctx,
-                            fn_def_id,
-                            *severity,
-                            PanicCategory::ExplicitPanic,
-                            "NoPanic",
-                        );
-                    }
-                    FunctionRule::NoIndexPanic(severity) => {
-                        self.check_panic_category(
-                            ctx,
-                            fn_def_id,
-                            *severity,
-                            PanicCategory::IndexBounds,
-                            "NoIndexPanic",
-                        );
-                    }
-                }
-            }
+            let sig_span = signature_span(impl_item.span, &item_name);
+            self.apply_rules(
+                ctx,
+                fn_def_id,
+                &item_name,
+                &module_path,
+                *body_id,
+                sig,
+                sig_span,
+            );
         }
     }
 }
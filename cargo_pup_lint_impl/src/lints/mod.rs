@@ -1,6 +1,9 @@
 // This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
 
 pub mod configuration_factory;
+pub mod const_lint;
+pub mod enum_lint;
 pub mod function_lint;
 pub mod module_lint;
 pub mod struct_lint;
+pub mod trait_lint;
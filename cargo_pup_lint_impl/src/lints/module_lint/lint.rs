@@ -1,19 +1,41 @@
 // This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
 
 use crate::ArchitectureLintRule;
+use crate::ArchitectureLintRunner;
 use crate::declare_variable_severity_lint;
 use crate::helpers::lint_helpers::span_lint_and_help;
-use crate::helpers::queries::get_full_module_name;
+use crate::helpers::queries::{get_full_module_name, get_module_name_from_def_id};
 use cargo_pup_lint_config::module_lint::ModuleLint as ConfigModuleLint;
 use cargo_pup_lint_config::{ConfiguredLint, ModuleMatch, ModuleRule, Severity};
 use regex::Regex;
-use rustc_hir::{Item, ItemKind, UseKind};
+use rustc_hir::attrs::{AttributeKind, CfgEntry};
+use rustc_hir::def_id::{CRATE_DEF_ID, DefId, LOCAL_CRATE};
+use rustc_hir::{Attribute, HirId, Item, ItemKind, Mod, Node, PathSegment, UseKind, UsePath};
+use rustc_lexer::{FrontmatterAllowed, TokenKind, tokenize};
 use rustc_lint::{LateContext, LateLintPass, LintContext, LintStore};
+use rustc_middle::ty::print::{with_no_trimmed_paths, with_no_visible_paths};
 use rustc_session::impl_lint_pass;
+use rustc_span::hygiene::{ExpnKind, MacroKind};
+use rustc_span::{Span, Symbol, kw};
+use std::collections::HashSet;
+use std::ops::Range;
+use std::sync::LazyLock;
+
+// A module declaration with outer attributes, which are captured, and perhaps a visibility
+static ATTRIBUTED_MODULE: LazyLock<regex::bytes::Regex> = LazyLock::new(|| {
+    regex::bytes::Regex::new(r"((?:#\[[^\]]*\]\s*)+)(pub(\([^)]*\))?\s+)?mod\s").unwrap()
+});
+
+// A `#[cfg(..)]` attribute, capturing its predicate
+static CFG_ATTRIBUTE: LazyLock<regex::bytes::Regex> =
+    LazyLock::new(|| regex::bytes::Regex::new(r"#\[\s*cfg\s*\(([^\]]*)\)\s*\]").unwrap());
 
 pub struct ModuleLint {
     // Store the original configuration
     config: ConfigModuleLint,
+    // Where `std` uses have been reported, so a macro that expands to several paths
+    // into `std` is only reported once
+    reported_std_uses: HashSet<Span>,
 }
 
 impl ModuleLint {
@@ -24,19 +46,25 @@ impl ModuleLint {
                 // Simply clone the original configuration
                 Box::new(Self {
                     config: module_config.clone(),
+                    reported_std_uses: HashSet::new(),
                 })
             }
             _ => panic!("Expected a Module lint configuration"),
         }
     }
 
-    // Method to check if a module_lint path matches our configured module_lint patterns
-    fn matches_module(&self, module_path: &str) -> bool {
-        Self::evaluate_module_match(&self.config.matches, module_path)
+    // Method to check if a module_lint path matches our configured module_lint patterns.
+    // `file_path` is the source file the module or item lives in, when known
+    fn matches_module(&self, module_path: &str, file_path: Option<&str>) -> bool {
+        Self::evaluate_module_match(&self.config.matches, module_path, file_path)
     }
 
     // Helper method to evaluate a ModuleMatch against a module_lint path
-    fn evaluate_module_match(module_match: &ModuleMatch, module_path: &str) -> bool {
+    fn evaluate_module_match(
+        module_match: &ModuleMatch,
+        module_path: &str,
+        file_path: Option<&str>,
+    ) -> bool {
         match module_match {
             ModuleMatch::Module(pattern) => {
                 // Try to compile the pattern as a regex and match against module_lint path
@@ -49,15 +77,56 @@ impl ModuleLint {
                     }
                 }
             }
+            ModuleMatch::HasParent(pattern) => {
+                // The root module has no parent, so it never matches
+                let Some((parent_path, _)) = module_path.rsplit_once("::") else {
+                    return false;
+                };
+                match Regex::new(pattern) {
+                    Ok(regex) => regex.is_match(parent_path),
+                    Err(_) => {
+                        // Log error and return false for invalid regex
+                        eprintln!("Invalid regex pattern: {pattern}");
+                        false
+                    }
+                }
+            }
+            ModuleMatch::InFile(pattern) => {
+                // Without a real source file (e.g. when only a path is known) there's nothing to match
+                let Some(file_path) = file_path else {
+                    return false;
+                };
+                match Regex::new(pattern) {
+                    Ok(regex) => regex.is_match(file_path),
+                    Err(_) => {
+                        // Log error and return false for invalid regex
+                        eprintln!("Invalid regex pattern: {pattern}");
+                        false
+                    }
+                }
+            }
             ModuleMatch::AndMatches(left, right) => {
-                Self::evaluate_module_match(left, module_path)
-                    && Self::evaluate_module_match(right, module_path)
+                Self::evaluate_module_match(left, module_path, file_path)
+                    && Self::evaluate_module_match(right, module_path, file_path)
             }
             ModuleMatch::OrMatches(left, right) => {
-                Self::evaluate_module_match(left, module_path)
-                    || Self::evaluate_module_match(right, module_path)
+                Self::evaluate_module_match(left, module_path, file_path)
+                    || Self::evaluate_module_match(right, module_path, file_path)
+            }
+            ModuleMatch::NotMatch(inner) => {
+                !Self::evaluate_module_match(inner, module_path, file_path)
             }
-            ModuleMatch::NotMatch(inner) => !Self::evaluate_module_match(inner, module_path),
+        }
+    }
+
+    // Helper to resolve the source file a span comes from, if it's a real file
+    fn source_file_path(&self, ctx: &LateContext<'_>, span: rustc_span::Span) -> Option<String> {
+        let filename = ctx.sess().source_map().span_to_filename(span);
+        if let rustc_span::FileName::Real(filename) = filename {
+            let path = filename.path(rustc_span::RemapPathScopeComponents::DIAGNOSTICS);
+            Some(path.to_string_lossy().into_owned())
+        } else {
+            None
         }
     }
 
@@ -101,6 +170,187 @@ impl ModuleLint {
         }
     }
 
+    // Helper to count the imports in a module, excluding re-exports. A grouped
+    // `use a::{b, c}` is lowered to one item per imported name plus a list stem,
+    // so only the leaves are counted.
+    fn count_imports(&self, ctx: &LateContext<'_>, module_data: &rustc_hir::Mod<'_>) -> usize {
+        module_data
+            .item_ids
+            .iter()
+            .map(|&item_id| ctx.tcx.hir_item(item_id))
+            .filter(|item| {
+                matches!(
+                    item.kind,
+                    ItemKind::Use(_, UseKind::Single(_) | UseKind::Glob)
+                ) && item.vis_span.is_empty()
+                    && !item.span.from_expansion()
+            })
+            .count()
+    }
+
+    // Helper to count the public items declared in a module. A grouped `pub use a::{b, c}`
+    // is counted once per name, as for imports, and a glob re-export counts once.
+    fn count_public_items(
+        &self,
+        ctx: &LateContext<'_>,
+        module_data: &rustc_hir::Mod<'_>,
+        count_reexports: bool,
+    ) -> usize {
+        module_data
+            .item_ids
+            .iter()
+            .map(|&item_id| ctx.tcx.hir_item(item_id))
+            .filter(|item| {
+                let counted = match item.kind {
+                    ItemKind::Fn { .. }
+                    | ItemKind::Struct(..)
+                    | ItemKind::Enum(..)
+                    | ItemKind::Union(..)
+                    | ItemKind::Trait(..)
+                    | ItemKind::TyAlias(..)
+                    | ItemKind::Const(..)
+                    | ItemKind::Static(..) => true,
+                    ItemKind::Use(_, UseKind::Single(_) | UseKind::Glob) => count_reexports,
+                    _ => false,
+                };
+                counted
+                    && !item.span.from_expansion()
+                    && ctx.tcx.visibility(item.owner_id).is_public()
+            })
+            .count()
+    }
+
+    // Helper returning the required items that the module doesn't re-export with a
+    // single-item `pub use`, in the order they were configured
+    fn missing_reexports<'a>(
+        &self,
+        ctx: &LateContext<'_>,
+        module_data: &rustc_hir::Mod<'_>,
+        required: &'a [String],
+    ) -> Vec<&'a str> {
+        let crate_name = ctx.tcx.crate_name(LOCAL_CRATE).to_string();
+        let reexported: Vec<String> = module_data
+            .item_ids
+            .iter()
+            .map(|&item_id| ctx.tcx.hir_item(item_id))
+            .filter(|item| ctx.tcx.visibility(item.owner_id).is_public())
+            .filter_map(|item| match item.kind {
+                ItemKind::Use(path, UseKind::Single(_)) => Some(path),
+                _ => None,
+            })
+            .flat_map(|path| path.res.present_items())
+            .filter_map(|res| res.opt_def_id())
+            .map(|def_id| ctx.tcx.def_path_str(def_id))
+            .collect();
+
+        required
+            .iter()
+            .map(String::as_str)
+            .filter(|item| {
+                let item = strip_crate_prefix(item, &crate_name);
+                !reexported
+                    .iter()
+                    .any(|path| strip_crate_prefix(path, &crate_name) == item)
+            })
+            .collect()
+    }
+
+    // Helper to check if a module has tests. `#[cfg(test)]` modules are stripped before
+    // we see the HIR in a normal check, so the module's source is searched for one too.
+    // A child module called `tests` counts as well - e.g. `mod tests;` pointing at a
+    // file whose contents are `#[cfg(test)]`.
+    fn has_test_module(&self, ctx: &LateContext<'_>, module_data: &rustc_hir::Mod<'_>) -> bool {
+        let has_tests_child = module_data.item_ids.iter().any(|&item_id| {
+            let item = ctx.tcx.hir_item(item_id);
+            matches!(item.kind, ItemKind::Mod(ident, _) if is_test_module_name(ident.as_str()))
+        });
+        if has_tests_child {
+            return true;
+        }
+
+        let source_map = ctx.sess().source_map();
+        let inner_span = module_data.spans.inner_span;
+        let Ok(source) = source_map.span_to_snippet(inner_span) else {
+            return false;
+        };
+
+        // Only the module's own items count, so comments, string literals and the
+        // bodies of the child items the compiler kept are blanked out. A child module
+        // keeps its header, which may be a `#[cfg(test)]` module's when testing.
+        let mut blanked: Vec<Range<usize>> = Vec::new();
+        let mut offset = 0;
+        for token in tokenize(&source, FrontmatterAllowed::No) {
+            let len = token.len as usize;
+            if matches!(
+                token.kind,
+                TokenKind::LineComment { .. }
+                    | TokenKind::BlockComment { .. }
+                    | TokenKind::Literal { .. }
+            ) {
+                blanked.push(offset..offset + len);
+            }
+            offset += len;
+        }
+        for &item_id in module_data.item_ids {
+            let item = ctx.tcx.hir_item(item_id);
+            let body = match item.kind {
+                ItemKind::Mod(_, nested_data) => nested_data.spans.inner_span,
+                _ => item.span,
+            };
+            if !body.from_expansion() && inner_span.contains(body) {
+                let lo = (body.lo() - inner_span.lo()).0 as usize;
+                let hi = (body.hi() - inner_span.lo()).0 as usize;
+                blanked.push(lo..hi);
+            }
+        }
+
+        let mut source = source.into_bytes();
+        for range in blanked {
+            source[range].fill(b'_');
+        }
+        ATTRIBUTED_MODULE.captures_iter(&source).any(|module| {
+            CFG_ATTRIBUTE.captures_iter(&module[1]).any(|cfg| {
+                std::str::from_utf8(&cfg[1]).is_ok_and(cfg_predicate_mentions_test)
+            })
+        })
+    }
+
+    // Find the modules declared inline, with a body, directly inside a module. A module
+    // declared with `mod foo;` has its contents in a different file to its declaration.
+    fn inline_submodules<'tcx>(
+        &self,
+        ctx: &LateContext<'tcx>,
+        item: &Item<'tcx>,
+    ) -> Vec<&'tcx Item<'tcx>> {
+        let ItemKind::Mod(_, module_data) = item.kind else {
+            return Vec::new();
+        };
+        module_data
+            .item_ids
+            .iter()
+            .map(|&item_id| ctx.tcx.hir_item(item_id))
+            .filter(|nested| match nested.kind {
+                ItemKind::Mod(_, nested_data) => {
+                    !nested.span.from_expansion()
+                        && self.source_file_path(ctx, nested.span)
+                            == self.source_file_path(ctx, nested_data.spans.inner_span)
+                }
+                _ => false,
+            })
+            .collect()
+    }
+
+    // Helper to check if a module only declares submodules and re-exports, leaving
+    // nothing of its own to test
+    fn is_facade_module(&self, ctx: &LateContext<'_>, module_data: &rustc_hir::Mod<'_>) -> bool {
+        module_data.item_ids.iter().all(|&item_id| {
+            matches!(
+                ctx.tcx.hir_item(item_id).kind,
+                ItemKind::Use(..) | ItemKind::Mod(..) | ItemKind::ExternCrate(..)
+            )
+        })
+    }
+
     // Helper function to check if an item should be disallowed in an "empty" module context
     fn is_disallowed_in_empty_module(&self, item_kind: &ItemKind<'_>) -> bool {
         match item_kind {
@@ -129,6 +379,80 @@ impl ModuleLint {
         }
     }
 
+    // Helper to find the external crates a `use` item resolves into. The standard
+    // library crates and the current crate are never reported.
+    fn imported_external_crates(&self, ctx: &LateContext<'_>, path: &UsePath<'_>) -> Vec<String> {
+        let mut crates: Vec<String> = path
+            .res
+            .present_items()
+            .filter_map(|res| res.opt_def_id())
+            .filter(|def_id| def_id.krate != LOCAL_CRATE)
+            .map(|def_id| ctx.tcx.crate_name(def_id.krate).to_string())
+            .filter(|name| !matches!(name.as_str(), "std" | "core" | "alloc"))
+            .collect();
+        crates.sort();
+        crates.dedup();
+        crates
+    }
+
+    // Helper to list what a `use` item refers to, each with the names it can be matched
+    // by, the first of which is used to report it. Local items are named by where they
+    // are defined, as `crate::path::Item`, so renaming or re-exporting them doesn't hide
+    // what's imported. Items from other crates go by their public path and the path
+    // they're defined at, as well as the path as written when it starts from their crate.
+    fn import_targets(
+        &self,
+        ctx: &LateContext<'_>,
+        path: &UsePath<'_>,
+        written: &str,
+    ) -> Vec<Vec<String>> {
+        let mut targets: Vec<Vec<String>> = Vec::new();
+        for def_id in path.res.present_items().filter_map(|res| res.opt_def_id()) {
+            let defined_at = with_no_trimmed_paths!(ctx.tcx.def_path_str(def_id));
+            let names = if def_id.is_local() {
+                if defined_at.is_empty() {
+                    vec!["crate".to_string()]
+                } else {
+                    vec![format!("crate::{defined_at}")]
+                }
+            } else {
+                let mut names = vec![defined_at];
+                let definition =
+                    with_no_visible_paths!(with_no_trimmed_paths!(ctx.tcx.def_path_str(def_id)));
+                if !names.contains(&definition) {
+                    names.push(definition);
+                }
+                let crate_name = ctx.tcx.crate_name(def_id.krate);
+                if written.split("::").next() == Some(crate_name.as_str())
+                    && !names.iter().any(|name| name == written)
+                {
+                    names.push(written.to_string());
+                }
+                names
+            };
+            // A struct's constructor resolves alongside its type under the same name
+            if !targets.iter().any(|target| target[0] == names[0]) {
+                targets.push(names);
+            }
+        }
+        targets
+    }
+
+    // Helper to check if an item is declared directly in a crate root lib.rs or main.rs
+    fn is_in_crate_root_file(&self, ctx: &LateContext<'_>, item: &Item<'_>) -> bool {
+        if ctx.tcx.hir_get_parent_item(item.hir_id()).def_id != CRATE_DEF_ID {
+            return false;
+        }
+        let filename = ctx.sess().source_map().span_to_filename(item.span);
+        if let rustc_span::FileName::Real(filename) = filename {
+            let path = filename.path(rustc_span::RemapPathScopeComponents::DIAGNOSTICS);
+            path.file_name()
+                .is_some_and(|name| name == "lib.rs" || name == "main.rs")
+        } else {
+            false
+        }
+    }
+
     fn get_proc_macro_type(
         &self,
         ctx: &LateContext<'_>,
@@ -159,11 +483,13 @@ impl ModuleLint {
         None
     }
 
-    // Helper function to check for disallowed items in a module and call the callback when found
+    // Helper function to check for disallowed items in a module and call the callback when found.
+    // With `ignore_test_code`, items gated on `cfg(test)` are allowed too.
     fn check_for_disallowed_items<C>(
         &self,
         ctx: &LateContext<'_>,
         module_data: &rustc_hir::Mod<'_>,
+        ignore_test_code: bool,
         on_disallowed_item: C,
     ) where
         C: Fn(&Self, &LateContext<'_>, &rustc_hir::Item<'_>, &str, bool),
@@ -175,6 +501,9 @@ impl ModuleLint {
             if !self.is_disallowed_in_empty_module(&nested_item.kind) {
                 continue;
             }
+            if ignore_test_code && is_cfg_test(ctx, nested_item) {
+                continue;
+            }
 
             // Get item name from HIR for error messages
             let def_id = nested_item.owner_id.to_def_id();
@@ -194,6 +523,157 @@ impl ModuleLint {
     }
 }
 
+// Test modules are conventionally called `tests`, or occasionally `test`
+fn is_test_module_name(name: &str) -> bool {
+    name == "tests" || name == "test"
+}
+
+// A module is treated as a test module if it's conventionally named or gated on
+// `cfg(test)`
+fn is_test_module(ctx: &LateContext<'_>, item: &Item<'_>, name: &str) -> bool {
+    is_test_module_name(name) || is_cfg_test(ctx, item)
+}
+
+// Whether a `cfg` predicate in the source mentions `test` outside a `not(..)`, such as
+// `test` or `all(test, feature = "x")`, the way `is_cfg_test` reads the parsed attribute
+fn cfg_predicate_mentions_test(predicate: &str) -> bool {
+    let mut tokens = Vec::new();
+    let mut offset = 0;
+    for token in tokenize(predicate, FrontmatterAllowed::No) {
+        let len = token.len as usize;
+        if !matches!(token.kind, TokenKind::Whitespace) {
+            tokens.push((token.kind, &predicate[offset..offset + len]));
+        }
+        offset += len;
+    }
+
+    // The predicates the current token is nested in, e.g. `all` and `not` for the
+    // `test` in `all(unix, not(test))`
+    let mut enclosing: Vec<&str> = Vec::new();
+    for (i, (kind, text)) in tokens.iter().enumerate() {
+        let opens = tokens
+            .get(i + 1)
+            .is_some_and(|(next, _)| matches!(next, TokenKind::OpenParen));
+        match kind {
+            TokenKind::Ident if opens => enclosing.push(text),
+            TokenKind::Ident if *text == "test" && !enclosing.contains(&"not") => return true,
+            TokenKind::CloseParen => {
+                enclosing.pop();
+            }
+            _ => {}
+        }
+    }
+    false
+}
+
+// Whether the item is gated on `cfg(test)`, which only shows up when checking with `--tests`
+fn is_cfg_test(ctx: &LateContext<'_>, item: &Item<'_>) -> bool {
+    fn mentions_test(entry: &CfgEntry) -> bool {
+        match entry {
+            CfgEntry::All(entries, _) | CfgEntry::Any(entries, _) => {
+                entries.iter().any(mentions_test)
+            }
+            CfgEntry::NameValue { name, .. } => *name == rustc_span::sym::test,
+            CfgEntry::Not(..) | CfgEntry::Bool(..) | CfgEntry::Version(..) => false,
+        }
+    }
+
+    ctx.tcx.hir_attrs(item.hir_id()).iter().any(|attr| {
+        matches!(attr, Attribute::Parsed(AttributeKind::CfgTrace(entries))
+            if entries.iter().any(|(entry, _)| mentions_test(entry)))
+    })
+}
+
+// The name an item kind goes by in rule configuration, matching `DeniedItems` where
+// the kinds overlap
+fn item_kind_name(kind: &ItemKind<'_>) -> &'static str {
+    match kind {
+        ItemKind::ExternCrate(..) => "extern_crate",
+        ItemKind::Use(..) => "use",
+        ItemKind::Static(..) => "static",
+        ItemKind::Const(..) => "const",
+        ItemKind::Fn { .. } => "function",
+        ItemKind::Macro(..) => "declarative_macro",
+        ItemKind::Mod(..) => "module",
+        ItemKind::ForeignMod { .. } => "extern_block",
+        ItemKind::GlobalAsm { .. } => "global_asm",
+        ItemKind::TyAlias(..) => "type_alias",
+        ItemKind::Enum(..) => "enum",
+        ItemKind::Struct(..) => "struct",
+        ItemKind::Union(..) => "union",
+        ItemKind::Trait(..) => "trait",
+        ItemKind::TraitAlias(..) => "trait_alias",
+        ItemKind::Impl(..) => "impl",
+    }
+}
+
+// Paths into the current crate can be written with `crate::`, the crate's name, or neither
+fn strip_crate_prefix<'a>(path: &'a str, crate_name: &str) -> &'a str {
+    path.strip_prefix("crate::")
+        .or_else(|| path.strip_prefix(crate_name)?.strip_prefix("::"))
+        .unwrap_or(path)
+}
+
+fn matches_any_pattern(patterns: &[String], name: &str) -> bool {
+    patterns.iter().any(|pattern| match Regex::new(pattern) {
+        Ok(re) => re.is_match(name),
+        Err(_) => {
+            eprintln!("Invalid regex pattern: {pattern}");
+            false
+        }
+    })
+}
+
+// Name a module of the current crate the way import targets are named, `crate::...`
+fn local_module_name(ctx: &LateContext<'_>, module_path: &str) -> String {
+    let crate_name = ctx.tcx.crate_name(LOCAL_CRATE).to_ident_string();
+    match strip_crate_prefix(module_path, &crate_name) {
+        local if local == module_path => "crate".to_string(),
+        local => format!("crate::{local}"),
+    }
+}
+
+fn is_std_item(ctx: &LateContext<'_>, def_id: DefId) -> bool {
+    ctx.tcx.crate_name(def_id.krate).as_str() == "std"
+}
+
+// Whether a path is written from `std`, as in `std::fs` or `::std::fs`
+fn written_from_std(segments: &[PathSegment<'_>]) -> bool {
+    segments
+        .iter()
+        .find(|segment| segment.ident.name != kw::PathRoot)
+        .is_some_and(|segment| segment.ident.as_str() == "std")
+}
+
+// Finds the outermost macro defined in `std` that a span was expanded from, along with
+// where the macro was invoked in the source
+fn outermost_std_macro(ctx: &LateContext<'_>, span: Span) -> Option<(Symbol, Span)> {
+    let mut found = None;
+    let mut expn_data = span.ctxt().outer_expn_data();
+    loop {
+        if let ExpnKind::Macro(MacroKind::Bang, name) = expn_data.kind
+            && expn_data
+                .macro_def_id
+                .is_some_and(|def_id| is_std_item(ctx, def_id))
+        {
+            found = Some((name, expn_data.call_site.source_callsite()));
+        }
+        if !expn_data.call_site.from_expansion() {
+            return found;
+        }
+        expn_data = expn_data.call_site.ctxt().outer_expn_data();
+    }
+}
+
+// Describe an import that was matched through what it refers to
+fn resolved_to(import_module: &str, name: &str) -> String {
+    if name == import_module {
+        String::new()
+    } else {
+        format!(", as it refers to '{name}'")
+    }
+}
+
 // Define specific lints for different rule types
 declare_variable_severity_lint!(
     pub,
@@ -260,6 +740,126 @@ declare_variable_severity_lint!(
     "Module's mod.rs file must be empty (only allowed to re-export other modules)"
 );
 
+declare_variable_severity_lint!(
+    pub,
+    MODULE_ALLOWED_SUBMODULES_ONLY,
+    MODULE_ALLOWED_SUBMODULES_ONLY_LINT_DENY,
+    MODULE_ALLOWED_SUBMODULES_ONLY_LINT_WARN,
+    "Module may only contain the allowed submodules"
+);
+
+declare_variable_severity_lint!(
+    pub,
+    MODULE_REQUIRE_TESTS,
+    MODULE_REQUIRE_TESTS_LINT_DENY,
+    MODULE_REQUIRE_TESTS_LINT_WARN,
+    "Module must contain a test module"
+);
+
+declare_variable_severity_lint!(
+    pub,
+    MODULE_REQUIRE_REEXPORT,
+    MODULE_REQUIRE_REEXPORT_LINT_DENY,
+    MODULE_REQUIRE_REEXPORT_LINT_WARN,
+    "Module must re-export the required items"
+);
+
+declare_variable_severity_lint!(
+    pub,
+    MODULE_NAMING_CASE_STYLE,
+    MODULE_NAMING_CASE_STYLE_LINT_DENY,
+    MODULE_NAMING_CASE_STYLE_LINT_WARN,
+    "Module name must follow a naming convention"
+);
+
+declare_variable_severity_lint!(
+    pub,
+    MODULE_FORBID_ITEMS_IN_ROOT,
+    MODULE_FORBID_ITEMS_IN_ROOT_LINT_DENY,
+    MODULE_FORBID_ITEMS_IN_ROOT_LINT_WARN,
+    "Crate root may only contain the allowed kinds of item"
+);
+
+declare_variable_severity_lint!(
+    pub,
+    MODULE_ALLOWED_CRATES,
+    MODULE_ALLOWED_CRATES_LINT_DENY,
+    MODULE_ALLOWED_CRATES_LINT_WARN,
+    "Module may only import from the allowed crates"
+);
+
+declare_variable_severity_lint!(
+    pub,
+    MODULE_MAX_DEPTH,
+    MODULE_MAX_DEPTH_LINT_DENY,
+    MODULE_MAX_DEPTH_LINT_WARN,
+    "Module is nested too deeply"
+);
+
+declare_variable_severity_lint!(
+    pub,
+    MODULE_FORBID_NESTED_MODULES,
+    MODULE_FORBID_NESTED_MODULES_LINT_DENY,
+    MODULE_FORBID_NESTED_MODULES_LINT_WARN,
+    "Module must not contain inline submodules"
+);
+
+declare_variable_severity_lint!(
+    pub,
+    MODULE_REQUIRE_PREFIX,
+    MODULE_REQUIRE_PREFIX_LINT_DENY,
+    MODULE_REQUIRE_PREFIX_LINT_WARN,
+    "Module's submodules must share a name prefix"
+);
+
+declare_variable_severity_lint!(
+    pub,
+    MODULE_MAX_IMPORTS,
+    MODULE_MAX_IMPORTS_LINT_DENY,
+    MODULE_MAX_IMPORTS_LINT_WARN,
+    "Module has too many imports"
+);
+
+declare_variable_severity_lint!(
+    pub,
+    MODULE_MAX_PUBLIC_ITEMS,
+    MODULE_MAX_PUBLIC_ITEMS_LINT_DENY,
+    MODULE_MAX_PUBLIC_ITEMS_LINT_WARN,
+    "Module has too many public items"
+);
+
+declare_variable_severity_lint!(
+    pub,
+    MODULE_FORBID_IMPORT_FROM,
+    MODULE_FORBID_IMPORT_FROM_LINT_DENY,
+    MODULE_FORBID_IMPORT_FROM_LINT_WARN,
+    "Module must not import across a forbidden boundary"
+);
+
+declare_variable_severity_lint!(
+    pub,
+    MODULE_FORBID_REEXPORT_FROM,
+    MODULE_FORBID_REEXPORT_FROM_LINT_DENY,
+    MODULE_FORBID_REEXPORT_FROM_LINT_WARN,
+    "Module must not re-export internal items"
+);
+
+declare_variable_severity_lint!(
+    pub,
+    MODULE_FORBID_STD,
+    MODULE_FORBID_STD_LINT_DENY,
+    MODULE_FORBID_STD_LINT_WARN,
+    "Module must not depend on std"
+);
+
+declare_variable_severity_lint!(
+    pub,
+    MODULE_REQUIRE_SIBLING_MODULE,
+    MODULE_REQUIRE_SIBLING_MODULE_LINT_DENY,
+    MODULE_REQUIRE_SIBLING_MODULE_LINT_WARN,
+    "Module must have a sibling module of the required name"
+);
+
 impl_lint_pass!(ModuleLint => [
     MODULE_MUST_BE_NAMED_LINT_DENY, MODULE_MUST_BE_NAMED_LINT_WARN,
     MODULE_MUST_NOT_BE_NAMED_LINT_DENY, MODULE_MUST_NOT_BE_NAMED_LINT_WARN,
@@ -268,7 +868,22 @@ impl_lint_pass!(ModuleLint => [
     MODULE_MUST_HAVE_EMPTY_MOD_FILE_LINT_DENY, MODULE_MUST_HAVE_EMPTY_MOD_FILE_LINT_WARN,
     MODULE_RESTRICT_IMPORTS_LINT_DENY, MODULE_RESTRICT_IMPORTS_LINT_WARN,
     MODULE_WILDCARD_IMPORT_LINT_DENY, MODULE_WILDCARD_IMPORT_LINT_WARN,
-    MODULE_DENIED_ITEMS_LINT_DENY, MODULE_DENIED_ITEMS_LINT_WARN
+    MODULE_DENIED_ITEMS_LINT_DENY, MODULE_DENIED_ITEMS_LINT_WARN,
+    MODULE_ALLOWED_SUBMODULES_ONLY_LINT_DENY, MODULE_ALLOWED_SUBMODULES_ONLY_LINT_WARN,
+    MODULE_MAX_IMPORTS_LINT_DENY, MODULE_MAX_IMPORTS_LINT_WARN,
+    MODULE_MAX_PUBLIC_ITEMS_LINT_DENY, MODULE_MAX_PUBLIC_ITEMS_LINT_WARN,
+    MODULE_REQUIRE_TESTS_LINT_DENY, MODULE_REQUIRE_TESTS_LINT_WARN,
+    MODULE_NAMING_CASE_STYLE_LINT_DENY, MODULE_NAMING_CASE_STYLE_LINT_WARN,
+    MODULE_REQUIRE_REEXPORT_LINT_DENY, MODULE_REQUIRE_REEXPORT_LINT_WARN,
+    MODULE_FORBID_ITEMS_IN_ROOT_LINT_DENY, MODULE_FORBID_ITEMS_IN_ROOT_LINT_WARN,
+    MODULE_ALLOWED_CRATES_LINT_DENY, MODULE_ALLOWED_CRATES_LINT_WARN,
+    MODULE_MAX_DEPTH_LINT_DENY, MODULE_MAX_DEPTH_LINT_WARN,
+    MODULE_FORBID_NESTED_MODULES_LINT_DENY, MODULE_FORBID_NESTED_MODULES_LINT_WARN,
+    MODULE_REQUIRE_PREFIX_LINT_DENY, MODULE_REQUIRE_PREFIX_LINT_WARN,
+    MODULE_FORBID_IMPORT_FROM_LINT_DENY, MODULE_FORBID_IMPORT_FROM_LINT_WARN,
+    MODULE_FORBID_REEXPORT_FROM_LINT_DENY, MODULE_FORBID_REEXPORT_FROM_LINT_WARN,
+    MODULE_FORBID_STD_LINT_DENY, MODULE_FORBID_STD_LINT_WARN,
+    MODULE_REQUIRE_SIBLING_MODULE_LINT_DENY, MODULE_REQUIRE_SIBLING_MODULE_LINT_WARN
 ]);
 
 impl ArchitectureLintRule for ModuleLint {
@@ -277,7 +892,7 @@ impl ArchitectureLintRule for ModuleLint {
     }
 
     fn applies_to_module(&self, namespace: &str) -> bool {
-        self.matches_module(namespace)
+        self.matches_module(namespace, None)
     }
 
     fn applies_to_trait(&self, _trait_path: &str) -> bool {
@@ -291,23 +906,170 @@ impl ArchitectureLintRule for ModuleLint {
             // Create a new instance of ModuleLint to be used as LateLintPass
             Box::new(ModuleLint {
                 config: config_clone.clone(),
+                reported_std_uses: HashSet::new(),
             })
         });
     }
 }
 
 impl<'tcx> LateLintPass<'tcx> for ModuleLint {
+    // Paths outside of imports are only checked by ForbidStd, which also catches them
+    // in function bodies and signatures. Imports are checked in check_item.
+    fn check_path(&mut self, ctx: &LateContext<'tcx>, path: &rustc_hir::Path<'tcx>, hir_id: HirId) {
+        if !self
+            .config
+            .rules
+            .iter()
+            .any(|rule| matches!(rule, ModuleRule::ForbidStd(_)))
+            || matches!(
+                ctx.tcx.hir_node(hir_id),
+                Node::Item(Item {
+                    kind: ItemKind::Use(..),
+                    ..
+                })
+            )
+        {
+            return;
+        }
+        let _timer = ArchitectureLintRunner::time_lint(&self.config.name);
+
+        let module_path =
+            get_module_name_from_def_id(&ctx.tcx, ctx.tcx.parent_module(hir_id).to_def_id());
+        // Code expanded from a macro is reported as a use of the std macro, if any,
+        // where it's invoked
+        let (span, message) = if path.span.from_expansion() {
+            let Some((name, call_site)) = outermost_std_macro(ctx, path.span) else {
+                return;
+            };
+            (
+                call_site,
+                format!("Module '{module_path}' must not use the std macro '{name}!'"),
+            )
+        } else if let Some(def_id) = path.res.opt_def_id()
+            && is_std_item(ctx, def_id)
+        {
+            let item_path = with_no_trimmed_paths!(ctx.tcx.def_path_str(def_id));
+            (
+                path.span,
+                format!("Module '{module_path}' must not use '{item_path}' from std"),
+            )
+        } else if written_from_std(path.segments) {
+            let written = path
+                .segments
+                .iter()
+                .map(|s| s.ident.as_str())
+                .collect::<Vec<_>>()
+                .join("::");
+            (
+                path.span,
+                format!("Module '{module_path}' must not use '{written}' from std"),
+            )
+        } else {
+            return;
+        };
+
+        if !self.matches_module(&module_path, self.source_file_path(ctx, span).as_deref())
+            || !self.reported_std_uses.insert(span)
+        {
+            return;
+        }
+        for rule in &self.config.rules {
+            if let ModuleRule::ForbidStd(severity) = rule {
+                span_lint_and_help(
+                    ctx,
+                    MODULE_FORBID_STD::get_by_severity(*severity),
+                    self.name().as_str(),
+                    span,
+                    message.clone(),
+                    None,
+                    "Use the equivalent from `core` or `alloc` instead, or do without it",
+                );
+            }
+        }
+    }
+
+    // Each module's submodules are collected here, as the parent is the only place they
+    // can be seen side by side, and a parent missing a required sibling is reported once
+    fn check_mod(&mut self, ctx: &LateContext<'tcx>, module: &'tcx Mod<'tcx>, hir_id: HirId) {
+        let _timer = ArchitectureLintRunner::time_lint(&self.config.name);
+        let required: Vec<(&String, Severity)> = self
+            .config
+            .rules
+            .iter()
+            .filter_map(|rule| match rule {
+                ModuleRule::RequireSiblingModule(name, severity) => Some((name, *severity)),
+                _ => None,
+            })
+            .collect();
+        if required.is_empty() {
+            return;
+        }
+
+        let submodules: Vec<(Symbol, String, Option<String>)> = module
+            .item_ids
+            .iter()
+            .filter_map(|id| match ctx.tcx.hir_item(*id).kind {
+                ItemKind::Mod(ident, module_data) => Some((
+                    ident.name,
+                    get_full_module_name(&ctx.tcx, &id.owner_id),
+                    self.source_file_path(ctx, module_data.spans.inner_span),
+                )),
+                _ => None,
+            })
+            .collect();
+        let matched: Vec<String> = submodules
+            .iter()
+            .filter(|(_, path, file)| self.matches_module(path, file.as_deref()))
+            .map(|(name, _, _)| format!("'{name}'"))
+            .collect();
+        if matched.is_empty() {
+            return;
+        }
+
+        let parent_path = get_full_module_name(&ctx.tcx, &hir_id.owner);
+        for (name, severity) in required {
+            if submodules
+                .iter()
+                .any(|(sibling, _, _)| sibling.as_str() == name)
+            {
+                continue;
+            }
+            span_lint_and_help(
+                ctx,
+                MODULE_REQUIRE_SIBLING_MODULE::get_by_severity(severity),
+                self.name().as_str(),
+                ctx.tcx.def_span(hir_id.owner.def_id),
+                format!(
+                    "Module '{parent_path}' has no '{name}' module alongside {}",
+                    matched.join(", ")
+                ),
+                None,
+                format!("Add `mod {name};` to '{parent_path}'"),
+            );
+        }
+    }
+
     fn check_item(&mut self, ctx: &LateContext<'tcx>, item: &'tcx Item<'tcx>) {
+        let _timer = ArchitectureLintRunner::time_lint(&self.config.name);
         let parent_item = ctx.tcx.hir_get_parent_item(item.hir_id());
         let parent_module_path = get_full_module_name(&ctx.tcx, &parent_item);
+        // The item is written in its parent module's file, while a module's own
+        // contents may live in a file of their own (`mod foo;`)
+        let item_file = self.source_file_path(ctx, item.span);
+        let module_file = match item.kind {
+            ItemKind::Mod(_, module_data) => {
+                self.source_file_path(ctx, module_data.spans.inner_span)
+            }
+            _ => None,
+        };
 
         // Check if the parent module matches our patterns
-        if !self.matches_module(&parent_module_path) {
+        if !self.matches_module(&parent_module_path, item_file.as_deref()) {
             // Get the full path of the current item for module-specific rules
             if let ItemKind::Mod(_, _) = item.kind {
                 let full_item_path = get_full_module_name(&ctx.tcx, &item.owner_id);
                 // If neither the parent nor the full item path match, return
-                if !self.matches_module(&full_item_path) {
+                if !self.matches_module(&full_item_path, module_file.as_deref()) {
                     return;
                 }
             } else {
@@ -377,12 +1139,16 @@ impl<'tcx> LateLintPass<'tcx> for ModuleLint {
                         );
                     }
                 }
-                ModuleRule::MustBeEmpty(severity) => {
+                ModuleRule::MustBeEmpty {
+                    severity,
+                    ignore_test_code,
+                } => {
                     if let ItemKind::Mod(_, module_data) = item.kind {
                         let sev = severity; // Use severity in closure
                         self.check_for_disallowed_items(
                             ctx,
                             module_data,
+                            *ignore_test_code,
                             |slf, ctx, item, item_name, _is_mod_rs| {
                                 // For MustBeEmpty, we don't care if it's a mod.rs file or not
                                 span_lint_and_help(
@@ -398,12 +1164,16 @@ impl<'tcx> LateLintPass<'tcx> for ModuleLint {
                         );
                     }
                 }
-                ModuleRule::MustHaveEmptyModFile(severity) => {
+                ModuleRule::MustHaveEmptyModFile {
+                    severity,
+                    ignore_test_code,
+                } => {
                     if let ItemKind::Mod(_, module_data) = item.kind {
                         let sev = severity; // Use severity in closure
                         self.check_for_disallowed_items(
                             ctx,
                             module_data,
+                            *ignore_test_code,
                             |slf, ctx, item, item_name, is_mod_rs| {
                                 // Only emit the lint if this is in a mod.rs file
                                 if is_mod_rs {
@@ -426,28 +1196,39 @@ impl<'tcx> LateLintPass<'tcx> for ModuleLint {
                     denied,
                     severity,
                 } => {
-                    if let ItemKind::Use(path, _) = &item.kind {
+                    // The stem of a grouped import, `a::b` in `use a::b::{c, d}`, isn't an
+                    // import of its own; each of its leaves is checked instead
+                    if let ItemKind::Use(path, kind) = &item.kind
+                        && *kind != UseKind::ListStem
+                    {
                         let import_path: Vec<_> = path
                             .segments
                             .iter()
                             .map(|s| s.ident.as_str().to_string())
                             .collect();
                         let import_module = import_path.join("::");
+                        let targets = self.import_targets(ctx, path, &import_module);
+                        let resolved_to = |name: &str| resolved_to(&import_module, name);
 
-                        // Check allowed imports if specified
+                        // Check allowed imports if specified. Everything the import
+                        // refers to must be allowed.
                         if let Some(allowed) = allowed_only {
-                            let is_allowed =
-                                allowed.iter().any(|pattern| match Regex::new(pattern) {
-                                    Ok(re) => re.is_match(&import_module),
-                                    Err(_) => {
-                                        eprintln!("Invalid regex pattern: {pattern}");
-                                        false
-                                    }
-                                });
-
-                            if !is_allowed {
+                            let not_allowed = if targets.is_empty() {
+                                (!matches_any_pattern(allowed, &import_module))
+                                    .then(|| import_module.clone())
+                            } else {
+                                targets
+                                    .iter()
+                                    .find(|names| {
+                                        !names.iter().any(|name| matches_any_pattern(allowed, name))
+                                    })
+                                    .map(|names| names[0].clone())
+                            };
+
+                            if let Some(name) = not_allowed {
                                 let message = format!(
-                                    "Use of module '{import_module}' is not allowed; only {allowed:?} are permitted"
+                                    "Use of module '{import_module}' is not allowed{}; only {allowed:?} are permitted",
+                                    resolved_to(&name)
                                 );
 
                                 span_lint_and_help(
@@ -462,19 +1243,18 @@ impl<'tcx> LateLintPass<'tcx> for ModuleLint {
                             }
                         }
 
-                        // Check denied imports if specified
+                        // Check denied imports if specified, against the path as written
+                        // as well as what it refers to
                         if let Some(denied_list) = denied {
-                            let is_denied =
-                                denied_list.iter().any(|pattern| match Regex::new(pattern) {
-                                    Ok(re) => re.is_match(&import_module),
-                                    Err(_) => {
-                                        eprintln!("Invalid regex pattern: {pattern}");
-                                        false
-                                    }
-                                });
-
-                            if is_denied {
-                                let message = format!("Use of module '{import_module}' is denied");
+                            let denied_name = std::iter::once(&import_module)
+                                .chain(targets.iter().flatten())
+                                .find(|name| matches_any_pattern(denied_list, name));
+
+                            if let Some(name) = denied_name {
+                                let message = format!(
+                                    "Use of module '{import_module}' is denied{}",
+                                    resolved_to(name)
+                                );
 
                                 span_lint_and_help(
                                     ctx,
@@ -545,6 +1325,400 @@ impl<'tcx> LateLintPass<'tcx> for ModuleLint {
                         );
                     }
                 }
+                ModuleRule::AllowedSubmodulesOnly { allowed, severity } => {
+                    // Only direct children of a matched module are checked, so the
+                    // parent itself must match - not just this module's own path
+                    if let ItemKind::Mod(_, _) = item.kind
+                        && self.matches_module(&parent_module_path, item_file.as_deref())
+                    {
+                        let item_name = ctx.tcx.item_name(item.owner_id.def_id.to_def_id());
+                        let item_name_str = item_name.to_string();
+
+                        let is_allowed = allowed
+                            .iter()
+                            .any(|pattern| self.string_matches_pattern(&item_name_str, pattern));
+
+                        if !is_allowed {
+                            span_lint_and_help(
+                                ctx,
+                                MODULE_ALLOWED_SUBMODULES_ONLY::get_by_severity(*severity),
+                                self.name().as_str(),
+                                item.span,
+                                format!(
+                                    "Submodule '{item_name_str}' is not allowed in '{parent_module_path}'; only {allowed:?} are permitted"
+                                ),
+                                None,
+                                "Move this module elsewhere or rename it to an allowed submodule",
+                            );
+                        }
+                    }
+                }
+                ModuleRule::MaxImports(max, severity) => {
+                    // Imports are counted on the matched module itself, not on
+                    // submodules that are only visited because their parent matched
+                    if let ItemKind::Mod(_, module_data) = item.kind
+                        && self.matches_module(
+                            &get_full_module_name(&ctx.tcx, &item.owner_id),
+                            module_file.as_deref(),
+                        )
+                    {
+                        let count = self.count_imports(ctx, module_data);
+                        if count > *max {
+                            span_lint_and_help(
+                                ctx,
+                                MODULE_MAX_IMPORTS::get_by_severity(*severity),
+                                self.name().as_str(),
+                                item.span,
+                                format!(
+                                    "Module has {count} imports, exceeding the maximum of {max}"
+                                ),
+                                None,
+                                "Consider splitting this module into smaller, more focused modules",
+                            );
+                        }
+                    }
+                }
+                ModuleRule::MaxPublicItems {
+                    max,
+                    count_reexports,
+                    severity,
+                } => {
+                    // As with MaxImports, only the matched module itself is checked
+                    if let ItemKind::Mod(ident, module_data) = item.kind
+                        && self.matches_module(
+                            &get_full_module_name(&ctx.tcx, &item.owner_id),
+                            module_file.as_deref(),
+                        )
+                    {
+                        let count = self.count_public_items(ctx, module_data, *count_reexports);
+                        if count > *max {
+                            span_lint_and_help(
+                                ctx,
+                                MODULE_MAX_PUBLIC_ITEMS::get_by_severity(*severity),
+                                self.name().as_str(),
+                                item.span,
+                                format!(
+                                    "Module '{ident}' has {count} public items, exceeding the maximum of {max}"
+                                ),
+                                None,
+                                "Make items that are only used within the crate `pub(crate)`, or split the module's API into submodules",
+                            );
+                        }
+                    }
+                }
+                ModuleRule::RequireTests(severity) => {
+                    // As with MaxImports, only the matched module itself is checked
+                    if let ItemKind::Mod(ident, module_data) = item.kind
+                        && self.matches_module(
+                            &get_full_module_name(&ctx.tcx, &item.owner_id),
+                            module_file.as_deref(),
+                        )
+                        && !is_test_module_name(ident.as_str())
+                        && !self.is_facade_module(ctx, module_data)
+                        && !self.has_test_module(ctx, module_data)
+                    {
+                        span_lint_and_help(
+                            ctx,
+                            MODULE_REQUIRE_TESTS::get_by_severity(*severity),
+                            self.name().as_str(),
+                            item.span,
+                            format!("Module '{ident}' has no #[cfg(test)] test module"),
+                            None,
+                            "Add a `#[cfg(test)] mod tests` with tests for this module",
+                        );
+                    }
+                }
+                ModuleRule::RequireReexport { items, severity } => {
+                    // As with MaxImports, only the matched module itself is checked
+                    if let ItemKind::Mod(ident, module_data) = item.kind
+                        && self.matches_module(
+                            &get_full_module_name(&ctx.tcx, &item.owner_id),
+                            module_file.as_deref(),
+                        )
+                    {
+                        let missing = self.missing_reexports(ctx, module_data, items);
+                        if !missing.is_empty() {
+                            span_lint_and_help(
+                                ctx,
+                                MODULE_REQUIRE_REEXPORT::get_by_severity(*severity),
+                                self.name().as_str(),
+                                item.span,
+                                format!("Module '{ident}' must re-export: {}", missing.join(", ")),
+                                None,
+                                format!("Add `pub use {};` to this module", missing[0]),
+                            );
+                        }
+                    }
+                }
+                ModuleRule::NamingCaseStyle(style, severity) => {
+                    if let ItemKind::Mod(ident, _) = item.kind
+                        && !style.matches(ident.as_str())
+                    {
+                        span_lint_and_help(
+                            ctx,
+                            MODULE_NAMING_CASE_STYLE::get_by_severity(*severity),
+                            self.name().as_str(),
+                            item.span,
+                            format!("Module '{ident}' must be named in {style}"),
+                            None,
+                            format!("Rename this module to use {style}"),
+                        );
+                    }
+                }
+                ModuleRule::ForbidItemsInRoot {
+                    allowed_kinds,
+                    severity,
+                } => {
+                    // Skip items the compiler injects, like the prelude import
+                    if self.is_in_crate_root_file(ctx, item)
+                        && !item.span.in_external_macro(ctx.sess().source_map())
+                        && !allowed_kinds
+                            .iter()
+                            .any(|kind| kind == item_kind_name(&item.kind))
+                    {
+                        let kind = item_kind_name(&item.kind).replace('_', " ");
+                        let item_name = ctx
+                            .tcx
+                            .opt_item_name(item.owner_id.to_def_id())
+                            .map(|name| format!(" '{name}'"))
+                            .unwrap_or_default();
+                        span_lint_and_help(
+                            ctx,
+                            MODULE_FORBID_ITEMS_IN_ROOT::get_by_severity(*severity),
+                            self.name().as_str(),
+                            item.span,
+                            format!("{kind}{item_name} is not allowed in the crate root"),
+                            None,
+                            "Move this item into a submodule and re-export it if needed",
+                        );
+                    }
+                }
+                ModuleRule::AllowedCrates { crates, severity } => {
+                    if let ItemKind::Use(path, _) = &item.kind
+                        && !item.span.in_external_macro(ctx.sess().source_map())
+                    {
+                        for crate_name in self.imported_external_crates(ctx, path) {
+                            if !crates.contains(&crate_name) {
+                                span_lint_and_help(
+                                    ctx,
+                                    MODULE_ALLOWED_CRATES::get_by_severity(*severity),
+                                    self.name().as_str(),
+                                    item.span,
+                                    format!(
+                                        "Import from crate '{crate_name}' is not allowed; only {crates:?} are permitted"
+                                    ),
+                                    None,
+                                    format!(
+                                        "Remove this dependency on '{crate_name}', or add it to the allowed crates"
+                                    ),
+                                );
+                            }
+                        }
+                    }
+                }
+                ModuleRule::MaxDepth(max, severity) => {
+                    // As with MaxImports, only the matched module itself is checked
+                    let module_path = get_full_module_name(&ctx.tcx, &item.owner_id);
+                    if let ItemKind::Mod(..) = item.kind
+                        && self.matches_module(&module_path, module_file.as_deref())
+                    {
+                        // The crate root is depth 0, so its name doesn't count
+                        let depth = module_path.split("::").count() - 1;
+                        if depth > *max {
+                            span_lint_and_help(
+                                ctx,
+                                MODULE_MAX_DEPTH::get_by_severity(*severity),
+                                self.name().as_str(),
+                                item.span,
+                                format!(
+                                    "Module '{module_path}' is nested {depth} levels deep, exceeding the maximum of {max}"
+                                ),
+                                None,
+                                "Flatten the module tree by moving this module closer to the crate root",
+                            );
+                        }
+                    }
+                }
+                ModuleRule::ForbidNestedModules {
+                    allow_test_modules,
+                    severity,
+                } => {
+                    // Only the matched module itself is scanned, so each inline module is
+                    // reported once, from its parent
+                    let module_path = get_full_module_name(&ctx.tcx, &item.owner_id);
+                    if let ItemKind::Mod(ident, _) = item.kind
+                        && self.matches_module(&module_path, module_file.as_deref())
+                    {
+                        for nested in self.inline_submodules(ctx, item) {
+                            let ItemKind::Mod(nested_ident, _) = nested.kind else {
+                                continue;
+                            };
+                            if *allow_test_modules
+                                && is_test_module(ctx, nested, nested_ident.as_str())
+                            {
+                                continue;
+                            }
+                            span_lint_and_help(
+                                ctx,
+                                MODULE_FORBID_NESTED_MODULES::get_by_severity(*severity),
+                                self.name().as_str(),
+                                nested.span,
+                                format!("Module '{ident}' declares inline module '{nested_ident}'"),
+                                None,
+                                format!(
+                                    "Move '{nested_ident}' into a file of its own and declare it with `mod {nested_ident};`"
+                                ),
+                            );
+                        }
+                    }
+                }
+                ModuleRule::RequirePrefix(prefix, severity) => {
+                    // As with AllowedSubmodulesOnly, each child is checked against its
+                    // parent, so the matched module's own name doesn't need the prefix
+                    if let ItemKind::Mod(ident, _) = item.kind
+                        && self.matches_module(&parent_module_path, item_file.as_deref())
+                        && !ident.as_str().starts_with(prefix.as_str())
+                    {
+                        span_lint_and_help(
+                            ctx,
+                            MODULE_REQUIRE_PREFIX::get_by_severity(*severity),
+                            self.name().as_str(),
+                            item.span,
+                            format!(
+                                "Submodule '{ident}' of '{parent_module_path}' does not start with the required prefix '{prefix}'"
+                            ),
+                            None,
+                            format!("Rename the module to '{prefix}{ident}'"),
+                        );
+                    }
+                }
+                ModuleRule::ForbidImportFrom {
+                    source_patterns,
+                    target_patterns,
+                    severity,
+                } => {
+                    // Modules inside the boundary may import from it. Targets in the
+                    // current crate are named `crate::...`, so the importer is too.
+                    if let ItemKind::Use(path, kind) = &item.kind
+                        && *kind != UseKind::ListStem
+                        && matches_any_pattern(source_patterns, &parent_module_path)
+                        && !matches_any_pattern(
+                            target_patterns,
+                            &local_module_name(ctx, &parent_module_path),
+                        )
+                    {
+                        let import_module = path
+                            .segments
+                            .iter()
+                            .map(|s| s.ident.as_str())
+                            .collect::<Vec<_>>()
+                            .join("::");
+                        let targets = self.import_targets(ctx, path, &import_module);
+                        let forbidden = std::iter::once(&import_module)
+                            .chain(targets.iter().flatten())
+                            .find(|name| matches_any_pattern(target_patterns, name));
+
+                        if let Some(name) = forbidden {
+                            span_lint_and_help(
+                                ctx,
+                                MODULE_FORBID_IMPORT_FROM::get_by_severity(*severity),
+                                self.name().as_str(),
+                                item.span,
+                                format!(
+                                    "Module '{parent_module_path}' must not import '{import_module}'{}",
+                                    resolved_to(&import_module, name)
+                                ),
+                                None,
+                                format!(
+                                    "Modules matching {source_patterns:?} may not depend on {target_patterns:?}"
+                                ),
+                            );
+                        }
+                    }
+                }
+                ModuleRule::ForbidReexportFrom { patterns, severity } => {
+                    // Restricted re-exports like `pub(crate) use` don't leave the crate
+                    if let ItemKind::Use(path, kind) = &item.kind
+                        && *kind != UseKind::ListStem
+                        && !item.span.from_expansion()
+                        && ctx.tcx.visibility(item.owner_id).is_public()
+                    {
+                        let import_module = path
+                            .segments
+                            .iter()
+                            .map(|s| s.ident.as_str())
+                            .collect::<Vec<_>>()
+                            .join("::");
+                        // A glob re-exports the contents of what it names
+                        let glob = |name: &str| {
+                            if *kind == UseKind::Glob {
+                                format!("{name}::*")
+                            } else {
+                                name.to_string()
+                            }
+                        };
+                        let written = glob(&import_module);
+                        let targets: Vec<String> = self
+                            .import_targets(ctx, path, &import_module)
+                            .iter()
+                            .flatten()
+                            .map(|name| glob(name))
+                            .collect();
+                        let forbidden = std::iter::once(&written)
+                            .chain(&targets)
+                            .find(|name| matches_any_pattern(patterns, name));
+
+                        if let Some(name) = forbidden {
+                            span_lint_and_help(
+                                ctx,
+                                MODULE_FORBID_REEXPORT_FROM::get_by_severity(*severity),
+                                self.name().as_str(),
+                                item.span,
+                                format!(
+                                    "Module '{parent_module_path}' must not re-export '{written}'{}",
+                                    resolved_to(&written, name)
+                                ),
+                                None,
+                                format!(
+                                    "Items matching {patterns:?} are internal; use `pub(crate) use` if the crate needs the re-export"
+                                ),
+                            );
+                        }
+                    }
+                }
+                ModuleRule::ForbidStd(severity) => {
+                    // Other paths into std are checked in check_path
+                    if let ItemKind::Use(path, kind) = &item.kind
+                        && *kind != UseKind::ListStem
+                        && !item.span.in_external_macro(ctx.sess().source_map())
+                        && (written_from_std(path.segments)
+                            || path
+                                .res
+                                .present_items()
+                                .filter_map(|res| res.opt_def_id())
+                                .any(|def_id| is_std_item(ctx, def_id)))
+                    {
+                        let import_module = path
+                            .segments
+                            .iter()
+                            .map(|s| s.ident.as_str())
+                            .collect::<Vec<_>>()
+                            .join("::");
+                        span_lint_and_help(
+                            ctx,
+                            MODULE_FORBID_STD::get_by_severity(*severity),
+                            self.name().as_str(),
+                            item.span,
+                            format!(
+                                "Module '{parent_module_path}' must not import '{import_module}' from std"
+                            ),
+                            None,
+                            "Import the item from `core` or `alloc` instead, or do without it",
+                        );
+                    }
+                }
+                // Siblings are compared in check_mod, which sees all of a parent's submodules
+                ModuleRule::RequireSiblingModule(..) => {}
                 // Skip logical combinations for now (And, Or, Not)
                 ModuleRule::And(_, _) | ModuleRule::Or(_, _) | ModuleRule::Not(_) => {}
             }
@@ -1,19 +1,31 @@
 // This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
 
 use crate::ArchitectureLintRule;
+use crate::ArchitectureLintRunner;
 use crate::declare_variable_severity_lint;
 use crate::helpers::lint_helpers::span_lint_and_help;
+use crate::helpers::patterns::Patterns;
+use crate::helpers::queries::{count_own_generics, get_full_module_name};
 use cargo_pup_lint_config::{ConfiguredLint, StructMatch, StructRule};
-use regex::Regex;
-use rustc_hir::{Item, ItemKind, def_id::DefId};
-use rustc_lint::{LateContext, LateLintPass, LintStore};
+use rustc_hir::def::{DefKind, Res};
+use rustc_hir::def_id::CRATE_DEF_ID;
+use rustc_hir::{FieldDef, Item, ItemKind, VariantData, def_id::DefId};
+use rustc_lint::{LateContext, LateLintPass, LintContext, LintStore};
+use rustc_middle::ty::print::with_no_trimmed_paths;
+use rustc_middle::ty::{AssocKind, GenericParamDefKind, TyKind};
 use rustc_session::impl_lint_pass;
-use rustc_span::BytePos;
+use rustc_span::{BytePos, FileName, RemapPathScopeComponents, Span};
+use std::collections::HashSet;
 
 pub struct StructLint {
     name: String,
     matches: StructMatch,
     struct_rules: Vec<StructRule>,
+    // The patterns of the matchers and rules, compiled as they're first used
+    patterns: Patterns,
+    // The files a matched struct has been seen in, as NameMustMatchFile checks each
+    // file once, at the first matched struct in it
+    checked_files: HashSet<FileName>,
 }
 
 impl StructLint {
@@ -24,6 +36,8 @@ impl StructLint {
                 name: s.name.clone(),
                 matches: s.matches.clone(),
                 struct_rules: s.rules.to_vec(),
+                patterns: Patterns::new(s.name.clone()),
+                checked_files: HashSet::new(),
             })
         } else {
             panic!("Expected a Struct lint configuration")
@@ -31,16 +45,24 @@ impl StructLint {
     }
 
     // Helper method to check if a struct in a given crate should be linted
-    fn matches_struct(&self, crate_name: &str, struct_name: &str) -> bool {
-        self.evaluate_struct_match(&self.matches, crate_name, struct_name)
+    fn matches_struct(
+        &self,
+        ctx: &LateContext<'_>,
+        crate_name: &str,
+        struct_name: &str,
+        def_id: DefId,
+    ) -> bool {
+        self.evaluate_struct_match(ctx, &self.matches, crate_name, struct_name, def_id)
     }
 
     // Evaluates the complex matcher structure to determine if a struct matches
     fn evaluate_struct_match(
         &self,
+        ctx: &LateContext<'_>,
         matcher: &StructMatch,
         crate_name: &str,
         struct_name: &str,
+        def_id: DefId,
     ) -> bool {
         match matcher {
             StructMatch::Name(pattern) => {
@@ -58,31 +80,36 @@ impl StructLint {
                 // Attribute matching not yet implemented
                 false
             }
-            StructMatch::ImplementsTrait(_) => {
-                // Implementation will be handled in check_item
-                // Always return true here and do the filtering there
-                true
+            StructMatch::ImplementsTrait(pattern) => {
+                self.implements_matching_trait(ctx, def_id, pattern)
             }
+            StructMatch::HasField { name, type_pattern } => {
+                self.has_matching_field(ctx, def_id, name.as_deref(), type_pattern.as_deref())
+            }
+            StructMatch::InModule(pattern) => def_id.as_local().is_some_and(|local_def_id| {
+                let parent_item = ctx
+                    .tcx
+                    .hir_get_parent_item(ctx.tcx.local_def_id_to_hir_id(local_def_id));
+                let module_path = get_full_module_name(&ctx.tcx, &parent_item);
+                self.string_matches_pattern(&module_path, pattern)
+            }),
             StructMatch::AndMatches(left, right) => {
-                self.evaluate_struct_match(left, crate_name, struct_name)
-                    && self.evaluate_struct_match(right, crate_name, struct_name)
+                self.evaluate_struct_match(ctx, left, crate_name, struct_name, def_id)
+                    && self.evaluate_struct_match(ctx, right, crate_name, struct_name, def_id)
             }
             StructMatch::OrMatches(left, right) => {
-                self.evaluate_struct_match(left, crate_name, struct_name)
-                    || self.evaluate_struct_match(right, crate_name, struct_name)
+                self.evaluate_struct_match(ctx, left, crate_name, struct_name, def_id)
+                    || self.evaluate_struct_match(ctx, right, crate_name, struct_name, def_id)
             }
             StructMatch::NotMatch(inner) => {
-                !self.evaluate_struct_match(inner, crate_name, struct_name)
+                !self.evaluate_struct_match(ctx, inner, crate_name, struct_name, def_id)
             }
         }
     }
 
-    // Helper to determine if a string matches a pattern (exact match or regex)
+    // Helper to determine if a string matches a pattern
     fn string_matches_pattern(&self, string: &str, pattern: &str) -> bool {
-        match Regex::new(pattern) {
-            Ok(regex) => regex.is_match(string),
-            Err(_) => string == pattern,
-        }
+        self.patterns.is_match(pattern, string)
     }
 
     fn describe_pattern(&self, pattern: &str) -> &'static str {
@@ -95,65 +122,340 @@ impl StructLint {
         }
     }
 
-    // Check if this struct has any trait implementations that match our patterns
-    fn has_matching_trait_impl(&self, ctx: &LateContext<'_>, def_id: DefId) -> bool {
+    // Check if one of the struct's inherent impls has an associated function without a
+    // `self` receiver whose name matches the pattern
+    fn has_constructor(&self, ctx: &LateContext<'_>, def_id: DefId, name_pattern: &str) -> bool {
+        ctx.tcx.inherent_impls(def_id).iter().any(|impl_def_id| {
+            ctx.tcx
+                .associated_items(*impl_def_id)
+                .in_definition_order()
+                .any(|assoc| {
+                    matches!(
+                        assoc.kind,
+                        AssocKind::Fn {
+                            has_self: false,
+                            ..
+                        }
+                    ) && self.string_matches_pattern(assoc.name().as_str(), name_pattern)
+                })
+        })
+    }
+
+    // Check if this struct implements any trait whose full path matches the pattern
+    fn implements_matching_trait(
+        &self,
+        ctx: &LateContext<'_>,
+        def_id: DefId,
+        trait_pattern: &str,
+    ) -> bool {
         use crate::helpers::queries;
 
-        // Extract trait pattern if one exists in the matcher
-        fn needs_trait_check(matcher: &StructMatch) -> Option<String> {
-            match matcher {
-                StructMatch::ImplementsTrait(pattern) => Some(pattern.clone()),
-                StructMatch::AndMatches(left, right) => {
-                    needs_trait_check(left).or_else(|| needs_trait_check(right))
-                }
-                StructMatch::OrMatches(left, right) => {
-                    needs_trait_check(left).or_else(|| needs_trait_check(right))
-                }
-                StructMatch::NotMatch(inner) => needs_trait_check(inner),
-                _ => None,
-            }
+        // Get the type for the struct
+        let ty = ctx.tcx.type_of(def_id).skip_binder();
+
+        // Get parameter environment for the struct
+        let param_env = ctx.param_env;
+
+        // For each trait in all crates, check if:
+        // 1. The trait name matches our pattern
+        // 2. The struct implements the trait
+        ctx.tcx
+            .all_traits_including_private()
+            .filter(|trait_def_id| {
+                let full_trait_name =
+                    queries::get_full_canonical_trait_name_from_def_id(&ctx.tcx, *trait_def_id);
+                self.patterns.is_match(trait_pattern, &full_trait_name)
+            })
+            .any(|trait_def_id| queries::implements_trait(ctx.tcx, param_env, ty, trait_def_id))
+    }
+
+    // Check if the struct implements `std::fmt::Debug`, derived or by hand. The trait
+    // is resolved through its diagnostic item rather than by name.
+    fn implements_debug(&self, ctx: &LateContext<'_>, def_id: DefId) -> bool {
+        let Some(debug_def_id) = ctx.tcx.get_diagnostic_item(rustc_span::sym::Debug) else {
+            // Without core there's nothing to check against
+            return true;
+        };
+        self.implements_trait_def(ctx, def_id, debug_def_id)
+    }
+
+    // Check if the struct implements the given trait, derived, by hand or through a
+    // blanket impl
+    fn implements_trait_def(
+        &self,
+        ctx: &LateContext<'_>,
+        def_id: DefId,
+        trait_def_id: DefId,
+    ) -> bool {
+        use crate::helpers::queries;
+
+        let ty = ctx.tcx.type_of(def_id).instantiate_identity();
+        if queries::implements_trait(ctx.tcx, ctx.param_env, ty, trait_def_id) {
+            return true;
         }
 
-        // Check if we have any trait matchers
-        if let Some(trait_pattern) = needs_trait_check(&self.matches) {
-            // Create a regex from the trait pattern
-            let trait_regex = match Regex::new(&trait_pattern) {
-                Ok(regex) => regex,
-                Err(_) => return false, // If regex is invalid, consider no match
-            };
+        // A generic struct's derive is usually bounded on its parameters (`T: Debug`),
+        // which don't hold for the struct's own generics, so also accept any impl
+        // written for the struct itself.
+        let mut has_impl = false;
+        ctx.tcx
+            .for_each_relevant_impl(trait_def_id, ty, |impl_def_id| {
+                has_impl |= matches!(
+                    ctx.tcx.type_of(impl_def_id).instantiate_identity().kind(),
+                    TyKind::Adt(adt_def, _) if adt_def.did() == def_id
+                );
+            });
+        has_impl
+    }
+
+    // Resolve a trait path such as `serde::Serialize` to the traits it names, the way
+    // a `use` would: starting from the named crate's root and following re-exports.
+    // Traits in the crate being linted are named from `crate::`.
+    fn resolve_trait_path(&self, ctx: &LateContext<'_>, path: &str) -> Vec<DefId> {
+        let mut segments = path.trim_start_matches("::").split("::");
+        let Some(root) = segments.next() else {
+            return Vec::new();
+        };
+        let roots: Vec<DefId> = if root == "crate" {
+            vec![CRATE_DEF_ID.to_def_id()]
+        } else {
+            ctx.tcx
+                .crates(())
+                .iter()
+                .filter(|cnum| ctx.tcx.crate_name(**cnum).as_str() == root)
+                .map(|cnum| cnum.as_def_id())
+                .collect()
+        };
 
-            // Get the type for the struct
-            let ty = ctx.tcx.type_of(def_id).skip_binder();
+        // Every segment but the last names a module; the last must name a trait, as a
+        // derive macro can share its name
+        let segments: Vec<&str> = segments.collect();
+        roots
+            .into_iter()
+            .filter_map(|root| {
+                segments
+                    .iter()
+                    .enumerate()
+                    .try_fold(root, |module, (index, segment)| {
+                        let expected = if index + 1 == segments.len() {
+                            DefKind::Trait
+                        } else {
+                            DefKind::Mod
+                        };
+                        let children = match module.as_local() {
+                            Some(local) => ctx.tcx.module_children_local(local),
+                            None => ctx.tcx.module_children(module),
+                        };
+                        children.iter().find_map(|child| match child.res {
+                            Res::Def(kind, def_id)
+                                if kind == expected && child.ident.name.as_str() == *segment =>
+                            {
+                                Some(def_id)
+                            }
+                            _ => None,
+                        })
+                    })
+            })
+            .filter(|def_id| ctx.tcx.def_kind(*def_id) == DefKind::Trait)
+            .collect()
+    }
 
-            // Get parameter environment for the struct
-            let param_env = ctx.param_env;
+    // Check if any of the struct's fields matches both the name and type patterns.
+    // Types are rendered with regions erased, e.g. `&str` rather than `&'a str`.
+    fn has_matching_field(
+        &self,
+        ctx: &LateContext<'_>,
+        def_id: DefId,
+        name_pattern: Option<&str>,
+        type_pattern: Option<&str>,
+    ) -> bool {
+        ctx.tcx.adt_def(def_id).all_fields().any(|field| {
+            let name_matches = name_pattern
+                .is_none_or(|pattern| self.string_matches_pattern(field.name.as_str(), pattern));
+            let type_matches = type_pattern.is_none_or(|pattern| {
+                let field_ty = ctx
+                    .tcx
+                    .erase_and_anonymize_regions(ctx.tcx.type_of(field.did).instantiate_identity());
+                self.string_matches_pattern(&field_ty.to_string(), pattern)
+            });
+            name_matches && type_matches
+        })
+    }
 
-            // For each trait in all crates, check if:
-            // 1. The trait name matches our pattern
-            // 2. The struct implements the trait
-            for trait_def_id in ctx.tcx.all_traits_including_private() {
-                // Get the full canonical trait name
+    // Collect the trait patterns the matcher selects structs by. Patterns under a NOT
+    // select structs that do *not* implement the trait, so they are skipped.
+    fn matched_trait_patterns(matcher: &StructMatch) -> Vec<&str> {
+        match matcher {
+            StructMatch::ImplementsTrait(pattern) => vec![pattern.as_str()],
+            StructMatch::AndMatches(left, right) | StructMatch::OrMatches(left, right) => {
+                let mut patterns = Self::matched_trait_patterns(left);
+                patterns.extend(Self::matched_trait_patterns(right));
+                patterns
+            }
+            StructMatch::NotMatch(_)
+            | StructMatch::Name(_)
+            | StructMatch::HasAttribute(_)
+            | StructMatch::HasField { .. }
+            | StructMatch::InModule(_) => Vec::new(),
+        }
+    }
+
+    // Find the traits implemented for this struct in the local crate whose full path
+    // matches one of the given patterns
+    fn implemented_traits_matching(
+        &self,
+        ctx: &LateContext<'_>,
+        def_id: DefId,
+        patterns: &[&str],
+    ) -> Vec<DefId> {
+        use crate::helpers::queries;
+
+        ctx.tcx
+            .all_local_trait_impls(())
+            .iter()
+            .filter(|(trait_def_id, _)| {
                 let full_trait_name =
-                    queries::get_full_canonical_trait_name_from_def_id(&ctx.tcx, trait_def_id);
+                    queries::get_full_canonical_trait_name_from_def_id(&ctx.tcx, **trait_def_id);
+                patterns
+                    .iter()
+                    .any(|pattern| self.patterns.is_match(pattern, &full_trait_name))
+            })
+            .filter(|(_, impls)| {
+                impls.iter().any(|impl_def_id| {
+                    matches!(
+                        ctx.tcx.type_of(*impl_def_id).instantiate_identity().kind(),
+                        TyKind::Adt(adt_def, _) if adt_def.did() == def_id
+                    )
+                })
+            })
+            .map(|(trait_def_id, _)| *trait_def_id)
+            .collect()
+    }
 
-                // Check if the trait name matches our pattern
-                if trait_regex.is_match(&full_trait_name) {
-                    // If the trait name matches, check if our type implements this trait
-                    if queries::implements_trait(ctx.tcx, param_env, ty, trait_def_id) {
-                        return true;
-                    }
-                }
-            }
+    // Find the traits derived for this struct, along with the span of each derive. The
+    // impl's own span is inside the derive expansion, where lints are not reported, so
+    // we use its call site, which is the trait's name in the derive attribute.
+    fn derived_traits(&self, ctx: &LateContext<'_>, def_id: DefId) -> Vec<(String, Span)> {
+        ctx.tcx
+            .all_local_trait_impls(())
+            .iter()
+            .flat_map(|(trait_def_id, impls)| {
+                impls
+                    .iter()
+                    .map(move |impl_def_id| (*trait_def_id, *impl_def_id))
+            })
+            .filter(|(_, impl_def_id)| {
+                ctx.tcx.is_automatically_derived(impl_def_id.to_def_id())
+                    && matches!(
+                        ctx.tcx.type_of(*impl_def_id).instantiate_identity().kind(),
+                        TyKind::Adt(adt_def, _) if adt_def.did() == def_id
+                    )
+            })
+            .map(|(trait_def_id, impl_def_id)| {
+                (
+                    ctx.tcx.item_name(trait_def_id).to_string(),
+                    ctx.tcx.def_span(impl_def_id).source_callsite(),
+                )
+            })
+            .collect()
+    }
 
-            // If we get here, no matching trait implementations were found
-            return false;
+    // Find the public named fields of a struct without a doc comment. Tuple struct
+    // fields have no names to document, so they're skipped.
+    fn undocumented_public_fields<'a>(
+        &self,
+        ctx: &LateContext<'_>,
+        variant_data: &'a VariantData<'a>,
+    ) -> Vec<&'a FieldDef<'a>> {
+        let VariantData::Struct { fields, .. } = variant_data else {
+            return Vec::new();
+        };
+        fields
+            .iter()
+            .filter(|field| ctx.tcx.visibility(field.def_id).is_public())
+            .filter(|field| {
+                !ctx.tcx
+                    .hir_attrs(field.hir_id)
+                    .iter()
+                    .filter_map(|attr| attr.doc_str())
+                    .any(|doc| !doc.as_str().trim().is_empty())
+            })
+            .collect()
+    }
+
+    // The name a struct defined at `span` should have, going by its file: the file's
+    // stem, or the directory's name for a `mod.rs`. Crate roots, and spans outside a
+    // real file, give none.
+    fn expected_name_from_file(&self, ctx: &LateContext<'_>, span: Span) -> Option<String> {
+        let FileName::Real(filename) = ctx.sess().source_map().span_to_filename(span) else {
+            return None;
+        };
+        let path = filename.path(RemapPathScopeComponents::DIAGNOSTICS);
+        let stem = path.file_stem()?.to_str()?;
+        match stem {
+            "lib" | "main" => None,
+            "mod" => Some(path.parent()?.file_name()?.to_str()?.to_string()),
+            _ => Some(stem.to_string()),
         }
+    }
 
-        // If no trait patterns found, no trait constraints to enforce
-        true
+    // The names of the matched structs defined in the same file as `span`, outside
+    // macro expansions
+    fn matched_structs_in_file(
+        &self,
+        ctx: &LateContext<'_>,
+        crate_name: &str,
+        span: Span,
+    ) -> Vec<String> {
+        let source_map = ctx.sess().source_map();
+        let file = source_map.span_to_filename(span);
+        ctx.tcx
+            .hir_crate_items(())
+            .free_items()
+            .filter_map(|id| {
+                let item = ctx.tcx.hir_item(id);
+                if !matches!(item.kind, ItemKind::Struct(..))
+                    || item.span.from_expansion()
+                    || source_map.span_to_filename(item.span) != file
+                {
+                    return None;
+                }
+                let def_id = item.owner_id.to_def_id();
+                let name = ctx.tcx.item_name(def_id).to_string();
+                self.matches_struct(ctx, crate_name, &name, def_id)
+                    .then_some(name)
+            })
+            .collect()
     }
 }
 
+// Names compared without regard to case or underscores, so `HttpClient` and `http_client` agree
+fn normalize_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| *c != '_')
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+// The snake_case file name a struct name suggests, e.g. `http_client` for `HttpClient`
+// and `HTTPClient`
+fn snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut snake = String::new();
+    for (i, c) in chars.iter().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            let after_lower = chars[i - 1].is_lowercase() || chars[i - 1].is_ascii_digit();
+            let ends_acronym =
+                chars[i - 1].is_uppercase() && chars.get(i + 1).is_some_and(|n| n.is_lowercase());
+            if after_lower || ends_acronym {
+                snake.push('_');
+            }
+        }
+        snake.extend(c.to_lowercase());
+    }
+    snake
+}
+
 declare_variable_severity_lint!(
     pub,
     STRUCT_LINT_MUST_BE_NAMED,
@@ -194,6 +496,110 @@ declare_variable_severity_lint!(
     "Struct must have pub(crate) visibility"
 );
 
+declare_variable_severity_lint!(
+    pub,
+    STRUCT_LINT_NAME_MUST_CONTAIN_TRAIT_NAME,
+    STRUCT_LINT_NAME_MUST_CONTAIN_TRAIT_NAME_DENY,
+    STRUCT_LINT_NAME_MUST_CONTAIN_TRAIT_NAME_WARN,
+    "Struct name must contain the name of the traits it implements"
+);
+
+declare_variable_severity_lint!(
+    pub,
+    STRUCT_LINT_MUST_BE_NON_EXHAUSTIVE,
+    STRUCT_LINT_MUST_BE_NON_EXHAUSTIVE_DENY,
+    STRUCT_LINT_MUST_BE_NON_EXHAUSTIVE_WARN,
+    "Public struct must be marked #[non_exhaustive]"
+);
+
+declare_variable_severity_lint!(
+    pub,
+    STRUCT_LINT_FORBID_DERIVE,
+    STRUCT_LINT_FORBID_DERIVE_DENY,
+    STRUCT_LINT_FORBID_DERIVE_WARN,
+    "Struct must not derive forbidden traits"
+);
+
+declare_variable_severity_lint!(
+    pub,
+    STRUCT_LINT_MAX_MEMORY_SIZE,
+    STRUCT_LINT_MAX_MEMORY_SIZE_DENY,
+    STRUCT_LINT_MAX_MEMORY_SIZE_WARN,
+    "Struct must not exceed a maximum size in bytes"
+);
+
+declare_variable_severity_lint!(
+    pub,
+    STRUCT_LINT_REQUIRE_FIELD_DOCS,
+    STRUCT_LINT_REQUIRE_FIELD_DOCS_DENY,
+    STRUCT_LINT_REQUIRE_FIELD_DOCS_WARN,
+    "Public struct fields must have a doc comment"
+);
+
+declare_variable_severity_lint!(
+    pub,
+    STRUCT_LINT_CONSISTENT_FIELD_VISIBILITY,
+    STRUCT_LINT_CONSISTENT_FIELD_VISIBILITY_DENY,
+    STRUCT_LINT_CONSISTENT_FIELD_VISIBILITY_WARN,
+    "Struct fields must be either all public or all non-public"
+);
+
+declare_variable_severity_lint!(
+    pub,
+    STRUCT_LINT_MUST_IMPLEMENT_DEBUG,
+    STRUCT_LINT_MUST_IMPLEMENT_DEBUG_DENY,
+    STRUCT_LINT_MUST_IMPLEMENT_DEBUG_WARN,
+    "Struct must implement Debug"
+);
+
+declare_variable_severity_lint!(
+    pub,
+    STRUCT_LINT_MAX_GENERICS,
+    STRUCT_LINT_MAX_GENERICS_DENY,
+    STRUCT_LINT_MAX_GENERICS_WARN,
+    "Struct has too many generic parameters"
+);
+
+declare_variable_severity_lint!(
+    pub,
+    STRUCT_LINT_MAX_LIFETIMES,
+    STRUCT_LINT_MAX_LIFETIMES_DENY,
+    STRUCT_LINT_MAX_LIFETIMES_WARN,
+    "Struct has too many lifetime parameters"
+);
+
+declare_variable_severity_lint!(
+    pub,
+    STRUCT_LINT_FORBID_FIELD_TYPE,
+    STRUCT_LINT_FORBID_FIELD_TYPE_DENY,
+    STRUCT_LINT_FORBID_FIELD_TYPE_WARN,
+    "Struct has a field of a forbidden type"
+);
+
+declare_variable_severity_lint!(
+    pub,
+    STRUCT_LINT_MUST_IMPLEMENT,
+    STRUCT_LINT_MUST_IMPLEMENT_DENY,
+    STRUCT_LINT_MUST_IMPLEMENT_WARN,
+    "Struct doesn't implement all of its required traits"
+);
+
+declare_variable_severity_lint!(
+    pub,
+    STRUCT_LINT_REQUIRE_CONSTRUCTOR,
+    STRUCT_LINT_REQUIRE_CONSTRUCTOR_DENY,
+    STRUCT_LINT_REQUIRE_CONSTRUCTOR_WARN,
+    "Struct has no constructor"
+);
+
+declare_variable_severity_lint!(
+    pub,
+    STRUCT_LINT_NAME_MUST_MATCH_FILE,
+    STRUCT_LINT_NAME_MUST_MATCH_FILE_DENY,
+    STRUCT_LINT_NAME_MUST_MATCH_FILE_WARN,
+    "Struct is not named after its file"
+);
+
 impl_lint_pass!(StructLint => [
     STRUCT_LINT_MUST_BE_NAMED_DENY,
     STRUCT_LINT_MUST_BE_NAMED_WARN,
@@ -204,7 +610,33 @@ impl_lint_pass!(StructLint => [
     STRUCT_LINT_MUST_BE_PUBLIC_DENY,
     STRUCT_LINT_MUST_BE_PUBLIC_WARN,
     STRUCT_LINT_MUST_BE_PUB_CRATE_DENY,
-    STRUCT_LINT_MUST_BE_PUB_CRATE_WARN
+    STRUCT_LINT_MUST_BE_PUB_CRATE_WARN,
+    STRUCT_LINT_NAME_MUST_CONTAIN_TRAIT_NAME_DENY,
+    STRUCT_LINT_NAME_MUST_CONTAIN_TRAIT_NAME_WARN,
+    STRUCT_LINT_MUST_BE_NON_EXHAUSTIVE_DENY,
+    STRUCT_LINT_MUST_BE_NON_EXHAUSTIVE_WARN,
+    STRUCT_LINT_FORBID_DERIVE_DENY,
+    STRUCT_LINT_FORBID_DERIVE_WARN,
+    STRUCT_LINT_MAX_MEMORY_SIZE_DENY,
+    STRUCT_LINT_MAX_MEMORY_SIZE_WARN,
+    STRUCT_LINT_REQUIRE_FIELD_DOCS_DENY,
+    STRUCT_LINT_REQUIRE_FIELD_DOCS_WARN,
+    STRUCT_LINT_CONSISTENT_FIELD_VISIBILITY_DENY,
+    STRUCT_LINT_CONSISTENT_FIELD_VISIBILITY_WARN,
+    STRUCT_LINT_MUST_IMPLEMENT_DEBUG_DENY,
+    STRUCT_LINT_MUST_IMPLEMENT_DEBUG_WARN,
+    STRUCT_LINT_MAX_GENERICS_DENY,
+    STRUCT_LINT_MAX_GENERICS_WARN,
+    STRUCT_LINT_MAX_LIFETIMES_DENY,
+    STRUCT_LINT_MAX_LIFETIMES_WARN,
+    STRUCT_LINT_FORBID_FIELD_TYPE_DENY,
+    STRUCT_LINT_FORBID_FIELD_TYPE_WARN,
+    STRUCT_LINT_MUST_IMPLEMENT_DENY,
+    STRUCT_LINT_MUST_IMPLEMENT_WARN,
+    STRUCT_LINT_REQUIRE_CONSTRUCTOR_DENY,
+    STRUCT_LINT_REQUIRE_CONSTRUCTOR_WARN,
+    STRUCT_LINT_NAME_MUST_MATCH_FILE_DENY,
+    STRUCT_LINT_NAME_MUST_MATCH_FILE_WARN
 ]);
 
 impl ArchitectureLintRule for StructLint {
@@ -230,6 +662,8 @@ impl ArchitectureLintRule for StructLint {
                 name: name.clone(),
                 matches: matches.clone(),
                 struct_rules: struct_rules.clone(),
+                patterns: Patterns::new(name.clone()),
+                checked_files: HashSet::new(),
             })
         });
     }
@@ -237,8 +671,9 @@ impl ArchitectureLintRule for StructLint {
 
 impl<'tcx> LateLintPass<'tcx> for StructLint {
     fn check_item(&mut self, ctx: &LateContext<'tcx>, item: &'tcx Item<'tcx>) {
+        let _timer = ArchitectureLintRunner::time_lint(&self.name);
         // We only care about struct items
-        if let ItemKind::Struct(..) = item.kind {
+        if let ItemKind::Struct(_, _, variant_data) = item.kind {
             let item_name = ctx
                 .tcx
                 .item_name(item.owner_id.def_id.to_def_id())
@@ -249,13 +684,8 @@ impl<'tcx> LateLintPass<'tcx> for StructLint {
                 .to_string();
 
             // Check if this struct matches our patterns
-            if !self.matches_struct(&crate_name, &item_name) {
-                return;
-            }
-
-            // Check trait implementations if needed
             let def_id = item.owner_id.def_id.to_def_id();
-            if !self.has_matching_trait_impl(ctx, def_id) {
+            if !self.matches_struct(ctx, &crate_name, &item_name, def_id) {
                 return;
             }
 
@@ -292,6 +722,12 @@ impl<'tcx> LateLintPass<'tcx> for StructLint {
             // Truly private means no visibility keyword at all (inherited visibility)
             let is_private = !has_visibility_keyword;
 
+            // Whether this is the first matched struct seen in its file
+            let first_in_file = !item.span.from_expansion()
+                && self
+                    .checked_files
+                    .insert(ctx.sess().source_map().span_to_filename(item.span));
+
             // Apply rules
             for rule in &self.struct_rules {
                 match rule {
@@ -400,6 +836,308 @@ impl<'tcx> LateLintPass<'tcx> for StructLint {
                             );
                         }
                     }
+                    StructRule::NameMustContainTraitName(severity) => {
+                        let patterns = Self::matched_trait_patterns(&self.matches);
+                        for trait_def_id in self.implemented_traits_matching(ctx, def_id, &patterns)
+                        {
+                            let trait_name = ctx.tcx.item_name(trait_def_id).to_string();
+                            if !item_name.contains(&trait_name) {
+                                span_lint_and_help(
+                                    ctx,
+                                    STRUCT_LINT_NAME_MUST_CONTAIN_TRAIT_NAME::get_by_severity(
+                                        *severity,
+                                    ),
+                                    self.name().as_str(),
+                                    definition_span,
+                                    format!(
+                                        "Struct '{item_name}' implements '{trait_name}', but its name does not contain '{trait_name}'"
+                                    ),
+                                    None,
+                                    format!(
+                                        "Rename this struct to include '{trait_name}', e.g. '{item_name}{trait_name}'"
+                                    ),
+                                );
+                            }
+                        }
+                    }
+                    StructRule::MustBeNonExhaustive(severity) => {
+                        let is_non_exhaustive = ctx
+                            .tcx
+                            .adt_def(def_id)
+                            .non_enum_variant()
+                            .is_field_list_non_exhaustive();
+                        if is_public && !is_non_exhaustive {
+                            span_lint_and_help(
+                                ctx,
+                                STRUCT_LINT_MUST_BE_NON_EXHAUSTIVE::get_by_severity(*severity),
+                                self.name().as_str(),
+                                definition_span,
+                                format!(
+                                    "Public struct '{item_name}' must be marked #[non_exhaustive]"
+                                ),
+                                None,
+                                "Add #[non_exhaustive] so that adding fields is not a breaking change",
+                            );
+                        }
+                    }
+                    StructRule::ForbidDerive(forbidden, severity) => {
+                        for (trait_name, derive_span) in self.derived_traits(ctx, def_id) {
+                            if forbidden.contains(&trait_name) {
+                                span_lint_and_help(
+                                    ctx,
+                                    STRUCT_LINT_FORBID_DERIVE::get_by_severity(*severity),
+                                    self.name().as_str(),
+                                    derive_span,
+                                    format!("Struct '{item_name}' must not derive '{trait_name}'"),
+                                    None,
+                                    format!("Remove '{trait_name}' from the derive attribute"),
+                                );
+                            }
+                        }
+                    }
+                    StructRule::MaxMemorySize(max, severity) => {
+                        // Generic structs usually have no layout until they're instantiated
+                        let ty = ctx.tcx.type_of(def_id).instantiate_identity();
+                        if let Ok(layout) = ctx.tcx.layout_of(ctx.typing_env().as_query_input(ty)) {
+                            let size = layout.size.bytes();
+                            if size > *max {
+                                span_lint_and_help(
+                                    ctx,
+                                    STRUCT_LINT_MAX_MEMORY_SIZE::get_by_severity(*severity),
+                                    self.name().as_str(),
+                                    definition_span,
+                                    format!(
+                                        "Struct '{item_name}' is {size} bytes, exceeding the maximum of {max} bytes"
+                                    ),
+                                    None,
+                                    "Box large fields, or use smaller field types",
+                                );
+                            }
+                        }
+                    }
+                    StructRule::RequireFieldDocs(severity) => {
+                        for field in self.undocumented_public_fields(ctx, &variant_data) {
+                            span_lint_and_help(
+                                ctx,
+                                STRUCT_LINT_REQUIRE_FIELD_DOCS::get_by_severity(*severity),
+                                self.name().as_str(),
+                                field.span,
+                                format!(
+                                    "Public field '{}' of struct '{item_name}' is missing a doc comment",
+                                    field.ident
+                                ),
+                                None,
+                                "Add a /// doc comment describing this field",
+                            );
+                        }
+                    }
+                    StructRule::ConsistentFieldVisibility(severity) => {
+                        // Tuple struct fields are positional, so they're always consistent
+                        if let VariantData::Struct { fields, .. } = variant_data {
+                            let (public, non_public): (Vec<_>, Vec<_>) = fields
+                                .iter()
+                                .partition(|field| ctx.tcx.visibility(field.def_id).is_public());
+                            if !public.is_empty() && !non_public.is_empty() {
+                                let names = |fields: &[&FieldDef<'_>]| {
+                                    fields
+                                        .iter()
+                                        .map(|field| format!("'{}'", field.ident))
+                                        .collect::<Vec<_>>()
+                                        .join(", ")
+                                };
+                                span_lint_and_help(
+                                    ctx,
+                                    STRUCT_LINT_CONSISTENT_FIELD_VISIBILITY::get_by_severity(
+                                        *severity,
+                                    ),
+                                    self.name().as_str(),
+                                    definition_span,
+                                    format!(
+                                        "Struct '{item_name}' mixes public and non-public fields"
+                                    ),
+                                    None,
+                                    format!(
+                                        "Public fields: {}; non-public fields: {}. Make them all public, or none of them",
+                                        names(&public),
+                                        names(&non_public)
+                                    ),
+                                );
+                            }
+                        }
+                    }
+                    StructRule::MustImplementDebug(severity) => {
+                        if !self.implements_debug(ctx, def_id) {
+                            span_lint_and_help(
+                                ctx,
+                                STRUCT_LINT_MUST_IMPLEMENT_DEBUG::get_by_severity(*severity),
+                                self.name().as_str(),
+                                definition_span,
+                                format!("Struct '{item_name}' must implement Debug"),
+                                None,
+                                "Add #[derive(Debug)], or implement std::fmt::Debug by hand",
+                            );
+                        }
+                    }
+                    StructRule::MaxGenerics {
+                        max,
+                        count_lifetimes,
+                        severity,
+                    } => {
+                        let count = count_own_generics(ctx.tcx, def_id, *count_lifetimes);
+                        if count > *max {
+                            let counted = if *count_lifetimes {
+                                "generic parameters"
+                            } else {
+                                "generic type and const parameters"
+                            };
+                            span_lint_and_help(
+                                ctx,
+                                STRUCT_LINT_MAX_GENERICS::get_by_severity(*severity),
+                                self.name().as_str(),
+                                definition_span,
+                                format!(
+                                    "Struct '{item_name}' has {count} {counted}, exceeding the maximum of {max}"
+                                ),
+                                None,
+                                "Consider fixing some of the parameters to concrete types, or grouping them behind a trait",
+                            );
+                        }
+                    }
+                    StructRule::MaxLifetimes(max, severity) => {
+                        let count = ctx
+                            .tcx
+                            .generics_of(def_id)
+                            .own_params
+                            .iter()
+                            .filter(|param| matches!(param.kind, GenericParamDefKind::Lifetime))
+                            .count();
+                        if count > *max {
+                            span_lint_and_help(
+                                ctx,
+                                STRUCT_LINT_MAX_LIFETIMES::get_by_severity(*severity),
+                                self.name().as_str(),
+                                definition_span,
+                                format!(
+                                    "Struct '{item_name}' has {count} lifetime parameters, exceeding the maximum of {max}"
+                                ),
+                                None,
+                                "Consider sharing one lifetime between fields that borrow for as long, or owning some of the data",
+                            );
+                        }
+                    }
+                    StructRule::ForbidFieldType {
+                        type_patterns,
+                        severity,
+                    } => {
+                        for (index, field) in variant_data.fields().iter().enumerate() {
+                            let field_ty = ctx.tcx.type_of(field.def_id).instantiate_identity();
+                            let rendered = with_no_trimmed_paths!(field_ty.to_string());
+                            let Some(pattern) = type_patterns
+                                .iter()
+                                .find(|pattern| self.string_matches_pattern(&rendered, pattern))
+                            else {
+                                continue;
+                            };
+                            let field_name = if field.is_positional() {
+                                index.to_string()
+                            } else {
+                                field.ident.to_string()
+                            };
+                            span_lint_and_help(
+                                ctx,
+                                STRUCT_LINT_FORBID_FIELD_TYPE::get_by_severity(*severity),
+                                self.name().as_str(),
+                                field.span,
+                                format!(
+                                    "Field '{field_name}' of struct '{item_name}' has forbidden type '{rendered}'"
+                                ),
+                                None,
+                                format!(
+                                    "'{rendered}' matches the forbidden type pattern '{pattern}'"
+                                ),
+                            );
+                        }
+                    }
+                    StructRule::MustImplement(traits, severity) => {
+                        let missing: Vec<&str> = traits
+                            .iter()
+                            .filter(|path| {
+                                !self.resolve_trait_path(ctx, path).into_iter().any(
+                                    |trait_def_id| {
+                                        self.implements_trait_def(ctx, def_id, trait_def_id)
+                                    },
+                                )
+                            })
+                            .map(String::as_str)
+                            .collect();
+                        if !missing.is_empty() {
+                            span_lint_and_help(
+                                ctx,
+                                STRUCT_LINT_MUST_IMPLEMENT::get_by_severity(*severity),
+                                self.name().as_str(),
+                                definition_span,
+                                format!(
+                                    "Struct '{item_name}' must implement {}",
+                                    missing.join(", ")
+                                ),
+                                None,
+                                "Derive or implement the missing traits; a trait that can't be found in the crate's dependencies is also reported as missing",
+                            );
+                        }
+                    }
+                    StructRule::RequireConstructor {
+                        name_pattern,
+                        severity,
+                    } => {
+                        if !self.has_constructor(ctx, def_id, name_pattern) {
+                            span_lint_and_help(
+                                ctx,
+                                STRUCT_LINT_REQUIRE_CONSTRUCTOR::get_by_severity(*severity),
+                                self.name().as_str(),
+                                definition_span,
+                                format!(
+                                    "Struct '{item_name}' has no constructor matching '{name_pattern}'"
+                                ),
+                                None,
+                                "Add an associated function that returns a new instance, e.g. `pub fn new(..) -> Self`, to an impl block of the struct",
+                            );
+                        }
+                    }
+                    StructRule::NameMustMatchFile(severity) => {
+                        // The file needs one matched struct named after it, and the
+                        // others in it, such as helpers, are fine alongside that one
+                        if !first_in_file {
+                            continue;
+                        }
+                        let Some(expected) = self.expected_name_from_file(ctx, item.span) else {
+                            continue;
+                        };
+                        let names = self.matched_structs_in_file(ctx, &crate_name, item.span);
+                        if !names
+                            .iter()
+                            .any(|name| normalize_name(name) == normalize_name(&expected))
+                        {
+                            let found = names
+                                .iter()
+                                .map(|name| format!("'{name}'"))
+                                .collect::<Vec<_>>()
+                                .join(", ");
+                            span_lint_and_help(
+                                ctx,
+                                STRUCT_LINT_NAME_MUST_MATCH_FILE::get_by_severity(*severity),
+                                self.name().as_str(),
+                                definition_span,
+                                format!(
+                                    "No struct in '{expected}' is named after its file, found {found}"
+                                ),
+                                None,
+                                format!(
+                                    "Rename the file's primary struct after the file, or move '{item_name}' into '{}.rs'",
+                                    snake_case(&item_name)
+                                ),
+                            );
+                        }
+                    }
                     _ => {} // Ignore other rule types for now
                 }
             }
@@ -0,0 +1,350 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+use crate::ArchitectureLintRule;
+use crate::ArchitectureLintRunner;
+use crate::declare_variable_severity_lint;
+use crate::helpers::lint_helpers::span_lint_and_help;
+use crate::helpers::patterns::Patterns;
+use crate::helpers::queries::get_full_canonical_trait_name_from_def_id;
+use cargo_pup_lint_config::{ConfiguredLint, Severity, TraitMatch, TraitRule};
+use rustc_hir::def::DefKind;
+use rustc_hir::{Impl, Item, ItemKind, TraitItemId, TraitItemKind, def_id::DefId};
+use rustc_lint::{LateContext, LateLintPass, LintStore};
+use rustc_middle::ty::TyKind;
+use rustc_session::impl_lint_pass;
+
+pub struct TraitLint {
+    name: String,
+    matches: TraitMatch,
+    trait_rules: Vec<TraitRule>,
+    // The patterns of the matcher, compiled as they're first used
+    patterns: Patterns,
+}
+
+impl TraitLint {
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new(config: &ConfiguredLint) -> Box<dyn ArchitectureLintRule + Send> {
+        if let ConfiguredLint::Trait(t) = config {
+            Box::new(Self {
+                name: t.name.clone(),
+                matches: t.matches.clone(),
+                trait_rules: t.rules.to_vec(),
+                patterns: Patterns::new(t.name.clone()),
+            })
+        } else {
+            panic!("Expected a Trait lint configuration")
+        }
+    }
+
+    // Check a trait impl against the rules that apply to impl blocks, if its trait
+    // matches our patterns
+    fn check_trait_impl(&self, ctx: &LateContext<'_>, item: &Item<'_>, impl_data: &Impl<'_>) {
+        let Some(trait_def_id) = impl_data
+            .of_trait
+            .and_then(|header| header.trait_ref.trait_def_id())
+        else {
+            return;
+        };
+        // Derived impls can't be changed, so there's no point reporting them
+        if item.span.from_expansion()
+            || !evaluate_trait_match(
+                &self.patterns,
+                &self.matches,
+                &get_full_canonical_trait_name_from_def_id(&ctx.tcx, trait_def_id),
+            )
+        {
+            return;
+        }
+
+        let trait_name = ctx.tcx.item_name(trait_def_id).to_string();
+        let self_ty = ctx.tcx.type_of(item.owner_id).instantiate_identity();
+        for rule in &self.trait_rules {
+            match rule {
+                TraitRule::MaxImplMethods(max, severity) => {
+                    let methods = impl_data
+                        .items
+                        .iter()
+                        .filter(|id| ctx.tcx.def_kind(id.owner_id) == DefKind::AssocFn)
+                        .count();
+                    if methods > *max {
+                        span_lint_and_help(
+                            ctx,
+                            TRAIT_LINT_MAX_IMPL_METHODS::get_by_severity(*severity),
+                            self.name().as_str(),
+                            ctx.tcx.def_span(item.owner_id),
+                            format!(
+                                "Impl of '{trait_name}' for '{self_ty}' has {methods} methods, exceeding the maximum of {max}"
+                            ),
+                            None,
+                            format!(
+                                "Move some of this logic out of the '{trait_name}' impl, or split up the trait"
+                            ),
+                        );
+                    }
+                }
+                TraitRule::RequireImplementorsPrivate(_)
+                | TraitRule::RequireMethodDocs(_)
+                | TraitRule::MustBeObjectSafe(_) => {}
+            }
+        }
+    }
+
+    // Check a trait definition and the methods it declares, if the trait matches our
+    // patterns
+    fn check_trait_definition(
+        &self,
+        ctx: &LateContext<'_>,
+        item: &Item<'_>,
+        trait_items: &[TraitItemId],
+    ) {
+        let trait_def_id = item.owner_id.to_def_id();
+        if !evaluate_trait_match(
+            &self.patterns,
+            &self.matches,
+            &get_full_canonical_trait_name_from_def_id(&ctx.tcx, trait_def_id),
+        ) {
+            return;
+        }
+
+        let trait_name = ctx.tcx.item_name(trait_def_id).to_string();
+        for rule in &self.trait_rules {
+            match rule {
+                // Only public traits are a contract for others to implement
+                TraitRule::RequireMethodDocs(severity)
+                    if ctx.tcx.visibility(trait_def_id).is_public() =>
+                {
+                    self.check_method_docs(ctx, &trait_name, trait_items, *severity);
+                }
+                TraitRule::MustBeObjectSafe(severity) => {
+                    let mut reasons: Vec<String> = ctx
+                        .tcx
+                        .dyn_compatibility_violations(trait_def_id)
+                        .iter()
+                        .map(|violation| violation.error_msg().into_owned())
+                        .collect();
+                    reasons.dedup();
+                    if !reasons.is_empty() {
+                        span_lint_and_help(
+                            ctx,
+                            TRAIT_LINT_MUST_BE_OBJECT_SAFE::get_by_severity(*severity),
+                            self.name().as_str(),
+                            ctx.tcx.def_span(trait_def_id),
+                            format!(
+                                "Trait '{trait_name}' is not object safe, so it can't be used as `dyn {trait_name}`"
+                            ),
+                            None,
+                            format!(
+                                "'{trait_name}' is not object safe because {}",
+                                reasons.join("; ")
+                            ),
+                        );
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    // Report each method the trait declares without a doc comment
+    fn check_method_docs(
+        &self,
+        ctx: &LateContext<'_>,
+        trait_name: &str,
+        trait_items: &[TraitItemId],
+        severity: Severity,
+    ) {
+        for trait_item in trait_items.iter().map(|id| ctx.tcx.hir_trait_item(*id)) {
+            // Required and provided methods alike make up the trait's contract
+            if !matches!(trait_item.kind, TraitItemKind::Fn(..)) {
+                continue;
+            }
+            let has_doc = ctx
+                .tcx
+                .hir_attrs(trait_item.hir_id())
+                .iter()
+                .filter_map(|attr| attr.doc_str())
+                .any(|doc| !doc.as_str().trim().is_empty());
+            if !has_doc {
+                let method_name = trait_item.ident;
+                span_lint_and_help(
+                    ctx,
+                    TRAIT_LINT_REQUIRE_METHOD_DOCS::get_by_severity(severity),
+                    self.name().as_str(),
+                    ctx.tcx.def_span(trait_item.owner_id),
+                    format!(
+                        "Method '{method_name}' of trait '{trait_name}' is missing a doc comment"
+                    ),
+                    None,
+                    "Add a /// doc comment describing what implementations of this method must do",
+                );
+            }
+        }
+    }
+
+    // Find the local trait impls for the given type whose trait matches our patterns
+    fn matching_traits_implemented_by(&self, ctx: &LateContext<'_>, def_id: DefId) -> Vec<DefId> {
+        ctx.tcx
+            .all_local_trait_impls(())
+            .iter()
+            .filter(|(trait_def_id, _)| {
+                let trait_path =
+                    get_full_canonical_trait_name_from_def_id(&ctx.tcx, **trait_def_id);
+                evaluate_trait_match(&self.patterns, &self.matches, &trait_path)
+            })
+            .filter(|(_, impls)| {
+                impls.iter().any(|impl_def_id| {
+                    matches!(
+                        ctx.tcx.type_of(*impl_def_id).instantiate_identity().kind(),
+                        TyKind::Adt(adt_def, _) if adt_def.did() == def_id
+                    )
+                })
+            })
+            .map(|(trait_def_id, _)| *trait_def_id)
+            .collect()
+    }
+}
+
+// Evaluates the matcher against a trait's fully qualified path
+fn evaluate_trait_match(patterns: &Patterns, matcher: &TraitMatch, trait_path: &str) -> bool {
+    match matcher {
+        TraitMatch::Name(pattern) => patterns.is_match(pattern, trait_path),
+        TraitMatch::AndMatches(left, right) => {
+            evaluate_trait_match(patterns, left, trait_path)
+                && evaluate_trait_match(patterns, right, trait_path)
+        }
+        TraitMatch::OrMatches(left, right) => {
+            evaluate_trait_match(patterns, left, trait_path)
+                || evaluate_trait_match(patterns, right, trait_path)
+        }
+        TraitMatch::NotMatch(inner) => !evaluate_trait_match(patterns, inner, trait_path),
+    }
+}
+
+declare_variable_severity_lint!(
+    pub,
+    TRAIT_LINT_REQUIRE_IMPLEMENTORS_PRIVATE,
+    TRAIT_LINT_REQUIRE_IMPLEMENTORS_PRIVATE_DENY,
+    TRAIT_LINT_REQUIRE_IMPLEMENTORS_PRIVATE_WARN,
+    "Types implementing the trait must be private"
+);
+
+declare_variable_severity_lint!(
+    pub,
+    TRAIT_LINT_MAX_IMPL_METHODS,
+    TRAIT_LINT_MAX_IMPL_METHODS_DENY,
+    TRAIT_LINT_MAX_IMPL_METHODS_WARN,
+    "Impls of the trait must not have too many methods"
+);
+
+declare_variable_severity_lint!(
+    pub,
+    TRAIT_LINT_REQUIRE_METHOD_DOCS,
+    TRAIT_LINT_REQUIRE_METHOD_DOCS_DENY,
+    TRAIT_LINT_REQUIRE_METHOD_DOCS_WARN,
+    "Methods of the trait must have doc comments"
+);
+
+declare_variable_severity_lint!(
+    pub,
+    TRAIT_LINT_MUST_BE_OBJECT_SAFE,
+    TRAIT_LINT_MUST_BE_OBJECT_SAFE_DENY,
+    TRAIT_LINT_MUST_BE_OBJECT_SAFE_WARN,
+    "The trait must be object safe"
+);
+
+impl_lint_pass!(TraitLint => [
+    TRAIT_LINT_REQUIRE_IMPLEMENTORS_PRIVATE_DENY,
+    TRAIT_LINT_REQUIRE_IMPLEMENTORS_PRIVATE_WARN,
+    TRAIT_LINT_MAX_IMPL_METHODS_DENY,
+    TRAIT_LINT_MAX_IMPL_METHODS_WARN,
+    TRAIT_LINT_REQUIRE_METHOD_DOCS_DENY,
+    TRAIT_LINT_REQUIRE_METHOD_DOCS_WARN,
+    TRAIT_LINT_MUST_BE_OBJECT_SAFE_DENY,
+    TRAIT_LINT_MUST_BE_OBJECT_SAFE_WARN
+]);
+
+impl ArchitectureLintRule for TraitLint {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn applies_to_module(&self, _namespace: &str) -> bool {
+        false
+    }
+
+    fn applies_to_trait(&self, trait_path: &str) -> bool {
+        evaluate_trait_match(&self.patterns, &self.matches, trait_path)
+    }
+
+    fn register_late_pass(&self, lint_store: &mut LintStore) {
+        let name = self.name.clone();
+        let matches = self.matches.clone();
+        let trait_rules = self.trait_rules.clone();
+
+        lint_store.register_late_pass(move |_| {
+            Box::new(TraitLint {
+                name: name.clone(),
+                matches: matches.clone(),
+                trait_rules: trait_rules.clone(),
+                patterns: Patterns::new(name.clone()),
+            })
+        });
+    }
+}
+
+impl<'tcx> LateLintPass<'tcx> for TraitLint {
+    fn check_item(&mut self, ctx: &LateContext<'tcx>, item: &'tcx Item<'tcx>) {
+        let _timer = ArchitectureLintRunner::time_lint(&self.name);
+        if let ItemKind::Impl(impl_data) = &item.kind {
+            self.check_trait_impl(ctx, item, impl_data);
+            return;
+        }
+        if let ItemKind::Trait(.., trait_items) = item.kind {
+            self.check_trait_definition(ctx, item, trait_items);
+            return;
+        }
+
+        // Rules about implementors are checked from the implementing types, so that each
+        // violation is reported on (and can be allowed at) the type's own definition
+        if !matches!(
+            item.kind,
+            ItemKind::Struct(..) | ItemKind::Enum(..) | ItemKind::Union(..)
+        ) {
+            return;
+        }
+
+        let def_id = item.owner_id.to_def_id();
+        let type_name = ctx.tcx.item_name(def_id).to_string();
+
+        for rule in &self.trait_rules {
+            match rule {
+                TraitRule::RequireImplementorsPrivate(severity) => {
+                    if !ctx.tcx.visibility(def_id).is_public() {
+                        continue;
+                    }
+
+                    for trait_def_id in self.matching_traits_implemented_by(ctx, def_id) {
+                        let trait_name = ctx.tcx.item_name(trait_def_id).to_string();
+                        span_lint_and_help(
+                            ctx,
+                            TRAIT_LINT_REQUIRE_IMPLEMENTORS_PRIVATE::get_by_severity(*severity),
+                            self.name().as_str(),
+                            ctx.tcx.def_span(def_id),
+                            format!(
+                                "Type '{type_name}' implements '{trait_name}', so it must not be pub"
+                            ),
+                            None,
+                            format!(
+                                "Make '{type_name}' private and expose it to callers as '{trait_name}' instead"
+                            ),
+                        );
+                    }
+                }
+                // Checked on each impl block, and on the trait definition, instead
+                TraitRule::MaxImplMethods(..)
+                | TraitRule::RequireMethodDocs(..)
+                | TraitRule::MustBeObjectSafe(..) => {}
+            }
+        }
+    }
+}
@@ -62,18 +62,36 @@
 #![feature(try_blocks)]
 #![warn(rust_2018_idioms, unused_lifetimes)]
 
-use cargo_pup_common::cli::{PupArgs, PupCli, PupCommand};
+use cargo_pup_common::cli::{GroupBy, OutputFormat, PupArgs, PupCli, PupCommand, Verbosity};
 
 use ansi_term::Colour::{Blue, Cyan, Green, Red, Yellow};
 use ansi_term::Style;
-use cargo_pup_common::project_context::{PUP_DIR, ProjectContext};
-use cargo_pup_common::workspace::find_workspace_pup_ron;
-use cargo_pup_lint_config::LintBuilder;
+use cargo_pup_common::project_context::ProjectContext;
+use cargo_pup_common::timings::{LintTimings, TIMINGS_FILE};
+use cargo_pup_common::workspace::{find_crate_pup_ron, find_member_pup_rons};
+use cargo_pup_lint_config::config_diff::{diff_lints, render_diff};
+use cargo_pup_lint_config::config_loader::{config_files, load_config};
+use cargo_pup_lint_config::report::{ReportEntry, read_report, render_report, violation_counts};
+use cargo_pup_lint_config::{
+    ConfiguredLint, LintBuilder, LintViolation, Severity, parse_violations,
+};
+use notify_debouncer_full::new_debouncer;
+use notify_debouncer_full::notify::RecursiveMode;
+use regex::Regex;
+use std::collections::BTreeMap;
 use std::env;
 use std::error::Error;
 use std::fmt;
+use std::io::IsTerminal;
 use std::path::{Path, PathBuf};
-use std::process::{Command, exit};
+use std::process::{Command, Stdio, exit};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+/// Whether our own output is colored, decided once at startup from `--color` and
+/// `NO_COLOR`
+static COLOR_ENABLED: AtomicBool = AtomicBool::new(true);
 
 #[derive(Debug, PartialEq)]
 enum ProjectType {
@@ -86,6 +104,14 @@ enum ProjectType {
 enum CommandType {
     PrintModules,
     PrintTraits,
+    ListLints,
+    ValidateConfig,
+    Graph,
+    Stats,
+    ExportContext,
+    Watch,
+    Init,
+    Diff,
     Other,
 }
 
@@ -106,8 +132,11 @@ fn validate_project(config_path: Option<&str>) -> ProjectType {
     let pup_ron_path = match config_path {
         Some(path) => PathBuf::from(path),
         None => {
-            // Prioritize workspace root pup.ron, fallback to local only if not in workspace
-            find_workspace_pup_ron().unwrap_or_else(|| PathBuf::from("./pup.ron"))
+            // Each crate uses its nearest pup.ron, so from a workspace root without one
+            // it's enough for a member crate to have its own
+            find_crate_pup_ron(Path::new("."))
+                .or_else(|| find_member_pup_rons().into_iter().next())
+                .unwrap_or_else(|| PathBuf::from("./pup.ron"))
         }
     };
     let cargo_toml_path = Path::new("./Cargo.toml");
@@ -125,10 +154,25 @@ fn validate_project(config_path: Option<&str>) -> ProjectType {
     }
 }
 
+/// The style to paint with, or a plain one that adds no ANSI codes when colors are
+/// off. Everything cargo-pup colors itself goes through here.
+fn colored(style: impl Into<Style>) -> Style {
+    if COLOR_ENABLED.load(Ordering::Relaxed) {
+        style.into()
+    } else {
+        Style::new()
+    }
+}
+
+/// Whether `NO_COLOR` asks for plain output, which it does when set to anything
+fn no_color_requested() -> bool {
+    env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty())
+}
+
 fn show_ascii_puppy() {
     println!(
         "{}",
-        Cyan.paint(
+        colored(Cyan).paint(
             r#"
      / \__
     (    @\___
@@ -148,23 +192,12 @@ fn show_help() {
 fn show_version() {
     println!(
         "{} {}",
-        Style::new().bold().paint("cargo-pup version"),
-        Green.paint(env!("CARGO_PKG_VERSION"))
+        colored(Style::new().bold()).paint("cargo-pup version"),
+        colored(Green).paint(env!("CARGO_PKG_VERSION"))
     );
 }
 
 pub fn main() {
-    // Handle help and version flags
-    if env::args().any(|a| a == "--help" || a == "-h") {
-        show_help();
-        return;
-    }
-
-    if env::args().any(|a| a == "--version" || a == "-V") {
-        show_version();
-        return;
-    }
-
     // Are we being invoked as a rustc wrapper?
     if env::args().len() > 1 && env::args().nth(1).is_some_and(|a| a.ends_with("rustc")) {
         // Special case: Handle rustc version query
@@ -185,6 +218,24 @@ pub fn main() {
         return;
     }
 
+    // Settle on colors before printing anything, the help included
+    let color = PupArgs::parse(env::args()).color;
+    COLOR_ENABLED.store(
+        color.enabled(no_color_requested(), std::io::stdout().is_terminal()),
+        Ordering::Relaxed,
+    );
+
+    // Handle help and version flags
+    if env::args().any(|a| a == "--help" || a == "-h") {
+        show_help();
+        return;
+    }
+
+    if env::args().any(|a| a == "--version" || a == "-V") {
+        show_version();
+        return;
+    }
+
     // Parse command and arguments
     // Normal invocation - process args and run cargo
     let args: Vec<String> = env::args().collect();
@@ -206,10 +257,100 @@ pub fn main() {
         args[1] == "generate-config"
     };
 
+    // An explicitly chosen configuration file has to be there - we don't fall back to pup.ron
+    if let Some(config_path) = pup_args.config_path.as_deref()
+        && !is_generate_config
+        && command != CommandType::Init
+        && let Err(e) = check_config_readable(Path::new(config_path))
+    {
+        eprintln!("Error: {e:#}");
+        exit(1);
+    }
+
+    // list-lints only reads the configuration, so there's no need to involve cargo at all
+    if command == CommandType::ListLints {
+        if let Err(e) = process_list_lints(
+            pup_args.config_path.as_deref(),
+            selected_profile(&pup_args).as_deref(),
+        ) {
+            eprintln!("Error: {e:#}");
+            exit(1);
+        }
+        return;
+    }
+
+    // diff only compares two configurations, so it doesn't need cargo either
+    if let PupCommand::Diff { from, to } = &pup_args.command {
+        if from.is_empty() || to.is_empty() {
+            eprintln!(
+                "Error: diff needs two configuration files, e.g. cargo pup diff old.ron pup.ron"
+            );
+            exit(1);
+        }
+        match process_diff(
+            Path::new(from),
+            Path::new(to),
+            selected_profile(&pup_args).as_deref(),
+        ) {
+            Ok(diff) => print!("{diff}"),
+            Err(e) => {
+                eprintln!("{} {e:#}", colored(Red.bold()).paint("Error:"));
+                exit(1);
+            }
+        }
+        return;
+    }
+
+    // validate-config only parses the configuration, so it doesn't need cargo either
+    if command == CommandType::ValidateConfig {
+        let config_path = resolve_config_path(pup_args.config_path.as_deref());
+        match process_validate_config(&config_path, selected_profile(&pup_args).as_deref()) {
+            Ok(lint_count) => println!(
+                "{} {} is valid, defining {lint_count} lint(s)",
+                colored(Green).paint("OK"),
+                config_path.display()
+            ),
+            Err(e) => {
+                eprintln!("{} {e:#}", colored(Red.bold()).paint("Error:"));
+                exit(1);
+            }
+        }
+        return;
+    }
+
+    // init only writes template files, so it doesn't need cargo either
+    if command == CommandType::Init {
+        match process_init(Path::new("."), pup_args.init_test) {
+            Ok(created) => {
+                for path in &created {
+                    println!("{} {}", colored(Green).paint("Created"), path.display());
+                }
+                if pup_args.init_test {
+                    println!(
+                        "Add {} to your [dev-dependencies], then run {}",
+                        colored(Yellow).paint(format!(
+                            "cargo_pup_lint_config = \"{}\"",
+                            env!("CARGO_PKG_VERSION")
+                        )),
+                        colored(Green).paint("cargo test --test architecture")
+                    );
+                }
+            }
+            Err(e) => {
+                eprintln!("Error: {e}");
+                exit(1);
+            }
+        }
+        return;
+    }
+
     // Skip environment checks if we're generating a config or running print commands
     let skip_checks = is_generate_config
         || command == CommandType::PrintModules
-        || command == CommandType::PrintTraits;
+        || command == CommandType::PrintTraits
+        || command == CommandType::Graph
+        || command == CommandType::Stats
+        || command == CommandType::ExportContext;
 
     if !skip_checks {
         match validate_project(pup_args.config_path.as_deref()) {
@@ -218,30 +359,40 @@ pub fn main() {
             }
             ProjectType::RustProject => {
                 // In a Rust project but missing configuration file
-                show_ascii_puppy();
+                if pup_args.verbosity != Verbosity::Quiet {
+                    show_ascii_puppy();
+                }
                 println!(
                     "{}",
-                    Red.bold()
-                        .paint("Missing pup.ron configuration - nothing to do!")
+                    colored(Red.bold()).paint("Missing pup.ron configuration - nothing to do!")
                 );
                 println!("Consider generating an initial configuration:");
-                println!("  {}", Green.paint("cargo pup generate-config"));
+                println!("  {}", colored(Green).paint("cargo pup generate-config"));
                 exit(-1)
             }
             ProjectType::OtherDirectory => {
                 // Not in a cargo project directory
-                show_ascii_puppy();
-                println!("{}", Red.bold().paint("Not in a Cargo project directory!"));
+                if pup_args.verbosity != Verbosity::Quiet {
+                    show_ascii_puppy();
+                }
+                println!(
+                    "{}",
+                    colored(Red.bold()).paint("Not in a Cargo project directory!")
+                );
                 println!(
                     "{}",
-                    Yellow.paint("cargo-pup is an architectural linting tool for Rust projects.")
+                    colored(Yellow)
+                        .paint("cargo-pup is an architectural linting tool for Rust projects.")
                 );
                 println!("It needs to be run from a directory containing a Cargo.toml file.");
                 println!("\nTo use cargo-pup:");
                 println!("  1. Navigate to a Rust project directory");
-                println!("  2. Run {}", Green.paint("cargo pup generate-config"));
+                println!(
+                    "  2. Run {}",
+                    colored(Green).paint("cargo pup generate-config")
+                );
                 println!("  3. Edit the generated pup.ron file");
-                println!("  4. Run {}", Green.paint("cargo pup"));
+                println!("  4. Run {}", colored(Green).paint("cargo pup"));
                 exit(-1)
             }
         }
@@ -253,29 +404,97 @@ pub fn main() {
     // Process the command
     match command {
         CommandType::PrintModules => {
+            // Reject a bad filter before spending time on the build
+            let filter = match parse_filter(pup_args.filter.as_deref()) {
+                Ok(filter) => filter,
+                Err(e) => {
+                    eprintln!("Error: {e}");
+                    exit(1);
+                }
+            };
+
             // First run normal process to generate context data
             if let Err(code) = process(env::args()) {
                 exit(code.0);
             }
 
             // Then load and display the generated data
-            if let Err(e) = process_print_modules() {
+            if let Err(e) =
+                process_print_modules(&pup_args.pup_dir(), pup_args.verbosity, filter.as_ref())
+            {
                 eprintln!("Error: {e}");
                 exit(1);
             }
         }
         CommandType::PrintTraits => {
+            // Reject a bad filter before spending time on the build
+            let filter = match parse_filter(pup_args.filter.as_deref()) {
+                Ok(filter) => filter,
+                Err(e) => {
+                    eprintln!("Error: {e}");
+                    exit(1);
+                }
+            };
+
             // First run normal process to generate context data
             if let Err(code) = process(env::args()) {
                 exit(code.0);
             }
 
             // Then load and display the generated data
-            if let Err(e) = process_print_traits() {
+            if let Err(e) =
+                process_print_traits(&pup_args.pup_dir(), pup_args.verbosity, filter.as_ref())
+            {
+                eprintln!("Error: {e}");
+                exit(1);
+            }
+        }
+        CommandType::Graph => {
+            // First run normal process to generate context data
+            if let Err(code) = process(env::args()) {
+                exit(code.0);
+            }
+
+            // Then render the collected import edges
+            if let Err(e) = process_graph(&pup_args.pup_dir(), pup_args.output_path.as_deref()) {
+                eprintln!("Error: {e}");
+                exit(1);
+            }
+        }
+        CommandType::Stats => {
+            // First run normal process to generate context data
+            if let Err(code) = process(env::args()) {
+                exit(code.0);
+            }
+
+            // Then summarize it
+            if let Err(e) = process_stats(&pup_args.pup_dir(), pup_args.output_format) {
+                eprintln!("Error: {e}");
+                exit(1);
+            }
+        }
+        CommandType::ExportContext => {
+            // First run normal process to generate context data
+            if let Err(code) = process(env::args()) {
+                exit(code.0);
+            }
+
+            // Then write it out as it is
+            if let Err(e) =
+                process_export_context(&pup_args.pup_dir(), pup_args.output_path.as_deref())
+            {
                 eprintln!("Error: {e}");
                 exit(1);
             }
         }
+        CommandType::Watch => {
+            let config_path = pup_args.config_path.as_deref().map(Path::new);
+            process_watch(&args, config_path);
+        }
+        CommandType::ListLints => unreachable!("list-lints is handled above"),
+        CommandType::ValidateConfig => unreachable!("validate-config is handled above"),
+        CommandType::Init => unreachable!("init is handled above"),
+        CommandType::Diff => unreachable!("diff is handled above"),
         CommandType::Other => {
             // Run normal process flow
             if let Err(code) = process(env::args()) {
@@ -297,31 +516,106 @@ where
 
     // Check if we're generating config and the file already exists
     if command == PupCommand::GenerateConfig {
-        // Check for existing pup.ron and pup.generated.ron in the project root
-        let pup_ron_exists = Path::exists(Path::new("./pup.ron"));
-        let pup_generated_ron_exists = Path::exists(Path::new("./pup.generated.ron"));
-
-        // Determine target filename based on existence of pup.ron
-        let target_filename = if pup_ron_exists {
-            "pup.generated.ron"
-        } else {
-            "pup.ron"
-        };
+        let target = generated_config_path(pup_args.output_dir.as_deref());
+        if target.exists() {
+            println!("Error: {} already exists.", target.display());
+            println!("Remove this file if you want to regenerate the configuration.");
+            return Err(CommandExitStatus(1));
+        }
+    }
 
-        // If target file already exists, show error
-        if (target_filename == "pup.ron" && pup_ron_exists)
-            || (target_filename == "pup.generated.ron" && pup_generated_ron_exists)
+    // An unknown profile would only be reported once cargo runs pup-driver, so make sure
+    // the configuration loads with it before starting
+    let profile = selected_profile(&pup_args);
+    if command == PupCommand::Check
+        && let Some(profile) = profile.as_deref()
+    {
+        let config_path = resolve_config_path(pup_args.config_path.as_deref());
+        if config_path.exists()
+            && let Err(e) = load_config(&config_path, Some(profile))
         {
-            println!("Error: {target_filename} already exists in the project root.");
-            println!("Remove this file if you want to regenerate the configuration.");
+            eprintln!("Error: {e:#}");
             return Err(CommandExitStatus(1));
         }
     }
 
+    // With --since, only diagnostics in files changed since the given ref are reported
+    let changed_files = match &pup_args.since {
+        Some(since) => match changed_rust_files(Path::new("."), since) {
+            Ok(files) => Some(files),
+            Err(e) => {
+                eprintln!("Error: {e:#}");
+                return Err(CommandExitStatus(1));
+            }
+        },
+        None => None,
+    };
+
+    // pup-driver doesn't necessarily run from here, so it's given an absolute path
+    let current_dir = env::current_dir().map_err(|_| CommandExitStatus(1))?;
+    let pup_dir = pup_args.pup_dir();
+    let output_dir = pup_args
+        .output_dir
+        .as_ref()
+        .map(|dir| current_dir.join(dir).to_string_lossy().into_owned());
+
+    // Each run appends to a report file of its own. Its path is part of the arguments
+    // given to pup-driver, so cargo re-checks every crate rather than replaying cached
+    // diagnostics that would never reach the report.
+    // The same goes for the file each crate adds its lint timings to.
+    let run_file = |name: &str, extension: &str| -> Result<PathBuf, CommandExitStatus> {
+        let run_file = current_dir
+            .join(&pup_dir)
+            .join(format!("{name}-{}.{extension}", std::process::id()));
+        let prepared = std::fs::create_dir_all(current_dir.join(&pup_dir)).and_then(|_| {
+            match std::fs::remove_file(&run_file) {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+                _ => Ok(()),
+            }
+        });
+        if let Err(e) = prepared {
+            eprintln!("Error: Failed to prepare {}: {e}", run_file.display());
+            return Err(CommandExitStatus(1));
+        }
+        Ok(run_file)
+    };
+    // Lints with an escalate_after limit count their violations across the whole
+    // workspace, so the crates add them to the report even without --report
+    let limited_lints = if command == PupCommand::Check {
+        lints_with_escalation_limits(pup_args.config_path.as_deref(), profile.as_deref())
+    } else {
+        Vec::new()
+    };
+    let report_file = if command == PupCommand::Check
+        && (pup_args.report_path.is_some() || !limited_lints.is_empty())
+    {
+        Some(run_file("report", "jsonl")?)
+    } else {
+        None
+    };
+    let timings_file = if pup_args.timings && command == PupCommand::Check {
+        Some(run_file("timings", "json")?)
+    } else {
+        None
+    };
+
     // Create configuration to pass through to pup-driver
     let pup_cli = PupCli {
         command: pup_args.command.clone(),
         config_path: pup_args.config_path.clone(),
+        deny_warnings: pup_args.deny_warnings,
+        exit_zero: pup_args.exit_zero,
+        fail_fast: pup_args.fail_fast,
+        changed_files,
+        verbosity: pup_args.verbosity,
+        output_dir,
+        report_file: report_file
+            .as_ref()
+            .map(|path| path.to_string_lossy().into_owned()),
+        timings_file: timings_file
+            .as_ref()
+            .map(|path| path.to_string_lossy().into_owned()),
+        profile: profile.clone(),
     };
 
     // Convert args to string for environment
@@ -330,6 +624,29 @@ where
     // Format cargo args for environment
     let cargo_args_str = pup_args.cargo_args.join("__PUP_ARG_SEP__");
 
+    // Commands that only read the project contexts can use the ones already in the pup
+    // directory when each was built from the same arguments and unchanged files
+    let reads_contexts = matches!(
+        command,
+        PupCommand::PrintModules
+            | PupCommand::PrintTraits
+            | PupCommand::GenerateConfig
+            | PupCommand::Graph
+            | PupCommand::Stats
+            | PupCommand::ExportContext
+    );
+    if reads_contexts
+        && ProjectContext::contexts_are_fresh(&pup_dir, &cli_args, &pup_args.cargo_args)
+    {
+        if pup_args.verbosity == Verbosity::Verbose {
+            eprintln!("cargo-pup: project contexts are up to date");
+        }
+        if command == PupCommand::GenerateConfig {
+            generate_config_from_contexts(&pup_args, &pup_dir);
+        }
+        return Ok(());
+    }
+
     // Get the same toolchain used for pup-driver
     let toolchain = get_toolchain();
 
@@ -351,85 +668,206 @@ where
     cmd.arg("run").arg(&toolchain).arg("cargo");
 
     // Set up environment variables
-    let current_dir = env::current_dir().map_err(|_| CommandExitStatus(1))?;
     cmd.env("RUSTC_WORKSPACE_WRAPPER", get_pup_path())
         .env("PUP_CLI_ARGS", cli_args)
         .env("PUP_CARGO_ARGS", cargo_args_str)
         .env("PUP_ORIGINAL_DIR", current_dir.to_str().unwrap())
         .arg("check")
         .arg("--target-dir")
-        .arg(".pup");
+        .arg(&pup_dir);
 
     // Add cargo args
     cmd.args(&pup_args.cargo_args);
 
+    // Cargo renders the compiler's diagnostics, so it follows the same color choice
+    if let Some(color) = pup_args.color.cargo_value(no_color_requested()) {
+        cmd.arg("--color").arg(color);
+    }
+
+    // The JUnit report and grouped output are built from cargo's JSON messages, so
+    // capture them. The report takes precedence if both are asked for.
+    let write_report = pup_args.output_format == OutputFormat::Junit;
+    let group_by = pup_args.group_by.filter(|_| !write_report);
+    if write_report || group_by.is_some() {
+        cmd.arg("--message-format=json").stdout(Stdio::piped());
+    }
+
+    if pup_args.verbosity == Verbosity::Verbose && command == PupCommand::Check {
+        let config_path = resolve_config_path(pup_args.config_path.as_deref());
+        eprintln!("cargo-pup: checking with {}", config_path.display());
+    }
+
     // Run cargo with our wrapper
-    let exit_status = cmd
+    let started = Instant::now();
+    let output = cmd
         .spawn()
         .expect("could not run cargo")
-        .wait()
+        .wait_with_output()
         .expect("failed to wait for cargo?");
+    let exit_status = output.status;
+    if pup_args.verbosity == Verbosity::Verbose {
+        eprintln!("cargo-pup: finished in {:.2?}", started.elapsed());
+    }
 
-    // If we just ran generate-config and it succeeded, generate the combined config file
-    if exit_status.success() && command == PupCommand::GenerateConfig {
-        // Load all contexts from the .pup directory
-        let pup_dir = Path::new(PUP_DIR);
-        if !pup_dir.exists() {
-            // No .pup directory, so there are no contexts to process
-            return Ok(());
+    if write_report {
+        let cargo_stdout = String::from_utf8_lossy(&output.stdout);
+        // The report goes to a file, so the diagnostics are still shown as usual
+        for rendered in rendered_compiler_messages(&cargo_stdout) {
+            eprint!("{rendered}");
+        }
+        if let Err(e) = write_junit_report(
+            pup_args.config_path.as_deref(),
+            profile.as_deref(),
+            pup_args.output_path.as_deref(),
+            &cargo_stdout,
+        ) {
+            eprintln!("Error: {e}");
+            return Err(CommandExitStatus(1));
         }
+    }
 
-        // Load all contexts using ProjectContext's loading functionality
-        let (contexts, crate_names) = match ProjectContext::load_all_contexts_with_crate_names() {
-            Ok((contexts, crate_names)) => (contexts, crate_names),
+    let mut escalated = false;
+    if let Some(report_file) = &report_file {
+        let entries = read_report(report_file);
+        let _ = std::fs::remove_file(report_file);
+        let entries = match entries {
+            Ok(entries) => entries,
             Err(e) => {
-                println!("Warning: Failed to load project contexts: {e}");
-                println!(
-                    "Make sure that context files (with *_context.json suffix) exist in the .pup directory."
-                );
-                println!("These files should be created by pup-driver during compilation.");
-                return Ok(());
+                eprintln!("Error: Failed to read {}: {e}", report_file.display());
+                return Err(CommandExitStatus(1));
             }
         };
+        if let Some(report_path) = &pup_args.report_path
+            && let Err(e) = write_workspace_report(&entries, Path::new(report_path))
+        {
+            eprintln!("Error: {e:#}");
+            return Err(CommandExitStatus(1));
+        }
+        // With --exit-zero, lints over their limit are only reported
+        for message in escalation_messages(&limited_lints, &entries) {
+            if pup_args.exit_zero {
+                eprintln!("warning: {message}");
+            } else {
+                eprintln!("error: {message}");
+                escalated = true;
+            }
+        }
+    }
+
+    if let Some(timings_file) = &timings_file {
+        report_timings(timings_file, &current_dir.join(&pup_dir).join(TIMINGS_FILE));
+    }
+
+    if let Some(group_by) = group_by {
+        let cargo_stdout = String::from_utf8_lossy(&output.stdout);
+        // Anything that isn't a pup violation, such as a compile error, is shown as usual
+        for rendered in other_compiler_messages(&cargo_stdout) {
+            eprint!("{rendered}");
+        }
+        print!(
+            "{}",
+            render_grouped_violations(&parse_violations(&cargo_stdout), group_by)
+        );
+    }
+
+    // If we just ran generate-config and it succeeded, generate the combined config file
+    if exit_status.success() && command == PupCommand::GenerateConfig {
+        generate_config_from_contexts(&pup_args, &pup_dir);
+    }
+
+    if !exit_status.success() {
+        Err(CommandExitStatus(exit_status.code().unwrap_or(-1)))
+    } else if escalated {
+        Err(CommandExitStatus(1))
+    } else {
+        Ok(())
+    }
+}
 
-        if contexts.modules.is_empty() && contexts.traits.is_empty() {
-            println!("No modules or traits found to generate configuration from.");
-            return Ok(());
+/// Generate the combined configuration from the project contexts in the pup directory
+fn generate_config_from_contexts(pup_args: &PupArgs, pup_dir: &Path) {
+    // Load all contexts from the .pup directory
+    if !pup_dir.exists() {
+        // No .pup directory, so there are no contexts to process
+        return;
+    }
+
+    // Load all contexts using ProjectContext's loading functionality
+    let (contexts, crate_names) = match ProjectContext::load_all_contexts_from_dir(pup_dir) {
+        Ok((contexts, crate_names)) => (contexts, crate_names),
+        Err(e) => {
+            println!("Warning: Failed to load project contexts: {e}");
+            println!(
+                "Make sure that context files (with *_context.json suffix) exist in {}.",
+                pup_dir.display()
+            );
+            println!("These files should be created by pup-driver during compilation.");
+            return;
         }
+    };
+
+    if contexts.modules.is_empty() && contexts.traits.is_empty() {
+        println!("No modules or traits found to generate configuration from.");
+        return;
+    }
 
+    if pup_args.verbosity != Verbosity::Quiet {
         println!("Loaded project contexts from {} crates.", crate_names.len());
+    }
 
-        // Determine target filename based on existence of pup.ron
-        let pup_ron_exists = Path::exists(Path::new("./pup.ron"));
-        let target_filename = if pup_ron_exists {
-            "pup.generated.ron"
-        } else {
-            "pup.ron"
-        };
+    let target = generated_config_path(pup_args.output_dir.as_deref());
 
-        // Generate the configuration from the loaded contexts using LintBuilder
-        let builder = LintBuilder::generate_from_contexts(&[contexts]);
+    // Generate the configuration from the loaded contexts using LintBuilder
+    let notes = feature_gate_notes(&contexts);
+    let builder = LintBuilder::generate_from_contexts(&[contexts]);
 
-        // Write the generated configuration to the target file
-        match builder.write_to_file(target_filename) {
-            Ok(_) => {
-                println!(
-                    "Created {} with configuration generated from {} crates' contexts",
-                    target_filename,
-                    crate_names.len()
-                );
-            }
-            Err(e) => {
-                println!("Warning: Failed to write {target_filename}: {e}");
-            }
+    // Write the generated configuration to the target file
+    let written = ron::ser::to_string_pretty(&builder, ron::ser::PrettyConfig::default())
+        .map_err(std::io::Error::other)
+        .and_then(|config| std::fs::write(&target, format!("{notes}{config}")));
+    match written {
+        Ok(_) => {
+            println!(
+                "Created {} with configuration generated from {} crates' contexts",
+                target.display(),
+                crate_names.len()
+            );
+        }
+        Err(e) => {
+            println!("Warning: Failed to write {}: {e}", target.display());
         }
     }
+}
 
-    if exit_status.success() {
-        Ok(())
+/// Where generate-config writes its configuration: `pup.ron`, or `pup.generated.ron`
+/// when the project already has a `pup.ron`. With `--output-dir` the file goes into
+/// that directory rather than the project root.
+fn generated_config_path(output_dir: Option<&str>) -> PathBuf {
+    let file_name = if Path::new("./pup.ron").exists() {
+        "pup.generated.ron"
     } else {
-        Err(CommandExitStatus(exit_status.code().unwrap_or(-1)))
+        "pup.ron"
+    };
+    Path::new(output_dir.unwrap_or(".")).join(file_name)
+}
+
+/// Comment lines for the top of a generated configuration, listing the feature-gated
+/// modules that were seen while generating it. Modules behind features that weren't
+/// enabled aren't visible at all, so the notes also mention `--all-features`.
+fn feature_gate_notes(context: &ProjectContext) -> String {
+    if context.feature_gated_modules.is_empty() {
+        return String::new();
+    }
+
+    let mut notes = String::from(
+        "// The following modules are feature-gated, so rules generated for them only apply\n\
+         // when their cfg holds. Generate with --all-features to include every gated module.\n",
+    );
+    for gated in &context.feature_gated_modules {
+        notes.push_str(&format!("//   {}: #[cfg({})]\n", gated.module, gated.cfg));
     }
+    notes.push('\n');
+    notes
 }
 
 fn get_pup_path() -> String {
@@ -510,130 +948,961 @@ fn get_command_type(args: &[String]) -> CommandType {
         && ((args.len() > 2 && args[1] == "pup" && args[2] == "print-traits")
             || (args[1] == "print-traits"));
 
+    // Check for list-lints command
+    let is_list_lints = args.len() > 1
+        && ((args.len() > 2 && args[1] == "pup" && args[2] == "list-lints")
+            || (args[1] == "list-lints"));
+
+    // Check for graph command
+    let is_graph = args.len() > 1
+        && ((args.len() > 2 && args[1] == "pup" && args[2] == "graph") || (args[1] == "graph"));
+
+    // Check for stats command
+    let is_stats = args.len() > 1
+        && ((args.len() > 2 && args[1] == "pup" && args[2] == "stats") || (args[1] == "stats"));
+
+    // Check for export-context command
+    let is_export_context = args.len() > 1
+        && ((args.len() > 2 && args[1] == "pup" && args[2] == "export-context")
+            || (args[1] == "export-context"));
+
+    // Check for watch command
+    let is_watch = args.len() > 1
+        && ((args.len() > 2 && args[1] == "pup" && args[2] == "watch") || (args[1] == "watch"));
+
+    // Check for validate-config command
+    let is_validate_config = args.len() > 1
+        && ((args.len() > 2 && args[1] == "pup" && args[2] == "validate-config")
+            || (args[1] == "validate-config"));
+
+    // Check for init command
+    let is_init = args.len() > 1
+        && ((args.len() > 2 && args[1] == "pup" && args[2] == "init") || (args[1] == "init"));
+
+    // Check for diff command
+    let is_diff = args.len() > 1
+        && ((args.len() > 2 && args[1] == "pup" && args[2] == "diff") || (args[1] == "diff"));
+
     if is_print_modules {
         CommandType::PrintModules
     } else if is_print_traits {
         CommandType::PrintTraits
+    } else if is_list_lints {
+        CommandType::ListLints
+    } else if is_validate_config {
+        CommandType::ValidateConfig
+    } else if is_graph {
+        CommandType::Graph
+    } else if is_stats {
+        CommandType::Stats
+    } else if is_export_context {
+        CommandType::ExportContext
+    } else if is_watch {
+        CommandType::Watch
+    } else if is_init {
+        CommandType::Init
+    } else if is_diff {
+        CommandType::Diff
     } else {
         CommandType::Other
     }
 }
 
+/// Compile the `--filter` pattern of print-modules and print-traits, if one was given
+fn parse_filter(filter: Option<&str>) -> anyhow::Result<Option<Regex>> {
+    use anyhow::Context;
+
+    filter
+        .map(|pattern| {
+            Regex::new(pattern).with_context(|| format!("Invalid --filter pattern '{pattern}'"))
+        })
+        .transpose()
+}
+
 /// Process the print-modules command by loading contexts from disk and displaying them
-fn process_print_modules() -> anyhow::Result<()> {
+fn process_print_modules(
+    pup_dir: &Path,
+    verbosity: Verbosity,
+    filter: Option<&Regex>,
+) -> anyhow::Result<()> {
     use anyhow::Context;
     use cargo_pup_common::project_context::ProjectContext;
 
-    // Load all context data from .pup directory
-    let (context, crate_names) = ProjectContext::load_all_contexts_with_crate_names()
+    // Load all context data from the .pup directory
+    let (context, crate_names) = ProjectContext::load_all_contexts_from_dir(pup_dir)
         .context("Failed to load project context data")?;
 
     // Use the utility function to print the modules
-    print_modules(&context, &crate_names)?;
+    print_modules(&context, &crate_names, verbosity, filter)?;
     Ok(())
 }
 
 /// Process the print-traits command by loading contexts from disk and displaying them
-fn process_print_traits() -> anyhow::Result<()> {
+fn process_print_traits(
+    pup_dir: &Path,
+    verbosity: Verbosity,
+    filter: Option<&Regex>,
+) -> anyhow::Result<()> {
     use anyhow::Context;
     use cargo_pup_common::project_context::ProjectContext;
 
-    // Load all context data from .pup directory
-    let (context, crate_names) = ProjectContext::load_all_contexts_with_crate_names()
+    // Load all context data from the .pup directory
+    let (context, crate_names) = ProjectContext::load_all_contexts_from_dir(pup_dir)
         .context("Failed to load project context data")?;
 
     // Use the utility function to print the traits
-    print_traits(&context, &crate_names)?;
+    print_traits(&context, &crate_names, verbosity, filter)?;
     Ok(())
 }
 
-fn get_toolchain() -> String {
-    // We want to run with the same toolchain we were built with. This deals
-    // with the dynamic-linking-against-librustc_driver piece, but _will_ add that toolchain
-    // to the user's local rustup installs.
-    let toolchain_config = include_str!("../rust-toolchain.toml");
-    let toml = toml::from_str::<toml::Value>(toolchain_config).unwrap();
+/// Process the graph command by loading contexts from disk and writing the module
+/// import graph as Graphviz, to `modules.dot` in the .pup directory unless another
+/// path is given
+fn process_graph(pup_dir: &Path, output_path: Option<&str>) -> anyhow::Result<()> {
+    use anyhow::Context;
+    use cargo_pup_common::project_context::ProjectContext;
 
-    // Work out which toolchain we want to run against
-    toml.get("toolchain")
-        .unwrap()
-        .get("channel")
-        .unwrap()
-        .as_str()
-        .unwrap()
-        .to_string()
-}
+    let (context, crate_names) = ProjectContext::load_all_contexts_from_dir(pup_dir)
+        .context("Failed to load project context data")?;
 
-#[must_use]
-pub fn help_message() -> String {
-    format!(
-        "
-{title}: Checks your architecture against your architecture lint file.
+    let output_path = match output_path {
+        Some(path) => PathBuf::from(path),
+        None => pup_dir.join("modules.dot"),
+    };
+    std::fs::write(&output_path, render_module_graph(&context, &crate_names))
+        .with_context(|| format!("Failed to write {}", output_path.display()))?;
 
-{usage_label}:
-    cargo pup [COMMAND] [OPTIONS] [--] [CARGO_ARGS...]
+    println!("Module graph written to {}", output_path.display());
+    Ok(())
+}
 
-{commands_label}:
-    {check}            Run architectural lints (default)
-    {print_modules}    Print all modules and applicable lints
-    {print_traits}     Print all traits
-    {generate_config}  Generates an initial pup.ron for your project.
+/// Process the stats command by loading contexts from disk and printing aggregate
+/// metrics about them, as a table or as JSON
+fn process_stats(pup_dir: &Path, format: OutputFormat) -> anyhow::Result<()> {
+    use anyhow::Context;
+    use cargo_pup_common::stats::ProjectStats;
 
-{options_label}:
-    -h, --help             Print this message
-    -V, --version          Print version info and exit
-    --pup-config=PATH      Specify an alternative configuration file path
+    let (context, crate_names) = ProjectContext::load_all_contexts_from_dir(pup_dir)
+        .context("Failed to load project context data")?;
+
+    let stats = ProjectStats::from_context(&context, &crate_names);
+    if format == OutputFormat::Json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&stats).context("Failed to serialize stats")?
+        );
+    } else {
+        print!("{}", stats.render_text());
+    }
+    Ok(())
+}
+
+/// Process the export-context command by loading contexts from disk and writing the
+/// merged context as pretty JSON, to stdout unless a path is given
+fn process_export_context(pup_dir: &Path, output_path: Option<&str>) -> anyhow::Result<()> {
+    use anyhow::Context;
+
+    let (context, _) = ProjectContext::load_all_contexts_from_dir(pup_dir)
+        .context("Failed to load project context data")?;
+    let json =
+        serde_json::to_string_pretty(&context).context("Failed to serialize project context")?;
+
+    match output_path {
+        Some(path) => {
+            std::fs::write(path, format!("{json}\n"))
+                .with_context(|| format!("Failed to write {path}"))?;
+            eprintln!("Project context written to {path}");
+        }
+        None => println!("{json}"),
+    }
+    Ok(())
+}
+
+/// Render the module import graph in Graphviz dot format. Only edges between modules
+/// of the analysed crates are included; imports from external dependencies are dropped.
+fn render_module_graph(context: &ProjectContext, crate_names: &[String]) -> String {
+    let is_local = |module: &str| {
+        let krate = module.split("::").next().unwrap_or(module);
+        crate_names.iter().any(|name| name == krate)
+    };
+
+    let mut dot = String::from("digraph modules {\n");
+    for dependency in &context.module_dependencies {
+        if is_local(&dependency.from) && is_local(&dependency.to) {
+            dot.push_str(&format!(
+                "    \"{}\" -> \"{}\";\n",
+                dependency.from, dependency.to
+            ));
+        }
+    }
+    dot.push_str("}\n");
+    dot
+}
+
+/// Write the JUnit report for a check run to `output_path`, or to stdout if no path is given.
+/// Violations are parsed from cargo's JSON message stream.
+fn write_junit_report(
+    config_path: Option<&str>,
+    profile: Option<&str>,
+    output_path: Option<&str>,
+    cargo_stdout: &str,
+) -> anyhow::Result<()> {
+    use anyhow::Context;
+
+    // Every lint the check ran gets a test case, those from included files too
+    let config_path = resolve_config_path(config_path);
+    let lint_builder = load_config(&config_path, profile)
+        .with_context(|| format!("Failed to load {}", config_path.display()))?;
+
+    let report = render_junit_report(&lint_builder.lints, &parse_violations(cargo_stdout));
+    match output_path {
+        Some(path) => {
+            std::fs::write(path, report).with_context(|| format!("Failed to write {path}"))?;
+            println!("JUnit report written to {path}");
+        }
+        None => print!("{report}"),
+    }
+    Ok(())
+}
+
+/// Print the time each lint spent across the workspace, slowest first, and keep the
+/// timings of this run at `kept_path` for later tooling to read
+fn report_timings(timings_file: &Path, kept_path: &Path) {
+    let timings = match LintTimings::read(timings_file) {
+        Ok(timings) => timings,
+        Err(e) => {
+            eprintln!(
+                "Warning: couldn't read lint timings from {}: {e}",
+                timings_file.display()
+            );
+            return;
+        }
+    };
+    if timings.crates.is_empty() {
+        // Cargo had nothing to check, or every crate failed to compile first
+        eprintln!("No lints ran, so there are no timings to report");
+        return;
+    }
+    eprint!("{}", timings.render_text());
+    if let Err(e) = std::fs::rename(timings_file, kept_path) {
+        eprintln!(
+            "Warning: couldn't keep lint timings at {}: {e}",
+            kept_path.display()
+        );
+    }
+}
+
+/// Render the violations each crate appended to the run's report as one report for the
+/// whole workspace, and write it to `output_path`
+fn write_workspace_report(entries: &[ReportEntry], output_path: &Path) -> anyhow::Result<()> {
+    use anyhow::Context;
+
+    std::fs::write(output_path, render_report(entries))
+        .with_context(|| format!("Failed to write {}", output_path.display()))?;
+    println!(
+        "Workspace report written to {}, with {} violation(s)",
+        output_path.display(),
+        entries.len()
+    );
+    Ok(())
+}
+
+/// The lints a check runs that have an `escalate_after` limit, from the configuration
+/// given with `--pup-config` or else the workspace members' pup.ron files. A lint is
+/// counted by name across the workspace, so the first configuration to name it sets its
+/// limit. A configuration that doesn't load has none, and pup-driver reports what's
+/// wrong with it.
+fn lints_with_escalation_limits(
+    config_path: Option<&str>,
+    profile: Option<&str>,
+) -> Vec<ConfiguredLint> {
+    let configs = match config_path {
+        Some(config_path) => vec![PathBuf::from(config_path)],
+        None => {
+            let mut configs = find_member_pup_rons();
+            configs.push(resolve_config_path(None));
+            configs
+        }
+    };
+
+    let mut lints: Vec<ConfiguredLint> = Vec::new();
+    for config in configs.iter().filter(|config| config.exists()) {
+        let Ok(lint_builder) = load_config(config, profile) else {
+            continue;
+        };
+        for lint in lint_builder.lints {
+            if lint.escalate_after().is_some()
+                && !lints.iter().any(|known| known.name() == lint.name())
+            {
+                lints.push(lint);
+            }
+        }
+    }
+    lints
+}
+
+/// Describe every lint that reported more violations across the workspace than its
+/// `escalate_after` limit
+fn escalation_messages(lints: &[ConfiguredLint], entries: &[ReportEntry]) -> Vec<String> {
+    let counts = violation_counts(entries);
+    lints
+        .iter()
+        .filter_map(|lint| {
+            let limit = lint.escalate_after()?;
+            let name = lint.name();
+            let count = counts.get(name).copied().unwrap_or(0);
+            (count > limit).then(|| {
+                format!(
+                    "cargo-pup lint '{name}' reported {count} violation(s), more than its escalate_after limit of {limit}"
+                )
+            })
+        })
+        .collect()
+}
+
+/// Render a JUnit XML report with one test suite per lint kind and one test case per
+/// configured lint. Each violation becomes a failure of its lint's test case. Violations
+/// from lints that aren't in the configuration are reported under an `other` suite.
+fn render_junit_report(lints: &[ConfiguredLint], violations: &[LintViolation]) -> String {
+    // Group the lint names by kind, keeping the configuration's order
+    let mut suites: Vec<(&str, Vec<&str>)> = Vec::new();
+    for lint in lints {
+        match suites.iter_mut().find(|(kind, _)| *kind == lint.kind()) {
+            Some((_, names)) => names.push(lint.name()),
+            None => suites.push((lint.kind(), vec![lint.name()])),
+        }
+    }
+    let mut unconfigured: Vec<&str> = violations
+        .iter()
+        .map(|violation| violation.lint_name.as_str())
+        .filter(|name| !lints.iter().any(|lint| lint.name() == *name))
+        .collect();
+    unconfigured.sort();
+    unconfigured.dedup();
+    if !unconfigured.is_empty() {
+        suites.push(("other", unconfigured));
+    }
+
+    let failures_of = |name: &str| {
+        violations
+            .iter()
+            .filter(|violation| violation.lint_name == name)
+            .collect::<Vec<_>>()
+    };
+    let failed_count = |names: &[&str]| {
+        names
+            .iter()
+            .filter(|name| !failures_of(name).is_empty())
+            .count()
+    };
+
+    let total_tests: usize = suites.iter().map(|(_, names)| names.len()).sum();
+    let total_failures: usize = suites.iter().map(|(_, names)| failed_count(names)).sum();
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str(&format!(
+        "<testsuites name=\"cargo-pup\" tests=\"{total_tests}\" failures=\"{total_failures}\">\n"
+    ));
+    for (kind, names) in &suites {
+        xml.push_str(&format!(
+            "  <testsuite name=\"{kind}\" tests=\"{}\" failures=\"{}\">\n",
+            names.len(),
+            failed_count(names)
+        ));
+        for name in names {
+            let failures = failures_of(name);
+            let name = xml_escape(name);
+            if failures.is_empty() {
+                xml.push_str(&format!(
+                    "    <testcase name=\"{name}\" classname=\"{kind}\"/>\n"
+                ));
+                continue;
+            }
+            xml.push_str(&format!(
+                "    <testcase name=\"{name}\" classname=\"{kind}\">\n"
+            ));
+            for violation in failures {
+                let severity = match violation.severity {
+                    Severity::Error => "error",
+                    Severity::Warn => "warn",
+                };
+                let message = xml_escape(&violation.message);
+                xml.push_str(&format!(
+                    "      <failure message=\"{message}\" type=\"{severity}\">{}:{}: {message}</failure>\n",
+                    xml_escape(&violation.file),
+                    violation.line
+                ));
+            }
+            xml.push_str("    </testcase>\n");
+        }
+        xml.push_str("  </testsuite>\n");
+    }
+    xml.push_str("</testsuites>\n");
+    xml
+}
+
+/// Render violations grouped by source file or by lint name, with a count for each
+/// group. Groups are sorted by name, and violations within a group by file and line.
+fn render_grouped_violations(violations: &[LintViolation], group_by: GroupBy) -> String {
+    let mut groups: BTreeMap<&str, Vec<&LintViolation>> = BTreeMap::new();
+    for violation in violations {
+        let key = match group_by {
+            GroupBy::File => violation.file.as_str(),
+            GroupBy::Lint => violation.lint_name.as_str(),
+        };
+        groups.entry(key).or_default().push(violation);
+    }
+
+    let mut out = String::new();
+    for (key, group) in &mut groups {
+        group.sort_by(|a, b| (&a.file, a.line).cmp(&(&b.file, b.line)));
+        let noun = if group.len() == 1 {
+            "violation"
+        } else {
+            "violations"
+        };
+        out.push_str(&format!(
+            "{} ({} {noun})\n",
+            colored(Style::new().bold()).paint(*key),
+            group.len()
+        ));
+        for violation in group.iter() {
+            let severity = match violation.severity {
+                Severity::Error => colored(Red).paint("error"),
+                Severity::Warn => colored(Yellow).paint("warning"),
+            };
+            // Within a group, show whichever of file and lint isn't the group's name
+            let location = match group_by {
+                GroupBy::File => format!("line {}", violation.line),
+                GroupBy::Lint => format!("{}:{}", violation.file, violation.line),
+            };
+            let detail = match group_by {
+                GroupBy::File => format!(" [{}]", violation.lint_name),
+                GroupBy::Lint => String::new(),
+            };
+            out.push_str(&format!(
+                "  {location}: {severity}{detail} {}\n",
+                violation.message
+            ));
+        }
+        out.push('\n');
+    }
+
+    let group_noun = match (group_by, groups.len()) {
+        (GroupBy::File, 1) => "file",
+        (GroupBy::File, _) => "files",
+        (GroupBy::Lint, 1) => "lint",
+        (GroupBy::Lint, _) => "lints",
+    };
+    out.push_str(&format!(
+        "{} violation(s) across {} {group_noun}\n",
+        violations.len(),
+        groups.len()
+    ));
+    out
+}
+
+/// The rendered text of every compiler message in cargo's JSON output, cargo-pup
+/// violations included, as rustc would have printed them
+fn rendered_compiler_messages(cargo_stdout: &str) -> Vec<String> {
+    rendered_messages(cargo_stdout.lines())
+}
+
+/// The rendered text of compiler messages in cargo's JSON output that aren't cargo-pup
+/// violations, like compile errors from the project itself.
+fn other_compiler_messages(cargo_stdout: &str) -> Vec<String> {
+    rendered_messages(
+        cargo_stdout
+            .lines()
+            .filter(|line| parse_violations(line).is_empty()),
+    )
+}
+
+fn rendered_messages<'a>(lines: impl Iterator<Item = &'a str>) -> Vec<String> {
+    lines
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .filter(|msg| msg["reason"] == "compiler-message")
+        .filter_map(|msg| msg["message"]["rendered"].as_str().map(str::to_string))
+        .collect()
+}
+
+/// Escape text for use in XML attributes and element content
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// How long the watched files must stay unchanged before a change triggers a check
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Process the watch command. Runs a check, then waits for a `.rs` file or one of the
+/// configuration files, includes and all, to change and runs it again, until interrupted.
+fn process_watch(args: &[String], config_path: Option<&Path>) -> ! {
+    let check_args = watch_check_args(args);
+    let root = match Path::new(".").canonicalize() {
+        Ok(root) => root,
+        Err(e) => {
+            eprintln!("Error: Failed to watch the current directory: {e}");
+            exit(1);
+        }
+    };
+
+    // Changes are batched up until the files have stayed unchanged for a moment, so
+    // that a burst of saves only triggers one check
+    let (sender, changes) = mpsc::channel();
+    let mut debouncer = match new_debouncer(WATCH_DEBOUNCE, None, sender) {
+        Ok(debouncer) => debouncer,
+        Err(e) => {
+            eprintln!("Error: Failed to watch for changes: {e}");
+            exit(1);
+        }
+    };
+    if let Err(e) = debouncer.watch(&root, RecursiveMode::Recursive) {
+        eprintln!("Error: Failed to watch {}: {e}", root.display());
+        exit(1);
+    }
+
+    loop {
+        // The includes can change with each edit, so they're resolved for every run.
+        // Files outside the project, such as a shared include, are watched on their own.
+        let config_files = watched_config_files(config_path);
+        for config_file in &config_files {
+            if !config_file.starts_with(&root) {
+                let _ = debouncer.watch(config_file, RecursiveMode::NonRecursive);
+            }
+        }
+
+        // Clear the screen so only the latest results are shown, unless the output
+        // isn't meant to carry escape codes
+        if COLOR_ENABLED.load(Ordering::Relaxed) {
+            print!("\x1B[2J\x1B[1;1H");
+        }
+        // Lint failures are expected while iterating, so the exit status is ignored
+        let _ = process(check_args.iter().cloned());
+        println!(
+            "\n{}",
+            colored(Blue).paint(
+                "Watching for changes to .rs files and the configuration (Ctrl+C to stop)..."
+            )
+        );
+
+        // Changes made during the run are already queued, so they aren't missed. Files
+        // that were only read, e.g. by the check itself, don't count.
+        for result in &changes {
+            match result {
+                Ok(events) => {
+                    if events
+                        .iter()
+                        .filter(|event| !event.kind.is_access())
+                        .flat_map(|event| &event.paths)
+                        .any(|path| is_watched_change(path, &root, &config_files))
+                    {
+                        break;
+                    }
+                }
+                Err(errors) => {
+                    for e in errors {
+                        eprintln!("Warning: Failed to watch for changes: {e}");
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The arguments for each check run by the watch command: the original arguments with
+/// `watch` swapped for `check`, so cargo passthrough arguments are preserved.
+fn watch_check_args(args: &[String]) -> Vec<String> {
+    let command_idx = if args.get(1).map(String::as_str) == Some("pup") {
+        2
+    } else {
+        1
+    };
+
+    let mut check_args = args.to_vec();
+    if check_args.get(command_idx).map(String::as_str) == Some("watch") {
+        check_args[command_idx] = "check".to_string();
+    }
+    check_args
+}
+
+/// The configuration files a check reads: the one given with `--pup-config`, or else
+/// the workspace members' pup.ron files, each along with the files it includes
+fn watched_config_files(config_path: Option<&Path>) -> Vec<PathBuf> {
+    let configs = match config_path {
+        Some(config_path) => vec![config_path.to_path_buf()],
+        None => {
+            let mut configs = find_member_pup_rons();
+            configs.push(resolve_config_path(None));
+            configs
+        }
+    };
+
+    let mut files = Vec::new();
+    for config in configs {
+        // A configuration that doesn't load yet is still watched, so fixing it re-runs
+        let loaded =
+            config_files(&config).unwrap_or_else(|_| vec![config.canonicalize().unwrap_or(config)]);
+        for file in loaded {
+            if !files.contains(&file) {
+                files.push(file);
+            }
+        }
+    }
+    files
+}
+
+/// Whether a change to `path` should re-run the check: it's one of the configuration
+/// files, or a `.rs` or `pup.ron` file under `root`. Build output and hidden directories
+/// (such as `.git` and `.pup`) are skipped.
+fn is_watched_change(path: &Path, root: &Path, config_files: &[PathBuf]) -> bool {
+    if config_files.iter().any(|file| file == path) {
+        return true;
+    }
+    let Ok(relative) = path.strip_prefix(root) else {
+        return false;
+    };
+
+    let skipped = relative
+        .parent()
+        .into_iter()
+        .flat_map(Path::components)
+        .any(|component| {
+            let name = component.as_os_str().to_string_lossy();
+            name == "target" || name.starts_with('.')
+        });
+    !skipped
+        && (path.extension().is_some_and(|ext| ext == "rs")
+            || path.file_name().is_some_and(|name| name == "pup.ron"))
+}
+
+/// List the `.rs` files in the git repository containing `dir` that differ from the
+/// `since` ref, including uncommitted changes and new untracked files. Paths are
+/// absolute, so pup-driver can compare them with the files it compiles wherever cargo
+/// runs it from.
+fn changed_rust_files(dir: &Path, since: &str) -> anyhow::Result<Vec<String>> {
+    use anyhow::Context;
+
+    let git = |args: &[&str]| -> anyhow::Result<String> {
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(args)
+            .output()
+            .context("Failed to run git")?;
+        if !output.status.success() {
+            anyhow::bail!(
+                "git {} failed: {}",
+                args.join(" "),
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    };
+
+    let toplevel = PathBuf::from(git(&["rev-parse", "--show-toplevel"])?.trim());
+    let changed = git(&["diff", "--name-only", since, "--"])?;
+    // New files aren't in the diff until they're added, so they're listed separately
+    let untracked = git(&[
+        "ls-files",
+        "--others",
+        "--exclude-standard",
+        "--full-name",
+        "--",
+        ":/*.rs",
+    ])?;
+    Ok(changed
+        .lines()
+        .chain(untracked.lines())
+        .filter(|name| name.ends_with(".rs"))
+        .map(|name| {
+            let path = toplevel.join(name);
+            // Deleted files can't be canonicalized, but can't have diagnostics either
+            path.canonicalize().unwrap_or(path).display().to_string()
+        })
+        .collect())
+}
+
+/// Check that a configuration file given with --config can actually be read
+fn check_config_readable(path: &Path) -> anyhow::Result<()> {
+    use anyhow::Context;
+
+    let metadata = std::fs::metadata(path)
+        .with_context(|| format!("Configuration file '{}' does not exist", path.display()))?;
+    if !metadata.is_file() {
+        anyhow::bail!("Configuration file '{}' is not a file", path.display());
+    }
+    std::fs::File::open(path)
+        .with_context(|| format!("Configuration file '{}' can't be read", path.display()))?;
+    Ok(())
+}
+
+/// The profile chosen with --pup-profile, or failing that the PUP_PROFILE environment variable
+fn selected_profile(pup_args: &PupArgs) -> Option<String> {
+    pup_args
+        .profile
+        .clone()
+        .or_else(|| env::var("PUP_PROFILE").ok())
+}
+
+/// Resolve the configuration file, preferring an explicit --config path over the
+/// pup.ron nearest the current directory
+fn resolve_config_path(config_path: Option<&str>) -> PathBuf {
+    match config_path {
+        Some(path) => PathBuf::from(path),
+        None => find_crate_pup_ron(Path::new(".")).unwrap_or_else(|| PathBuf::from("./pup.ron")),
+    }
+}
+
+/// Process the list-lints command by loading the configuration, includes and all, and
+/// printing one line per lint
+fn process_list_lints(config_path: Option<&str>, profile: Option<&str>) -> anyhow::Result<()> {
+    use anyhow::Context;
+
+    let config_path = resolve_config_path(config_path);
+
+    let lint_builder = load_config(&config_path, profile)
+        .with_context(|| format!("Failed to load {}", config_path.display()))?;
+
+    for line in format_lint_list(&lint_builder) {
+        println!("{line}");
+    }
+    Ok(())
+}
+
+/// Process the validate-config command by loading the configuration the way a check
+/// does, without compiling anything. Returns the number of lints it defines once the
+/// includes are merged.
+fn process_validate_config(config_path: &Path, profile: Option<&str>) -> anyhow::Result<usize> {
+    Ok(load_config(config_path, profile)?.lints.len())
+}
+
+/// Process the diff command by comparing the lints of two configuration files, with
+/// their includes merged in, and the result rendered as text
+fn process_diff(from: &Path, to: &Path, profile: Option<&str>) -> anyhow::Result<String> {
+    use anyhow::Context;
+
+    let from_builder =
+        load_config(from, profile).with_context(|| format!("Failed to load {}", from.display()))?;
+    let to_builder =
+        load_config(to, profile).with_context(|| format!("Failed to load {}", to.display()))?;
+
+    Ok(render_diff(&diff_lints(
+        &from_builder.lints,
+        &to_builder.lints,
+    )))
+}
+
+/// Formats each configured lint as a tab-separated `name kind severity` line.
+/// This is deliberately plain - no colours or headers - so it can be grepped.
+fn format_lint_list(lint_builder: &LintBuilder) -> Vec<String> {
+    lint_builder
+        .lints
+        .iter()
+        .map(|lint| {
+            let severity = match lint.severity() {
+                Some(Severity::Error) => "error",
+                Some(Severity::Warn) => "warn",
+                None => "none",
+            };
+            format!("{}\t{}\t{}", lint.name(), lint.kind(), severity)
+        })
+        .collect()
+}
+
+/// Starter integration test written by `cargo pup init --test`
+const ARCHITECTURE_TEST_TEMPLATE: &str = r#"use cargo_pup_lint_config::{FunctionLintExt, LintBuilder, LintBuilderExt, Severity};
+
+// Architecture rules for this project. `assert_lints` builds the project with
+// cargo-pup and fails the test if any of the rules are violated.
+#[test]
+fn architecture() {
+    let mut builder = LintBuilder::new();
+
+    builder
+        .function_lint()
+        .lint_named("function_length_limit")
+        .matching(|m| m.name_regex(".*"))
+        .with_severity(Severity::Warn)
+        .max_length(50)
+        .build();
+
+    builder
+        .assert_lints(None)
+        .expect("Architecture rules should pass");
+}
+"#;
+
+/// Process the init command by writing a minimal pup.ron, and with `with_test` a starter
+/// `tests/architecture.rs`, into `root`. Nothing is written if any of the files exist.
+fn process_init(root: &Path, with_test: bool) -> anyhow::Result<Vec<PathBuf>> {
+    use anyhow::Context;
+    use cargo_pup_lint_config::FunctionLintExt;
+
+    if !root.join("Cargo.toml").exists() {
+        anyhow::bail!("init needs to be run from a directory containing a Cargo.toml file");
+    }
+
+    let config_path = root.join("pup.ron");
+    let test_path = root.join("tests").join("architecture.rs");
+    let mut targets = vec![&config_path];
+    if with_test {
+        targets.push(&test_path);
+    }
+    if let Some(existing) = targets.iter().find(|path| path.exists()) {
+        anyhow::bail!(
+            "{} already exists. Remove this file if you want to re-run init.",
+            existing.display()
+        );
+    }
+
+    let mut builder = LintBuilder::new();
+    builder
+        .function_lint()
+        .lint_named("function_length_limit")
+        .matching(|m| m.name_regex(".*"))
+        .with_severity(Severity::Warn)
+        .max_length(50)
+        .build();
+    builder
+        .write_to_file(&config_path)
+        .with_context(|| format!("Failed to write {}", config_path.display()))?;
+
+    if with_test {
+        std::fs::create_dir_all(root.join("tests")).context("Failed to create tests directory")?;
+        std::fs::write(&test_path, ARCHITECTURE_TEST_TEMPLATE)
+            .with_context(|| format!("Failed to write {}", test_path.display()))?;
+    }
+
+    Ok(targets.into_iter().cloned().collect())
+}
+
+fn get_toolchain() -> String {
+    // We want to run with the same toolchain we were built with. This deals
+    // with the dynamic-linking-against-librustc_driver piece, but _will_ add that toolchain
+    // to the user's local rustup installs.
+    let toolchain_config = include_str!("../rust-toolchain.toml");
+    let toml = toml::from_str::<toml::Value>(toolchain_config).unwrap();
+
+    // Work out which toolchain we want to run against
+    toml.get("toolchain")
+        .unwrap()
+        .get("channel")
+        .unwrap()
+        .as_str()
+        .unwrap()
+        .to_string()
+}
+
+#[must_use]
+pub fn help_message() -> String {
+    format!(
+        "
+{title}: Checks your architecture against your architecture lint file.
+
+{usage_label}:
+    cargo pup [COMMAND] [OPTIONS] [--] [CARGO_ARGS...]
+
+{commands_label}:
+    {check}            Run architectural lints (default)
+    {print_modules}    Print all modules and applicable lints
+    {print_traits}     Print all traits
+    {generate_config}  Generates an initial pup.ron for your project.
+    {list_lints}       List the configured lints with their kind and severity
+    {validate_config}  Check that pup.ron parses, without compiling anything
+    {init}             Write a minimal pup.ron; with --test, also tests/architecture.rs
+    {graph}            Write the module import graph to .pup/modules.dot
+    {stats}            Summarize the modules, traits, structs and functions found
+    {export_context}   Write the modules, traits and their lints as JSON, for other tools
+    {watch}            Re-run check whenever a .rs file or pup.ron changes
+    {diff} FROM TO     Show the lints added, removed or changed between two config files
+
+{options_label}:
+    -h, --help             Print this message
+    -V, --version          Print version info and exit
+    --config PATH          Use this configuration file instead of pup.ron (alias: --pup-config).
+                           cargo's --config KEY=VALUE and --config FILE.toml are passed on
+    --pup-profile NAME     Run the lints of this profile from the configuration instead of the
+                           top-level ones (or set PUP_PROFILE)
+    --deny-warnings        Exit with an error if any lint reports a warning
+    --exit-zero            Report violations without failing, e.g. in a pre-commit hook
+    --fail-fast            Stop at the first violation reported as an error
+    -q, --quiet            Only print results, without the puppy or banners
+    -v, --verbose          Also print which lints ran, the files checked and timings
+    --color WHEN           Color output: auto (default, off when NO_COLOR is set), always or never
+    --format FORMAT        Report check results as text (default) or junit, stats as text or json
+    --since REF            Only report check results in .rs files changed since a git ref
+    --group-by GROUP       Show check violations grouped by file or by lint
+    --report PATH          Write one report of check violations across the whole workspace
+    --timings              Report how long each lint spent checking, slowest first
+    --filter REGEX         Only print the modules or traits whose path matches
+    --output PATH          Where graph, export-context or check --format junit write to
+    --output-dir PATH      Use this directory instead of .pup for build output and contexts
 
 Any additional arguments will be passed directly to cargo:
     --features=FEATURES    Cargo features to enable
+    --all-features         Enable all features, e.g. so generate-config sees feature-gated modules
     --manifest-path=PATH   Path to Cargo.toml
 
-{note} to allow or deny lints from your code, e.g.:
+{note} to allow a configured rule by name from your code, e.g.:
     #[allow(pup::some_lint)]
 ",
-        title = Style::new().bold().paint("Pretty Useful Pup"),
-        usage_label = Blue.bold().paint("Usage"),
-        commands_label = Blue.bold().paint("Commands"),
-        check = Green.paint("check"),
-        print_modules = Green.paint("print-modules"),
-        print_traits = Green.paint("print-traits"),
-        generate_config = Green.paint("generate-config"),
-        options_label = Blue.bold().paint("Options"),
-        note = Yellow.paint("You can use tool lints")
+        title = colored(Style::new().bold()).paint("Pretty Useful Pup"),
+        usage_label = colored(Blue.bold()).paint("Usage"),
+        commands_label = colored(Blue.bold()).paint("Commands"),
+        check = colored(Green).paint("check"),
+        print_modules = colored(Green).paint("print-modules"),
+        print_traits = colored(Green).paint("print-traits"),
+        generate_config = colored(Green).paint("generate-config"),
+        list_lints = colored(Green).paint("list-lints"),
+        validate_config = colored(Green).paint("validate-config"),
+        init = colored(Green).paint("init"),
+        graph = colored(Green).paint("graph"),
+        stats = colored(Green).paint("stats"),
+        export_context = colored(Green).paint("export-context"),
+        watch = colored(Green).paint("watch"),
+        diff = colored(Green).paint("diff"),
+        options_label = colored(Blue.bold()).paint("Options"),
+        note = colored(Yellow).paint("You can use tool lints")
     )
 }
 
 /// Format and print the modules in the project context
-pub fn print_modules(context: &ProjectContext, crate_names: &[String]) -> anyhow::Result<()> {
-    use ansi_term::Colour::{Blue, Cyan, Green};
+pub fn print_modules(
+    context: &ProjectContext,
+    crate_names: &[String],
+    verbosity: Verbosity,
+    filter: Option<&Regex>,
+) -> anyhow::Result<()> {
+    use ansi_term::Colour::{Blue, Green};
     use cargo_pup_common::project_context::ModuleInfo;
     use std::collections::BTreeMap;
 
     // Print a header
-    println!(
-        "{}",
-        Cyan.paint(
-            r#"
-     / \__
-    (    @\___
-    /         O
-   /   (_____/
-  /_____/   U
-"#
-        )
-    );
-
-    if crate_names.len() > 1 {
-        println!("Modules from multiple crates: {}", crate_names.join(", "));
-    } else {
-        println!("Modules from crate: {}", context.module_root);
+    if verbosity != Verbosity::Quiet {
+        show_ascii_puppy();
+        if crate_names.len() > 1 {
+            println!("Modules from multiple crates: {}", crate_names.join(", "));
+        } else {
+            println!("Modules from crate: {}", context.module_root);
+        }
+        if let Some(filter) = filter {
+            println!("Only showing modules matching '{}'", filter.as_str());
+        }
+        println!();
     }
-    println!();
 
     // Group modules by crate
     let mut modules_by_crate: BTreeMap<String, Vec<&ModuleInfo>> = BTreeMap::new();
 
-    for module_info in &context.modules {
+    for module_info in context
+        .modules
+        .iter()
+        .filter(|module_info| filter.is_none_or(|filter| filter.is_match(&module_info.name)))
+    {
         // Extract crate name from module path (everything before the first ::)
         if let Some(idx) = module_info.name.find("::") {
             let crate_name = &module_info.name[..idx];
@@ -652,7 +1921,7 @@ pub fn print_modules(context: &ProjectContext, crate_names: &[String]) -> anyhow
 
     // Print modules organized by crate
     for (crate_name, modules) in modules_by_crate {
-        println!("{}", Blue.paint(&crate_name));
+        println!("{}", colored(Blue).paint(&crate_name));
 
         // Group modules by their path structure and display them hierarchically
         let mut module_map: BTreeMap<String, Vec<String>> = BTreeMap::new();
@@ -672,7 +1941,7 @@ pub fn print_modules(context: &ProjectContext, crate_names: &[String]) -> anyhow
 
             // If module path is empty, it's the root module, otherwise add it to the map
             if module_path.is_empty() {
-                println!("  :: [{}]", Green.paint(&lints_str));
+                println!("  :: [{}]", colored(Green).paint(&lints_str));
             } else {
                 module_map.entry(module_path).or_default().push(lints_str);
             }
@@ -689,8 +1958,8 @@ pub fn print_modules(context: &ProjectContext, crate_names: &[String]) -> anyhow
 
             println!(
                 "  ::{} [{}]",
-                Blue.paint(&module_path),
-                Green.paint(&combined_lints)
+                colored(Blue).paint(&module_path),
+                colored(Green).paint(&combined_lints)
             );
         }
 
@@ -701,36 +1970,38 @@ pub fn print_modules(context: &ProjectContext, crate_names: &[String]) -> anyhow
 }
 
 /// Format and print the traits in the project context
-pub fn print_traits(context: &ProjectContext, crate_names: &[String]) -> anyhow::Result<()> {
-    use ansi_term::Colour::{Blue, Cyan, Green};
+pub fn print_traits(
+    context: &ProjectContext,
+    crate_names: &[String],
+    verbosity: Verbosity,
+    filter: Option<&Regex>,
+) -> anyhow::Result<()> {
+    use ansi_term::Colour::{Blue, Green};
     use cargo_pup_common::project_context::TraitInfo;
     use std::collections::BTreeMap;
 
     // Print a header
-    println!(
-        "{}",
-        Cyan.paint(
-            r#"
-     / \__
-    (    @\___
-    /         O
-   /   (_____/
-  /_____/   U
-"#
-        )
-    );
-
-    if crate_names.len() > 1 {
-        println!("Traits from multiple crates: {}", crate_names.join(", "));
-    } else {
-        println!("Traits from crate: {}", context.module_root);
+    if verbosity != Verbosity::Quiet {
+        show_ascii_puppy();
+        if crate_names.len() > 1 {
+            println!("Traits from multiple crates: {}", crate_names.join(", "));
+        } else {
+            println!("Traits from crate: {}", context.module_root);
+        }
+        if let Some(filter) = filter {
+            println!("Only showing traits matching '{}'", filter.as_str());
+        }
+        println!();
     }
-    println!();
 
     // Group traits by crate
     let mut traits_by_crate: BTreeMap<String, Vec<&TraitInfo>> = BTreeMap::new();
 
-    for trait_info in &context.traits {
+    for trait_info in context
+        .traits
+        .iter()
+        .filter(|trait_info| filter.is_none_or(|filter| filter.is_match(&trait_info.name)))
+    {
         // Extract crate name from trait path (everything before the first ::)
         if let Some(idx) = trait_info.name.find("::") {
             let crate_name = &trait_info.name[..idx];
@@ -747,7 +2018,7 @@ pub fn print_traits(context: &ProjectContext, crate_names: &[String]) -> anyhow:
 
     // Print traits organized by crate
     for (crate_name, traits) in traits_by_crate {
-        println!("{}", Blue.paint(&crate_name));
+        println!("{}", colored(Blue).paint(&crate_name));
 
         for trait_info in traits {
             // Get the crate-relative trait path (everything after crate_name::)
@@ -764,19 +2035,19 @@ pub fn print_traits(context: &ProjectContext, crate_names: &[String]) -> anyhow:
 
             // If trait path is empty, it's the root trait, otherwise add it to the map
             if trait_path.is_empty() {
-                println!("  :: [{}]", Green.paint(&lints_str));
+                println!("  :: [{}]", colored(Green).paint(&lints_str));
             } else {
                 println!(
                     "  ::{} [{}]",
-                    Blue.paint(&trait_path),
-                    Green.paint(&lints_str)
+                    colored(Blue).paint(&trait_path),
+                    colored(Green).paint(&lints_str)
                 );
             }
 
             // Print implementors with indentation
             if !trait_info.implementors.is_empty() {
                 for implementor in &trait_info.implementors {
-                    println!("    → {}", Green.paint(implementor));
+                    println!("    → {}", colored(Green).paint(implementor));
                 }
             }
         }
@@ -948,16 +2219,31 @@ mod tests {
             assert!(help.contains("print-modules"));
             assert!(help.contains("print-traits"));
             assert!(help.contains("generate-config"));
+            assert!(help.contains("list-lints"));
+            assert!(help.contains("graph"));
+            assert!(help.contains("watch"));
             assert!(help.contains("Options"));
             assert!(help.contains("-h, --help"));
             assert!(help.contains("-V, --version"));
         }
 
         #[test]
-        fn test_show_ascii_puppy() {
-            // This is a difficult function to test directly since it prints to stdout
-            // We'll just call it to ensure it doesn't panic
-            show_ascii_puppy();
+        fn test_help_message_without_colors() {
+            COLOR_ENABLED.store(false, Ordering::Relaxed);
+            let help = help_message();
+            COLOR_ENABLED.store(true, Ordering::Relaxed);
+
+            // Every label is left as plain text
+            assert!(!help.contains('\x1b'), "{help}");
+            assert!(help.contains("Pretty Useful Pup"));
+            assert!(help.contains("--color WHEN"));
+        }
+
+        #[test]
+        fn test_show_ascii_puppy() {
+            // This is a difficult function to test directly since it prints to stdout
+            // We'll just call it to ensure it doesn't panic
+            show_ascii_puppy();
         }
 
         #[test]
@@ -1363,9 +2649,788 @@ mod tests {
         }
     }
 
+    /// Tests for the --config flag
+    mod config_path_tests {
+        use super::*;
+
+        #[test]
+        fn test_check_config_readable() {
+            let temp_dir = TempDir::new().expect("Failed to create temp directory");
+            let config = temp_dir.path().join("pup.strict.ron");
+            fs::write(&config, "(lints: [])").unwrap();
+
+            assert!(check_config_readable(&config).is_ok());
+
+            let err = check_config_readable(&temp_dir.path().join("missing.ron"))
+                .expect_err("a missing file should be rejected");
+            assert!(err.to_string().contains("does not exist"));
+
+            let err =
+                check_config_readable(temp_dir.path()).expect_err("a directory should be rejected");
+            assert!(err.to_string().contains("is not a file"));
+        }
+    }
+
+    /// Tests for the --since flag
+    mod since_tests {
+        use super::*;
+
+        fn git(dir: &Path, args: &[&str]) {
+            let status = Command::new("git")
+                .arg("-C")
+                .arg(dir)
+                .args(["-c", "user.name=pup", "-c", "user.email=pup@example.com"])
+                .args(args)
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .status()
+                .expect("Failed to run git");
+            assert!(status.success(), "git {args:?} failed");
+        }
+
+        #[test]
+        fn test_changed_rust_files() {
+            let temp_dir = TempDir::new().expect("Failed to create temp directory");
+            let root = temp_dir.path().canonicalize().unwrap();
+            fs::create_dir(root.join("src")).unwrap();
+            fs::write(root.join("src/lib.rs"), "pub mod a;\n").unwrap();
+            fs::write(root.join("src/a.rs"), "").unwrap();
+            fs::write(root.join("README.md"), "").unwrap();
+            git(&root, &["init", "-q"]);
+            git(&root, &["add", "."]);
+            git(&root, &["commit", "-q", "-m", "initial"]);
+
+            // Uncommitted changes and new files count, and only .rs files are listed
+            fs::write(root.join("src/a.rs"), "pub fn a() {}\n").unwrap();
+            fs::write(root.join("README.md"), "changed\n").unwrap();
+            fs::write(root.join("src/b.rs"), "pub fn b() {}\n").unwrap();
+            fs::write(root.join("build.rs"), "fn main() {}\n").unwrap();
+            fs::write(root.join("NOTES.md"), "new\n").unwrap();
+            // Ignored files don't
+            fs::write(root.join(".gitignore"), "/target\n").unwrap();
+            fs::create_dir(root.join("target")).unwrap();
+            fs::write(root.join("target/out.rs"), "").unwrap();
+
+            let changed = changed_rust_files(&root.join("src"), "HEAD").expect("git diff");
+            assert_eq!(
+                changed,
+                vec![
+                    root.join("src/a.rs").display().to_string(),
+                    root.join("build.rs").display().to_string(),
+                    root.join("src/b.rs").display().to_string(),
+                ]
+            );
+
+            assert!(changed_rust_files(&root, "no-such-ref").is_err());
+        }
+    }
+
+    /// Tests for the list-lints command
+    mod list_lints_tests {
+        use super::*;
+        use cargo_pup_lint_config::{FunctionLintExt, ModuleLintExt, StructLintExt};
+
+        #[test]
+        fn test_list_lints_command_detection() {
+            let args = vec!["cargo-pup".to_string(), "list-lints".to_string()];
+            assert_eq!(get_command_type(&args), CommandType::ListLints);
+
+            let args = vec![
+                "cargo".to_string(),
+                "pup".to_string(),
+                "list-lints".to_string(),
+            ];
+            assert_eq!(get_command_type(&args), CommandType::ListLints);
+        }
+
+        #[test]
+        fn test_format_lint_list() {
+            let mut builder = LintBuilder::new();
+            builder
+                .module_lint()
+                .lint_named("no_wildcards")
+                .matching(|m| m.module(".*"))
+                .no_wildcard_imports()
+                .build();
+            builder
+                .struct_lint()
+                .lint_named("private_models")
+                .matching(|m| m.name(".*Model"))
+                .must_be_public()
+                .with_severity(Severity::Error)
+                .must_be_named(".*Model".into())
+                .build();
+            builder
+                .function_lint()
+                .lint_named("short_functions")
+                .matching(|m| m.name_regex(".*"))
+                .with_severity(Severity::Error)
+                .max_length(50)
+                .build();
+
+            assert_eq!(
+                format_lint_list(&builder),
+                vec![
+                    "no_wildcards\tmodule\twarn",
+                    "private_models\tstruct\terror",
+                    "short_functions\tfunction\terror",
+                ]
+            );
+        }
+
+        #[test]
+        fn test_included_lints_are_listed() {
+            let temp_dir = TempDir::new().unwrap();
+            fs::write(
+                temp_dir.path().join("pup.base.ron"),
+                r#"(lints: [Module((name: "shared", matches: Module(".*"), rules: [MustNotBeEmpty(Error)]))])"#,
+            )
+            .unwrap();
+            let config = temp_dir.path().join("pup.ron");
+            fs::write(
+                &config,
+                r#"(include: ["pup.base.ron"], lints: [Module((name: "local", matches: Module(".*"), rules: [NoWildcardImports(Warn)]))])"#,
+            )
+            .unwrap();
+
+            assert_eq!(
+                format_lint_list(&load_config(&config, None).unwrap()),
+                vec!["shared\tmodule\terror", "local\tmodule\twarn"]
+            );
+        }
+    }
+
+    /// Tests for the validate-config command
+    mod validate_config_tests {
+        use super::*;
+
+        const NO_WILDCARDS: &str = r#"Module((name: "no_wildcards", matches: Module(".*"), rules: [NoWildcardImports(Warn)]))"#;
+
+        #[test]
+        fn test_validate_config_command_detection() {
+            let args = vec![
+                "cargo".to_string(),
+                "pup".to_string(),
+                "validate-config".to_string(),
+            ];
+            assert_eq!(get_command_type(&args), CommandType::ValidateConfig);
+        }
+
+        #[test]
+        fn test_valid_config_counts_lints_across_includes() {
+            let temp_dir = TempDir::new().unwrap();
+            fs::write(
+                temp_dir.path().join("pup.base.ron"),
+                format!(
+                    r#"(lints: [{NO_WILDCARDS}, Module((name: "not_empty", matches: Module(".*"), rules: [MustNotBeEmpty(Warn)]))])"#
+                ),
+            )
+            .unwrap();
+            let config = temp_dir.path().join("pup.ron");
+            // The local lint replaces the included one of the same name
+            fs::write(
+                &config,
+                format!(r#"(include: ["pup.base.ron"], lints: [{NO_WILDCARDS}])"#),
+            )
+            .unwrap();
+
+            assert_eq!(process_validate_config(&config, None).unwrap(), 2);
+        }
+
+        #[test]
+        fn test_invalid_config_points_at_the_problem() {
+            let temp_dir = TempDir::new().unwrap();
+            fs::write(
+                temp_dir.path().join("pup.base.ron"),
+                r#"(lints: [
+    Module((name: "no_wildcards", matches: Module(".*"), rules: [NoWildcardImport(Warn)])),
+])"#,
+            )
+            .unwrap();
+            let config = temp_dir.path().join("pup.ron");
+            fs::write(&config, r#"(include: ["pup.base.ron"], lints: [])"#).unwrap();
+
+            // Errors in included files are reported against the included file
+            let error = format!("{:#}", process_validate_config(&config, None).unwrap_err());
+            assert!(
+                error.contains("pup.base.ron:2:66: unknown module rule `NoWildcardImport`"),
+                "{error}"
+            );
+            assert!(
+                error.contains("did you mean `NoWildcardImports`?"),
+                "{error}"
+            );
+        }
+
+        #[test]
+        fn test_include_cycle_is_reported() {
+            let temp_dir = TempDir::new().unwrap();
+            let config = temp_dir.path().join("pup.ron");
+            fs::write(&config, r#"(include: ["pup.ron"], lints: [])"#).unwrap();
+
+            let error = process_validate_config(&config, None)
+                .unwrap_err()
+                .to_string();
+            assert!(error.contains("includes itself"), "{error}");
+        }
+
+        #[test]
+        fn test_unset_variable_is_reported() {
+            let temp_dir = TempDir::new().unwrap();
+            let config = temp_dir.path().join("pup.ron");
+            fs::write(
+                &config,
+                r#"(lints: [Module((name: "internal", matches: Module("^${PUP_TEST_SURELY_UNSET_CRATE}::internal$"), rules: [MustNotBeEmpty(Warn)]))])"#,
+            )
+            .unwrap();
+
+            let error = process_validate_config(&config, None)
+                .unwrap_err()
+                .to_string();
+            assert!(error.contains("PUP_TEST_SURELY_UNSET_CRATE"), "{error}");
+        }
+
+        #[test]
+        fn test_profile_is_applied() {
+            let temp_dir = TempDir::new().unwrap();
+            let config = temp_dir.path().join("pup.ron");
+            fs::write(
+                &config,
+                format!(
+                    r#"(lints: [{NO_WILDCARDS}], profiles: {{"ci": [Module((name: "not_empty", matches: Module(".*"), rules: [MustNotBeEmpty(Error)])), Module((name: "no_glob_imports", matches: Module(".*"), rules: [NoWildcardImports(Error)]))]}})"#
+                ),
+            )
+            .unwrap();
+
+            assert_eq!(process_validate_config(&config, None).unwrap(), 1);
+            assert_eq!(process_validate_config(&config, Some("ci")).unwrap(), 2);
+            let error = process_validate_config(&config, Some("nightly"))
+                .unwrap_err()
+                .to_string();
+            assert!(error.contains("Profile 'nightly' not found"), "{error}");
+        }
+    }
+
+    /// Tests for the diff command
+    mod diff_tests {
+        use super::*;
+
+        #[test]
+        fn test_diff_command_detection() {
+            let args = vec![
+                "cargo".to_string(),
+                "pup".to_string(),
+                "diff".to_string(),
+                "old.ron".to_string(),
+                "pup.ron".to_string(),
+            ];
+            assert_eq!(get_command_type(&args), CommandType::Diff);
+        }
+
+        #[test]
+        fn test_diff_between_config_files() {
+            let temp_dir = TempDir::new().unwrap();
+            let from = temp_dir.path().join("old.ron");
+            let to = temp_dir.path().join("pup.ron");
+            fs::write(
+                &from,
+                r#"(lints: [Module((name: "no_wildcards", matches: Module(".*"), rules: [NoWildcardImports(Warn)]))])"#,
+            )
+            .unwrap();
+            fs::write(
+                &to,
+                r#"(lints: [Module((name: "no_wildcards", matches: Module(".*"), rules: [NoWildcardImports(Error)]))])"#,
+            )
+            .unwrap();
+
+            let diff = process_diff(&from, &to, None).unwrap();
+            assert!(diff.contains("  ~ no_wildcards (module)\n"), "{diff}");
+            assert!(diff.contains("severity: warn -> error"), "{diff}");
+            assert!(diff.ends_with("0 added, 0 removed, 1 changed\n"), "{diff}");
+
+            // A file that can't be loaded is named in the error
+            let missing = temp_dir.path().join("missing.ron");
+            let error = format!("{:#}", process_diff(&missing, &to, None).unwrap_err());
+            assert!(error.contains("missing.ron"), "{error}");
+        }
+
+        #[test]
+        fn test_diff_compares_included_lints() {
+            let temp_dir = TempDir::new().unwrap();
+            fs::write(
+                temp_dir.path().join("pup.base.ron"),
+                r#"(lints: [Module((name: "shared", matches: Module(".*"), rules: [MustNotBeEmpty(Warn)]))])"#,
+            )
+            .unwrap();
+            let from = temp_dir.path().join("old.ron");
+            let to = temp_dir.path().join("pup.ron");
+            fs::write(&from, r#"(lints: [])"#).unwrap();
+            fs::write(&to, r#"(include: ["pup.base.ron"], lints: [])"#).unwrap();
+
+            let diff = process_diff(&from, &to, None).unwrap();
+            assert!(diff.contains("shared (module)"), "{diff}");
+            assert!(diff.ends_with("1 added, 0 removed, 0 changed\n"), "{diff}");
+        }
+    }
+
+    /// Tests for the init command
+    mod init_tests {
+        use super::*;
+
+        fn cargo_project() -> TempDir {
+            let temp_dir = TempDir::new().expect("Failed to create temp directory");
+            fs::write(
+                temp_dir.path().join("Cargo.toml"),
+                "[package]\nname = \"app\"\n",
+            )
+            .expect("Failed to write Cargo.toml");
+            temp_dir
+        }
+
+        #[test]
+        fn test_init_command_detection() {
+            let args = vec!["cargo".to_string(), "pup".to_string(), "init".to_string()];
+            assert_eq!(get_command_type(&args), CommandType::Init);
+        }
+
+        #[test]
+        fn test_init_writes_config_and_test() {
+            let temp_dir = cargo_project();
+
+            let created = process_init(temp_dir.path(), true).expect("init should succeed");
+
+            assert_eq!(created.len(), 2);
+            let builder = LintBuilder::read_from_file(temp_dir.path().join("pup.ron"))
+                .expect("pup.ron should be a valid configuration");
+            assert_eq!(builder.lints.len(), 1);
+            let test = fs::read_to_string(temp_dir.path().join("tests/architecture.rs"))
+                .expect("tests/architecture.rs should be written");
+            assert!(test.contains("assert_lints(None)"));
+        }
+
+        #[test]
+        fn test_init_without_test_only_writes_config() {
+            let temp_dir = cargo_project();
+
+            process_init(temp_dir.path(), false).expect("init should succeed");
+
+            assert!(temp_dir.path().join("pup.ron").exists());
+            assert!(!temp_dir.path().join("tests").exists());
+        }
+
+        #[test]
+        fn test_init_refuses_to_overwrite() {
+            let temp_dir = cargo_project();
+            fs::create_dir(temp_dir.path().join("tests")).unwrap();
+            fs::write(temp_dir.path().join("tests/architecture.rs"), "// mine\n").unwrap();
+
+            let err = process_init(temp_dir.path(), true).expect_err("init should refuse");
+
+            assert!(err.to_string().contains("already exists"));
+            // Nothing is written when any of the files is already there
+            assert!(!temp_dir.path().join("pup.ron").exists());
+            assert_eq!(
+                fs::read_to_string(temp_dir.path().join("tests/architecture.rs")).unwrap(),
+                "// mine\n"
+            );
+        }
+
+        #[test]
+        fn test_init_requires_cargo_project() {
+            let temp_dir = TempDir::new().expect("Failed to create temp directory");
+            assert!(process_init(temp_dir.path(), false).is_err());
+        }
+    }
+
+    /// Tests for the graph command
+    mod graph_tests {
+        use super::*;
+        use cargo_pup_common::project_context::ModuleDependency;
+
+        #[test]
+        fn test_graph_command_detection() {
+            let args = vec!["cargo".to_string(), "pup".to_string(), "graph".to_string()];
+            assert_eq!(get_command_type(&args), CommandType::Graph);
+        }
+
+        #[test]
+        fn test_render_module_graph() {
+            let mut context = ProjectContext::new();
+            context.module_dependencies = vec![
+                ModuleDependency {
+                    from: "app::api".to_string(),
+                    to: "app::db".to_string(),
+                },
+                ModuleDependency {
+                    from: "app::api".to_string(),
+                    to: "std::collections".to_string(),
+                },
+                ModuleDependency {
+                    from: "app::db".to_string(),
+                    to: "models".to_string(),
+                },
+            ];
+
+            let dot = render_module_graph(&context, &["app".to_string(), "models".to_string()]);
+            assert_eq!(
+                dot,
+                "digraph modules {\n    \"app::api\" -> \"app::db\";\n    \"app::db\" -> \"models\";\n}\n"
+            );
+        }
+    }
+
+    mod feature_gate_notes_tests {
+        use super::*;
+        use cargo_pup_common::project_context::FeatureGatedModule;
+
+        #[test]
+        fn test_no_notes_without_gated_modules() {
+            assert_eq!(feature_gate_notes(&ProjectContext::new()), "");
+        }
+
+        #[test]
+        fn test_notes_list_gated_modules() {
+            let mut context = ProjectContext::new();
+            context.feature_gated_modules = vec![FeatureGatedModule {
+                module: "app::metrics".to_string(),
+                cfg: "feature = \"metrics\"".to_string(),
+            }];
+
+            let notes = feature_gate_notes(&context);
+            assert!(notes.contains("//   app::metrics: #[cfg(feature = \"metrics\")]\n"));
+            assert!(
+                notes
+                    .lines()
+                    .all(|line| line.is_empty() || line.starts_with("//"))
+            );
+        }
+    }
+
+    /// Tests for the stats command
+    mod stats_tests {
+        use super::*;
+
+        #[test]
+        fn test_stats_command_detection() {
+            let args = vec!["cargo".to_string(), "pup".to_string(), "stats".to_string()];
+            assert_eq!(get_command_type(&args), CommandType::Stats);
+        }
+    }
+
+    /// Tests for the export-context command
+    mod export_context_tests {
+        use super::*;
+
+        #[test]
+        fn test_export_context_command_detection() {
+            let args = vec!["cargo-pup".to_string(), "export-context".to_string()];
+            assert_eq!(get_command_type(&args), CommandType::ExportContext);
+        }
+
+        #[test]
+        fn test_export_context_writes_merged_context() {
+            let temp_dir = tempfile::tempdir().unwrap();
+            for (crate_name, module) in [("app", "app::api"), ("core", "core::model")] {
+                ProjectContext::with_data_and_base_dir(
+                    vec![module.to_string()],
+                    crate_name.to_string(),
+                    Vec::new(),
+                    temp_dir.path(),
+                )
+                .serialize_to_file()
+                .unwrap();
+            }
+
+            let output = temp_dir.path().join("context.json");
+            process_export_context(temp_dir.path(), output.to_str()).unwrap();
+
+            let exported: serde_json::Value =
+                serde_json::from_str(&std::fs::read_to_string(&output).unwrap()).unwrap();
+            let mut modules: Vec<&str> = exported["modules"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|module| module["name"].as_str().unwrap())
+                .collect();
+            modules.sort();
+            assert_eq!(modules, vec!["app::api", "core::model"]);
+            assert!(exported["traits"].is_array());
+        }
+    }
+
+    /// Tests for the JUnit report written by check --format junit
+    mod junit_tests {
+        use super::*;
+        use cargo_pup_lint_config::{FunctionLintExt, ModuleLintExt};
+
+        fn violation(lint_name: &str, message: &str, line: usize) -> LintViolation {
+            LintViolation {
+                lint_name: lint_name.to_string(),
+                severity: Severity::Error,
+                file: "src/lib.rs".to_string(),
+                line,
+                message: message.to_string(),
+            }
+        }
+
+        #[test]
+        fn test_render_junit_report_counts() {
+            let mut builder = LintBuilder::new();
+            builder
+                .module_lint()
+                .lint_named("no_wildcards")
+                .matching(|m| m.module(".*"))
+                .no_wildcard_imports()
+                .build();
+            builder
+                .function_lint()
+                .lint_named("short_functions")
+                .matching(|m| m.name_regex(".*"))
+                .max_length(20)
+                .build();
+            builder
+                .function_lint()
+                .lint_named("no_unwrap")
+                .matching(|m| m.name_regex(".*"))
+                .no_unwrap()
+                .build();
+
+            let violations = vec![
+                violation("no_unwrap", "Function 'a' calls unwrap", 3),
+                violation("no_unwrap", "Function 'b' calls <unwrap> & co", 9),
+                violation("short_functions", "Function 'c' is too long", 12),
+                violation("removed_lint", "Not in the configuration", 1),
+            ];
+
+            let xml = render_junit_report(&builder.lints, &violations);
+
+            assert!(xml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n"));
+            assert!(xml.contains("<testsuites name=\"cargo-pup\" tests=\"4\" failures=\"3\">"));
+            assert!(xml.contains("<testsuite name=\"module\" tests=\"1\" failures=\"0\">"));
+            assert!(xml.contains("<testsuite name=\"function\" tests=\"2\" failures=\"2\">"));
+            assert!(xml.contains("<testsuite name=\"other\" tests=\"1\" failures=\"1\">"));
+            assert_eq!(xml.matches("<testsuite ").count(), 3);
+            assert_eq!(xml.matches("<testcase ").count(), 4);
+            assert_eq!(xml.matches("<failure ").count(), 4);
+            assert!(xml.contains("<testcase name=\"no_wildcards\" classname=\"module\"/>"));
+            assert!(xml.contains(
+                "src/lib.rs:9: Function &apos;b&apos; calls &lt;unwrap&gt; &amp; co</failure>"
+            ));
+        }
+
+        #[test]
+        fn test_render_junit_report_without_lints() {
+            let xml = render_junit_report(&[], &[]);
+            assert!(xml.contains("<testsuites name=\"cargo-pup\" tests=\"0\" failures=\"0\">"));
+            assert!(!xml.contains("<testsuite "));
+        }
+    }
+
+    /// Tests for escalate_after limits, which count violations across the workspace
+    mod escalation_tests {
+        use super::*;
+        use cargo_pup_lint_config::FunctionLintExt;
+
+        fn entry(crate_name: &str, file: &str, line: usize) -> ReportEntry {
+            ReportEntry {
+                crate_name: crate_name.to_string(),
+                violation: LintViolation {
+                    lint_name: "no_unwrap".to_string(),
+                    severity: Severity::Warn,
+                    file: file.to_string(),
+                    line,
+                    message: "Use of unwrap() is forbidden".to_string(),
+                },
+            }
+        }
+
+        fn no_unwrap(escalate_after: usize) -> Vec<ConfiguredLint> {
+            let mut builder = LintBuilder::new();
+            builder
+                .function_lint()
+                .lint_named("no_unwrap")
+                .matching(|m| m.name_regex(".*"))
+                .escalate_after(escalate_after)
+                .no_unwrap()
+                .build();
+            builder.lints
+        }
+
+        #[test]
+        fn test_limit_applies_to_the_workspace_total() {
+            // Neither crate goes over the limit on its own
+            let entries = vec![
+                entry("api", "api/src/lib.rs", 3),
+                entry("api", "api/src/lib.rs", 7),
+                entry("core", "core/src/lib.rs", 4),
+                entry("core", "core/src/lib.rs", 9),
+            ];
+            assert_eq!(
+                escalation_messages(&no_unwrap(3), &entries),
+                vec![
+                    "cargo-pup lint 'no_unwrap' reported 4 violation(s), more than its escalate_after limit of 3"
+                ]
+            );
+            assert!(escalation_messages(&no_unwrap(4), &entries).is_empty());
+        }
+
+        #[test]
+        fn test_violation_reported_by_two_crates_counts_once() {
+            // A library's tests report what the library itself did
+            let entries = vec![
+                entry("core", "core/src/lib.rs", 4),
+                entry("core", "core/src/lib.rs", 4),
+            ];
+            assert!(escalation_messages(&no_unwrap(1), &entries).is_empty());
+        }
+    }
+
+    /// Tests for the grouped output of check --group-by
+    mod group_by_tests {
+        use super::*;
+
+        const CARGO_STDOUT: &str = concat!(
+            r#"{"reason":"compiler-message","message":{"level":"error","message":"Use of unwrap() is not allowed","rendered":"error: Use of unwrap() is not allowed\n","spans":[{"file_name":"src/lib.rs","line_start":12,"is_primary":true}],"children":[{"level":"note","message":"Applied by cargo-pup rule 'no_unwrap'.","spans":[],"children":[]}]}}"#,
+            "\n",
+            r#"{"reason":"compiler-message","message":{"level":"error","message":"cannot find value `x`","rendered":"error: cannot find value `x`\n","spans":[{"file_name":"src/lib.rs","line_start":4,"is_primary":true}],"children":[]}}"#,
+            "\n",
+            r#"{"reason":"build-finished","success":false}"#,
+        );
+
+        #[test]
+        fn test_compiler_messages() {
+            assert_eq!(
+                rendered_compiler_messages(CARGO_STDOUT),
+                vec![
+                    "error: Use of unwrap() is not allowed\n",
+                    "error: cannot find value `x`\n"
+                ]
+            );
+            assert_eq!(
+                other_compiler_messages(CARGO_STDOUT),
+                vec!["error: cannot find value `x`\n"]
+            );
+        }
+
+        fn violation(lint_name: &str, file: &str, line: usize) -> LintViolation {
+            LintViolation {
+                lint_name: lint_name.to_string(),
+                severity: Severity::Warn,
+                file: file.to_string(),
+                line,
+                message: format!("{lint_name} failed"),
+            }
+        }
+
+        fn violations() -> Vec<LintViolation> {
+            vec![
+                violation("short_functions", "src/b.rs", 9),
+                violation("no_wildcards", "src/a.rs", 1),
+                violation("short_functions", "src/a.rs", 4),
+            ]
+        }
+
+        #[test]
+        fn test_group_by_file() {
+            let out = render_grouped_violations(&violations(), GroupBy::File);
+
+            let a = out.find("src/a.rs").unwrap();
+            let b = out.find("src/b.rs").unwrap();
+            assert!(a < b, "groups should be sorted: {out}");
+            assert!(out.contains("(2 violations)"));
+            assert!(out.contains("(1 violation)"));
+            assert!(out.contains(" [short_functions] short_functions failed"));
+            assert!(out.contains("  line 4: "));
+            assert!(out.ends_with("3 violation(s) across 2 files\n"));
+        }
+
+        #[test]
+        fn test_group_by_lint() {
+            let out = render_grouped_violations(&violations(), GroupBy::Lint);
+
+            let wildcards = out.find("no_wildcards").unwrap();
+            let short = out.find("short_functions").unwrap();
+            assert!(wildcards < short, "groups should be sorted: {out}");
+            // Violations are ordered by file and line within their group
+            let first = out.find("  src/a.rs:4: ").unwrap();
+            let second = out.find("  src/b.rs:9: ").unwrap();
+            assert!(first < second);
+            assert!(out.ends_with("3 violation(s) across 2 lints\n"));
+        }
+
+        #[test]
+        fn test_group_by_without_violations() {
+            let out = render_grouped_violations(&[], GroupBy::File);
+            assert_eq!(out, "0 violation(s) across 0 files\n");
+        }
+    }
+
+    /// Tests for the watch command
+    mod watch_tests {
+        use super::*;
+
+        fn to_args(args: &[&str]) -> Vec<String> {
+            args.iter().map(|s| s.to_string()).collect()
+        }
+
+        #[test]
+        fn test_watch_command_detection() {
+            assert_eq!(
+                get_command_type(&to_args(&["cargo", "pup", "watch"])),
+                CommandType::Watch
+            );
+            assert_eq!(
+                get_command_type(&to_args(&["cargo-pup", "watch"])),
+                CommandType::Watch
+            );
+        }
+
+        #[test]
+        fn test_watch_check_args_keep_cargo_args() {
+            assert_eq!(
+                watch_check_args(&to_args(&["cargo", "pup", "watch", "--features=foo"])),
+                to_args(&["cargo", "pup", "check", "--features=foo"])
+            );
+            assert_eq!(
+                watch_check_args(&to_args(&["cargo-pup", "watch", "--pup-config", "a.ron"])),
+                to_args(&["cargo-pup", "check", "--pup-config", "a.ron"])
+            );
+        }
+
+        #[test]
+        fn test_is_watched_change() {
+            let root = Path::new("/project");
+            let config_files = vec![PathBuf::from("/shared/base.ron")];
+            let watched = |path: &str| is_watched_change(Path::new(path), root, &config_files);
+
+            assert!(watched("/project/src/lib.rs"));
+            assert!(watched("/project/crates/a/pup.ron"));
+            assert!(watched("/shared/base.ron"));
+
+            assert!(!watched("/project/Cargo.toml"));
+            assert!(!watched("/project/target/debug/build.rs"));
+            assert!(!watched("/project/.pup/ignored.rs"));
+            assert!(!watched("/elsewhere/lib.rs"));
+        }
+
+        #[test]
+        fn test_watched_config_files_include_includes() {
+            let temp_dir = TempDir::new().unwrap();
+            let root = temp_dir.path().canonicalize().unwrap();
+            fs::write(root.join("base.ron"), "(lints: [])").unwrap();
+            let config = root.join("custom.ron");
+            fs::write(&config, r#"(include: ["base.ron"], lints: [])"#).unwrap();
+
+            assert_eq!(
+                watched_config_files(Some(&config)),
+                vec![config.clone(), root.join("base.ron")]
+            );
+        }
+    }
+
     /// Tests for command line processing
     mod command_line_processing_tests {
-        use cargo_pup_common::cli::{PupArgs, PupCli, PupCommand};
+        use cargo_pup_common::cli::{PupArgs, PupCli, PupCommand, Verbosity};
         #[test]
         fn test_arguments_parsing() {
             // Test different argument combinations
@@ -1456,6 +3521,15 @@ mod tests {
             let pup_cli = PupCli {
                 config_path: None,
                 command: PupCommand::PrintModules,
+                deny_warnings: false,
+                exit_zero: false,
+                fail_fast: false,
+                changed_files: None,
+                verbosity: Verbosity::Normal,
+                output_dir: None,
+                report_file: None,
+                timings_file: None,
+                profile: None,
             };
 
             // Serialize it
@@ -1471,6 +3545,15 @@ mod tests {
             let pup_cli = PupCli {
                 config_path: None,
                 command: PupCommand::GenerateConfig,
+                deny_warnings: false,
+                exit_zero: false,
+                fail_fast: false,
+                changed_files: None,
+                verbosity: Verbosity::Normal,
+                output_dir: None,
+                report_file: None,
+                timings_file: None,
+                profile: None,
             };
 
             // Serialize it
@@ -14,9 +14,14 @@ extern crate rustc_span;
 extern crate rustc_trait_selection;
 
 use anyhow::Result;
-use cargo_pup_common::cli::{PupCli, PupCommand};
-use cargo_pup_common::workspace::find_workspace_pup_ron;
+use cargo_pup_common::cli::{PupCli, PupCommand, Verbosity};
+use cargo_pup_common::workspace::find_crate_pup_ron;
 
+use cargo_pup_lint_config::config_loader;
+use cargo_pup_lint_config::report::append_report_entries;
+use cargo_pup_lint_impl::helpers::lint_helpers::{
+    restrict_to_files, take_report_entries, warning_emitted,
+};
 use cargo_pup_lint_impl::lints::configuration_factory::LintConfigurationFactory;
 use cargo_pup_lint_impl::{ArchitectureLintCollection, ArchitectureLintRunner, Mode};
 use rustc_session::{EarlyDiagCtxt, config::ErrorOutputType};
@@ -50,30 +55,48 @@ pub fn main() -> Result<()> {
         orig_args.extend(vec!["--sysroot".into(), find_sysroot()]);
     }
 
+    // Load our configuration from CLI args, defaulting to check mode if there are none
+    let cli_env = std::env::var("PUP_CLI_ARGS").unwrap_or_default();
+    let cli_config = if cli_env.is_empty() || is_ui_testing {
+        PupCli::default()
+    } else {
+        PupCli::from_env_str(&cli_env)
+    };
+
     // Default to check mode if we're in UI testing
     let mode = if is_ui_testing {
         Mode::Check
     } else {
-        // Load our configuration from CLI args
-        let binding = std::env::var("PUP_CLI_ARGS").unwrap_or_default();
-        let cli_args = binding.as_str();
-        let config = if cli_args.is_empty() {
-            // Default to check mode if no CLI args
-            PupCli::default()
-        } else {
-            PupCli::from_env_str(cli_args)
-        };
-
-        match config.command {
+        match cli_config.command {
             PupCommand::PrintModules => Mode::PrintModules,
             PupCommand::PrintTraits => Mode::PrintTraits,
             PupCommand::Check => Mode::Check,
             PupCommand::GenerateConfig => Mode::GenerateConfig,
+            PupCommand::Graph => Mode::Graph,
+            PupCommand::Stats => Mode::Stats,
+            PupCommand::ExportContext => Mode::ExportContext,
+            PupCommand::ListLints => {
+                unreachable!("list-lints is handled by cargo-pup without invoking the compiler")
+            }
+            PupCommand::ValidateConfig => {
+                unreachable!(
+                    "validate-config is handled by cargo-pup without invoking the compiler"
+                )
+            }
+            PupCommand::Watch => {
+                unreachable!("watch runs check for each change, so never reaches pup-driver")
+            }
+            PupCommand::Init => {
+                unreachable!("init only writes template files, so never reaches pup-driver")
+            }
+            PupCommand::Diff { .. } => {
+                unreachable!("diff is handled by cargo-pup without invoking the compiler")
+            }
         }
     };
 
     // Log it, so we can work out what is going on
-    log_invocation(&orig_args)?;
+    log_invocation(&cli_config.pup_dir(), &orig_args)?;
 
     // Parse cargo arguments from environment
     let mut cargo_args = Vec::new();
@@ -85,6 +108,9 @@ pub fn main() -> Result<()> {
         }
     }
 
+    // The configuration files the lints come from, outside of UI tests
+    let mut config_files = Vec::new();
+
     // Determine the lint collection to use
     let lint_collection = if mode == Mode::GenerateConfig {
         // For generate-config mode, use an empty collection
@@ -97,7 +123,8 @@ pub fn main() -> Result<()> {
         // Try loading from pup.ron
         let ron_path = test_dir.join("pup.ron");
         if ron_path.exists() {
-            match LintConfigurationFactory::from_file(ron_path.to_str().unwrap().to_string()) {
+            match LintConfigurationFactory::from_file(ron_path.to_str().unwrap().to_string(), None)
+            {
                 Ok(lint_rules) => ArchitectureLintCollection::new(lint_rules),
                 Err(e) => {
                     // In UI tests, print detailed error messages about configuration issues
@@ -110,36 +137,42 @@ pub fn main() -> Result<()> {
         }
     } else {
         // For normal operation, load from specified config file or default to pup.ron
-        // Get config path from CLI args if provided
-        let binding = std::env::var("PUP_CLI_ARGS").unwrap_or_default();
-        let cli_args = binding.as_str();
-        let cli_config = if cli_args.is_empty() {
-            PupCli::default()
-        } else {
-            PupCli::from_env_str(cli_args)
-        };
-
         let original_dir = if let Ok(original_dir) = env::var("PUP_ORIGINAL_DIR") {
             PathBuf::from(original_dir)
         } else {
             env::current_dir()?
         };
 
-        let config_path = if let Some(path) = cli_config.config_path {
+        let config_path = if let Some(path) = &cli_config.config_path {
             // Use provided config path, resolve relative to original directory
-            if Path::new(&path).is_absolute() {
+            if Path::new(path).is_absolute() {
                 PathBuf::from(path)
             } else {
                 original_dir.join(path)
             }
         } else {
-            // Default to workspace root pup.ron, fallback to local only if not in workspace
-            find_workspace_pup_ron().unwrap_or_else(|| original_dir.join("pup.ron"))
+            // Default to the pup.ron nearest the crate being compiled, so each crate in a
+            // workspace can have its own. Cargo tells us where the crate's manifest is.
+            let crate_dir = env::var_os("CARGO_MANIFEST_DIR")
+                .map(PathBuf::from)
+                .unwrap_or_else(|| original_dir.clone());
+            find_crate_pup_ron(&crate_dir).unwrap_or_else(|| original_dir.join("pup.ron"))
         };
 
         if config_path.exists() {
-            match LintConfigurationFactory::from_file(config_path.to_str().unwrap().to_string()) {
+            // If the includes can't be resolved, the configuration itself is still tracked
+            config_files = config_loader::config_files(&config_path)
+                .unwrap_or_else(|_| vec![config_path.clone()]);
+            match LintConfigurationFactory::from_file(
+                config_path.to_str().unwrap().to_string(),
+                cli_config.profile.as_deref(),
+            ) {
                 Ok(lint_rules) => ArchitectureLintCollection::new(lint_rules),
+                // A check that ran no lints would pass, whatever the code looks like
+                Err(e) if mode == Mode::Check => {
+                    eprintln!("error: failed to load {}: {e:#}", config_path.display());
+                    process::exit(1);
+                }
                 Err(e) => {
                     eprintln!("Failed to parse {}: {}", config_path.display(), e);
                     ArchitectureLintCollection::new(Vec::new())
@@ -159,10 +192,26 @@ pub fn main() -> Result<()> {
         std::env::var("PUP_CLI_ARGS").unwrap_or_default()
     };
 
+    // With `check --since`, diagnostics are only reported in the changed files
+    if let Some(changed_files) = &cli_config.changed_files {
+        restrict_to_files(changed_files.iter().map(PathBuf::from));
+    }
+
     let mut runner = ArchitectureLintRunner::new(mode.clone(), cli_args, lint_collection);
     runner.set_cargo_args(cargo_args);
+    if let Some(timings_file) = &cli_config.timings_file {
+        runner.set_timings_file(PathBuf::from(timings_file));
+    }
+    runner.set_verbose(cli_config.verbosity == Verbosity::Verbose);
+    runner.set_exit_zero(cli_config.exit_zero);
+    // With --exit-zero, errors from our lints are no reason to stop
+    runner.set_fail_fast(cli_config.fail_fast && !cli_config.exit_zero);
+    runner.set_pup_dir(cli_config.pup_dir());
+    runner.set_config_files(config_files);
 
-    rustc_driver::run_compiler(&orig_args, &mut runner);
+    // Catch compilation errors, so cargo sees rustc's usual exit code for them
+    let exit_code =
+        rustc_driver::catch_with_exit_code(|| rustc_driver::run_compiler(&orig_args, &mut runner));
 
     // Print out our lints
     if mode != Mode::GenerateConfig {
@@ -172,7 +221,20 @@ pub fn main() -> Result<()> {
         }
     }
 
-    process::exit(0);
+    // Add this crate's violations to the report for the whole workspace
+    if let Some(report_file) = &cli_config.report_file
+        && let Err(e) = append_report_entries(Path::new(report_file), &take_report_entries())
+    {
+        eprintln!("Warning: Failed to add to the workspace report: {e}");
+    }
+
+    // The warnings have already rendered as warnings; only the exit code changes
+    if exit_code == 0 && cli_config.deny_warnings && !cli_config.exit_zero && warning_emitted() {
+        eprintln!("error: cargo-pup lints emitted warnings and --deny-warnings is set");
+        process::exit(1);
+    }
+
+    process::exit(exit_code);
 }
 
 /// Find the source file from the command line arguments
@@ -206,12 +268,12 @@ fn find_sysroot() -> String {
 }
 
 ///
-/// Appends the given arguments to `.pup/invocations.txt` in the current working directory
-/// with a timestamp prepended.
+/// Appends the given arguments to `invocations.txt` in the .pup directory, relative to
+/// the current working directory, with a timestamp prepended.
 ///
-fn log_invocation(orig_args: &[String]) -> std::io::Result<()> {
+fn log_invocation(pup_dir: &Path, orig_args: &[String]) -> std::io::Result<()> {
     let cwd = std::env::current_dir()?;
-    let log_path = cwd.join(".pup/invocations.txt");
+    let log_path = cwd.join(pup_dir).join("invocations.txt");
 
     // Ensure the directory exists
     if let Some(parent) = log_path.parent() {
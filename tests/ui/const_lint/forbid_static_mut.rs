@@ -0,0 +1,16 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//@compile-flags: --crate-name test_const_forbid_static_mut
+//@compile-flags: --crate-type lib
+
+// This test verifies that ForbidStaticMut reports `static mut` items only
+
+use std::sync::atomic::AtomicBool;
+
+pub static INITIALIZED: AtomicBool = AtomicBool::new(false);
+
+pub static mut COUNTER: u64 = 0; //~ ERROR: Static 'COUNTER' is declared `static mut`
+
+static mut BUFFER: [u8; 16] = [0; 16]; //~ ERROR: Static 'BUFFER' is declared `static mut`
+
+pub const LIMIT: u64 = 100;
@@ -0,0 +1,16 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//@compile-flags: --crate-name test_const_forbid_type
+//@compile-flags: --crate-type lib
+
+// This test verifies that ForbidType matches the fully qualified type of consts and
+// statics
+
+use std::sync::Mutex;
+use std::sync::atomic::AtomicU64;
+
+pub static REQUEST_COUNT: AtomicU64 = AtomicU64::new(0);
+
+pub static REGISTRY: Mutex<Vec<String>> = Mutex::new(Vec::new()); //~ ERROR: Static 'REGISTRY' has forbidden type 'std::sync::Mutex<std::vec::Vec<std::string::String>>'
+
+pub const EMPTY_LOCK: Mutex<u32> = Mutex::new(0); //~ ERROR: Constant 'EMPTY_LOCK' has forbidden type 'std::sync::Mutex<u32>'
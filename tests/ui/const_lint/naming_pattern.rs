@@ -0,0 +1,30 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//@compile-flags: --crate-name test_const_naming_pattern
+//@compile-flags: --crate-type lib
+
+#![allow(non_upper_case_globals)]
+
+// This test verifies that NamingPattern checks the names of consts and statics in the
+// matched modules, but not the `const _` items that macros expand to
+
+pub mod config {
+    pub const MAX_RETRIES: u32 = 3;
+
+    pub static DEFAULT_HOST: &str = "localhost";
+
+    pub const timeoutSeconds: u64 = 30; //~ ERROR: Constant 'timeoutSeconds' doesn't match the naming pattern
+
+    pub static default_port: u16 = 8080; //~ ERROR: Static 'default_port' doesn't match the naming pattern
+
+    const _: () = ();
+
+    pub fn retries() -> u32 {
+        // Consts declared inside functions are checked too
+        const local_limit: u32 = 10; //~ ERROR: Constant 'local_limit' doesn't match the naming pattern
+        local_limit.min(MAX_RETRIES)
+    }
+}
+
+// Outside the matched module, anything goes
+pub const lowercase_elsewhere: u32 = 1;
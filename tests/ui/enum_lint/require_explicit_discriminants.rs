@@ -0,0 +1,35 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//@compile-flags: --crate-name test_enum_explicit_discriminants
+//@compile-flags: --crate-type lib
+
+// This test verifies that RequireExplicitDiscriminants reports the variants of C-style
+// enums that don't give their discriminant, and leaves enums holding data alone
+
+pub mod ffi {
+    #[repr(C)]
+    pub enum Status {
+        Ready = 0,
+        Busy = 1,
+        Failed = 2,
+    }
+
+    #[repr(u8)]
+    pub enum Mode {
+        Read = 1,
+        Write, //~ ERROR: Variant 'Mode::Write' has no explicit discriminant
+        Append, //~ ERROR: Variant 'Mode::Append' has no explicit discriminant
+    }
+
+    // Holds data, so its discriminants aren't seen across the boundary
+    pub enum Message {
+        Ping,
+        Data(Vec<u8>),
+    }
+}
+
+// Outside the matched module, implicit discriminants are fine
+pub enum Color {
+    Red,
+    Green,
+}
@@ -0,0 +1,64 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//@compile-flags: --crate-name test_allow_attribute
+//@compile-flags: --crate-type lib
+
+// This test verifies that #[allow(pup::<rule>)] suppresses the named rule only,
+// while other rules keep applying to the same item, that #[expect(pup::<rule>)]
+// suppresses it too, and that the attribute nearest the item wins
+
+pub fn not_allowed(x: Option<i32>) -> i32 {
+    x.unwrap() //~ ERROR: Function may panic
+}
+
+// Only no_unwrap is suppressed - the function is still too long
+#[allow(pup::no_unwrap)]
+pub fn unwrap_allowed(x: Option<i32>) -> i32 { //~ ERROR: Function exceeds maximum length
+    let y = x.unwrap();
+    let z = y + 1;
+    z * 2
+}
+
+// Allowing a rule on a parent applies to everything inside it
+#[allow(pup::no_unwrap)]
+pub mod relaxed {
+    pub fn uses_unwrap(x: Option<i32>) -> i32 {
+        x.unwrap()
+    }
+}
+
+// Expecting a rule suppresses it like allowing it does
+#[expect(pup::no_unwrap)]
+pub fn unwrap_expected(x: Option<i32>) -> i32 {
+    x.unwrap()
+}
+
+// A warn or deny closer to the item turns an allowed rule back on
+#[allow(pup::no_unwrap)]
+pub mod mostly_relaxed {
+    #[warn(pup::no_unwrap)]
+    pub fn warned(x: Option<i32>) -> i32 {
+        x.unwrap() //~ ERROR: Function may panic
+    }
+
+    #[deny(pup::no_unwrap)]
+    pub mod strict {
+        pub fn denied(x: Option<i32>) -> i32 {
+            x.unwrap() //~ ERROR: Function may panic
+        }
+
+        // ...and an allow closer still turns it off again
+        #[allow(pup::no_unwrap)]
+        pub fn allowed_again(x: Option<i32>) -> i32 {
+            x.unwrap()
+        }
+    }
+}
+
+// Allowing both rules silences the function entirely
+#[allow(pup::no_unwrap, pup::short_functions)]
+pub fn everything_allowed(x: Option<i32>) -> i32 {
+    let y = x.unwrap();
+    let z = y + 1;
+    z * 2
+}
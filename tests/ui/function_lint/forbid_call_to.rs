@@ -0,0 +1,42 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//@compile-flags: --crate-name test_forbid_call_to
+//@compile-flags: --crate-type lib
+
+// This test verifies that ForbidCallTo (configured with `^std::process::exit$`,
+// `^std::env::var$` and `::Vec::<.*>::push$`) reports each call to a forbidden
+// function or method, however the call is written.
+
+use std::env;
+use std::process::exit;
+
+pub fn shutdown(code: i32) -> ! {
+    exit(code) //~ ERROR: Function 'shutdown' calls forbidden function 'std::process::exit'
+}
+
+pub fn read_config() -> Option<String> {
+    env::var("APP_CONFIG").ok() //~ ERROR: Function 'read_config' calls forbidden function 'std::env::var'
+}
+
+pub fn lazy_config() -> impl Fn() -> bool {
+    // Calls in closures count towards the enclosing function
+    || std::env::var("APP_DEBUG").is_ok() //~ ERROR: Function 'lazy_config' calls forbidden function 'std::env::var'
+}
+
+pub fn collect(values: &mut Vec<u32>) {
+    values.push(1); //~ ERROR: Function 'collect' calls forbidden function 'std::vec::Vec::<T, A>::push'
+    Vec::push(values, 2); //~ ERROR: Function 'collect' calls forbidden function 'std::vec::Vec::<T, A>::push'
+}
+
+// The anchored patterns only match the whole path, so similarly named functions are fine
+pub fn environment_size() -> usize {
+    env::vars().count()
+}
+
+pub struct Service;
+
+impl Service {
+    pub fn stop(&self) {
+        exit(0); //~ ERROR: Function 'stop' calls forbidden function 'std::process::exit'
+    }
+}
@@ -0,0 +1,60 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//@compile-flags: --crate-name test_forbid_indexing
+//@compile-flags: --crate-type lib
+
+// This test verifies that ForbidIndexing reports indexing expressions, and that
+// allow_constant_indices leaves out indexing by literals and constants.
+
+use std::collections::HashMap;
+
+pub mod strict {
+    use super::HashMap;
+
+    pub fn first(values: &[u32]) -> u32 {
+        values[0] //~ ERROR: Function 'first' uses indexing, which panics when out of bounds
+    }
+
+    pub fn lookup(map: &HashMap<&str, u32>, key: &str) -> u32 {
+        map[key] //~ ERROR: Function 'lookup' uses indexing, which panics when out of bounds
+    }
+
+    pub fn tail(values: &[u32]) -> &[u32] {
+        &values[1..] //~ ERROR: Function 'tail' uses indexing, which panics when out of bounds
+    }
+
+    pub fn reset(values: &mut [u32], index: usize) {
+        values[index] = 0; //~ ERROR: Function 'reset' uses indexing, which panics when out of bounds
+    }
+
+    pub fn sum_pairs(pairs: &[[u32; 2]]) -> Vec<u32> {
+        pairs
+            .iter()
+            .map(|pair| pair[0] + pair[1]) //~ ERROR: Function 'sum_pairs' uses indexing, which panics when out of bounds
+            //~^ ERROR: Function 'sum_pairs' uses indexing, which panics when out of bounds
+            .collect()
+    }
+
+    // The safe alternatives are fine
+    pub fn first_checked(values: &[u32]) -> Option<u32> {
+        values.get(0).copied()
+    }
+}
+
+pub mod constants {
+    const HEADER_LEN: usize = 4;
+
+    pub struct Frame;
+
+    impl Frame {
+        pub const KIND: usize = 1;
+    }
+
+    pub fn header(bytes: &[u8; 8]) -> (u8, u8, u8) {
+        (bytes[0], bytes[HEADER_LEN], bytes[Frame::KIND])
+    }
+
+    pub fn at(bytes: &[u8], index: usize) -> u8 {
+        bytes[index] //~ ERROR: Function 'at' uses indexing, which panics when out of bounds
+    }
+}
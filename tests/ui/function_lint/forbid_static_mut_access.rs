@@ -0,0 +1,47 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//@compile-flags: --crate-name test_forbid_static_mut_access
+//@compile-flags: --crate-type lib
+
+// This test verifies that ForbidStaticMutAccess reports every read and write of a
+// static mut, even inside unsafe blocks, while plain statics and atomics are fine.
+
+use std::sync::atomic::{AtomicU32, Ordering};
+
+static mut COUNTER: u32 = 0;
+static LIMIT: u32 = 10;
+static HITS: AtomicU32 = AtomicU32::new(0);
+
+pub fn increment() {
+    unsafe {
+        COUNTER += 1; //~ ERROR: Function 'increment' accesses static mut 'COUNTER'
+    }
+}
+
+pub fn current() -> u32 {
+    unsafe { COUNTER } //~ ERROR: Function 'current' accesses static mut 'COUNTER'
+}
+
+pub fn counter_ptr() -> *mut u32 {
+    &raw mut COUNTER //~ ERROR: Function 'counter_ptr' accesses static mut 'COUNTER'
+}
+
+pub fn reset_later() -> impl Fn() {
+    || unsafe { COUNTER = 0 } //~ ERROR: Function 'reset_later' accesses static mut 'COUNTER'
+}
+
+pub struct Tracker;
+
+impl Tracker {
+    pub fn double(&self) {
+        unsafe {
+            COUNTER = COUNTER * 2; //~ ERROR: Function 'double' accesses static mut 'COUNTER'
+            //~^ ERROR: Function 'double' accesses static mut 'COUNTER'
+        }
+    }
+}
+
+// Immutable statics and atomics are fine
+pub fn hit() -> u32 {
+    HITS.fetch_add(1, Ordering::Relaxed).min(LIMIT)
+}
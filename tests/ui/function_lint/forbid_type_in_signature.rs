@@ -0,0 +1,38 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//@compile-flags: --crate-name test_forbid_type_in_signature
+//@compile-flags: --crate-type lib
+
+// This test verifies that ForbidTypeInSignature reports parameters and return types
+// matching the forbidden patterns `^std::rc::Rc<` and `^\*mut `, naming the position.
+
+use std::rc::Rc;
+use std::sync::Arc;
+
+pub fn shares_rc(value: Rc<u32>) -> u32 { //~ ERROR: uses forbidden type 'std::rc::Rc<u32>' in its parameter 1
+    *value
+}
+
+pub fn makes_rc(value: u32) -> Rc<u32> { //~ ERROR: uses forbidden type 'std::rc::Rc<u32>' in its return type
+    Rc::new(value)
+}
+
+pub fn raw_second(len: usize, data: *mut u8) -> usize { //~ ERROR: uses forbidden type '*mut u8' in its parameter 2
+    let _ = data;
+    len
+}
+
+pub struct Cache;
+
+impl Cache {
+    pub fn get(&self, key: &str) -> Option<Rc<str>> { //~ ERROR: uses forbidden type 'std::option::Option<std::rc::Rc<str>>' in its return type
+        let _ = key;
+        None
+    }
+}
+
+// Arc and *const pointers are allowed - no lint
+pub fn shares_arc(value: Arc<u32>, data: *const u8) -> Arc<u32> {
+    let _ = data;
+    value
+}
@@ -0,0 +1,23 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//@compile-flags: --crate-name test_type_pattern_as_text
+//@compile-flags: --crate-type lib
+
+// This test verifies that a ForbidTypeInSignature pattern that isn't a valid regex,
+// `*mut u8`, only matches a type rendered exactly as it.
+
+pub fn raw(data: *mut u8) -> usize { //~ ERROR: uses forbidden type '*mut u8' in its parameter 1
+    let _ = data;
+    0
+}
+
+// The pattern isn't searched for within other types - no lint
+pub fn nested(len: usize) -> Option<*mut u8> {
+    let _ = len;
+    None
+}
+
+// *const pointers are allowed - no lint
+pub fn read_only(data: *const u8) -> *const u8 {
+    data
+}
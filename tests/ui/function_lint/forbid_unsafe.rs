@@ -0,0 +1,42 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//@compile-flags: --crate-name test_forbid_unsafe
+//@compile-flags: --crate-type lib
+
+// This test verifies that ForbidUnsafe flags unsafe blocks and unsafe fn
+// declarations, except in the `ffi` module, which the matcher excludes.
+
+pub fn read_first(values: &[u32]) -> u32 {
+    unsafe { *values.get_unchecked(0) } //~ ERROR: Function 'read_first' contains an unsafe block
+}
+
+// Also reported by the crate-wide IsUnsafe/MustNotExist lint in this directory
+pub unsafe fn raw_read(ptr: *const u32) -> u32 { //~ ERROR: Function 'raw_read' is declared unsafe
+//~^ ERROR: Function 'raw_read' is forbidden by lint rule
+    unsafe { *ptr } //~ ERROR: Function 'raw_read' contains an unsafe block
+}
+
+pub struct Buffer(Vec<u8>);
+
+impl Buffer {
+    pub fn first(&self) -> u8 {
+        unsafe { *self.0.as_ptr() } //~ ERROR: Function 'first' contains an unsafe block
+    }
+
+    // Safe methods are fine
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+// Safe functions are fine
+pub fn sum(values: &[u32]) -> u32 {
+    values.iter().sum()
+}
+
+pub mod ffi {
+    // The ffi module is excluded by the matcher, so no lint
+    pub fn call_native(values: &[u8]) -> u8 {
+        unsafe { *values.as_ptr() }
+    }
+}
@@ -0,0 +1,45 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//@compile-flags: --crate-name test_is_method
+//@compile-flags: --crate-type lib
+
+// This test verifies that the IsFreeFunction and IsMethod matchers tell module-level
+// functions apart from associated ones. Free functions may take one parameter by value,
+// and every method, with or without `self`, needs a doc comment.
+
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+pub fn origin_distance(x: i64, y: i64) -> i64 { //~ ERROR: Function 'origin_distance' takes 2 parameters by value, exceeding the maximum of 1
+    x.abs() + y.abs()
+}
+
+pub fn negate(point: &Point) -> Point {
+    Point { x: -point.x, y: -point.y }
+}
+
+impl Point {
+    // Two by-value parameters, but a method, so only its missing doc is reported
+    pub fn new(x: i64, y: i64) -> Self { //~ ERROR: Public function is missing a doc comment
+        Point { x, y }
+    }
+
+    /// Documented, so nothing to report
+    pub fn translate(self, dx: i64, dy: i64) -> Self {
+        Point { x: self.x + dx, y: self.y + dy }
+    }
+}
+
+pub trait Shape {
+    fn area(&self) -> i64;
+}
+
+// Trait impl functions are methods too, but take the trait's visibility, so they need
+// no doc comment of their own
+impl Shape for Point {
+    fn area(&self) -> i64 {
+        0
+    }
+}
@@ -0,0 +1,44 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//@compile-flags: --crate-name test_max_by_value_parameters
+//@compile-flags: --crate-type lib
+
+// This test verifies that MaxByValueParameters counts only the parameters that aren't
+// references, including a by-value `self`
+
+pub struct Document {
+    pub title: String,
+    pub body: Vec<u8>,
+}
+
+// Three owned values exceed the limit of two
+pub fn publish(title: String, body: Vec<u8>, tags: Vec<String>) -> Document { //~ ERROR: Function 'publish' takes 3 parameters by value, exceeding the maximum of 2
+    let _ = tags;
+    Document { title, body }
+}
+
+// References don't count, however many there are
+pub fn compare(a: &Document, b: &Document, c: &mut Document, limit: usize) -> bool {
+    c.title.clear();
+    a.title == b.title && a.body.len() < limit
+}
+
+// Two by-value parameters are within the limit
+pub fn rename(document: Document, title: String) -> Document {
+    Document { title, ..document }
+}
+
+impl Document {
+    // By-reference self isn't counted
+    pub fn merge(&self, other: Document, separator: u8) -> Vec<u8> {
+        let mut body = self.body.clone();
+        body.push(separator);
+        body.extend(other.body);
+        body
+    }
+
+    // By-value self is
+    pub fn into_parts(self, prefix: String, suffix: String) -> (String, Vec<u8>) { //~ ERROR: Function 'into_parts' takes 3 parameters by value, exceeding the maximum of 2
+        (format!("{prefix}{}{suffix}", self.title), self.body)
+    }
+}
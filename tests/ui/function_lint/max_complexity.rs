@@ -0,0 +1,69 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//@compile-flags: --crate-name test_max_complexity
+//@compile-flags: --crate-type lib
+
+// This test verifies that MaxComplexity (configured with a maximum of 3) reports
+// functions whose decision points plus one exceed the limit.
+
+// Complexity 1: no decision points
+pub fn straight_line(a: u32, b: u32) -> u32 {
+    a + b
+}
+
+// Complexity 3: two extra match arms; the guard is not counted
+pub fn at_limit(value: Option<u32>) -> u32 {
+    match value {
+        Some(v) if v > 10 => v,
+        Some(v) => v + 1,
+        None => 0,
+    }
+}
+
+// Complexity 1: branches inside assert! and friends come from macro expansion
+pub fn only_macros(value: u32) {
+    assert!(value > 0);
+    assert_eq!(value, value);
+}
+
+// Complexity 4: for (+1), if (+1), && (+1)
+pub fn sum_large(values: &[u32], enabled: bool) -> u32 { //~ ERROR: Function has a cyclomatic complexity of 4, exceeding the maximum of 3
+    let mut total = 0;
+    for v in values {
+        if *v > 10 && enabled {
+            total += v;
+        }
+    }
+    total
+}
+
+// Complexity 6: ? (+1), while (+1), loop (+1), if (+1), || (+1)
+pub fn parse_and_reduce(input: &str) -> Result<u32, std::num::ParseIntError> { //~ ERROR: Function has a cyclomatic complexity of 6, exceeding the maximum of 3
+    let mut n: u32 = input.parse()?;
+    while n > 100 {
+        n /= 2;
+    }
+    loop {
+        if n % 2 == 0 || n == 1 {
+            break;
+        }
+        n -= 1;
+    }
+    Ok(n)
+}
+
+pub struct Values(Vec<u32>);
+
+impl Values {
+    // Complexity 4: if (+1), then && (+1) and || (+1) inside the closure
+    pub fn interesting(&self) -> Vec<u32> { //~ ERROR: Function has a cyclomatic complexity of 4, exceeding the maximum of 3
+        if self.0.is_empty() {
+            return Vec::new();
+        }
+        self.0
+            .iter()
+            .copied()
+            .filter(|v| *v > 1 && *v < 10 || *v == 100)
+            .collect()
+    }
+}
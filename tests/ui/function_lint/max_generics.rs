@@ -0,0 +1,37 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//@compile-flags: --crate-name test_max_generics
+//@compile-flags: --crate-type lib
+
+// This test verifies that the MaxGenerics rule counts only the function's own
+// generic parameters, and only counts lifetimes when asked to
+
+use std::fmt::Debug;
+
+// Two type parameters is within the limit
+pub fn type_within_limit<A, B>(a: A, b: B) {}
+
+// Three type parameters exceeds the limit
+pub fn type_too_many<A, B, C>(a: A, b: B, c: C) {} //~ ERROR: Function has 3 generic type and const parameters, exceeding the maximum of 2
+
+// impl Trait arguments and const parameters are generic parameters too
+pub fn type_impl_trait<const N: usize>(a: impl Debug, b: impl Clone) {} //~ ERROR: Function has 3 generic type and const parameters, exceeding the maximum of 2
+
+// Lifetimes are ignored unless count_lifetimes is set
+pub fn type_with_lifetimes<'a, 'b, A, B>(a: &'a A, b: &'b B) {}
+
+// Explicit lifetimes are counted for this rule, elided ones are not
+pub fn lifetime_too_many<'a, 'b, A>(a: &'a A, b: &'b str) {} //~ ERROR: Function has 3 generic parameters, exceeding the maximum of 2
+
+pub fn lifetime_elided<A, B>(a: &A, b: &B) {}
+
+pub fn lifetime_anonymous<A, B>(a: &'_ A, b: &'_ B) {}
+
+pub struct Container<T, U>(T, U);
+
+impl<T, U> Container<T, U> {
+    // Parameters inherited from the impl are not counted
+    pub fn type_method<V>(&self, v: V) {}
+
+    pub fn type_method_too_many<V, W, X>(&self, v: V, w: W, x: X) {} //~ ERROR: Function has 3 generic type and const parameters, exceeding the maximum of 2
+}
@@ -0,0 +1,98 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//@compile-flags: --crate-name test_max_locals
+//@compile-flags: --crate-type lib
+
+// This test verifies that MaxLocals (configured with a maximum of 3) counts the
+// variables bound by `let`, including in nested blocks, but not those in closures
+// or nested functions. Functions named `matching_` also count match arm bindings.
+
+// 3 locals: parameters and `for` loop bindings don't count
+pub fn at_the_limit(values: &[u32]) -> u32 {
+    let mut total = 0;
+    let count = values.len() as u32;
+    for value in values {
+        total += value;
+    }
+    let average = total / count;
+    average
+}
+
+// 3 locals: bindings in closures and nested functions belong to them
+pub fn closures_are_separate(values: &[u32]) -> u32 {
+    fn helper(value: u32) -> u32 {
+        let a = value + 1;
+        let b = a * 2;
+        let c = b - 1;
+        c
+    }
+
+    let doubled: Vec<u32> = values
+        .iter()
+        .map(|v| {
+            let x = v * 2;
+            let y = x + 1;
+            y
+        })
+        .collect();
+    let first = helper(doubled[0]);
+    let last = helper(doubled[doubled.len() - 1]);
+    first + last
+}
+
+// 4 locals: a destructuring `let` binds two, and nested blocks count
+pub fn too_many(pair: (u32, u32)) -> u32 { //~ ERROR: Function has 4 local variables, exceeding the maximum of 3
+    let (left, right) = pair;
+    let sum = {
+        let scaled = left * 2;
+        scaled + right
+    };
+    sum
+}
+
+// 4 locals: `if let` and `while let` bind variables too
+pub fn let_expressions(mut values: Vec<Option<u32>>) -> u32 { //~ ERROR: Function has 4 local variables, exceeding the maximum of 3
+    let mut total = 0;
+    if let Some(Some(first)) = values.first() {
+        total += first;
+    }
+    while let Some(next) = values.pop() {
+        if let Some(value) = next {
+            total += value;
+        }
+    }
+    total
+}
+
+// Match arm bindings aren't counted without the toggle: 1 local
+pub fn match_arms(value: Result<u32, String>) -> u32 {
+    let fallback = 0;
+    match value {
+        Ok(a) => a,
+        Err(message) if message.is_empty() => fallback,
+        Err(_) => fallback + 1,
+    }
+}
+
+// With the toggle, the arm bindings push this to 4 locals
+pub fn matching_arms(value: Result<u32, String>) -> u32 { //~ ERROR: Function has 4 local variables, exceeding the maximum of 3
+    let fallback = 0;
+    let backup = 1;
+    match value {
+        Ok(a) => a,
+        Err(message) if message.is_empty() => fallback,
+        Err(_) => backup,
+    }
+}
+
+pub struct Parser;
+
+impl Parser {
+    // Methods are checked too: 4 locals
+    pub fn parse(&self, input: &str) -> Option<(u32, u32)> { //~ ERROR: Function has 4 local variables, exceeding the maximum of 3
+        let trimmed = input.trim();
+        let (head, tail) = trimmed.split_once(',')?;
+        let first = head.parse().ok()?;
+        Some((first, tail.parse().ok()?))
+    }
+}
@@ -0,0 +1,66 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//@compile-flags: --crate-name test_max_statements
+//@compile-flags: --crate-type lib
+
+// This test verifies that MaxStatements (configured with a maximum of 4) counts
+// statements rather than lines, so comments and blank lines don't count.
+
+// 4 statements, however many lines of commentary come with them
+pub fn well_documented(values: &[u32]) -> u32 {
+    // Start from nothing.
+    //
+    // This accumulator holds the running total while we walk the
+    // input, and is returned once every value has been seen.
+    let mut total = 0;
+
+    // Walk the input.
+    //
+    // The values are added one at a time. Overflow is not a concern
+    // for the inputs this function is called with.
+
+
+    for value in values {
+        // Each value is added to the total.
+        total += value;
+    }
+
+    // Hand back the result.
+    total
+}
+
+// 4 statements: macros count once, not for the code they expand to
+pub fn macros_count_once(value: u32) -> u32 {
+    assert!(value > 0);
+    println!("{value}");
+    let doubled = value * 2;
+    doubled
+}
+
+// 5 statements, including the one inside the closure
+pub fn too_many(values: &[u32]) -> u32 { //~ ERROR: Function has 5 statements, exceeding the maximum of 4
+    let first = values[0];
+    let last = values[values.len() - 1];
+    let sum = values.iter().map(|v| {
+        v * 2
+    }).sum::<u32>();
+    first + last + sum
+}
+
+pub struct Report;
+
+impl Report {
+    // Methods are checked too. Nested items aren't statements: 5 statements
+    pub fn render(&self, lines: &[&str]) -> String { //~ ERROR: Function has 5 statements, exceeding the maximum of 4
+        fn indent(line: &str) -> String {
+            format!("  {line}")
+        }
+
+        let mut out = String::new();
+        for line in lines {
+            out.push_str(&indent(line));
+            out.push('\n');
+        }
+        out
+    }
+}
@@ -0,0 +1,42 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//@compile-flags: --crate-name test_min_length
+//@compile-flags: --crate-type lib
+
+// This test verifies that the MinLength matcher only selects functions whose body
+// spans at least the given number of lines - here, 5
+
+pub fn short_undocumented() -> i32 {
+    1
+}
+
+pub fn long_undocumented() -> i32 { //~ ERROR: Public function is missing a doc comment
+    let a = 1;
+    let b = 2;
+    let c = 3;
+    a + b + c
+}
+
+/// Long, but documented
+pub fn long_documented() -> i32 {
+    let a = 1;
+    let b = 2;
+    let c = 3;
+    a + b + c
+}
+
+pub struct Widget;
+
+impl Widget {
+    // The body spans exactly five lines, so it is matched
+    pub fn just_long_enough(&self) -> i32 { //~ ERROR: Public function is missing a doc comment
+        let a = 1;
+        let b = 2;
+        a + b
+    }
+
+    pub fn just_too_short(&self) -> i32 {
+        let a = 1;
+        a + 1
+    }
+}
@@ -0,0 +1,32 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//@compile-flags: --crate-name test_must_be_async
+//@compile-flags: --crate-type lib
+//@edition:2021
+
+// This test verifies that MustBeAsync reports matched functions that are not
+// declared `async fn`. Functions whose name starts with `handle_` are matched.
+
+pub async fn handle_ping() -> u32 {
+    1
+}
+
+pub fn handle_sync() -> u32 { //~ ERROR: Function 'handle_sync' must be async
+    2
+}
+
+// Returning a future without `async fn` still doesn't satisfy the rule
+pub fn handle_deferred() -> impl std::future::Future<Output = u32> { //~ ERROR: Function 'handle_deferred' must be async
+    async { 3 }
+}
+
+pub struct Processor;
+
+impl Processor {
+    pub async fn handle_job(&self) {}
+
+    pub fn handle_blocking(&self) {} //~ ERROR: Function 'handle_blocking' must be async
+}
+
+// Not matched - no lint
+pub fn helper() {}
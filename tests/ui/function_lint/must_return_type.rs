@@ -0,0 +1,50 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//@compile-flags: --crate-name test_must_return_type
+//@compile-flags: --crate-type lib
+
+// This test verifies that MustReturnType reports matched functions whose return type
+// doesn't match the pattern, for free functions, methods and functions without `->`.
+
+pub mod handlers {
+    pub struct Request;
+
+    pub fn get_user(_request: &Request) -> Result<String, String> {
+        Ok("user".to_string())
+    }
+
+    pub fn get_order(_request: &Request) -> Option<u64> { //~ ERROR: Function 'get_order' returns 'std::option::Option<u64>', but must return a `Result`
+        None
+    }
+
+    pub fn health_check() { //~ ERROR: Function 'health_check' returns '()', but must return a `Result`
+    }
+
+    impl Request {
+        pub fn parse(_body: &str) -> Result<Self, String> {
+            Ok(Request)
+        }
+
+        pub fn header(&self, _name: &str) -> &str { //~ ERROR: Function 'header' returns '&str', but must return a `Result`
+            ""
+        }
+    }
+}
+
+pub mod builders {
+    #[derive(Default)]
+    pub struct Config {
+        pub retries: u32,
+    }
+
+    impl Config {
+        pub fn retries(&mut self, retries: u32) -> &mut Self {
+            self.retries = retries;
+            self
+        }
+
+        pub fn verbose(self) -> Self { //~ ERROR: Function 'verbose' returns 'builders::Config', but must return `&mut Self`
+            self
+        }
+    }
+}
@@ -0,0 +1,37 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//@compile-flags: --crate-name test_name_affix
+//@compile-flags: --crate-type lib
+
+// This test verifies the NameStartsWith and NameEndsWith matchers: `put_*` methods
+// that consume `self` are forbidden, as are `*_unchecked` functions.
+
+pub struct Request {
+    url: String,
+}
+
+impl Request {
+    // Taking &mut self is fine
+    pub fn put_url(&mut self, url: String) {
+        self.url = url;
+    }
+
+    pub fn put_timeout(self, _timeout: u64) -> Self { //~ ERROR: Function 'put_timeout' is forbidden by lint rule
+        self
+    }
+
+    // Consuming, but without the prefix
+    pub fn and_url(mut self, url: String) -> Self {
+        self.url = url;
+        self
+    }
+}
+
+pub fn parse_unchecked(input: &str) -> u32 { //~ ERROR: Function 'parse_unchecked' is forbidden by lint rule
+    input.len() as u32
+}
+
+// The suffix has to be at the end of the name
+pub fn unchecked_parse(input: &str) -> u32 {
+    input.len() as u32
+}
@@ -0,0 +1,18 @@
+//@compile-flags: --crate-name=test_no_allocation_generic
+
+// A generic function is reported once, however many times it's instantiated
+
+fn boxed<T>(value: T) -> Box<T> {
+    Box::new(value) //~ ERROR: Function allocates heap memory
+}
+
+fn sum_on_stack<T: Copy + std::ops::Add<Output = T>>(a: T, b: T) -> T {
+    a + b
+}
+
+fn main() {
+    let _int = boxed(1); //~ ERROR: Function allocates heap memory
+    let _text = boxed("text");
+    let _ = sum_on_stack(1, 2);
+    let _ = sum_on_stack(1.0, 2.0);
+}
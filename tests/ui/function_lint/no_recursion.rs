@@ -0,0 +1,53 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//@compile-flags: --crate-name test_no_recursion
+//@compile-flags: --crate-type lib
+
+// This test verifies that NoRecursion reports each call a function makes to itself,
+// and that mutual recursion is not detected
+
+pub fn factorial(n: u64) -> u64 {
+    if n == 0 {
+        1
+    } else {
+        n * factorial(n - 1) //~ ERROR: Function 'factorial' calls itself recursively
+    }
+}
+
+pub fn factorial_iterative(n: u64) -> u64 {
+    (1..=n).product()
+}
+
+// Calls from closures in the body count too
+pub fn sum_tree(children: &[Vec<u64>], node: usize) -> u64 {
+    children[node]
+        .iter()
+        .map(|child| sum_tree(children, *child as usize)) //~ ERROR: Function 'sum_tree' calls itself recursively
+        .sum()
+}
+
+// Mutual recursion is out of scope
+pub fn is_even(n: u64) -> bool {
+    n == 0 || is_odd(n - 1)
+}
+
+pub fn is_odd(n: u64) -> bool {
+    n != 0 && is_even(n - 1)
+}
+
+pub struct Node {
+    next: Option<Box<Node>>,
+}
+
+impl Node {
+    pub fn len(&self) -> usize {
+        1 + self.next.as_ref().map_or(0, |next| next.len()) //~ ERROR: Function 'len' calls itself recursively
+    }
+
+    pub fn depth(node: &Node) -> usize {
+        match &node.next {
+            Some(next) => 1 + Node::depth(next), //~ ERROR: Function 'depth' calls itself recursively
+            None => 1,
+        }
+    }
+}
@@ -0,0 +1,61 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//@compile-flags: --crate-name test_overrides_default
+//@compile-flags: --crate-type lib
+
+// This test verifies that the OverridesTraitMethod matcher picks out trait impl methods
+// that replace a default body, leaving implementations of required methods, inherent
+// methods and the trait's own default bodies alone.
+
+pub trait Greeter {
+    fn name(&self) -> String;
+
+    // The default body itself isn't an override
+    fn greet(&self) -> String {
+        format!("Hello, {}", self.name())
+    }
+}
+
+pub struct English;
+
+// Only implements the required method
+impl Greeter for English {
+    fn name(&self) -> String {
+        "world".to_string()
+    }
+}
+
+pub struct Pirate;
+
+impl Greeter for Pirate {
+    fn name(&self) -> String {
+        "matey".to_string()
+    }
+
+    fn greet(&self) -> String { //~ ERROR: Function 'greet' is forbidden by lint rule
+        format!("Ahoy, {}", self.name())
+    }
+}
+
+pub struct Countdown(pub u32);
+
+impl Countdown {
+    // Inherent methods override nothing
+    pub fn size_hint(&self) -> usize {
+        self.0 as usize
+    }
+}
+
+// Traits from other crates are resolved too: `next` is required, `size_hint` provided
+impl Iterator for Countdown {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        self.0 = self.0.checked_sub(1)?;
+        Some(self.0)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) { //~ ERROR: Function 'size_hint' is forbidden by lint rule
+        (self.0 as usize, Some(self.0 as usize))
+    }
+}
@@ -0,0 +1,36 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//@compile-flags: --crate-name test_parameter_naming_pattern
+//@compile-flags: --crate-type lib
+
+// This test verifies that ParameterNamingPattern reports parameters whose names don't
+// match the pattern, in free functions and methods, while `self`, `_` and
+// destructured parameters are left alone
+
+pub fn scale(v: u32, factor: u32) -> u32 { //~ ERROR: Parameter 'v' of function 'scale' doesn't match the naming pattern
+    v * factor
+}
+
+// `i` and `j` are allowed by the pattern
+pub fn cell(i: usize, j: usize, width: usize) -> usize {
+    i * width + j
+}
+
+pub fn distance((x, y): (i32, i32), _: i32, mut total: i32) -> i32 {
+    total += x - y;
+    total
+}
+
+pub struct Counter {
+    count: u32,
+}
+
+impl Counter {
+    pub fn add(&mut self, n: u32) { //~ ERROR: Parameter 'n' of function 'add' doesn't match the naming pattern
+        self.count += n;
+    }
+
+    pub fn reset(self, count: u32) -> Self {
+        Self { count }
+    }
+}
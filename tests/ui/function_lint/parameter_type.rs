@@ -0,0 +1,36 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//@compile-flags: --crate-name test_parameter_type
+//@compile-flags: --crate-type lib
+
+// This test verifies that ParameterType matches functions by the rendered
+// type of any of their parameters
+
+pub struct Connection;
+
+pub mod db {
+    use super::Connection;
+
+    // Allowed: functions taking a mutable connection live in the db module
+    pub fn execute(conn: &mut Connection, query: &str) {}
+}
+
+pub mod handlers {
+    use super::Connection;
+
+    // Matched through a mutable reference in any position
+    pub fn handle_request(id: u32, conn: &mut Connection) {} //~ ERROR: is forbidden by lint rule
+
+    // Shared references don't match the pattern
+    pub fn read_only(conn: &Connection) {}
+
+    // Neither do owned values
+    pub fn take_ownership(conn: Connection) {}
+
+    pub struct Service;
+
+    impl Service {
+        // Methods are matched on their explicit parameters
+        pub fn run<'a>(&self, conn: &'a mut Connection) {} //~ ERROR: is forbidden by lint rule
+    }
+}
@@ -0,0 +1,38 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//@compile-flags: --crate-name test_require_doc_comment
+//@compile-flags: --crate-type lib
+
+// This test verifies that RequireDocComment flags undocumented public functions
+// and inherent methods, but not private functions or trait impl methods
+
+/// Documented with a doc comment
+pub fn documented() {}
+
+#[doc = "Documented with a doc attribute"]
+pub fn documented_with_attribute() {}
+
+pub fn undocumented() {} //~ ERROR: Public function is missing a doc comment
+
+// Private functions are not part of the public API
+fn private_undocumented() {}
+
+pub(crate) fn crate_undocumented() {}
+
+pub struct Widget;
+
+impl Widget {
+    /// Documented inherent method
+    pub fn documented_method(&self) {}
+
+    pub fn undocumented_method(&self) {} //~ ERROR: Public function is missing a doc comment
+
+    fn private_method(&self) {}
+}
+
+impl Clone for Widget {
+    // Trait impl methods inherit the trait's documentation
+    fn clone(&self) -> Self {
+        Widget
+    }
+}
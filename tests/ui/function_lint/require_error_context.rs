@@ -0,0 +1,103 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//@compile-flags: --crate-name test_require_error_context
+//@compile-flags: --crate-type lib
+
+// This test verifies that RequireErrorContext reports each `?` that turns another
+// error type into an `anyhow::Error` without `.context(...)` or `.with_context(...)`.
+
+// A stand-in for the anyhow crate, whose items have the same paths as anyhow's own
+pub mod anyhow {
+    pub struct Error(String);
+
+    impl<E: std::error::Error> From<E> for Error {
+        fn from(error: E) -> Self {
+            Error(error.to_string())
+        }
+    }
+
+    pub type Result<T, E = Error> = core::result::Result<T, E>;
+
+    pub trait Context<T> {
+        fn context(self, context: &'static str) -> Result<T>;
+        fn with_context<F: FnOnce() -> String>(self, f: F) -> Result<T>;
+    }
+
+    impl<T, E: std::error::Error> Context<T> for core::result::Result<T, E> {
+        fn context(self, context: &'static str) -> Result<T> {
+            self.map_err(|error| Error(format!("{context}: {error}")))
+        }
+
+        fn with_context<F: FnOnce() -> String>(self, f: F) -> Result<T> {
+            self.map_err(|error| Error(format!("{}: {error}", f())))
+        }
+    }
+}
+
+pub mod commands {
+    use crate::anyhow::{Context, Result};
+    use std::num::ParseIntError;
+
+    pub fn with_context(input: &str) -> Result<u32> {
+        let port = input.parse::<u32>().context("parsing the port")?;
+        let retries = input
+            .parse::<u32>()
+            .with_context(|| format!("parsing retries from '{input}'"))?;
+        Ok(port + retries)
+    }
+
+    fn load(input: &str) -> Result<u32> {
+        Ok(input.parse::<u32>().context("loading")?)
+    }
+
+    pub fn already_anyhow(input: &str) -> Result<u32> {
+        // The error already has its context
+        let value = load(input)?;
+        Ok(value)
+    }
+
+    pub fn bare(input: &str) -> Result<u32> {
+        let value = input.parse::<u32>()?; //~ ERROR: Function 'bare' propagates 'std::num::ParseIntError' with `?` without adding context
+        Ok(value)
+    }
+
+    pub fn in_closure(inputs: &[&str]) -> Result<Vec<u32>> {
+        inputs
+            .iter()
+            .map(|input| -> Result<u32> { Ok(input.parse::<u32>()?) }) //~ ERROR: Function 'in_closure' propagates 'std::num::ParseIntError'
+            .collect()
+    }
+
+    pub async fn in_async(input: &str) -> Result<u32> {
+        Ok(input.parse::<u32>()?) //~ ERROR: Function 'in_async' propagates 'std::num::ParseIntError'
+    }
+
+    // Not converted into an anyhow::Error, so nothing to add context to
+    pub fn own_error(input: &str) -> std::result::Result<u32, ParseIntError> {
+        let value = input.parse::<u32>()?;
+        Ok(value)
+    }
+
+    pub fn option(values: &[u32]) -> Option<u32> {
+        let first = values.first()?;
+        Some(*first)
+    }
+
+    pub struct Config;
+
+    impl Config {
+        // Methods are checked too
+        pub fn port(&self, input: &str) -> Result<u16> {
+            Ok(input.parse::<u16>()?) //~ ERROR: Function 'port' propagates 'std::num::ParseIntError'
+        }
+    }
+}
+
+pub mod library {
+    use crate::anyhow::Result;
+
+    // Not matched, so bare `?` is fine here
+    pub fn bare(input: &str) -> Result<u32> {
+        Ok(input.parse::<u32>()?)
+    }
+}
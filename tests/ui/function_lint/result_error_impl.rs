@@ -65,6 +65,25 @@ mod error_module {
     pub fn module_function_with_impl() -> Result<(), WithErrorImpl> {
         Ok(())
     }
+
+    // Whether a generic error implements Error depends on its arguments, so each
+    // instantiation is checked on its own
+    pub fn module_function_wrapped_no_impl() -> Result<(), Wrapper<NoErrorImpl>> { //~ ERROR: Error type 'Wrapper<NoErrorImpl>' in Result does not implement Error trait
+        Ok(())
+    }
+
+    pub fn module_function_wrapped_with_impl() -> Result<(), Wrapper<WithErrorImpl>> {
+        Ok(())
+    }
+
+    // ... and on the bounds in scope
+    pub fn module_function_generic_bounded<E: Error>() -> Result<(), Wrapper<E>> {
+        Ok(())
+    }
+
+    pub fn module_function_generic_unbounded<E>() -> Result<(), Wrapper<E>> { //~ ERROR: Error type 'Wrapper<E>' in Result does not implement Error trait
+        Ok(())
+    }
 }
 
 mod unaffected_module {
@@ -107,3 +126,15 @@ impl fmt::Debug for WithErrorImpl {
 }
 
 impl Error for WithErrorImpl {} 
+
+// A wrapper that implements Error only when the type it wraps does
+#[derive(Debug)]
+pub struct Wrapper<E>(pub E);
+
+impl<E: fmt::Display> fmt::Display for Wrapper<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "wrapped: {}", self.0)
+    }
+}
+
+impl<E: Error> Error for Wrapper<E> {}
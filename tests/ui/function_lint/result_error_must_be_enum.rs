@@ -0,0 +1,73 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//@compile-flags: --crate-name test_result_error_must_be_enum
+//@compile-flags: --crate-type lib
+
+// This test verifies that ResultErrorMustBeEnum reports functions whose Result error
+// type isn't an enum, and that boxed errors are only accepted when the rule allows them.
+
+use std::error::Error;
+
+#[derive(Debug)]
+pub enum ParseError {
+    Empty,
+    Invalid(String),
+}
+
+#[derive(Debug)]
+pub struct MessageError(pub String);
+
+pub type ParseResult<T> = Result<T, ParseError>;
+
+pub mod strict {
+    use super::{MessageError, ParseError, ParseResult};
+    use std::error::Error;
+
+    pub fn parse(input: &str) -> Result<u32, ParseError> {
+        input.parse().map_err(|_| ParseError::Invalid(input.to_string()))
+    }
+
+    // Aliases are seen through
+    pub fn parse_alias(input: &str) -> ParseResult<u32> {
+        parse(input)
+    }
+
+    pub fn parse_message(input: &str) -> Result<u32, MessageError> { //~ ERROR: Function 'parse_message' returns error type 'MessageError', which is not an enum
+        parse(input).map_err(|_| MessageError(input.to_string()))
+    }
+
+    pub fn parse_string(input: &str) -> Result<u32, String> { //~ ERROR: Function 'parse_string' returns error type 'std::string::String', which is not an enum
+        parse(input).map_err(|_| input.to_string())
+    }
+
+    pub fn parse_boxed(input: &str) -> Result<u32, Box<dyn Error>> { //~ ERROR: Function 'parse_boxed' returns error type 'std::boxed::Box<(dyn std::error::Error + 'static)>', which is not an enum
+        Ok(parse(input).map_err(|_| input.to_string())?)
+    }
+
+    pub struct Parser;
+
+    impl Parser {
+        pub fn parse(&self, input: &str) -> Result<u32, MessageError> { //~ ERROR: Function 'parse' returns error type 'MessageError', which is not an enum
+            parse_message(input)
+        }
+    }
+
+    // Functions that don't return a Result aren't affected
+    pub fn parse_or_zero(input: &str) -> u32 {
+        parse(input).unwrap_or(0)
+    }
+}
+
+pub mod boxed {
+    use super::{Error, MessageError};
+
+    pub fn parse_boxed(input: &str) -> Result<u32, Box<dyn Error>> {
+        Ok(input.parse::<u32>()?)
+    }
+
+    pub fn parse_message(input: &str) -> Result<u32, MessageError> { //~ ERROR: Function 'parse_message' returns error type 'MessageError', which is not an enum
+        input.parse().map_err(|_| MessageError(input.to_string()))
+    }
+}
+
+fn main() {}
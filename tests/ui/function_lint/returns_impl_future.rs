@@ -0,0 +1,46 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//@compile-flags: --crate-name test_returns_impl_future
+//@compile-flags: --crate-type lib
+
+// This test verifies that the ReturnsImplFuture matcher catches `async fn` and
+// functions returning `impl Future`, but not other opaque or boxed return types
+
+use std::future::Future;
+use std::pin::Pin;
+
+async fn async_sugar() -> u32 { //~ ERROR: Function 'async_sugar' is forbidden by lint rule
+    1
+}
+
+fn desugared() -> impl Future<Output = u32> { //~ ERROR: Function 'desugared' is forbidden by lint rule
+    async { 1 }
+}
+
+fn send_future() -> impl Future<Output = ()> + Send + 'static { //~ ERROR: Function 'send_future' is forbidden by lint rule
+    async {}
+}
+
+// Other opaque types aren't futures
+fn numbers() -> impl Iterator<Item = u32> {
+    0..3
+}
+
+// Boxed futures aren't opaque, so they're not matched
+fn boxed() -> Pin<Box<dyn Future<Output = u32>>> {
+    Box::pin(async { 1 })
+}
+
+fn sync_function() -> u32 {
+    1
+}
+
+struct Client;
+
+impl Client {
+    fn fetch(&self) -> impl Future<Output = String> + '_ { //~ ERROR: Function 'fetch' is forbidden by lint rule
+        async { String::new() }
+    }
+
+    fn close(&self) {}
+}
@@ -0,0 +1,43 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//@compile-flags: --crate-name test_allowed_crates
+//@compile-flags: --crate-type lib
+
+extern crate proc_macro;
+
+pub mod domain {
+    // The standard library crates are always allowed
+    use std::collections::HashMap;
+    use core::fmt::Debug;
+    extern crate alloc;
+    use alloc::vec::Vec;
+
+    // As is the current crate
+    use crate::shared::Id;
+
+    use proc_macro::TokenStream; //~ ERROR: Import from crate 'proc_macro' is not allowed; only ["serde"] are permitted
+
+    pub struct Order {
+        pub id: Id,
+        pub lines: Vec<HashMap<String, u32>>,
+    }
+
+    pub fn describe(value: &dyn Debug) -> String {
+        format!("{value:?}")
+    }
+
+    pub fn tokens(stream: TokenStream) -> TokenStream {
+        stream
+    }
+}
+
+// Modules outside of domain may use any crate
+pub mod shared {
+    use proc_macro::Span;
+
+    pub type Id = u64;
+
+    pub fn span() -> Span {
+        Span::call_site()
+    }
+}
@@ -0,0 +1,37 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//@compile-flags: --crate-name test_allowed_submodules
+//@compile-flags: --crate-type lib
+
+pub mod api {
+    pub mod routes {
+        // Descendants below the direct children are not checked
+        pub mod anything_goes {
+            pub fn route() {}
+        }
+    }
+
+    pub mod handlers {
+        pub fn handle() {}
+    }
+
+    pub mod dto {
+        pub struct Request;
+    }
+
+    pub mod utils { //~ ERROR: Submodule 'utils' is not allowed in 'test_allowed_submodules::api'; only ["^routes$", "^handlers$", "^dto$"] are permitted
+        pub fn help() {}
+    }
+
+    // Non-module items are not restricted by this rule
+    pub fn version() -> u32 {
+        1
+    }
+}
+
+// Modules outside of api are not checked
+pub mod internal {
+    pub mod utils {
+        pub fn help() {}
+    }
+}
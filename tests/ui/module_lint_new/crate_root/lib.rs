@@ -0,0 +1,33 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//@compile-flags: --crate-name test_forbid_items_in_root
+//@compile-flags: --crate-type lib
+
+// This test verifies that ForbidItemsInRoot reports items other than modules and
+// imports declared directly in the crate's lib.rs.
+
+pub mod config {
+    // Items in submodules are allowed, even when declared inline in lib.rs
+    pub struct Config;
+
+    pub fn load() -> Config {
+        Config
+    }
+}
+
+pub use config::Config;
+use std::fmt;
+
+pub struct Server; //~ ERROR: struct 'Server' is not allowed in the crate root
+
+impl fmt::Debug for Server { //~ ERROR: impl is not allowed in the crate root
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Server")
+    }
+}
+
+pub fn start() {} //~ ERROR: function 'start' is not allowed in the crate root
+
+macro_rules! noop { //~ ERROR: declarative macro 'noop' is not allowed in the crate root
+    () => {};
+}
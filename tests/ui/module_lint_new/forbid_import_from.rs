@@ -0,0 +1,37 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//@compile-flags: --crate-name test_forbid_import_from
+//@compile-flags: --crate-type lib
+
+pub mod internal {
+    pub fn store() {}
+
+    pub mod cache {
+        // Inside the boundary, so importing from it is fine
+        use crate::internal::store;
+    }
+}
+
+pub mod storage {
+    pub fn connect() {}
+
+    pub use crate::internal::store as save;
+}
+
+pub mod api {
+    use std::fmt::Debug;
+
+    use crate::internal::store; //~ ERROR: Module 'test_forbid_import_from::api' must not import 'crate::internal::store'
+    use crate::storage::save; //~ ERROR: Module 'test_forbid_import_from::api' must not import 'crate::storage::save', as it refers to 'crate::internal::store'
+
+    pub mod v1 {
+        // A second boundary in the same lint
+        use crate::storage::connect; //~ ERROR: Module 'test_forbid_import_from::api::v1' must not import 'crate::storage::connect'
+    }
+}
+
+pub mod cli {
+    // Not a source module, so it may import anything
+    use crate::internal::store;
+    use crate::storage::connect;
+}
@@ -0,0 +1,32 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//@compile-flags: --crate-name test_forbid_nested_modules
+//@compile-flags: --crate-type lib
+//@compile-flags: --cfg test
+
+// This test verifies that ForbidNestedModules reports inline modules declared
+// directly inside a matched module, and that allow_test_modules exempts test
+// modules. `--cfg test` keeps the `#[cfg(test)]` modules around to check.
+
+pub mod strict {
+    pub fn run() {}
+
+    pub mod helpers { //~ ERROR: Module 'strict' declares inline module 'helpers'
+        // Only direct children are reported
+        pub mod deeper {}
+    }
+
+    #[cfg(test)]
+    mod tests {} //~ ERROR: Module 'strict' declares inline module 'tests'
+}
+
+pub mod lenient {
+    pub fn run() {}
+
+    pub mod helpers {} //~ ERROR: Module 'lenient' declares inline module 'helpers'
+
+    #[cfg(test)]
+    mod checks {}
+
+    mod tests {}
+}
@@ -0,0 +1,46 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//@compile-flags: --crate-name test_forbid_reexport_from
+//@compile-flags: --crate-type lib
+
+// This test verifies that ForbidReexportFrom reports public re-exports of internal
+// items, matching what a re-export resolves to rather than only how it's written.
+
+pub mod internal {
+    pub struct Secret;
+    pub struct Token;
+    pub fn rotate() {}
+
+    pub mod keys {
+        pub struct Key;
+    }
+}
+
+pub mod public {
+    pub struct Handle;
+
+    // Refers to internal::Token, under a name that doesn't say so
+    pub use crate::internal::Token as PublicToken;
+}
+
+pub mod api {
+    use crate::internal::rotate; // Private imports are fine
+
+    pub use crate::internal::Secret; //~ ERROR: Module 'test_forbid_reexport_from::api' must not re-export 'crate::internal::Secret'
+    pub use super::internal::keys::Key; //~ ERROR: must not re-export 'super::internal::keys::Key', as it refers to 'crate::internal::keys::Key'
+    pub use crate::public::PublicToken; //~ ERROR: must not re-export 'crate::public::PublicToken', as it refers to 'crate::internal::Token'
+    pub use crate::internal::keys::*; //~ ERROR: must not re-export 'crate::internal::keys::*'
+    pub use std::sync::Arc; //~ ERROR: must not re-export 'std::sync::Arc'
+
+    pub use crate::public::Handle;
+    pub(crate) use crate::internal::Token;
+
+    pub fn refresh() {
+        rotate();
+    }
+}
+
+pub mod cli {
+    // Not matched, so it may re-export anything
+    pub use crate::internal::Secret;
+}
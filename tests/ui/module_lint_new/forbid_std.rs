@@ -0,0 +1,54 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//@compile-flags: --crate-name test_forbid_std
+//@compile-flags: --crate-type lib
+
+pub mod firmware {
+    // Imports from core and alloc are fine, even for items std re-exports
+    extern crate alloc;
+    use alloc::vec::Vec;
+    use core::fmt;
+
+    use std::collections::HashMap; //~ ERROR: Module 'test_forbid_std::firmware' must not import 'std::collections::HashMap' from std
+    use std::vec::Vec as StdVec; //~ ERROR: Module 'test_forbid_std::firmware' must not import 'std::vec::Vec' from std
+
+    pub struct Registers(Vec<u32>);
+
+    impl fmt::Debug for Registers {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("Registers")
+        }
+    }
+
+    pub fn table() -> HashMap<u32, u32> { //~ ERROR: Module 'test_forbid_std::firmware' must not use 'std::collections::HashMap' from std
+        HashMap::new() //~ ERROR: Module 'test_forbid_std::firmware' must not use 'std::collections::HashMap' from std
+    }
+
+    pub fn read_config() -> usize {
+        std::fs::read("config").map(|bytes| bytes.len()).unwrap_or(0) //~ ERROR: Module 'test_forbid_std::firmware' must not use 'std::fs::read' from std
+    }
+
+    pub fn log(value: u32) {
+        println!("{value}"); //~ ERROR: Module 'test_forbid_std::firmware' must not use the std macro 'println!'
+    }
+
+    pub fn trace(value: u32) -> u32 {
+        dbg!(value) //~ ERROR: Module 'test_forbid_std::firmware' must not use the std macro 'dbg!'
+    }
+
+    pub fn sizes() -> StdVec<usize> {
+        // vec! is defined in alloc, and format! too
+        let _ = alloc::format!("{}", 1);
+        alloc::vec![1, 2, 3]
+    }
+}
+
+pub mod host {
+    // std is fine outside the matched module
+    use std::collections::HashMap;
+
+    pub fn table() -> HashMap<u32, u32> {
+        println!("host");
+        HashMap::new()
+    }
+}
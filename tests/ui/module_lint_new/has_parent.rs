@@ -0,0 +1,27 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//@compile-flags: --crate-name test_has_parent
+//@compile-flags: --crate-type lib
+
+// The parent module itself is not matched by HasParent
+pub mod api {
+    // Direct children of api must follow the version naming pattern
+    pub mod v1 {
+        pub fn handle() {}
+    }
+
+    pub mod v2 {
+        pub fn handle() {}
+    }
+
+    pub mod legacy { //~ ERROR: Module must match pattern '^v[0-9]+$', found 'legacy'
+        pub fn handle() {}
+    }
+}
+
+// Modules outside of api are not matched
+pub mod internal {
+    pub mod helpers {
+        pub fn help() {}
+    }
+}
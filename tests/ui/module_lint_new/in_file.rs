@@ -0,0 +1,20 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//@compile-flags: --crate-name test_in_file
+//@compile-flags: --crate-type lib
+
+// Everything in this file is matched by its path, whatever module it's in
+pub struct Root; //~ ERROR: struct 'Root' is not allowed in this module
+
+pub mod nested {
+    pub struct Nested; //~ ERROR: struct 'Nested' is not allowed in this module
+
+    pub mod deeper {
+        pub struct Deeper; //~ ERROR: struct 'Deeper' is not allowed in this module
+    }
+}
+
+// The file matcher can still be combined with module matchers
+pub mod legacy {
+    pub struct Legacy;
+}
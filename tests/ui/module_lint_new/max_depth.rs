@@ -0,0 +1,26 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//@compile-flags: --crate-name test_max_depth
+//@compile-flags: --crate-type lib
+
+// This test verifies that MaxDepth (configured with a maximum of 2) reports
+// modules nested more than two levels below the crate root.
+
+// Depth 1
+pub mod api {
+    // Depth 2
+    pub mod v1 {
+        // Depth 3
+        pub mod handlers { //~ ERROR: Module 'test_max_depth::api::v1::handlers' is nested 3 levels deep, exceeding the maximum of 2
+            // Depth 4
+            pub mod users { //~ ERROR: Module 'test_max_depth::api::v1::handlers::users' is nested 4 levels deep, exceeding the maximum of 2
+                pub fn list() {}
+            }
+        }
+    }
+}
+
+// Depth 1
+pub mod util {
+    pub fn help() {}
+}
@@ -0,0 +1,37 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//@compile-flags: --crate-name test_max_imports
+//@compile-flags: --crate-type lib
+
+// This test verifies that MaxImports counts each imported name in a matched
+// module, and doesn't count re-exports
+
+pub mod busy { //~ ERROR: Module has 4 imports, exceeding the maximum of 3
+    use std::collections::{HashMap, HashSet};
+    use std::fmt::Debug;
+    use std::io::*;
+
+    pub fn run(_: HashMap<u32, u32>, _: HashSet<u32>, _: &dyn Debug, _: &dyn Read) {}
+
+    // Imports in submodules count towards the submodule, not this module
+    pub mod nested {
+        use std::fmt::Display;
+        use std::rc::Rc;
+        use std::sync::Arc;
+        use std::cell::Cell;
+
+        pub fn run(_: &dyn Display, _: Rc<u32>, _: Arc<u32>, _: Cell<u32>) {}
+    }
+}
+
+pub mod focused {
+    use std::collections::HashMap;
+    use std::fmt::Debug;
+
+    // Re-exports are part of the module's API rather than its dependencies
+    pub use std::rc::Rc;
+    pub use std::sync::Arc;
+    pub(crate) use std::cell::Cell;
+
+    pub fn run(_: HashMap<u32, u32>, _: &dyn Debug) {}
+}
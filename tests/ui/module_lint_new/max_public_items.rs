@@ -0,0 +1,53 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//@compile-flags: --crate-name test_max_public_items
+//@compile-flags: --crate-type lib
+
+// This test verifies that MaxPublicItems counts the public items declared directly
+// in a matched module, and only counts re-exports when asked to
+
+mod internal {
+    pub struct Cache;
+    pub struct Pool;
+}
+
+// Four public items, one more than allowed
+pub mod wide { //~ ERROR: Module 'wide' has 4 public items, exceeding the maximum of 3
+    pub fn open() {}
+    pub struct Handle;
+    pub enum Mode {
+        Read,
+        Write,
+    }
+    pub const LIMIT: usize = 8;
+
+    // Items restricted to the crate, or private, are not part of the public surface
+    pub(crate) fn helper() {}
+    fn private() {}
+
+    // Nor are re-exports, unless they're counted
+    pub use crate::internal::{Cache, Pool};
+}
+
+// Within the limit, as the re-exports aren't counted
+pub mod narrow {
+    pub fn open() {}
+    pub trait Source {}
+    pub use crate::internal::{Cache, Pool};
+
+    // Items in submodules count towards the submodule
+    pub mod types {
+        pub type Id = u64;
+        pub static NAME: &str = "narrow";
+    }
+}
+
+// Here each re-exported name counts
+pub mod reexporting { //~ ERROR: Module 'reexporting' has 4 public items, exceeding the maximum of 3
+    pub fn open() {}
+    pub union Bits {
+        pub int: u32,
+        pub float: f32,
+    }
+    pub use crate::internal::{Cache, Pool};
+}
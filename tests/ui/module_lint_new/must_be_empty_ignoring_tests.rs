@@ -0,0 +1,38 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//@compile-flags: --crate-name test_must_be_empty_ignoring_tests
+//@compile-flags: --crate-type lib
+//@compile-flags: --cfg test
+
+// This test verifies that MustBeEmpty with ignoring_test_code doesn't count items
+// gated on `#[cfg(test)]`, while `strict` still reports them. `--cfg test` keeps the
+// test-only items around to check.
+
+mod shapes {
+    pub struct Circle;
+}
+
+pub mod facade {
+    pub use crate::shapes::Circle;
+
+    #[cfg(test)]
+    fn fixture() -> Circle {
+        Circle
+    }
+
+    #[cfg(test)]
+    impl Circle {
+        pub fn unit() -> Self {
+            fixture()
+        }
+    }
+
+    pub fn not_test_code() {} //~ ERROR: Item 'not_test_code' not allowed in empty module
+}
+
+pub mod strict {
+    pub use crate::shapes::Circle;
+
+    #[cfg(test)]
+    pub const RADIUS: u32 = 1; //~ ERROR: Item 'RADIUS' not allowed in empty module
+}
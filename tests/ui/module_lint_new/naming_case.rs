@@ -0,0 +1,18 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//@compile-flags: --crate-name test_naming_case
+//@compile-flags: --crate-type lib
+
+// This test verifies that NamingCaseStyle(Snake) flags modules whose names
+// aren't snake_case, including nested modules
+#![allow(non_snake_case)]
+
+pub mod http_client {
+    pub mod v2_routes {}
+
+    pub mod RetryPolicy {} //~ ERROR: Module 'RetryPolicy' must be named in snake_case
+}
+
+pub mod dataStore {} //~ ERROR: Module 'dataStore' must be named in snake_case
+
+pub mod legacy__api {} //~ ERROR: Module 'legacy__api' must be named in snake_case
@@ -0,0 +1,32 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//@compile-flags: --crate-name test_require_prefix
+//@compile-flags: --crate-type lib
+
+// The matched module's own name doesn't need the prefix
+pub mod events {
+    pub mod evt_created {
+        // Descendants below the direct children are not checked
+        pub mod payload {
+            pub struct Payload;
+        }
+    }
+
+    pub mod evt_deleted {
+        pub fn handle() {}
+    }
+
+    pub mod updated { //~ ERROR: Submodule 'updated' of 'test_require_prefix::events' does not start with the required prefix 'evt_'
+        pub fn handle() {}
+    }
+
+    // Non-module items are not checked
+    pub fn dispatch() {}
+}
+
+// Modules outside of events are not checked
+pub mod commands {
+    pub mod created {
+        pub fn handle() {}
+    }
+}
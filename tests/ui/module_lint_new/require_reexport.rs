@@ -0,0 +1,31 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//@compile-flags: --crate-name test_require_reexport
+//@compile-flags: --crate-type lib
+
+// This test verifies that RequireReexport lists every required item that a matched
+// module doesn't `pub use`, in a single diagnostic
+
+mod internal {
+    pub struct Client;
+    pub struct Config;
+    pub fn connect() {}
+}
+
+// Complete facade, using both `crate::` and crate-name style paths in the config
+pub mod api {
+    pub use crate::internal::Client;
+    pub use crate::internal::{Config, connect};
+    pub use std::sync::Arc;
+}
+
+pub mod partial { //~ ERROR: Module 'partial' must re-export: crate::internal::Config, test_require_reexport::internal::connect, std::sync::Arc
+    pub use crate::internal::Client;
+    // A private import is not a re-export
+    use crate::internal::Config;
+}
+
+// Glob re-exports don't count
+pub mod glob { //~ ERROR: Module 'glob' must re-export: crate::internal::Client, crate::internal::Config, test_require_reexport::internal::connect, std::sync::Arc
+    pub use crate::internal::*;
+}
@@ -0,0 +1,38 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//@compile-flags: --crate-name test_require_sibling_module
+//@compile-flags: --crate-type lib
+
+// Each service has its errors next to it
+pub mod billing {
+    pub mod error {
+        pub struct BillingError;
+    }
+
+    pub mod invoices {
+        pub fn issue() {}
+    }
+}
+
+// Reported once, however many services are missing it
+pub mod users { //~ ERROR: Module 'test_require_sibling_module::users' has no 'error' module alongside 'accounts', 'sessions'
+    pub mod accounts {
+        pub fn create() {}
+    }
+
+    pub mod sessions {
+        pub fn open() {}
+    }
+
+    // Submodules that aren't matched don't need one
+    pub mod helpers {
+        pub fn format() {}
+    }
+}
+
+// Without a matched submodule, there's nothing to check
+pub mod config {
+    pub mod helpers {
+        pub fn load() {}
+    }
+}
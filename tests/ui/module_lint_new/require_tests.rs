@@ -0,0 +1,111 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//@compile-flags: --crate-name test_require_tests
+//@compile-flags: --crate-type lib
+
+// This test verifies that RequireTests flags modules without a #[cfg(test)]
+// module, and exempts test modules and modules that only re-export
+
+pub mod untested { //~ ERROR: Module 'untested' has no #[cfg(test)] test module
+    pub fn add(a: u32, b: u32) -> u32 {
+        a + b
+    }
+}
+
+pub mod tested {
+    pub fn add(a: u32, b: u32) -> u32 {
+        a + b
+    }
+
+    #[cfg(test)]
+    mod tests {
+        #[test]
+        fn adds() {
+            assert_eq!(super::add(1, 2), 3);
+        }
+    }
+}
+
+pub mod tested_with_attributes {
+    pub fn double(a: u32) -> u32 {
+        a * 2
+    }
+
+    #[cfg(test)]
+    #[allow(clippy::module_inception)]
+    pub(crate) mod double_tests {}
+}
+
+// Any cfg predicate that holds when testing counts, however it's spaced
+pub mod tested_with_feature {
+    pub fn negate(a: i32) -> i32 {
+        -a
+    }
+
+    #[cfg(all(test, feature = "x"))]
+    mod feature_tests {}
+}
+
+pub mod tested_with_spacing {
+    pub fn square(a: u32) -> u32 {
+        a * a
+    }
+
+    #[cfg( test )]
+    mod spaced_tests {}
+}
+
+pub mod not_tested { //~ ERROR: Module 'not_tested' has no #[cfg(test)] test module
+    pub fn cube(a: u32) -> u32 {
+        a * a * a
+    }
+
+    #[cfg(not(test))]
+    mod production_only {}
+}
+
+// A `tests` child module counts, even when it isn't cfg(test) itself
+pub mod tested_by_child {
+    pub fn triple(a: u32) -> u32 {
+        a * 3
+    }
+
+    mod tests {}
+}
+
+// Test modules only count when they're declared in the module itself, not when they
+// appear in a comment, a string or a module nested further down
+pub mod commented_out { //~ ERROR: Module 'commented_out' has no #[cfg(test)] test module
+    pub fn quadruple(a: u32) -> u32 {
+        a * 4
+    }
+
+    // #[cfg(test)]
+    // mod tests {}
+
+    /* #[cfg(test)] mod more_tests {} */
+}
+
+pub mod quoted { //~ ERROR: Module 'quoted' has no #[cfg(test)] test module
+    pub const TEMPLATE: &str = "#[cfg(test)] mod tests {}";
+}
+
+pub mod parent { //~ ERROR: Module 'parent' has no #[cfg(test)] test module
+    pub fn halve(a: u32) -> u32 {
+        a / 2
+    }
+
+    pub mod child {
+        pub fn third(a: u32) -> u32 {
+            a / 3
+        }
+
+        #[cfg(test)]
+        mod child_tests {}
+    }
+}
+
+// Only re-exports, so there's nothing to test - no lint
+pub mod facade {
+    pub use super::tested::add;
+}
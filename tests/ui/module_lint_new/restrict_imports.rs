@@ -21,4 +21,27 @@ use crate::dummy_denied::anything; //~ ERROR: Use of module 'crate::dummy_denied
 pub mod nested {
     // These should also be checked against the rules
     use std::collections::HashMap; // Allowed - in allowed list
-} 
+}
+
+// A re-export under another name, outside the matched module
+pub mod reexports {
+    pub use crate::dummy_denied::anything as harmless;
+}
+
+// Imports are checked against what they refer to, so neither a re-export nor an
+// alias of the module hides the denied item
+use crate::reexports::harmless; //~ ERROR: Use of module 'crate::reexports::harmless' is not allowed, as it refers to 'crate::dummy_denied::anything'
+                                //~^ ERROR: Use of module 'crate::reexports::harmless' is denied, as it refers to 'crate::dummy_denied::anything'
+use crate::dummy_denied as dd; //~ ERROR: Use of module 'crate::dummy_denied' is not allowed; only ["std::*", "core::*"] are permitted
+                               //~^ ERROR: Use of module 'crate::dummy_denied' is denied
+use dd::anything as renamed; //~ ERROR: Use of module 'dd::anything' is not allowed, as it refers to 'crate::dummy_denied::anything'
+                             //~^ ERROR: Use of module 'dd::anything' is denied, as it refers to 'crate::dummy_denied::anything'
+
+// Each import in a group is checked on its own, and the shared `std` prefix isn't
+// reported as an import of its own
+use std::{cmp, hash};
+use crate::{
+    dummy_denied::anything as grouped, //~ ERROR: Use of module 'crate::dummy_denied::anything' is not allowed
+                                       //~^ ERROR: Use of module 'crate::dummy_denied::anything' is denied
+    reexports as aliased_reexports, //~ ERROR: Use of module 'crate::reexports' is not allowed
+};
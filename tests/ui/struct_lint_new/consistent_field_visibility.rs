@@ -0,0 +1,54 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//@compile-flags: --crate-name test_consistent_field_visibility
+//@compile-flags: --crate-type lib
+
+// This test verifies that ConsistentFieldVisibility reports structs mixing
+// public and non-public named fields.
+
+pub struct AllPublic {
+    pub id: u64,
+    pub name: String,
+}
+
+pub struct AllPrivate {
+    id: u64,
+    name: String,
+}
+
+pub struct Session { //~ ERROR: Struct 'Session' mixes public and non-public fields
+    pub user: String,
+    pub expires_at: u64,
+    token: String,
+}
+
+// pub(crate) is not public, so it doesn't match the pub field
+pub struct Config { //~ ERROR: Struct 'Config' mixes public and non-public fields
+    pub name: String,
+    pub(crate) secret: String,
+}
+
+// Single-field and tuple structs are trivially consistent
+pub struct Single {
+    pub value: u32,
+}
+
+pub struct Pair(pub u32, u32);
+
+impl AllPrivate {
+    pub fn describe(&self) -> String {
+        format!("{} {}", self.id, self.name)
+    }
+}
+
+impl Session {
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+}
+
+impl Pair {
+    pub fn second(&self) -> u32 {
+        self.1
+    }
+}
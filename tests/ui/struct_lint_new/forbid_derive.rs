@@ -0,0 +1,37 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//@compile-flags: --crate-name test_forbid_derive
+//@compile-flags: --crate-type lib
+
+// This test verifies that the ForbidDerive rule reports each forbidden derive on
+// structs with a `secret` field, and leaves other derives alone.
+
+#[derive(Debug, Clone, PartialEq)]
+//~^ ERROR: Struct 'Token' must not derive 'Debug'
+//~| ERROR: Struct 'Token' must not derive 'Clone'
+pub struct Token {
+    pub secret: String,
+}
+
+// Only allowed derives - no lint
+#[derive(PartialEq, Eq, Hash)]
+pub struct ApiKey {
+    pub secret: String,
+}
+
+// A hand-written Debug impl isn't a derive - no lint
+pub struct Password {
+    pub secret: String,
+}
+
+impl std::fmt::Debug for Password {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Password(..)")
+    }
+}
+
+// No secret field, so the struct isn't matched - no lint
+#[derive(Debug, Clone)]
+pub struct Username {
+    pub name: String,
+}
@@ -0,0 +1,36 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//@compile-flags: --crate-name test_forbid_field_type
+//@compile-flags: --crate-type lib
+
+// This test verifies that ForbidFieldType (configured with "^std::sync::Mutex<" and
+// "RwLock") reports each field whose full type matches a forbidden pattern, in named
+// and tuple structs alike.
+
+pub mod lock_free {
+    use std::collections::HashMap;
+    use std::sync::atomic::AtomicU64;
+    use std::sync::{Arc, Mutex, RwLock};
+
+    pub struct Counters {
+        pub hits: AtomicU64,
+        pub names: Mutex<Vec<String>>, //~ ERROR: Field 'names' of struct 'Counters' has forbidden type 'std::sync::Mutex<std::vec::Vec<std::string::String>>'
+        pub index: RwLock<HashMap<String, u64>>, //~ ERROR: Field 'index' of struct 'Counters' has forbidden type 'std::sync::RwLock<std::collections::HashMap<std::string::String, u64>>'
+    }
+
+    // Only patterns anchored at the start of the type are limited to the outermost type
+    pub struct Shared(pub Arc<Mutex<u64>>, pub Arc<RwLock<u64>>); //~ ERROR: Field '1' of struct 'Shared' has forbidden type 'std::sync::Arc<std::sync::RwLock<u64>>'
+
+    pub struct Plain {
+        pub total: u64,
+    }
+}
+
+pub mod elsewhere {
+    use std::sync::Mutex;
+
+    // Outside the matched module, locks are fine
+    pub struct Cache {
+        pub entries: Mutex<Vec<u64>>,
+    }
+}
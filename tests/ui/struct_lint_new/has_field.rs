@@ -0,0 +1,32 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//@compile-flags: --crate-name test_has_field
+//@compile-flags: --crate-type lib
+
+// This test verifies that the StructMatch::HasField matcher selects structs by
+// the names and types of their fields. Matching structs must be named "Sensitive*".
+
+pub struct ApiSecret(String);
+
+// Has a field named password - should trigger the lint
+pub struct Credentials { //~ ERROR: Struct must match pattern '^Sensitive.*', found 'Credentials'
+    pub user: String,
+    pub password: String,
+}
+
+// Has a field whose type matches Secret - should trigger the lint
+pub struct ClientConfig<'a> { //~ ERROR: Struct must match pattern '^Sensitive.*', found 'ClientConfig'
+    pub endpoint: &'a str,
+    pub key: Option<ApiSecret>,
+}
+
+// Matches and is correctly named - no lint
+pub struct SensitiveLogin {
+    pub password: String,
+}
+
+// Field name only contains "password", and no field has a Secret type - no lint
+pub struct Settings {
+    pub password_hint: String,
+    pub retries: u32,
+}
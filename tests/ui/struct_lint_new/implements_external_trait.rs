@@ -0,0 +1,31 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//@compile-flags: --crate-name test_implements_external_trait
+//@compile-flags: --crate-type lib
+
+// This test verifies that the ImplementsTrait matcher resolves traits from other
+// crates, so that any struct rule can target e.g. every `Clone` type.
+
+// Derived implementations are matched
+#[derive(Clone)]
+pub struct DerivedClone {
+    pub id: u64, //~ ERROR: Public field 'id' of struct 'DerivedClone' is missing a doc comment
+}
+
+// So are hand-written ones
+pub struct ManualClone {
+    pub name: String, //~ ERROR: Public field 'name' of struct 'ManualClone' is missing a doc comment
+}
+
+impl Clone for ManualClone {
+    fn clone(&self) -> Self {
+        ManualClone {
+            name: self.name.clone(),
+        }
+    }
+}
+
+// Doesn't implement Clone, so the rule doesn't apply
+pub struct NotClone {
+    pub value: i32,
+}
@@ -0,0 +1,35 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//@compile-flags: --crate-name test_in_module
+//@compile-flags: --crate-type lib
+
+// This test verifies that the StructMatch::InModule matcher selects structs by the
+// path of their enclosing module. Structs in `domain` must not derive Clone.
+
+pub mod domain {
+    #[derive(Clone)] //~ ERROR: Struct 'Order' must not derive 'Clone'
+    pub struct Order {
+        pub id: u64,
+    }
+
+    pub mod billing {
+        #[derive(Clone)] //~ ERROR: Struct 'Invoice' must not derive 'Clone'
+        pub struct Invoice {
+            pub total: u64,
+        }
+    }
+}
+
+// Only the module name differs - no lint
+pub mod domain_dto {
+    #[derive(Clone)]
+    pub struct OrderDto {
+        pub id: u64,
+    }
+}
+
+// Outside any module - no lint
+#[derive(Clone)]
+pub struct Config {
+    pub verbose: bool,
+}
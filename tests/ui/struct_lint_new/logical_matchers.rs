@@ -0,0 +1,36 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//@compile-flags: --crate-name test_logical_matchers
+//@compile-flags: --crate-type lib
+
+// This test verifies that AND/OR/NOT struct matchers compose correctly, including
+// negating a trait matcher.
+
+pub trait Marker {}
+
+// Matches the OR branch and does not implement Marker - should trigger the lint
+pub struct UserModel { //~ ERROR: Struct 'UserModel' has pub visibility, but must be private
+    id: u64,
+}
+
+// Matches the other OR branch - should trigger the lint
+pub struct OrderEntity { //~ ERROR: Struct 'OrderEntity' has pub visibility, but must be private
+    id: u64,
+}
+
+// Implements Marker, so the NOT matcher excludes it
+pub struct MarkedModel {
+    id: u64,
+}
+
+impl Marker for MarkedModel {}
+
+// Matches neither OR branch
+pub struct Repository {
+    id: u64,
+}
+
+// Matches but is already private
+struct PrivateModel {
+    id: u64,
+}
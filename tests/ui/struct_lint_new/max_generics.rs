@@ -0,0 +1,39 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//@compile-flags: --crate-name test_struct_max_generics
+//@compile-flags: --crate-type lib
+
+// This test verifies that MaxGenerics (configured with a maximum of 2) counts type
+// and const parameters, and only counts lifetimes when count_lifetimes is set.
+
+pub mod types {
+    pub struct Pair<A, B> {
+        pub first: A,
+        pub second: B,
+    }
+
+    pub struct Buffer<T, const N: usize> {
+        pub items: [T; N],
+    }
+
+    pub struct Triple<A, B, C> { //~ ERROR: Struct 'Triple' has 3 generic type and const parameters, exceeding the maximum of 2
+        pub values: (A, B, C),
+    }
+
+    // Lifetimes aren't counted by default
+    pub struct Borrowed<'a, 'b, A, B> {
+        pub first: &'a A,
+        pub second: &'b B,
+    }
+}
+
+pub mod with_lifetimes {
+    pub struct View<'a, T> {
+        pub items: &'a [T],
+    }
+
+    pub struct Borrowed<'a, 'b, A> { //~ ERROR: Struct 'Borrowed' has 3 generic parameters, exceeding the maximum of 2
+        pub first: &'a A,
+        pub second: &'b A,
+    }
+}
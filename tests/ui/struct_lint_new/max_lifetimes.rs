@@ -0,0 +1,33 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//@compile-flags: --crate-name test_struct_max_lifetimes
+//@compile-flags: --crate-type lib
+
+// This test verifies that MaxLifetimes (configured with a maximum of 1) counts only
+// the lifetime parameters a struct declares.
+
+pub struct Owned {
+    pub name: String,
+}
+
+pub struct View<'a> {
+    pub name: &'a str,
+    pub tags: &'a [String],
+}
+
+// Type and const parameters are left to MaxGenerics
+pub struct Window<'a, T, U, const N: usize> {
+    pub items: &'a [T; N],
+    pub extra: U,
+}
+
+pub struct Join<'a, 'b> { //~ ERROR: Struct 'Join' has 2 lifetime parameters, exceeding the maximum of 1
+    pub left: &'a str,
+    pub right: &'b str,
+}
+
+pub struct Parser<'src, 'arena, 'ctx, T> { //~ ERROR: Struct 'Parser' has 3 lifetime parameters, exceeding the maximum of 1
+    pub source: &'src str,
+    pub arena: &'arena mut Vec<T>,
+    pub context: &'ctx [u8],
+}
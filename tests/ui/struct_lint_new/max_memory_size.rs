@@ -0,0 +1,29 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//@compile-flags: --crate-name test_max_memory_size
+//@compile-flags: --crate-type lib
+
+// This test verifies that MaxMemorySize (configured with a maximum of 16 bytes)
+// reports structs whose layout is larger, and skips generic structs.
+
+pub struct Header {
+    pub id: u64,
+    pub flags: u32,
+}
+
+pub struct Frame { //~ ERROR: Struct 'Frame' is 72 bytes, exceeding the maximum of 16 bytes
+    pub header: Header,
+    pub payload: [u8; 56],
+}
+
+// Boxing the payload keeps the struct itself small
+pub struct BoxedFrame {
+    pub header: Box<Header>,
+    pub payload: Box<[u8; 56]>,
+}
+
+// The size depends on T, so there's no layout to check
+pub struct Tagged<T> {
+    pub tag: [u8; 32],
+    pub value: T,
+}
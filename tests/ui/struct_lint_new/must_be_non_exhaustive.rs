@@ -0,0 +1,31 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//@compile-flags: --crate-name test_must_be_non_exhaustive
+//@compile-flags: --crate-type lib
+
+// This test verifies that the MustBeNonExhaustive rule requires public structs
+// to be marked #[non_exhaustive], and ignores structs that aren't public.
+
+// Public struct without the attribute - should trigger the lint
+pub struct Config { //~ ERROR: Public struct 'Config' must be marked #[non_exhaustive]
+    pub name: String,
+}
+
+// Public struct with the attribute - no lint
+#[non_exhaustive]
+pub struct Options {
+    pub verbose: bool,
+}
+
+// Public tuple struct without the attribute - should trigger the lint
+pub struct Wrapper(pub u32); //~ ERROR: Public struct 'Wrapper' must be marked #[non_exhaustive]
+
+// Crate-visible struct - not part of the public API, so no lint
+pub(crate) struct Internal {
+    pub value: u32,
+}
+
+// Private struct - no lint
+struct Private {
+    value: u32,
+}
@@ -0,0 +1,53 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//@compile-flags: --crate-name test_must_implement
+//@compile-flags: --crate-type lib
+
+// This test verifies that MustImplement resolves each required trait by path, whether
+// through its usual path (std::clone::Clone), its definition path (core::fmt::Debug)
+// or from crate:: for local traits, and reports only the traits that are missing.
+
+pub trait Entity {}
+
+pub trait Named {}
+
+// Blanket impls count like any other
+impl<T: Entity> Named for T {}
+
+#[derive(Clone, Debug)]
+pub struct Complete;
+
+impl Entity for Complete {}
+
+#[derive(Clone)]
+pub struct MissingDebug; //~ ERROR: Struct 'MissingDebug' must implement core::fmt::Debug
+
+impl Entity for MissingDebug {}
+
+#[derive(Debug)]
+pub struct MissingSeveral; //~ ERROR: Struct 'MissingSeveral' must implement std::clone::Clone, crate::Entity, crate::Named
+
+#[derive(Clone, Debug)]
+pub struct Wrapper<T> {
+    pub value: T,
+}
+
+impl<T> Entity for Wrapper<T> {}
+
+// A trait that is merely named Entity doesn't count
+pub mod other {
+    pub trait Entity {}
+}
+
+#[derive(Clone, Debug)]
+pub struct Impostor; //~ ERROR: Struct 'Impostor' must implement crate::Entity, crate::Named
+
+impl other::Entity for Impostor {}
+
+// A trait that can't be found in the dependencies is reported as missing
+pub mod dto {
+    #[derive(Clone, Debug)]
+    pub struct UserDto; //~ ERROR: Struct 'UserDto' must implement serde::Serialize
+
+    impl crate::Entity for UserDto {}
+}
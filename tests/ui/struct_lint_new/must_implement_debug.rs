@@ -0,0 +1,38 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//@compile-flags: --crate-name test_must_implement_debug
+//@compile-flags: --crate-type lib
+
+// This test verifies that MustImplementDebug accepts derived and manual Debug
+// impls, including on generic structs, and reports structs without one.
+
+#[derive(Debug)]
+pub struct Derived {
+    pub id: u64,
+}
+
+pub struct Manual {
+    pub secret: String,
+}
+
+impl std::fmt::Debug for Manual {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Manual { .. }")
+    }
+}
+
+#[derive(Debug)]
+pub struct Wrapper<T> {
+    pub value: T,
+}
+
+pub struct Missing { //~ ERROR: Struct 'Missing' must implement Debug
+    pub id: u64,
+}
+
+// A trait that is merely named Debug doesn't count
+pub trait Debug {}
+
+pub struct Impostor; //~ ERROR: Struct 'Impostor' must implement Debug
+
+impl Debug for Impostor {}
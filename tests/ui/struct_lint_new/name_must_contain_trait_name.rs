@@ -0,0 +1,34 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//@compile-flags: --crate-name test_name_contains_trait
+//@compile-flags: --crate-type lib
+
+// This test verifies that the NameMustContainTraitName rule checks struct names
+// against the simple names of the matched traits they implement.
+
+pub trait Repository {}
+
+pub trait Service {}
+
+// Name contains the trait name - no error
+pub struct UserRepositoryImpl;
+
+impl Repository for UserRepositoryImpl {}
+
+// Name does not contain the trait name - should trigger the lint
+pub struct UserStore; //~ ERROR: Struct 'UserStore' implements 'Repository', but its name does not contain 'Repository'
+
+impl Repository for UserStore {}
+
+// Implements both matched traits, but only mentions one of them
+pub struct BillingService; //~ ERROR: Struct 'BillingService' implements 'Repository', but its name does not contain 'Repository'
+
+impl Service for BillingService {}
+
+impl Repository for BillingService {}
+
+// Traits that are not part of the matcher are ignored
+#[derive(Debug, Clone)]
+pub struct AuditService;
+
+impl Service for AuditService {}
@@ -0,0 +1,27 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//@compile-flags: --crate-name test_name_must_match_file
+//@compile-flags: --crate-type lib
+//@check-pass
+
+// This test verifies that NameMustMatchFile is satisfied by one matched struct named
+// after the file, ignoring case and underscores, with helper structs alongside it.
+
+// The file's primary type
+pub struct NameMustMatchFile {
+    pub name: String,
+    pub server: HTTPServer,
+}
+
+// A helper next to the primary type
+pub struct HTTPServer {
+    pub port: u16,
+}
+
+pub mod nested {
+    // Inline modules share their parent's file, and its primary type
+    pub struct Connection;
+}
+
+// Structs the lint doesn't match are not checked
+struct Helper;
@@ -0,0 +1,18 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//@compile-flags: --crate-name test_name_must_match_file_missing
+//@compile-flags: --crate-type lib
+
+// This test verifies that NameMustMatchFile reports a file without a matched struct
+// named after it once, at its first matched struct.
+
+pub struct HTTPServer { //~ ERROR: No struct in 'name_must_match_file_missing' is named after its file, found 'HTTPServer', 'Connection'
+    pub port: u16,
+}
+
+pub mod nested {
+    pub struct Connection;
+}
+
+// Helper is named like nothing in particular, and the lint doesn't match it anyway
+struct Helper;
@@ -0,0 +1,74 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//@compile-flags: --crate-name test_require_constructor
+//@compile-flags: --crate-type lib
+
+pub struct Account {
+    balance: u64,
+}
+
+impl Account {
+    pub fn new() -> Self {
+        Account { balance: 0 }
+    }
+
+    pub fn balance(&self) -> u64 {
+        self.balance
+    }
+}
+
+// Only methods, so there's no way to build one
+pub struct Session { //~ ERROR: Struct 'Session' has no constructor matching '^new$'
+    token: String,
+}
+
+impl Session {
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+}
+
+// A trait impl doesn't count, as it's not an inherent associated function
+pub struct Config { //~ ERROR: Struct 'Config' has no constructor matching '^new$'
+    verbose: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config { verbose: false }
+    }
+}
+
+// The constructor can be in any of the struct's impl blocks
+pub struct Ledger {
+    entries: Vec<u64>,
+}
+
+impl Ledger {
+    pub fn total(&self) -> u64 {
+        self.entries.iter().sum()
+    }
+}
+
+impl Ledger {
+    pub fn new() -> Self {
+        Ledger { entries: Vec::new() }
+    }
+}
+
+pub mod builders {
+    // Matched with a pattern that also allows a builder
+    pub struct Request {
+        url: String,
+    }
+
+    impl Request {
+        pub fn builder(url: &str) -> Self {
+            Request { url: url.to_string() }
+        }
+    }
+
+    pub struct Response { //~ ERROR: Struct 'Response' has no constructor matching '^(new|builder)$'
+        status: u16,
+    }
+}
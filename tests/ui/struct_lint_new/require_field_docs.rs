@@ -0,0 +1,31 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//@compile-flags: --crate-name test_require_field_docs
+//@compile-flags: --crate-type lib
+
+// This test verifies that RequireFieldDocs reports public named fields without
+// a doc comment, and leaves private and positional fields alone.
+
+pub struct Account {
+    /// The account's unique identifier
+    pub id: u64,
+    pub owner: String, //~ ERROR: Public field 'owner' of struct 'Account' is missing a doc comment
+    #[doc = "Balance in cents"]
+    pub balance: i64,
+    pub(crate) audit_log: Vec<String>,
+    secret: String,
+}
+
+pub struct Settings {
+    ///
+    pub theme: String, //~ ERROR: Public field 'theme' of struct 'Settings' is missing a doc comment
+}
+
+// Tuple struct fields have no names to document
+pub struct Meters(pub f64);
+
+impl Account {
+    pub fn secret(&self) -> &str {
+        &self.secret
+    }
+}
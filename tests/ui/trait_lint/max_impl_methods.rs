@@ -0,0 +1,67 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//@compile-flags: --crate-name test_max_impl_methods
+//@compile-flags: --crate-type lib
+
+// This test verifies that MaxImplMethods (configured with a maximum of 2) reports
+// impls of a matched trait that define too many methods, and only counts methods.
+
+pub trait Handler {
+    type Output;
+    const NAME: &'static str;
+
+    fn handle(&self);
+    fn validate(&self) -> bool {
+        true
+    }
+    fn describe(&self) -> String {
+        String::new()
+    }
+}
+
+// Two methods, plus associated items that aren't counted
+pub struct Small;
+
+impl Handler for Small {
+    type Output = ();
+    const NAME: &'static str = "small";
+
+    fn handle(&self) {}
+    fn validate(&self) -> bool {
+        false
+    }
+}
+
+pub struct Large;
+
+impl Handler for Large { //~ ERROR: Impl of 'Handler' for 'Large' has 3 methods, exceeding the maximum of 2
+    type Output = u32;
+    const NAME: &'static str = "large";
+
+    fn handle(&self) {}
+    fn validate(&self) -> bool {
+        false
+    }
+    fn describe(&self) -> String {
+        "large".to_string()
+    }
+}
+
+// Inherent impls and other traits aren't checked
+impl Large {
+    pub fn one(&self) {}
+    pub fn two(&self) {}
+    pub fn three(&self) {}
+}
+
+pub trait Other {
+    fn a(&self);
+    fn b(&self);
+    fn c(&self);
+}
+
+impl Other for Large {
+    fn a(&self) {}
+    fn b(&self) {}
+    fn c(&self) {}
+}
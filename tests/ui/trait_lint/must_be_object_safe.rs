@@ -0,0 +1,36 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//@compile-flags: --crate-name test_must_be_object_safe
+//@compile-flags: --crate-type lib
+
+// This test verifies that MustBeObjectSafe reports matched traits that can't be
+// used as `dyn Trait`, private ones included, and leaves dyn-compatible ones alone
+
+pub trait Codec { //~ ERROR: Trait 'Codec' is not object safe, so it can't be used as `dyn Codec`
+    fn encode<T: ToString>(&self, value: T) -> String;
+
+    fn name() -> String;
+}
+
+trait Cloner: Sized { //~ ERROR: Trait 'Cloner' is not object safe, so it can't be used as `dyn Cloner`
+    fn duplicate(&self) -> Self;
+}
+
+// Methods that need `Self: Sized` are left off the vtable, so this is dyn compatible
+pub trait Plugin {
+    fn run(&self) -> u32;
+
+    fn new() -> Self
+    where
+        Self: Sized;
+
+    fn with<T>(&self, _value: T)
+    where
+        Self: Sized,
+    {
+    }
+}
+
+pub fn plugins() -> Vec<Box<dyn Plugin>> {
+    Vec::new()
+}
@@ -0,0 +1,48 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//@compile-flags: --crate-name test_require_implementors_private
+//@compile-flags: --crate-type lib
+
+// This test verifies that the RequireImplementorsPrivate rule reports every
+// public type implementing a matched trait, and ignores other traits.
+
+/// Implementors of this trait should be private
+pub trait Repository {
+    fn find(&self, id: u32) -> Option<String>;
+}
+
+pub trait Cache {}
+
+// Public implementor - should trigger the lint
+pub struct PostgresRepository; //~ ERROR: Type 'PostgresRepository' implements 'Repository', so it must not be pub
+
+impl Repository for PostgresRepository {
+    fn find(&self, _id: u32) -> Option<String> {
+        None
+    }
+}
+
+// Public enum implementor - should trigger the lint
+pub enum Backend { //~ ERROR: Type 'Backend' implements 'Repository', so it must not be pub
+    Memory,
+}
+
+impl Repository for Backend {
+    fn find(&self, _id: u32) -> Option<String> {
+        None
+    }
+}
+
+// Private implementor - no lint
+struct InMemoryRepository;
+
+impl Repository for InMemoryRepository {
+    fn find(&self, _id: u32) -> Option<String> {
+        None
+    }
+}
+
+// Public type implementing a different trait - no lint
+pub struct LruCache;
+
+impl Cache for LruCache {}
@@ -0,0 +1,52 @@
+// This product includes software developed at Datadog (https://www.datadoghq.com/) Copyright 2024 Datadog, Inc.
+
+//@compile-flags: --crate-name test_require_method_docs
+//@compile-flags: --crate-type lib
+
+// This test verifies that RequireMethodDocs reports both required and provided
+// methods of a matched public trait that have no doc comment
+
+pub trait Repository {
+    /// Items need no documentation of their own
+    type Item;
+
+    const TABLE: &'static str;
+
+    /// Look up an item by its id
+    fn find(&self, id: u64) -> Option<Self::Item>;
+
+    fn save(&mut self, item: Self::Item); //~ ERROR: Method 'save' of trait 'Repository' is missing a doc comment
+
+    fn count(&self) -> usize { //~ ERROR: Method 'count' of trait 'Repository' is missing a doc comment
+        0
+    }
+
+    #[doc = "Documented with a doc attribute"]
+    fn is_empty(&self) -> bool {
+        self.count() == 0
+    }
+}
+
+// Private traits are not part of the public contract
+trait InternalRepository {
+    fn find(&self, id: u64);
+}
+
+// Traits that don't match are not checked
+pub trait Cache {
+    fn get(&self, key: &str);
+}
+
+pub struct Store;
+
+impl Repository for Store {
+    type Item = u64;
+    const TABLE: &'static str = "store";
+
+    // Implementations are documented by the trait
+    fn find(&self, id: u64) -> Option<u64> {
+        Some(id)
+    }
+
+    fn save(&mut self, _item: u64) {}
+}